@@ -11,8 +11,8 @@ from biopython_rust import seq
 from biopython_rust import io
 
 # Core functionality re-exported at the top level
-from biopython_rust.seq import Sequence, DNASequence, RNASequence, ProteinSequence
-from biopython_rust.io import read_fasta, write_fasta, FastaRecord
+from biopython_rust.seq import Seq, Sequence, DNASequence, RNASequence, ProteinSequence, SequenceError
+from biopython_rust.io import read_fasta, write_fasta, read_fastq, write_fastq, FastaRecord, FastqRecord
 
 # Initialize the library
 import biopython_rust._rust_bindings as _rust
@@ -21,11 +21,16 @@ _rust.init()
 __all__ = [
     "seq",
     "io",
+    "Seq",
     "Sequence",
     "DNASequence",
     "RNASequence",
     "ProteinSequence",
+    "SequenceError",
     "read_fasta",
     "write_fasta",
+    "read_fastq",
+    "write_fastq",
     "FastaRecord",
+    "FastqRecord",
 ]
\ No newline at end of file
@@ -44,6 +44,51 @@ class FastaRecord:
         return f"FastaRecord(id='{self.id}'{desc_str}, sequence={repr(self.sequence)})"
 
 
+class FastqRecord(FastaRecord):
+    """A record from a FASTQ file."""
+    
+    def __init__(self, id: str, sequence: Sequence, quality: str,
+                 description: Optional[str] = None) -> None:
+        """
+        Initialize a FASTQ record.
+        
+        Args:
+            id: The sequence identifier (without the '@')
+            sequence: The sequence
+            quality: Phred+33 quality string, one character per base
+            description: Optional description
+        """
+        super().__init__(id, sequence, description)
+        self.quality = quality
+    
+    def __str__(self) -> str:
+        """Get the string representation of the record in FASTQ format."""
+        header = f"@{self.id}"
+        if self.description:
+            header += f" {self.description}"
+        
+        return f"{header}\n{self.sequence}\n+\n{self.quality}"
+    
+    def __repr__(self) -> str:
+        """Get a detailed representation of the record."""
+        desc_str = f", description='{self.description}'" if self.description else ""
+        return f"FastqRecord(id='{self.id}'{desc_str}, sequence={repr(self.sequence)})"
+
+
+def _typed_sequence(seq_data: bytes, seq_id: str, description: Optional[str]) -> Sequence:
+    """Wrap raw sequence data in the class matching its detected alphabet."""
+    alphabet = _rust.get_sequence_alphabet(seq_data)
+    
+    if alphabet == "DNA":
+        return DNASequence(seq_data, id=seq_id, description=description)
+    elif alphabet == "RNA":
+        return RNASequence(seq_data, id=seq_id, description=description)
+    elif alphabet == "Protein":
+        return ProteinSequence(seq_data, id=seq_id, description=description)
+    else:
+        return Sequence(seq_data, id=seq_id, description=description)
+
+
 def read_fasta(path: Union[str, Path]) -> List[FastaRecord]:
     """
     Read sequences from a FASTA file.
@@ -69,20 +114,7 @@ def read_fasta(path: Union[str, Path]) -> List[FastaRecord]:
     for rust_record in fasta_records:
         seq_id = rust_record["id"]
         description = rust_record.get("description")
-        seq_data = rust_record["sequence"]
-        
-        # Try to detect the sequence type
-        alphabet = _rust.get_sequence_alphabet(seq_data)
-        
-        if alphabet == "DNA":
-            sequence = DNASequence(seq_data, id=seq_id, description=description)
-        elif alphabet == "RNA":
-            sequence = RNASequence(seq_data, id=seq_id, description=description)
-        elif alphabet == "Protein":
-            sequence = ProteinSequence(seq_data, id=seq_id, description=description)
-        else:
-            sequence = Sequence(seq_data, id=seq_id, description=description)
-        
+        sequence = _typed_sequence(rust_record["sequence"], seq_id, description)
         records.append(FastaRecord(seq_id, sequence, description))
     
     return records
@@ -117,6 +149,57 @@ def write_fasta(records: List[FastaRecord], path: Union[str, Path]) -> None:
         raise SequenceError(f"Failed to write FASTA file: {e}")
 
 
+def read_fastq(path: Union[str, Path]) -> List[FastqRecord]:
+    """
+    Read sequences and quality scores from a FASTQ file.
+    
+    Args:
+        path: Path to the FASTQ file
+        
+    Returns:
+        List of FastqRecord objects
+        
+    Raises:
+        SequenceError: If the records are malformed
+        OSError: If the file cannot be read
+    """
+    fastq_records = _rust.read_fastq(str(path))
+    
+    records = []
+    for rust_record in fastq_records:
+        seq_id = rust_record["id"]
+        description = rust_record.get("description")
+        sequence = _typed_sequence(rust_record["sequence"], seq_id, description)
+        quality = rust_record["quality"].decode("ascii")
+        records.append(FastqRecord(seq_id, sequence, quality, description))
+    
+    return records
+
+
+def write_fastq(records: List[FastqRecord], path: Union[str, Path]) -> None:
+    """
+    Write sequences and quality scores to a FASTQ file.
+    
+    Args:
+        records: List of FastqRecord objects
+        path: Path to write the FASTQ file
+        
+    Raises:
+        OSError: If the file cannot be written
+    """
+    rust_records = [
+        {
+            "id": record.id,
+            "description": record.description,
+            "sequence": record.sequence._rust_seq,
+            "quality": record.quality.encode("ascii"),
+        }
+        for record in records
+    ]
+    
+    _rust.write_fastq(rust_records, str(path))
+
+
 def detect_format(path: Union[str, Path]) -> str:
     """
     Detect the format of a sequence file.
@@ -140,7 +223,6 @@ def detect_format(path: Union[str, Path]) -> str:
 
 
 # Additional reader functions that could be implemented:
-# - read_fastq
 # - read_genbank
 # - read_embl
 # - read_clustal
@@ -9,9 +9,11 @@ import numpy as np
 from pathlib import Path
 import biopython_rust._rust_bindings as _rust
 
-class SequenceError(Exception):
-    """Exception raised for sequence-related errors."""
-    pass
+# Raised by the Rust extension for invalid sequences; a subclass of ValueError
+SequenceError = _rust.SequenceError
+
+# Biopython-style sequence class implemented in Rust
+Seq = _rust.Seq
 
 
 class Sequence:
@@ -258,6 +260,18 @@ class DNASequence(Sequence):
             return _rust.gc_content(self._rust_seq)
         except RuntimeError as e:
             raise SequenceError(str(e))
+    
+    def translate(self, table: int = 1) -> "ProteinSequence":
+        """
+        Translate the sequence to protein.
+        
+        Args:
+            table: NCBI translation table number
+        
+        Returns:
+            A ProteinSequence; stop codons translate to '*'
+        """
+        return ProteinSequence(_rust.translate_sequence(self._rust_seq, table))
 
 
 class RNASequence(Sequence):
@@ -345,6 +359,18 @@ class RNASequence(Sequence):
             return _rust.gc_content(self._rust_seq)
         except RuntimeError as e:
             raise SequenceError(str(e))
+    
+    def translate(self, table: int = 1) -> "ProteinSequence":
+        """
+        Translate the sequence to protein.
+        
+        Args:
+            table: NCBI translation table number
+        
+        Returns:
+            A ProteinSequence; stop codons translate to '*'
+        """
+        return ProteinSequence(_rust.translate_sequence(self._rust_seq, table))
 
 
 class ProteinSequence(Sequence):
@@ -393,6 +419,21 @@ class ProteinSequence(Sequence):
             return _rust.protein_molecular_weight(self._rust_seq)
         except RuntimeError as e:
             raise SequenceError(str(e))
+    
+    def isoelectric_point(self) -> float:
+        """
+        Calculate the isoelectric point of the protein.
+        
+        Returns:
+            The pH at which the protein has no net charge
+        
+        Raises:
+            SequenceError: If the isoelectric point cannot be calculated
+        """
+        try:
+            return _rust.protein_isoelectric_point(self._rust_seq)
+        except RuntimeError as e:
+            raise SequenceError(str(e))
 
 
 # Utility functions
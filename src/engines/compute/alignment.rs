@@ -4,7 +4,9 @@
 //! algorithms used in bioinformatics.
 
 use super::{ComputeResult, ComputeError};
+use crate::engines::core::parallel::adaptive_parallel_execute;
 use std::cmp;
+use std::collections::HashSet;
 
 /// Different types of alignment algorithms
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -19,6 +21,7 @@ pub enum AlignmentType {
 
 /// Different scoring schemes for alignments
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ScoringScheme {
     /// Score for a match
     pub match_score: i32,
@@ -41,8 +44,230 @@ impl Default for ScoringScheme {
     }
 }
 
+/// Residue order of the rows and columns of the built-in substitution matrices
+const MATRIX_RESIDUES: &[u8; 24] = b"ARNDCQEGHILKMFPSTWYVBZX*";
+
+/// Index of the `X` (unknown residue) row, used for residues not in the matrix
+const UNKNOWN_RESIDUE_INDEX: u8 = 22;
+
+/// Map every byte to its matrix row, falling back to `X`
+const fn build_residue_index() -> [u8; 256] {
+    let mut index = [UNKNOWN_RESIDUE_INDEX; 256];
+    let mut i = 0;
+    while i < MATRIX_RESIDUES.len() {
+        let residue = MATRIX_RESIDUES[i];
+        index[residue as usize] = i as u8;
+        index[residue.to_ascii_lowercase() as usize] = i as u8;
+        i += 1;
+    }
+    index
+}
+
+static RESIDUE_INDEX: [u8; 256] = build_residue_index();
+
+/// BLOSUM62 scores in `MATRIX_RESIDUES` order
+static BLOSUM62: [[i8; 24]; 24] = [
+    //  A   R   N   D   C   Q   E   G   H   I   L   K   M   F   P   S   T   W   Y   V   B   Z   X   *
+    [ 4, -1, -2, -2,  0, -1, -1,  0, -2, -1, -1, -1, -1, -2, -1,  1,  0, -3, -2,  0, -2, -1,  0, -4], // A
+    [-1,  5,  0, -2, -3,  1,  0, -2,  0, -3, -2,  2, -1, -3, -2, -1, -1, -3, -2, -3, -1,  0, -1, -4], // R
+    [-2,  0,  6,  1, -3,  0,  0,  0,  1, -3, -3,  0, -2, -3, -2,  1,  0, -4, -2, -3,  3,  0, -1, -4], // N
+    [-2, -2,  1,  6, -3,  0,  2, -1, -1, -3, -4, -1, -3, -3, -1,  0, -1, -4, -3, -3,  4,  1, -1, -4], // D
+    [ 0, -3, -3, -3,  9, -3, -4, -3, -3, -1, -1, -3, -1, -2, -3, -1, -1, -2, -2, -1, -3, -3, -2, -4], // C
+    [-1,  1,  0,  0, -3,  5,  2, -2,  0, -3, -2,  1,  0, -3, -1,  0, -1, -2, -1, -2,  0,  3, -1, -4], // Q
+    [-1,  0,  0,  2, -4,  2,  5, -2,  0, -3, -3,  1, -2, -3, -1,  0, -1, -3, -2, -2,  1,  4, -1, -4], // E
+    [ 0, -2,  0, -1, -3, -2, -2,  6, -2, -4, -4, -2, -3, -3, -2,  0, -2, -2, -3, -3, -1, -2, -1, -4], // G
+    [-2,  0,  1, -1, -3,  0,  0, -2,  8, -3, -3, -1, -2, -1, -2, -1, -2, -2,  2, -3,  0,  0, -1, -4], // H
+    [-1, -3, -3, -3, -1, -3, -3, -4, -3,  4,  2, -3,  1,  0, -3, -2, -1, -3, -1,  3, -3, -3, -1, -4], // I
+    [-1, -2, -3, -4, -1, -2, -3, -4, -3,  2,  4, -2,  2,  0, -3, -2, -1, -2, -1,  1, -4, -3, -1, -4], // L
+    [-1,  2,  0, -1, -3,  1,  1, -2, -1, -3, -2,  5, -1, -3, -1,  0, -1, -3, -2, -2,  0,  1, -1, -4], // K
+    [-1, -1, -2, -3, -1,  0, -2, -3, -2,  1,  2, -1,  5,  0, -2, -1, -1, -1, -1,  1, -3, -1, -1, -4], // M
+    [-2, -3, -3, -3, -2, -3, -3, -3, -1,  0,  0, -3,  0,  6, -4, -2, -2,  1,  3, -1, -3, -3, -1, -4], // F
+    [-1, -2, -2, -1, -3, -1, -1, -2, -2, -3, -3, -1, -2, -4,  7, -1, -1, -4, -3, -2, -2, -1, -2, -4], // P
+    [ 1, -1,  1,  0, -1,  0,  0,  0, -1, -2, -2,  0, -1, -2, -1,  4,  1, -3, -2, -2,  0,  0,  0, -4], // S
+    [ 0, -1,  0, -1, -1, -1, -1, -2, -2, -1, -1, -1, -1, -2, -1,  1,  5, -2, -2,  0, -1, -1,  0, -4], // T
+    [-3, -3, -4, -4, -2, -2, -3, -2, -2, -3, -2, -3, -1,  1, -4, -3, -2, 11,  2, -3, -4, -3, -2, -4], // W
+    [-2, -2, -2, -3, -2, -1, -2, -3,  2, -1, -1, -2, -1,  3, -3, -2, -2,  2,  7, -1, -3, -2, -1, -4], // Y
+    [ 0, -3, -3, -3, -1, -2, -2, -3, -3,  3,  1, -2,  1, -1, -2, -2,  0, -3, -1,  4, -3, -2, -1, -4], // V
+    [-2, -1,  3,  4, -3,  0,  1, -1,  0, -3, -4,  0, -3, -3, -2,  0, -1, -4, -3, -3,  4,  1, -1, -4], // B
+    [-1,  0,  0,  1, -3,  3,  4, -2,  0, -3, -3,  1, -1, -3, -1,  0, -1, -3, -2, -2,  1,  4, -1, -4], // Z
+    [ 0, -1, -1, -1, -2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -2,  0,  0, -2, -1, -1, -1, -1, -1, -4], // X
+    [-4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4,  1], // *
+];
+
+/// BLOSUM45 scores in `MATRIX_RESIDUES` order
+static BLOSUM45: [[i8; 24]; 24] = [
+    //  A   R   N   D   C   Q   E   G   H   I   L   K   M   F   P   S   T   W   Y   V   B   Z   X   *
+    [ 5, -2, -1, -2, -1, -1, -1,  0, -2, -1, -1, -1, -1, -2, -1,  1,  0, -2, -2,  0, -1, -1,  0, -5], // A
+    [-2,  7,  0, -1, -3,  1,  0, -2,  0, -3, -2,  3, -1, -2, -2, -1, -1, -2, -1, -2, -1,  0, -1, -5], // R
+    [-1,  0,  6,  2, -2,  0,  0,  0,  1, -2, -3,  0, -2, -2, -2,  1,  0, -4, -2, -3,  4,  0, -1, -5], // N
+    [-2, -1,  2,  7, -3,  0,  2, -1,  0, -4, -3,  0, -3, -4, -1,  0, -1, -4, -2, -3,  5,  1, -1, -5], // D
+    [-1, -3, -2, -3, 12, -3, -3, -3, -3, -3, -2, -3, -2, -2, -4, -1, -1, -5, -3, -1, -2, -3, -2, -5], // C
+    [-1,  1,  0,  0, -3,  6,  2, -2,  1, -2, -2,  1,  0, -4, -1,  0, -1, -2, -1, -3,  0,  4, -1, -5], // Q
+    [-1,  0,  0,  2, -3,  2,  6, -2,  0, -3, -2,  1, -2, -3,  0,  0, -1, -3, -2, -3,  1,  4, -1, -5], // E
+    [ 0, -2,  0, -1, -3, -2, -2,  7, -2, -4, -3, -2, -2, -3, -2,  0, -2, -2, -3, -3, -1, -2, -1, -5], // G
+    [-2,  0,  1,  0, -3,  1,  0, -2, 10, -3, -2, -1,  0, -2, -2, -1, -2, -3,  2, -3,  0,  0, -1, -5], // H
+    [-1, -3, -2, -4, -3, -2, -3, -4, -3,  5,  2, -3,  2,  0, -2, -2, -1, -2,  0,  3, -3, -3, -1, -5], // I
+    [-1, -2, -3, -3, -2, -2, -2, -3, -2,  2,  5, -3,  2,  1, -3, -3, -1, -2,  0,  1, -3, -2, -1, -5], // L
+    [-1,  3,  0,  0, -3,  1,  1, -2, -1, -3, -3,  5, -1, -3, -1, -1, -1, -2, -1, -2,  0,  1, -1, -5], // K
+    [-1, -1, -2, -3, -2,  0, -2, -2,  0,  2,  2, -1,  6,  0, -2, -2, -1, -2,  0,  1, -2, -1, -1, -5], // M
+    [-2, -2, -2, -4, -2, -4, -3, -3, -2,  0,  1, -3,  0,  8, -3, -2, -1,  1,  3,  0, -3, -3, -1, -5], // F
+    [-1, -2, -2, -1, -4, -1,  0, -2, -2, -2, -3, -1, -2, -3,  9, -1, -1, -3, -3, -3, -2, -1, -1, -5], // P
+    [ 1, -1,  1,  0, -1,  0,  0,  0, -1, -2, -3, -1, -2, -2, -1,  4,  2, -4, -2, -1,  0,  0,  0, -5], // S
+    [ 0, -1,  0, -1, -1, -1, -1, -2, -2, -1, -1, -1, -1, -1, -1,  2,  5, -3, -1,  0,  0, -1,  0, -5], // T
+    [-2, -2, -4, -4, -5, -2, -3, -2, -3, -2, -2, -2, -2,  1, -3, -4, -3, 15,  3, -3, -4, -2, -2, -5], // W
+    [-2, -1, -2, -2, -3, -1, -2, -3,  2,  0,  0, -1,  0,  3, -3, -2, -1,  3,  8, -1, -2, -2, -1, -5], // Y
+    [ 0, -2, -3, -3, -1, -3, -3, -3, -3,  3,  1, -2,  1,  0, -3, -1,  0, -3, -1,  5, -3, -3, -1, -5], // V
+    [-1, -1,  4,  5, -2,  0,  1, -1,  0, -3, -3,  0, -2, -3, -2,  0,  0, -4, -2, -3,  4,  2, -1, -5], // B
+    [-1,  0,  0,  1, -3,  4,  4, -2,  0, -3, -2,  1, -1, -3, -1,  0, -1, -2, -2, -3,  2,  4, -1, -5], // Z
+    [ 0, -1, -1, -1, -2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,  0,  0, -2, -1, -1, -1, -1, -1, -5], // X
+    [-5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5,  1], // *
+];
+
+/// PAM250 scores in `MATRIX_RESIDUES` order
+static PAM250: [[i8; 24]; 24] = [
+    //  A   R   N   D   C   Q   E   G   H   I   L   K   M   F   P   S   T   W   Y   V   B   Z   X   *
+    [ 2, -2,  0,  0, -2,  0,  0,  1, -1, -1, -2, -1, -1, -3,  1,  1,  1, -6, -3,  0,  0,  0,  0, -8], // A
+    [-2,  6,  0, -1, -4,  1, -1, -3,  2, -2, -3,  3,  0, -4,  0,  0, -1,  2, -4, -2, -1,  0, -1, -8], // R
+    [ 0,  0,  2,  2, -4,  1,  1,  0,  2, -2, -3,  1, -2, -3,  0,  1,  0, -4, -2, -2,  2,  1,  0, -8], // N
+    [ 0, -1,  2,  4, -5,  2,  3,  1,  1, -2, -4,  0, -3, -6, -1,  0,  0, -7, -4, -2,  3,  3, -1, -8], // D
+    [-2, -4, -4, -5, 12, -5, -5, -3, -3, -2, -6, -5, -5, -4, -3,  0, -2, -8,  0, -2, -4, -5, -3, -8], // C
+    [ 0,  1,  1,  2, -5,  4,  2, -1,  3, -2, -2,  1, -1, -5,  0, -1, -1, -5, -4, -2,  1,  3, -1, -8], // Q
+    [ 0, -1,  1,  3, -5,  2,  4,  0,  1, -2, -3,  0, -2, -5, -1,  0,  0, -7, -4, -2,  3,  3, -1, -8], // E
+    [ 1, -3,  0,  1, -3, -1,  0,  5, -2, -3, -4, -2, -3, -5,  0,  1,  0, -7, -5, -1,  0,  0, -1, -8], // G
+    [-1,  2,  2,  1, -3,  3,  1, -2,  6, -2, -2,  0, -2, -2,  0, -1, -1, -3,  0, -2,  1,  2, -1, -8], // H
+    [-1, -2, -2, -2, -2, -2, -2, -3, -2,  5,  2, -2,  2,  1, -2, -1,  0, -5, -1,  4, -2, -2, -1, -8], // I
+    [-2, -3, -3, -4, -6, -2, -3, -4, -2,  2,  6, -3,  4,  2, -3, -3, -2, -2, -1,  2, -3, -3, -1, -8], // L
+    [-1,  3,  1,  0, -5,  1,  0, -2,  0, -2, -3,  5,  0, -5, -1,  0,  0, -3, -4, -2,  1,  0, -1, -8], // K
+    [-1,  0, -2, -3, -5, -1, -2, -3, -2,  2,  4,  0,  6,  0, -2, -2, -1, -4, -2,  2, -2, -2, -1, -8], // M
+    [-3, -4, -3, -6, -4, -5, -5, -5, -2,  1,  2, -5,  0,  9, -5, -3, -3,  0,  7, -1, -4, -5, -2, -8], // F
+    [ 1,  0,  0, -1, -3,  0, -1,  0,  0, -2, -3, -1, -2, -5,  6,  1,  0, -6, -5, -1, -1,  0, -1, -8], // P
+    [ 1,  0,  1,  0,  0, -1,  0,  1, -1, -1, -3,  0, -2, -3,  1,  2,  1, -2, -3, -1,  0,  0,  0, -8], // S
+    [ 1, -1,  0,  0, -2, -1,  0,  0, -1,  0, -2,  0, -1, -3,  0,  1,  3, -5, -3,  0,  0, -1,  0, -8], // T
+    [-6,  2, -4, -7, -8, -5, -7, -7, -3, -5, -2, -3, -4,  0, -6, -2, -5, 17,  0, -6, -5, -6, -4, -8], // W
+    [-3, -4, -2, -4,  0, -4, -4, -5,  0, -1, -1, -4, -2,  7, -5, -3, -3,  0, 10, -2, -3, -4, -2, -8], // Y
+    [ 0, -2, -2, -2, -2, -2, -2, -1, -2,  4,  2, -2,  2, -1, -1, -1,  0, -6, -2,  4, -2, -2, -1, -8], // V
+    [ 0, -1,  2,  3, -4,  1,  3,  0,  1, -2, -3,  1, -2, -4, -1,  0,  0, -5, -3, -2,  3,  2, -1, -8], // B
+    [ 0,  0,  1,  3, -5,  3,  3,  0,  2, -2, -3,  0, -2, -5,  0,  0, -1, -6, -4, -2,  2,  3, -1, -8], // Z
+    [ 0, -1,  0, -1, -3, -1, -1, -1, -1, -1, -1, -1, -1, -2, -1,  0,  0, -4, -2, -1, -1, -1, -1, -8], // X
+    [-8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8,  1], // *
+];
+
+/// A residue substitution matrix for protein alignment
+///
+/// The built-in matrices are static tables, so no file IO is needed. Residues
+/// are matched case-insensitively; residues not in the matrix score as `X`.
+#[derive(Debug, Clone)]
+pub struct SubstitutionMatrix {
+    name: &'static str,
+    scores: &'static [[i8; 24]; 24],
+    /// Penalty for opening a gap
+    pub gap_open_penalty: i32,
+    /// Penalty for extending a gap
+    pub gap_extend_penalty: i32,
+}
+
+impl SubstitutionMatrix {
+    /// BLOSUM62 with gap open -11 and gap extend -1
+    pub fn blosum62() -> Self {
+        Self { name: "BLOSUM62", scores: &BLOSUM62, gap_open_penalty: -11, gap_extend_penalty: -1 }
+    }
+    
+    /// BLOSUM45 with gap open -15 and gap extend -2
+    pub fn blosum45() -> Self {
+        Self { name: "BLOSUM45", scores: &BLOSUM45, gap_open_penalty: -15, gap_extend_penalty: -2 }
+    }
+    
+    /// PAM250 with gap open -14 and gap extend -2
+    pub fn pam250() -> Self {
+        Self { name: "PAM250", scores: &PAM250, gap_open_penalty: -14, gap_extend_penalty: -2 }
+    }
+    
+    /// Look up a built-in matrix by name (case-insensitive)
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name.to_uppercase().as_str() {
+            "BLOSUM62" => Some(Self::blosum62()),
+            "BLOSUM45" => Some(Self::blosum45()),
+            "PAM250" => Some(Self::pam250()),
+            _ => None,
+        }
+    }
+    
+    /// Set the gap penalties
+    pub fn with_gap_penalties(mut self, gap_open_penalty: i32, gap_extend_penalty: i32) -> Self {
+        self.gap_open_penalty = gap_open_penalty;
+        self.gap_extend_penalty = gap_extend_penalty;
+        self
+    }
+    
+    /// Get the matrix name
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+    
+    /// Get the substitution score for a pair of residues
+    pub fn score(&self, a: u8, b: u8) -> i32 {
+        let row = RESIDUE_INDEX[a as usize] as usize;
+        let col = RESIDUE_INDEX[b as usize] as usize;
+        self.scores[row][col] as i32
+    }
+}
+
+/// Scoring used by the alignment functions
+///
+/// Either a flat match/mismatch [`ScoringScheme`] or a [`SubstitutionMatrix`].
+/// Both convert from a reference, so `&scheme` and `&matrix` can be passed
+/// directly to [`align`], [`needleman_wunsch`] and friends.
+#[derive(Debug, Clone, Copy)]
+pub enum Scoring<'a> {
+    /// Flat match/mismatch scores
+    Simple(&'a ScoringScheme),
+    /// Residue-pair scores from a substitution matrix
+    Matrix(&'a SubstitutionMatrix),
+}
+
+impl<'a> Scoring<'a> {
+    /// Get the score for aligning two residues
+    pub fn substitution(&self, a: u8, b: u8) -> i32 {
+        match self {
+            Scoring::Simple(scheme) => {
+                if a == b { scheme.match_score } else { scheme.mismatch_penalty }
+            },
+            Scoring::Matrix(matrix) => matrix.score(a, b),
+        }
+    }
+    
+    /// Get the gap open penalty
+    pub fn gap_open(&self) -> i32 {
+        match self {
+            Scoring::Simple(scheme) => scheme.gap_open_penalty,
+            Scoring::Matrix(matrix) => matrix.gap_open_penalty,
+        }
+    }
+    
+    /// Get the gap extend penalty
+    pub fn gap_extend(&self) -> i32 {
+        match self {
+            Scoring::Simple(scheme) => scheme.gap_extend_penalty,
+            Scoring::Matrix(matrix) => matrix.gap_extend_penalty,
+        }
+    }
+}
+
+impl<'a> From<&'a ScoringScheme> for Scoring<'a> {
+    fn from(scheme: &'a ScoringScheme) -> Self {
+        Scoring::Simple(scheme)
+    }
+}
+
+impl<'a> From<&'a SubstitutionMatrix> for Scoring<'a> {
+    fn from(matrix: &'a SubstitutionMatrix) -> Self {
+        Scoring::Matrix(matrix)
+    }
+}
+
 /// Represents an alignment between two sequences
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Alignment {
     /// First sequence aligned (with gaps)
     pub seq1_aligned: Vec<u8>,
@@ -81,6 +306,57 @@ impl Alignment {
         };
     }
     
+    /// Get the CIGAR string of the aligned columns, treating seq1 as the reference
+    ///
+    /// Columns with residues in both sequences are `M` (match or mismatch), a
+    /// gap in seq1 is an insertion `I` and a gap in seq2 is a deletion `D`.
+    /// Use [`to_cigar_clipped`](Self::to_cigar_clipped) to include the
+    /// unaligned ends of a local alignment.
+    pub fn to_cigar(&self) -> String {
+        format_cigar(&self.cigar_ops())
+    }
+    
+    /// Get the CIGAR string with the unaligned ends of seq1 soft-clipped
+    ///
+    /// `seq1_len` is the full length of seq1. Bases before `seq1_start` and
+    /// from `seq1_end` on, as left by local alignment, become leading and
+    /// trailing `S` operations around the operations of
+    /// [`to_cigar`](Self::to_cigar).
+    pub fn to_cigar_clipped(&self, seq1_len: usize) -> String {
+        let mut ops = self.cigar_ops();
+        
+        if self.seq1_start > 0 {
+            ops.insert(0, ('S', self.seq1_start));
+        }
+        let trailing = seq1_len.saturating_sub(self.seq1_end);
+        if trailing > 0 {
+            ops.push(('S', trailing));
+        }
+        
+        format_cigar(&ops)
+    }
+    
+    /// Run-length encode the aligned columns as CIGAR operations
+    fn cigar_ops(&self) -> Vec<(char, usize)> {
+        let mut ops: Vec<(char, usize)> = Vec::new();
+        
+        for (&a, &b) in self.seq1_aligned.iter().zip(&self.seq2_aligned) {
+            let op = match (a, b) {
+                (b'-', b'-') => continue,
+                (b'-', _) => 'I',
+                (_, b'-') => 'D',
+                _ => 'M',
+            };
+            
+            match ops.last_mut() {
+                Some((last, count)) if *last == op => *count += 1,
+                _ => ops.push((op, 1)),
+            }
+        }
+        
+        ops
+    }
+    
     /// Get the alignment as a formatted string
     pub fn format(&self) -> String {
         let mut result = String::new();
@@ -107,7 +383,7 @@ impl Alignment {
             // Match line
             result.push_str("      ");
             for j in i..end {
-                if self.seq1_aligned[j] == self.seq2_aligned[j] && self.seq1_aligned[j] != b'-' {
+                if is_identical_column(self.seq1_aligned[j], self.seq2_aligned[j]) {
                     result.push('|');
                 } else {
                     result.push(' ');
@@ -127,15 +403,93 @@ impl Alignment {
         
         result
     }
+    
+    /// Get the alignment in CLUSTAL format
+    ///
+    /// Rows are interleaved in blocks of 60 columns under a `CLUSTAL`
+    /// header, with names padded to a common width and followed by a
+    /// conservation line: `*` for identical residues, `:` and `.` for
+    /// residues in the same strong or weak Gonnet group, as ClustalW writes
+    /// them. Whitespace in names is replaced by `_`.
+    pub fn to_clustal(&self, name1: &str, name2: &str) -> String {
+        const LINE_WIDTH: usize = 60;
+        
+        let names: Vec<String> = [name1, name2]
+            .iter()
+            .map(|name| name.split_whitespace().collect::<Vec<_>>().join("_"))
+            .collect();
+        let name_width = names.iter().map(|name| name.len()).max().unwrap_or(0) + 6;
+        
+        let mut result = String::from("CLUSTAL W multiple sequence alignment\n\n");
+        let total_len = self.seq1_aligned.len();
+        
+        for i in (0..total_len).step_by(LINE_WIDTH) {
+            let end = cmp::min(i + LINE_WIDTH, total_len);
+            
+            result.push('\n');
+            for (name, row) in names.iter().zip([&self.seq1_aligned, &self.seq2_aligned]) {
+                result.push_str(&format!("{:<width$}", name, width = name_width));
+                result.push_str(&String::from_utf8_lossy(&row[i..end]));
+                result.push('\n');
+            }
+            
+            // Conservation line
+            result.push_str(&" ".repeat(name_width));
+            for j in i..end {
+                result.push(clustal_conservation(self.seq1_aligned[j], self.seq2_aligned[j]));
+            }
+            result.push('\n');
+        }
+        
+        result
+    }
+}
+
+/// Check whether an alignment column holds the same residue in both rows
+fn is_identical_column(a: u8, b: u8) -> bool {
+    a == b && a != b'-'
+}
+
+/// Residue groups ClustalW marks with `:` (strong) and `.` (weak)
+const CLUSTAL_STRONG_GROUPS: [&[u8]; 9] = [
+    b"STA", b"NEQK", b"NHQK", b"NDEQ", b"QHRK", b"MILV", b"MILF", b"HY", b"FYW",
+];
+const CLUSTAL_WEAK_GROUPS: [&[u8]; 11] = [
+    b"CSA", b"ATV", b"SAG", b"STNK", b"STPA", b"SGND", b"SNDEQK", b"NDEQHK", b"NEQHRK", b"FVLIM", b"HFY",
+];
+
+/// Get the CLUSTAL conservation symbol for a column
+fn clustal_conservation(a: u8, b: u8) -> char {
+    let (a, b) = (a.to_ascii_uppercase(), b.to_ascii_uppercase());
+    let in_same_group = |groups: &[&[u8]]| groups.iter().any(|group| group.contains(&a) && group.contains(&b));
+    
+    if a == b'-' || b == b'-' {
+        ' '
+    } else if is_identical_column(a, b) {
+        '*'
+    } else if in_same_group(&CLUSTAL_STRONG_GROUPS) {
+        ':'
+    } else if in_same_group(&CLUSTAL_WEAK_GROUPS) {
+        '.'
+    } else {
+        ' '
+    }
+}
+
+/// Format CIGAR operations as a string such as `5M1I3M`
+fn format_cigar(ops: &[(char, usize)]) -> String {
+    ops.iter().map(|(op, count)| format!("{}{}", count, op)).collect()
 }
 
 /// Perform sequence alignment using the specified algorithm
-pub fn align(
+pub fn align<'a>(
     seq1: &[u8],
     seq2: &[u8],
     alignment_type: AlignmentType,
-    scoring: &ScoringScheme,
+    scoring: impl Into<Scoring<'a>>,
 ) -> ComputeResult<Alignment> {
+    let scoring = scoring.into();
+    
     match alignment_type {
         AlignmentType::Global => needleman_wunsch(seq1, seq2, scoring),
         AlignmentType::Local => smith_waterman(seq1, seq2, scoring),
@@ -143,92 +497,230 @@ pub fn align(
     }
 }
 
+/// Align many sequence pairs in parallel on the global thread pool
+///
+/// Results are in the same order as `pairs`. If any pair fails, the error of
+/// the first failing pair is returned with its index in the message.
+pub fn align_batch<'a>(
+    pairs: &[(&[u8], &[u8])],
+    alignment_type: AlignmentType,
+    scoring: impl Into<Scoring<'a>>,
+) -> ComputeResult<Vec<Alignment>> {
+    let scoring = scoring.into();
+    let items: Vec<(usize, &[u8], &[u8])> = pairs
+        .iter()
+        .enumerate()
+        .map(|(index, &(seq1, seq2))| (index, seq1, seq2))
+        .collect();
+    
+    adaptive_parallel_execute(items, move |&(index, seq1, seq2)| {
+        align(seq1, seq2, alignment_type, scoring).map_err(|e| with_pair_index(e, index))
+    })
+    .into_iter()
+    .collect()
+}
+
+/// Align several sequences with the center-star method
+///
+/// Every pair is aligned globally with [`needleman_wunsch`]; the center is
+/// the sequence with the highest summed score against the others (the
+/// first one on ties). Each other sequence is then placed against the
+/// center, and gaps opened in the center by any pairwise alignment are
+/// inserted into every row ("once a gap, always a gap"). Rows are returned
+/// in input order and all have the same length. Inputs should not already
+/// contain `-`.
+///
+/// The pairs are aligned on the calling thread, so no thread pool is needed.
+pub fn star_align<'a>(
+    seqs: &[&[u8]],
+    scoring: impl Into<Scoring<'a>>,
+) -> ComputeResult<Vec<Vec<u8>>> {
+    let scoring = scoring.into();
+    match seqs {
+        [] => return Err(ComputeError::InvalidInput("No sequences to align".to_string())),
+        [[]] => {
+            return Err(ComputeError::InvalidInput("Sequences cannot be empty".to_string()))
+        },
+        [seq] => return Ok(vec![seq.to_vec()]),
+        _ => {}
+    }
+    
+    let alignments: Vec<Alignment> = (0..seqs.len())
+        .flat_map(|i| (i + 1..seqs.len()).map(move |j| (seqs[i], seqs[j])))
+        .map(|(seq1, seq2)| needleman_wunsch(seq1, seq2, scoring))
+        .collect::<ComputeResult<_>>()?;
+    
+    // Pairs are ordered (0, 1), (0, 2), ..., (1, 2), ...
+    let pair_index = |i: usize, j: usize| i * seqs.len() - i * (i + 1) / 2 + (j - i - 1);
+    let mut totals = vec![0i64; seqs.len()];
+    for i in 0..seqs.len() {
+        for j in i + 1..seqs.len() {
+            let score = alignments[pair_index(i, j)].score as i64;
+            totals[i] += score;
+            totals[j] += score;
+        }
+    }
+    let center = (0..seqs.len()).fold(0, |best, i| if totals[i] > totals[best] { i } else { best });
+    
+    // Each other sequence against the center, as (center row, other row)
+    let against_center: Vec<(usize, &[u8], &[u8])> = (0..seqs.len())
+        .filter(|&k| k != center)
+        .map(|k| {
+            let alignment = &alignments[pair_index(center.min(k), center.max(k))];
+            if center < k {
+                (k, alignment.seq1_aligned.as_slice(), alignment.seq2_aligned.as_slice())
+            } else {
+                (k, alignment.seq2_aligned.as_slice(), alignment.seq1_aligned.as_slice())
+            }
+        })
+        .collect();
+    
+    // Most gaps any alignment opens before each center residue (and at the end)
+    let center_len = seqs[center].len();
+    let mut gaps = vec![0usize; center_len + 1];
+    for &(_, center_row, _) in &against_center {
+        for (slot, count) in gaps_before_residues(center_row, center_len).into_iter().enumerate() {
+            gaps[slot] = gaps[slot].max(count);
+        }
+    }
+    
+    let mut rows = vec![Vec::new(); seqs.len()];
+    rows[center] = merge_star_row(seqs[center], seqs[center], &gaps);
+    for (k, center_row, other_row) in against_center {
+        rows[k] = merge_star_row(center_row, other_row, &gaps);
+    }
+    
+    Ok(rows)
+}
+
+/// Count the gaps in an aligned center row before each of its residues
+fn gaps_before_residues(center_row: &[u8], center_len: usize) -> Vec<usize> {
+    let mut counts = vec![0usize; center_len + 1];
+    let mut residue = 0;
+    for &c in center_row {
+        if c == b'-' {
+            counts[residue] += 1;
+        } else {
+            residue += 1;
+        }
+    }
+    counts
+}
+
+/// Lay out one row of a star alignment against the merged center gaps
+///
+/// Columns where this row's pairwise alignment has a gap in the center are
+/// kept first; the remaining gap columns other alignments opened at the
+/// same position are padded with `-`.
+fn merge_star_row(center_row: &[u8], row: &[u8], gaps: &[usize]) -> Vec<u8> {
+    let width = gaps.iter().sum::<usize>() + gaps.len() - 1;
+    let mut merged = Vec::with_capacity(width);
+    let mut residue = 0;
+    let mut inserted = 0;
+    
+    for (&c, &r) in center_row.iter().zip(row) {
+        if c == b'-' {
+            inserted += 1;
+        } else {
+            merged.resize(merged.len() + gaps[residue] - inserted, b'-');
+            residue += 1;
+            inserted = 0;
+        }
+        merged.push(r);
+    }
+    merged.resize(merged.len() + gaps[residue] - inserted, b'-');
+    
+    merged
+}
+
+/// Prefix an error message with the index of the pair that caused it
+fn with_pair_index(error: ComputeError, index: usize) -> ComputeError {
+    let context = |msg: String| format!("pair {}: {}", index, msg);
+    match error {
+        ComputeError::InvalidInput(msg) => ComputeError::InvalidInput(context(msg)),
+        ComputeError::ComputationError(msg) => ComputeError::ComputationError(context(msg)),
+        ComputeError::UnsupportedOperation(msg) => ComputeError::UnsupportedOperation(context(msg)),
+        ComputeError::ResourceLimitExceeded(msg) => ComputeError::ResourceLimitExceeded(context(msg)),
+    }
+}
+
 /// Perform global alignment using the Needleman-Wunsch algorithm
-pub fn needleman_wunsch(
+///
+/// Uses Gotoh's three-matrix formulation so that affine gap penalties are
+/// applied exactly: a gap of length `k` costs `gap_open_penalty +
+/// (k - 1) * gap_extend_penalty`.
+pub fn needleman_wunsch<'a>(
     seq1: &[u8],
     seq2: &[u8],
-    scoring: &ScoringScheme,
+    scoring: impl Into<Scoring<'a>>,
 ) -> ComputeResult<Alignment> {
     if seq1.is_empty() || seq2.is_empty() {
         return Err(ComputeError::InvalidInput("Sequences cannot be empty".to_string()));
     }
     
+    let scoring = scoring.into();
+    
     let m = seq1.len();
     let n = seq2.len();
     
-    // Initialize scoring matrix
-    let mut dp = vec![vec![0; n + 1]; m + 1];
+    // Score matrices: M (ends in match/mismatch), Ix (ends in a gap in seq2),
+    // Iy (ends in a gap in seq1)
+    const NEG_INF: i32 = i32::MIN / 2;
+    let mut mat_m = vec![vec![NEG_INF; n + 1]; m + 1];
+    let mut mat_ix = vec![vec![NEG_INF; n + 1]; m + 1];
+    let mut mat_iy = vec![vec![NEG_INF; n + 1]; m + 1];
     
-    // Initialize traceback matrix
-    // 0 = diagonal (match/mismatch), 1 = left (gap in seq1), 2 = up (gap in seq2)
-    let mut traceback = vec![vec![0; n + 1]; m + 1];
+    // Traceback matrices: the matrix each cell came from (0 = M, 1 = Ix, 2 = Iy)
+    let mut tb_m = vec![vec![0u8; n + 1]; m + 1];
+    let mut tb_ix = vec![vec![0u8; n + 1]; m + 1];
+    let mut tb_iy = vec![vec![0u8; n + 1]; m + 1];
     
     // Initialize first row and column with gap penalties
-    dp[0][0] = 0;
+    mat_m[0][0] = 0;
     for i in 1..=m {
-        if i == 1 {
-            dp[i][0] = scoring.gap_open_penalty;
-        } else {
-            dp[i][0] = dp[i-1][0] + scoring.gap_extend_penalty;
-        }
-        traceback[i][0] = 2; // gap in seq2
+        mat_ix[i][0] = scoring.gap_open() + (i as i32 - 1) * scoring.gap_extend();
+        tb_ix[i][0] = if i == 1 { 0 } else { 1 };
     }
     
     for j in 1..=n {
-        if j == 1 {
-            dp[0][j] = scoring.gap_open_penalty;
-        } else {
-            dp[0][j] = dp[0][j-1] + scoring.gap_extend_penalty;
-        }
-        traceback[0][j] = 1; // gap in seq1
+        mat_iy[0][j] = scoring.gap_open() + (j as i32 - 1) * scoring.gap_extend();
+        tb_iy[0][j] = if j == 1 { 0 } else { 2 };
     }
     
-    // Fill the DP matrix
+    // Fill the DP matrices
     for i in 1..=m {
         for j in 1..=n {
             // Calculate match/mismatch score
-            let match_score = if seq1[i-1] == seq2[j-1] {
-                scoring.match_score
-            } else {
-                scoring.mismatch_penalty
-            };
+            let match_score = scoring.substitution(seq1[i-1], seq2[j-1]);
             
-            // Calculate scores for each possible move
-            let diagonal = dp[i-1][j-1] + match_score;
-            
-            // Gap in seq1 (horizontal move)
-            let left_score = if traceback[i][j-1] == 1 {
-                // Extend existing gap
-                dp[i][j-1] + scoring.gap_extend_penalty
-            } else {
-                // Open new gap
-                dp[i][j-1] + scoring.gap_open_penalty
-            };
+            // Match/mismatch: best of any state at the diagonal neighbor
+            let (best, from) = best_of_three(mat_m[i-1][j-1], mat_ix[i-1][j-1], mat_iy[i-1][j-1]);
+            mat_m[i][j] = best + match_score;
+            tb_m[i][j] = from;
             
-            // Gap in seq2 (vertical move)
-            let up_score = if traceback[i-1][j] == 2 {
-                // Extend existing gap
-                dp[i-1][j] + scoring.gap_extend_penalty
-            } else {
-                // Open new gap
-                dp[i-1][j] + scoring.gap_open_penalty
-            };
+            // Gap in seq2 (vertical move): open from M/Iy or extend Ix
+            let (best, from) = best_of_three(
+                mat_m[i-1][j] + scoring.gap_open(),
+                mat_ix[i-1][j] + scoring.gap_extend(),
+                mat_iy[i-1][j] + scoring.gap_open(),
+            );
+            mat_ix[i][j] = best;
+            tb_ix[i][j] = from;
             
-            // Choose the best score
-            if diagonal >= left_score && diagonal >= up_score {
-                dp[i][j] = diagonal;
-                traceback[i][j] = 0; // diagonal
-            } else if left_score >= up_score {
-                dp[i][j] = left_score;
-                traceback[i][j] = 1; // left
-            } else {
-                dp[i][j] = up_score;
-                traceback[i][j] = 2; // up
-            }
+            // Gap in seq1 (horizontal move): open from M/Ix or extend Iy
+            let (best, from) = best_of_three(
+                mat_m[i][j-1] + scoring.gap_open(),
+                mat_ix[i][j-1] + scoring.gap_open(),
+                mat_iy[i][j-1] + scoring.gap_extend(),
+            );
+            mat_iy[i][j] = best;
+            tb_iy[i][j] = from;
         }
     }
     
-    // Traceback to construct the alignment
+    // Traceback to construct the alignment, starting from the best final state
+    let (score, mut state) = best_of_three(mat_m[m][n], mat_ix[m][n], mat_iy[m][n]);
+    
     let mut aligned_seq1 = Vec::new();
     let mut aligned_seq2 = Vec::new();
     
@@ -236,22 +728,25 @@ pub fn needleman_wunsch(
     let mut j = n;
     
     while i > 0 || j > 0 {
-        if i > 0 && j > 0 && traceback[i][j] == 0 {
+        if state == 0 && i > 0 && j > 0 {
             // Diagonal move (match/mismatch)
             aligned_seq1.push(seq1[i-1]);
             aligned_seq2.push(seq2[j-1]);
+            state = tb_m[i][j];
             i -= 1;
             j -= 1;
-        } else if j > 0 && traceback[i][j] == 1 {
-            // Left move (gap in seq1)
-            aligned_seq1.push(b'-');
-            aligned_seq2.push(seq2[j-1]);
-            j -= 1;
-        } else if i > 0 && traceback[i][j] == 2 {
+        } else if state == 1 && i > 0 {
             // Up move (gap in seq2)
             aligned_seq1.push(seq1[i-1]);
             aligned_seq2.push(b'-');
+            state = tb_ix[i][j];
             i -= 1;
+        } else if state == 2 && j > 0 {
+            // Left move (gap in seq1)
+            aligned_seq1.push(b'-');
+            aligned_seq2.push(seq2[j-1]);
+            state = tb_iy[i][j];
+            j -= 1;
         } else {
             // Should not happen with properly initialized traceback
             break;
@@ -266,7 +761,7 @@ pub fn needleman_wunsch(
     let mut alignment = Alignment {
         seq1_aligned: aligned_seq1,
         seq2_aligned: aligned_seq2,
-        score: dp[m][n],
+        score,
         seq1_start: 0,
         seq1_end: m,
         seq2_start: 0,
@@ -280,115 +775,377 @@ pub fn needleman_wunsch(
     Ok(alignment)
 }
 
-/// Perform local alignment using the Smith-Waterman algorithm
-pub fn smith_waterman(
+/// Perform global alignment in linear space using Hirschberg's algorithm
+///
+/// Uses the Myers-Miller divide-and-conquer formulation so affine gaps are
+/// scored exactly as in [`needleman_wunsch`]. Only O(min(m, n)) memory is
+/// used for the DP rows. The score always matches `needleman_wunsch`, and so
+/// do the aligned rows whenever the optimal alignment is unique; when several
+/// alignments share the optimal score, the two may break the tie differently.
+/// The gap open penalty must be at least as severe as the extend penalty.
+pub fn hirschberg_align<'a>(
     seq1: &[u8],
     seq2: &[u8],
-    scoring: &ScoringScheme,
+    scoring: impl Into<Scoring<'a>>,
 ) -> ComputeResult<Alignment> {
     if seq1.is_empty() || seq2.is_empty() {
         return Err(ComputeError::InvalidInput("Sequences cannot be empty".to_string()));
     }
     
-    let m = seq1.len();
-    let n = seq2.len();
+    let scoring = scoring.into();
     
-    // Initialize scoring matrix
-    let mut dp = vec![vec![0; n + 1]; m + 1];
+    if scoring.gap_open() > scoring.gap_extend() {
+        return Err(ComputeError::InvalidInput(
+            "Gap open penalty must not be smaller than the gap extend penalty".to_string()
+        ));
+    }
     
-    // Initialize traceback matrix
-    // 0 = diagonal (match/mismatch), 1 = left (gap in seq1), 2 = up (gap in seq2), 3 = stop
-    let mut traceback = vec![vec![3; n + 1]; m + 1];
+    // Keep the rows sized by the shorter sequence; scoring is symmetric
+    let swapped = seq2.len() > seq1.len();
+    let (a, b) = if swapped { (seq2, seq1) } else { (seq1, seq2) };
     
-    // Fill the DP matrix
-    let mut max_score = 0;
-    let mut max_i = 0;
-    let mut max_j = 0;
+    let gaps = AffineGaps::new(&scoring);
+    let mut aligned_a = Vec::with_capacity(a.len() + b.len());
+    let mut aligned_b = Vec::with_capacity(a.len() + b.len());
+    hirschberg_divide(a, b, &scoring, &gaps, gaps.open, gaps.open, &mut aligned_a, &mut aligned_b);
     
-    for i in 1..=m {
-        for j in 1..=n {
-            // Calculate match/mismatch score
-            let match_score = if seq1[i-1] == seq2[j-1] {
-                scoring.match_score
-            } else {
-                scoring.mismatch_penalty
-            };
-            
-            // Calculate scores for each possible move
-            let diagonal = dp[i-1][j-1] + match_score;
-            
-            // Gap in seq1 (horizontal move)
-            let left_score = dp[i][j-1] + (if traceback[i][j-1] == 1 {
-                scoring.gap_extend_penalty
-            } else {
-                scoring.gap_open_penalty
-            });
-            
-            // Gap in seq2 (vertical move)
-            let up_score = dp[i-1][j] + (if traceback[i-1][j] == 2 {
-                scoring.gap_extend_penalty
-            } else {
-                scoring.gap_open_penalty
-            });
-            
-            // Local alignment allows stopping at any point
-            let scores = [0, diagonal, left_score, up_score];
-            let max_idx = scores.iter().enumerate()
-                .max_by_key(|&(_, &score)| score)
-                .map(|(idx, _)| idx)
-                .unwrap();
-            
-            dp[i][j] = scores[max_idx];
-            
-            // Set traceback based on the chosen move
-            traceback[i][j] = match max_idx {
-                0 => 3, // stop (local alignment can start/end anywhere)
-                1 => 0, // diagonal
-                2 => 1, // left
-                3 => 2, // up
-                _ => unreachable!(),
-            };
-            
-            // Keep track of the maximum score for starting the traceback
-            if dp[i][j] > max_score {
-                max_score = dp[i][j];
-                max_i = i;
-                max_j = j;
-            }
-        }
-    }
+    let (aligned_seq1, aligned_seq2) = if swapped {
+        (aligned_b, aligned_a)
+    } else {
+        (aligned_a, aligned_b)
+    };
     
-    // Traceback to construct the alignment
-    let mut aligned_seq1 = Vec::new();
-    let mut aligned_seq2 = Vec::new();
+    let mut alignment = Alignment {
+        score: score_global_alignment(&aligned_seq1, &aligned_seq2, &scoring),
+        seq1_aligned: aligned_seq1,
+        seq2_aligned: aligned_seq2,
+        seq1_start: 0,
+        seq1_end: seq1.len(),
+        seq2_start: 0,
+        seq2_end: seq2.len(),
+        identity: 0.0,
+    };
     
-    let mut i = max_i;
-    let mut j = max_j;
+    alignment.calculate_identity();
     
-    // Record the end positions for local alignment
-    let seq1_end = i;
-    let seq2_end = j;
+    Ok(alignment)
+}
+
+/// Gap penalties split as `gap(k) = open + k * extend`
+struct AffineGaps {
+    open: i32,
+    extend: i32,
+}
+
+impl AffineGaps {
+    fn new(scoring: &Scoring) -> Self {
+        Self {
+            open: scoring.gap_open() - scoring.gap_extend(),
+            extend: scoring.gap_extend(),
+        }
+    }
     
-    // Traceback until we hit a cell with score 0 or a "stop" traceback
-    while i > 0 && j > 0 && dp[i][j] > 0 && traceback[i][j] != 3 {
-        if traceback[i][j] == 0 {
-            // Diagonal move (match/mismatch)
-            aligned_seq1.push(seq1[i-1]);
-            aligned_seq2.push(seq2[j-1]);
-            i -= 1;
-            j -= 1;
-        } else if traceback[i][j] == 1 {
-            // Left move (gap in seq1)
-            aligned_seq1.push(b'-');
-            aligned_seq2.push(seq2[j-1]);
-            j -= 1;
-        } else if traceback[i][j] == 2 {
-            // Up move (gap in seq2)
-            aligned_seq1.push(seq1[i-1]);
-            aligned_seq2.push(b'-');
-            i -= 1;
+    /// Score of a gap of length `k` (zero for an empty gap)
+    fn gap(&self, k: usize) -> i32 {
+        if k == 0 { 0 } else { self.open + k as i32 * self.extend }
+    }
+}
+
+/// Align `a` against `b`, appending the columns to the output
+///
+/// `open_start` and `open_end` are the open penalties charged to a gap in `b`
+/// (a run of deletions from `a`) at either end; they are zero when the gap
+/// continues one already opened by the enclosing subproblem.
+#[allow(clippy::too_many_arguments)]
+fn hirschberg_divide(
+    a: &[u8],
+    b: &[u8],
+    scoring: &Scoring,
+    gaps: &AffineGaps,
+    open_start: i32,
+    open_end: i32,
+    out_a: &mut Vec<u8>,
+    out_b: &mut Vec<u8>,
+) {
+    let m = a.len();
+    let n = b.len();
+    
+    // Base cases: one side empty, or a single residue left in `a`
+    if n == 0 {
+        out_a.extend_from_slice(a);
+        out_b.extend(std::iter::repeat_n(b'-', m));
+        return;
+    }
+    if m == 0 {
+        out_a.extend(std::iter::repeat_n(b'-', n));
+        out_b.extend_from_slice(b);
+        return;
+    }
+    if m == 1 {
+        // Either delete the residue and insert all of `b`, or align it to the best column
+        let mut best = cmp::max(open_start, open_end) + gaps.extend + gaps.gap(n);
+        let mut best_j = 0;
+        for j in 1..=n {
+            let score = gaps.gap(j - 1) + scoring.substitution(a[0], b[j - 1]) + gaps.gap(n - j);
+            if score > best {
+                best = score;
+                best_j = j;
+            }
+        }
+        
+        if best_j == 0 {
+            // Place the deletion next to whichever neighbouring gap it extends
+            if open_start >= open_end {
+                out_a.push(a[0]);
+                out_b.push(b'-');
+                out_a.extend(std::iter::repeat_n(b'-', n));
+                out_b.extend_from_slice(b);
+            } else {
+                out_a.extend(std::iter::repeat_n(b'-', n));
+                out_b.extend_from_slice(b);
+                out_a.push(a[0]);
+                out_b.push(b'-');
+            }
+        } else {
+            out_a.extend(std::iter::repeat_n(b'-', best_j - 1));
+            out_b.extend_from_slice(&b[..best_j - 1]);
+            out_a.push(a[0]);
+            out_b.push(b[best_j - 1]);
+            out_a.extend(std::iter::repeat_n(b'-', n - best_j));
+            out_b.extend_from_slice(&b[best_j..]);
+        }
+        return;
+    }
+    
+    let mid = m / 2;
+    let (mid_j, through_gap) = hirschberg_split(a, b, mid, scoring, gaps, open_start, open_end);
+    
+    if through_gap {
+        // The optimal path crosses the middle row inside a gap spanning a[mid - 1] and a[mid]
+        hirschberg_divide(&a[..mid - 1], &b[..mid_j], scoring, gaps, open_start, 0, out_a, out_b);
+        out_a.extend_from_slice(&a[mid - 1..=mid]);
+        out_b.extend_from_slice(b"--");
+        hirschberg_divide(&a[mid + 1..], &b[mid_j..], scoring, gaps, 0, open_end, out_a, out_b);
+    } else {
+        hirschberg_divide(&a[..mid], &b[..mid_j], scoring, gaps, open_start, gaps.open, out_a, out_b);
+        hirschberg_divide(&a[mid..], &b[mid_j..], scoring, gaps, gaps.open, open_end, out_a, out_b);
+    }
+}
+
+/// Find where the optimal path crosses row `mid` of `a`
+///
+/// Returns the column and whether the crossing happens inside a gap in `b`.
+fn hirschberg_split(
+    a: &[u8],
+    b: &[u8],
+    mid: usize,
+    scoring: &Scoring,
+    gaps: &AffineGaps,
+    open_start: i32,
+    open_end: i32,
+) -> (usize, bool) {
+    const NEG_INF: i32 = i32::MIN / 2;
+    let n = b.len();
+    
+    // Forward pass over a[..mid]: best score overall (cc) and ending in a deletion (dd)
+    let mut cc = vec![0; n + 1];
+    let mut dd = vec![NEG_INF; n + 1];
+    for (j, score) in cc.iter_mut().enumerate().skip(1) {
+        *score = gaps.gap(j);
+    }
+    
+    let mut t = open_start;
+    for &residue in &a[..mid] {
+        let mut diag = cc[0];
+        t += gaps.extend;
+        let mut c = t;
+        cc[0] = c;
+        let mut ins = NEG_INF;
+        
+        for j in 1..=n {
+            ins = cmp::max(ins, c + gaps.open) + gaps.extend;
+            dd[j] = cmp::max(dd[j], cc[j] + gaps.open) + gaps.extend;
+            c = cmp::max(diag + scoring.substitution(residue, b[j - 1]), cmp::max(dd[j], ins));
+            diag = cc[j];
+            cc[j] = c;
+        }
+    }
+    dd[0] = cc[0];
+    
+    // Reverse pass over a[mid..]: best score overall (rr) and starting with a deletion (ss)
+    let mut rr = vec![0; n + 1];
+    let mut ss = vec![NEG_INF; n + 1];
+    for (j, score) in rr.iter_mut().enumerate().take(n) {
+        *score = gaps.gap(n - j);
+    }
+    
+    let mut t = open_end;
+    for &residue in a[mid..].iter().rev() {
+        let mut diag = rr[n];
+        t += gaps.extend;
+        let mut c = t;
+        rr[n] = c;
+        let mut ins = NEG_INF;
+        
+        for j in (0..n).rev() {
+            ins = cmp::max(ins, c + gaps.open) + gaps.extend;
+            ss[j] = cmp::max(ss[j], rr[j] + gaps.open) + gaps.extend;
+            c = cmp::max(diag + scoring.substitution(residue, b[j]), cmp::max(ss[j], ins));
+            diag = rr[j];
+            rr[j] = c;
+        }
+    }
+    ss[n] = rr[n];
+    
+    // Join the halves, counting a gap that crosses the middle row only once
+    let mut best = NEG_INF;
+    let mut best_j = 0;
+    let mut through_gap = false;
+    for j in 0..=n {
+        if cc[j] + rr[j] > best {
+            best = cc[j] + rr[j];
+            best_j = j;
+            through_gap = false;
         }
     }
+    for j in 0..=n {
+        if dd[j] + ss[j] - gaps.open > best {
+            best = dd[j] + ss[j] - gaps.open;
+            best_j = j;
+            through_gap = true;
+        }
+    }
+    
+    (best_j, through_gap)
+}
+
+/// Score a global alignment column by column with affine gaps
+fn score_global_alignment(aligned1: &[u8], aligned2: &[u8], scoring: &Scoring) -> i32 {
+    let mut score = 0;
+    // 0 = no gap, 1 = gap in seq2, 2 = gap in seq1
+    let mut gap_state = 0;
+    
+    for (&a, &b) in aligned1.iter().zip(aligned2.iter()) {
+        let state = if b == b'-' { 1 } else if a == b'-' { 2 } else { 0 };
+        score += match state {
+            0 => scoring.substitution(a, b),
+            s if s == gap_state => scoring.gap_extend(),
+            _ => scoring.gap_open(),
+        };
+        gap_state = state;
+    }
+    
+    score
+}
+
+/// Perform local alignment using the Smith-Waterman algorithm
+///
+/// Uses the same three-matrix Gotoh formulation as [`needleman_wunsch`], so
+/// a gap of length `k` costs `gap_open_penalty + (k - 1) *
+/// gap_extend_penalty`. Alignments start and end on aligned residues.
+pub fn smith_waterman<'a>(
+    seq1: &[u8],
+    seq2: &[u8],
+    scoring: impl Into<Scoring<'a>>,
+) -> ComputeResult<Alignment> {
+    if seq1.is_empty() || seq2.is_empty() {
+        return Err(ComputeError::InvalidInput("Sequences cannot be empty".to_string()));
+    }
+    
+    let scoring = scoring.into();
+    
+    let m = seq1.len();
+    let n = seq2.len();
+    
+    // Score matrices: M (ends in match/mismatch), Ix (ends in a gap in seq2),
+    // Iy (ends in a gap in seq1). The borders are unreachable; an alignment
+    // starts by opening M from an empty prefix instead.
+    const NEG_INF: i32 = i32::MIN / 2;
+    let mut mat_m = vec![vec![NEG_INF; n + 1]; m + 1];
+    let mut mat_ix = vec![vec![NEG_INF; n + 1]; m + 1];
+    let mut mat_iy = vec![vec![NEG_INF; n + 1]; m + 1];
+    
+    // Traceback matrices: the matrix each cell came from (0 = M, 1 = Ix,
+    // 2 = Iy, 3 = alignment starts here)
+    let mut tb_m = vec![vec![3u8; n + 1]; m + 1];
+    let mut tb_ix = vec![vec![0u8; n + 1]; m + 1];
+    let mut tb_iy = vec![vec![0u8; n + 1]; m + 1];
+    
+    // Fill the DP matrices
+    let mut max_score = 0;
+    let mut max_i = 0;
+    let mut max_j = 0;
+    
+    for i in 1..=m {
+        for j in 1..=n {
+            // Calculate match/mismatch score
+            let match_score = scoring.substitution(seq1[i-1], seq2[j-1]);
+            
+            // Match/mismatch: extend the best diagonal state, or start afresh
+            let (best, from) = best_of_three(mat_m[i-1][j-1], mat_ix[i-1][j-1], mat_iy[i-1][j-1]);
+            if best > 0 {
+                mat_m[i][j] = best + match_score;
+                tb_m[i][j] = from;
+            } else {
+                mat_m[i][j] = match_score;
+                tb_m[i][j] = 3;
+            }
+            
+            // Gap in seq2 (vertical move): open from M/Iy or extend Ix
+            let (best, from) = best_of_three(
+                mat_m[i-1][j] + scoring.gap_open(),
+                mat_ix[i-1][j] + scoring.gap_extend(),
+                mat_iy[i-1][j] + scoring.gap_open(),
+            );
+            mat_ix[i][j] = best;
+            tb_ix[i][j] = from;
+            
+            // Gap in seq1 (horizontal move): open from M/Ix or extend Iy
+            let (best, from) = best_of_three(
+                mat_m[i][j-1] + scoring.gap_open(),
+                mat_ix[i][j-1] + scoring.gap_open(),
+                mat_iy[i][j-1] + scoring.gap_extend(),
+            );
+            mat_iy[i][j] = best;
+            tb_iy[i][j] = from;
+            
+            // Keep track of the maximum score for starting the traceback
+            if mat_m[i][j] > max_score {
+                max_score = mat_m[i][j];
+                max_i = i;
+                max_j = j;
+            }
+        }
+    }
+    
+    // Traceback to construct the alignment
+    let mut aligned_seq1 = Vec::new();
+    let mut aligned_seq2 = Vec::new();
+    
+    let mut i = max_i;
+    let mut j = max_j;
+    
+    // Record the end positions for local alignment
+    let seq1_end = i;
+    let seq2_end = j;
+    
+    // Every move decreases i + j, and a start marker or the matrix edge
+    // ends the traceback, so the loop always terminates
+    let mut state = if max_score > 0 { 0 } else { 3 };
+    while i > 0 && j > 0 {
+        let (step_i, step_j, next) = match state {
+            0 => (1, 1, tb_m[i][j]),  // diagonal move (match/mismatch)
+            1 => (1, 0, tb_ix[i][j]), // up move (gap in seq2)
+            2 => (0, 1, tb_iy[i][j]), // left move (gap in seq1)
+            _ => break,
+        };
+        
+        aligned_seq1.push(if step_i == 1 { seq1[i-1] } else { b'-' });
+        aligned_seq2.push(if step_j == 1 { seq2[j-1] } else { b'-' });
+        i -= step_i;
+        j -= step_j;
+        state = next;
+    }
     
     // Record the start positions for local alignment
     let seq1_start = i;
@@ -421,15 +1178,17 @@ pub fn smith_waterman(
 /// Semi-global alignment is a variation where gaps at the beginning and end
 /// of one sequence are not penalized (useful for aligning a short sequence
 /// to a long one).
-pub fn semi_global_align(
+pub fn semi_global_align<'a>(
     seq1: &[u8],
     seq2: &[u8],
-    scoring: &ScoringScheme,
+    scoring: impl Into<Scoring<'a>>,
 ) -> ComputeResult<Alignment> {
     if seq1.is_empty() || seq2.is_empty() {
         return Err(ComputeError::InvalidInput("Sequences cannot be empty".to_string()));
     }
     
+    let scoring = scoring.into();
+    
     let m = seq1.len();
     let n = seq2.len();
     
@@ -456,27 +1215,23 @@ pub fn semi_global_align(
     for i in 1..=m {
         for j in 1..=n {
             // Calculate match/mismatch score
-            let match_score = if seq1[i-1] == seq2[j-1] {
-                scoring.match_score
-            } else {
-                scoring.mismatch_penalty
-            };
+            let match_score = scoring.substitution(seq1[i-1], seq2[j-1]);
             
             // Calculate scores for each possible move
             let diagonal = dp[i-1][j-1] + match_score;
             
             // Gap in seq1 (horizontal move)
             let left_score = dp[i][j-1] + (if traceback[i][j-1] == 1 {
-                scoring.gap_extend_penalty
+                scoring.gap_extend()
             } else {
-                scoring.gap_open_penalty
+                scoring.gap_open()
             });
             
             // Gap in seq2 (vertical move)
             let up_score = dp[i-1][j] + (if traceback[i-1][j] == 2 {
-                scoring.gap_extend_penalty
+                scoring.gap_extend()
             } else {
-                scoring.gap_open_penalty
+                scoring.gap_open()
             });
             
             // Choose the best score
@@ -520,26 +1275,24 @@ pub fn semi_global_align(
     let mut aligned_seq1 = Vec::new();
     let mut aligned_seq2 = Vec::new();
     
-    let mut i = max_i;
-    let mut j = max_j;
-    
     // Record the end positions
-    let seq1_end = i;
-    let seq2_end = j;
+    let seq1_end = max_i;
+    let seq2_end = max_j;
     
-    // Add gaps at the end if necessary
-    while i < m {
-        aligned_seq1.push(seq1[i]);
+    // Add gaps at the end if necessary (in reverse, like the traceback)
+    for k in (max_i..m).rev() {
+        aligned_seq1.push(seq1[k]);
         aligned_seq2.push(b'-');
-        i += 1;
     }
     
-    while j < n {
+    for k in (max_j..n).rev() {
         aligned_seq1.push(b'-');
-        aligned_seq2.push(seq2[j]);
-        j += 1;
+        aligned_seq2.push(seq2[k]);
     }
     
+    let mut i = max_i;
+    let mut j = max_j;
+    
     // Traceback until we hit the beginning of either sequence
     while i > 0 && j > 0 {
         if traceback[i][j] == 0 {
@@ -561,6 +1314,10 @@ pub fn semi_global_align(
         }
     }
     
+    // Record the start positions where the traceback stopped
+    let seq1_start = i;
+    let seq2_start = j;
+    
     // Add gaps at the beginning if necessary
     while i > 0 {
         aligned_seq1.push(seq1[i-1]);
@@ -574,10 +1331,6 @@ pub fn semi_global_align(
         j -= 1;
     }
     
-    // Record the start positions
-    let seq1_start = 0;
-    let seq2_start = 0;
-    
     // Reverse the alignment (we traced backwards)
     aligned_seq1.reverse();
     aligned_seq2.reverse();
@@ -600,6 +1353,286 @@ pub fn semi_global_align(
     Ok(alignment)
 }
 
+/// Perform banded global alignment with affine gap penalties
+///
+/// Only cells within `band_width` of the main diagonal are computed, reducing
+/// time and memory to O(n * band_width). The band must be at least as wide as
+/// the length difference between the two sequences.
+pub fn banded_global_align(
+    seq1: &[u8],
+    seq2: &[u8],
+    scoring: &ScoringScheme,
+    band_width: usize,
+) -> ComputeResult<Alignment> {
+    banded_align_inner(seq1, seq2, scoring, band_width).map(|(alignment, _)| alignment)
+}
+
+/// Perform banded global alignment with an automatically sized band
+///
+/// The initial band width is estimated from the length difference of the
+/// sequences plus a margin derived from a k-mer based divergence estimate.
+/// If the optimal path touches the edge of the band, the band is doubled and
+/// the alignment retried until the path stays inside the band or the band
+/// covers the full matrix.
+pub fn banded_global_align_auto(
+    seq1: &[u8],
+    seq2: &[u8],
+    scoring: &ScoringScheme,
+) -> ComputeResult<Alignment> {
+    banded_align_widening(seq1, seq2, scoring, estimate_band_width(seq1, seq2))
+}
+
+/// Banded alignment that widens the band until the path no longer touches its edge
+fn banded_align_widening(
+    seq1: &[u8],
+    seq2: &[u8],
+    scoring: &ScoringScheme,
+    initial_width: usize,
+) -> ComputeResult<Alignment> {
+    let full_width = cmp::max(seq1.len(), seq2.len());
+    let length_diff = seq1.len().abs_diff(seq2.len());
+    let mut band_width = cmp::max(initial_width, length_diff).max(1).min(full_width);
+    
+    loop {
+        let (alignment, touches_edge) = banded_align_inner(seq1, seq2, scoring, band_width)?;
+        
+        if !touches_edge || band_width >= full_width {
+            return Ok(alignment);
+        }
+        
+        band_width = cmp::min(band_width * 2, full_width);
+    }
+}
+
+/// Estimate a band width from the length difference and k-mer divergence
+fn estimate_band_width(seq1: &[u8], seq2: &[u8]) -> usize {
+    const KMER_SIZE: usize = 8;
+    const MIN_MARGIN: usize = 8;
+    
+    let length_diff = seq1.len().abs_diff(seq2.len());
+    let (shorter, longer) = if seq1.len() <= seq2.len() { (seq1, seq2) } else { (seq2, seq1) };
+    
+    let k = cmp::min(KMER_SIZE, shorter.len());
+    if k == 0 {
+        return length_diff + MIN_MARGIN;
+    }
+    
+    // Fraction of k-mers in the shorter sequence missing from the longer one
+    let longer_kmers: HashSet<&[u8]> = longer.windows(k).collect();
+    let total = shorter.len() - k + 1;
+    let shared = shorter.windows(k).filter(|kmer| longer_kmers.contains(kmer)).count();
+    let divergence = 1.0 - (shared as f64) / (total as f64);
+    
+    // Each difference destroys up to k k-mers; assume any of them may be an
+    // indel that moves the path away from the diagonal
+    let estimated_differences = (divergence * shorter.len() as f64 / k as f64).ceil() as usize;
+    
+    length_diff + cmp::max(MIN_MARGIN, estimated_differences)
+}
+
+/// Banded Gotoh alignment, returning the alignment and whether the band may
+/// have cut off a better path
+///
+/// The band is considered too narrow when the traceback touches its edge, or
+/// when a path leaving the band through an edge cell could still outscore the
+/// banded optimum (its score so far plus the best possible remainder).
+fn banded_align_inner(
+    seq1: &[u8],
+    seq2: &[u8],
+    scoring: &ScoringScheme,
+    band_width: usize,
+) -> ComputeResult<(Alignment, bool)> {
+    if seq1.is_empty() || seq2.is_empty() {
+        return Err(ComputeError::InvalidInput("Sequences cannot be empty".to_string()));
+    }
+    
+    let m = seq1.len();
+    let n = seq2.len();
+    let w = band_width;
+    
+    if w < m.abs_diff(n) {
+        return Err(ComputeError::InvalidInput(format!(
+            "Band width {} is smaller than the length difference {}",
+            w, m.abs_diff(n)
+        )));
+    }
+    
+    // Row i stores columns i-w..=i+w
+    let row_len = 2 * w + 1;
+    let idx = |i: usize, j: usize| -> Option<usize> {
+        if j + w < i || j > i + w {
+            None
+        } else {
+            Some(i * row_len + j + w - i)
+        }
+    };
+    
+    // Score matrices: M (match/mismatch), X (gap in seq2), Y (gap in seq1)
+    const NEG_INF: i32 = i32::MIN / 2;
+    let size = (m + 1) * row_len;
+    let mut mat_m = vec![NEG_INF; size];
+    let mut mat_x = vec![NEG_INF; size];
+    let mut mat_y = vec![NEG_INF; size];
+    
+    // Traceback: the matrix each cell came from (0 = M, 1 = X, 2 = Y)
+    let mut tb_m = vec![0u8; size];
+    let mut tb_x = vec![0u8; size];
+    let mut tb_y = vec![0u8; size];
+    
+    mat_m[w] = 0;
+    
+    for i in 0..=m {
+        let j_start = i.saturating_sub(w);
+        let j_end = cmp::min(n, i + w);
+        
+        for j in j_start..=j_end {
+            if i == 0 && j == 0 {
+                continue;
+            }
+            
+            let cell = i * row_len + j + w - i;
+            
+            // Match/mismatch (diagonal neighbor is always inside the band)
+            if i > 0 && j > 0 {
+                let prev = (i - 1) * row_len + j + w - i;
+                let (best, from) = best_of_three(mat_m[prev], mat_x[prev], mat_y[prev]);
+                let score = if seq1[i-1] == seq2[j-1] {
+                    scoring.match_score
+                } else {
+                    scoring.mismatch_penalty
+                };
+                mat_m[cell] = best + score;
+                tb_m[cell] = from;
+            }
+            
+            // Gap in seq2 (vertical move)
+            if let Some(prev) = if i > 0 { idx(i - 1, j) } else { None } {
+                let (best, from) = best_of_three(
+                    mat_m[prev] + scoring.gap_open_penalty,
+                    mat_x[prev] + scoring.gap_extend_penalty,
+                    mat_y[prev] + scoring.gap_open_penalty,
+                );
+                mat_x[cell] = best;
+                tb_x[cell] = from;
+            }
+            
+            // Gap in seq1 (horizontal move)
+            if let Some(prev) = if j > 0 { idx(i, j - 1) } else { None } {
+                let (best, from) = best_of_three(
+                    mat_m[prev] + scoring.gap_open_penalty,
+                    mat_x[prev] + scoring.gap_open_penalty,
+                    mat_y[prev] + scoring.gap_extend_penalty,
+                );
+                mat_y[cell] = best;
+                tb_y[cell] = from;
+            }
+        }
+    }
+    
+    // Start the traceback from the best state of the final cell
+    let end = idx(m, n).expect("final cell lies inside the band");
+    let (score, mut state) = best_of_three(mat_m[end], mat_x[end], mat_y[end]);
+    
+    // Upper bound on the score of any path from (i, j) to (m, n)
+    let best_step = cmp::max(scoring.match_score, scoring.mismatch_penalty);
+    let remainder_bound = |i: usize, j: usize| -> i32 {
+        let (rem_i, rem_j) = (m - i, n - j);
+        let gap_len = rem_i.abs_diff(rem_j) as i32;
+        let gap_cost = if gap_len == 0 {
+            0
+        } else {
+            scoring.gap_open_penalty + (gap_len - 1) * scoring.gap_extend_penalty
+        };
+        cmp::min(rem_i, rem_j) as i32 * best_step + gap_cost
+    };
+    
+    // Check the edge cells that have a neighbor outside the band
+    let mut touches_edge = false;
+    for i in 0..=m {
+        let edge_cells = [
+            if i + w < n { Some(i + w) } else { None },
+            if i >= w && i < m { Some(i - w) } else { None },
+        ];
+        for j in edge_cells.into_iter().flatten() {
+            let cell = i * row_len + j + w - i;
+            let (best, _) = best_of_three(mat_m[cell], mat_x[cell], mat_y[cell]);
+            if best > NEG_INF / 2 && best + remainder_bound(i, j) > score {
+                touches_edge = true;
+            }
+        }
+    }
+    
+    let mut aligned_seq1 = Vec::new();
+    let mut aligned_seq2 = Vec::new();
+    
+    let mut i = m;
+    let mut j = n;
+    
+    while i > 0 || j > 0 {
+        if j + w == i || j == i + w {
+            touches_edge = true;
+        }
+        
+        let cell = i * row_len + j + w - i;
+        
+        if state == 0 && i > 0 && j > 0 {
+            // Diagonal move (match/mismatch)
+            aligned_seq1.push(seq1[i-1]);
+            aligned_seq2.push(seq2[j-1]);
+            state = tb_m[cell];
+            i -= 1;
+            j -= 1;
+        } else if state == 1 && i > 0 {
+            // Up move (gap in seq2)
+            aligned_seq1.push(seq1[i-1]);
+            aligned_seq2.push(b'-');
+            state = tb_x[cell];
+            i -= 1;
+        } else if state == 2 && j > 0 {
+            // Left move (gap in seq1)
+            aligned_seq1.push(b'-');
+            aligned_seq2.push(seq2[j-1]);
+            state = tb_y[cell];
+            j -= 1;
+        } else {
+            // Should not happen with properly initialized matrices
+            break;
+        }
+    }
+    
+    // Reverse the alignment (we traced backwards)
+    aligned_seq1.reverse();
+    aligned_seq2.reverse();
+    
+    let mut alignment = Alignment {
+        seq1_aligned: aligned_seq1,
+        seq2_aligned: aligned_seq2,
+        score,
+        seq1_start: 0,
+        seq1_end: m,
+        seq2_start: 0,
+        seq2_end: n,
+        identity: 0.0,
+    };
+    
+    alignment.calculate_identity();
+    
+    Ok((alignment, touches_edge))
+}
+
+/// Pick the best of three scores, preferring earlier entries on ties
+///
+/// Returns the score and the index of the chosen entry.
+fn best_of_three(a: i32, b: i32, c: i32) -> (i32, u8) {
+    if a >= b && a >= c {
+        (a, 0)
+    } else if b >= c {
+        (b, 1)
+    } else {
+        (c, 2)
+    }
+}
+
 /// Calculate the edit distance (Levenshtein distance) between two sequences
 pub fn edit_distance(seq1: &[u8], seq2: &[u8]) -> usize {
     let m = seq1.len();
@@ -659,7 +1692,198 @@ mod tests {
         // ACGT-CGT
         assert_eq!(alignment.seq1_aligned, b"ACGTACGT");
         assert_eq!(alignment.seq2_aligned, b"ACGT-CGT");
-        assert_eq!(alignment.score, 11); // 7 matches * 2 - 1 gap * 2 = 12
+        assert_eq!(alignment.score, 12); // 7 matches * 2 - 1 gap * 2 = 12
+    }
+    
+    #[test]
+    fn test_global_alignment_affine_scores() {
+        let scoring = ScoringScheme::default();
+        
+        // Hand-computed scores (match 2, mismatch -1, gap open -2, extend -1)
+        let cases: &[(&[u8], &[u8], i32)] = &[
+            // 4 matches
+            (b"ACGT", b"ACGT", 8),
+            // 3 matches, 1 mismatch
+            (b"ACGT", b"AGGT", 5),
+            // 4 matches, one gap of length 4: -2 - 3 * 1
+            (b"AAAAAAAA", b"AAAA", 3),
+            // 8 matches, one internal gap of length 3: -2 - 2 * 1
+            (b"ACGTTTTACGT", b"ACGTACGT", 12),
+            // 4 matches, leading gap of length 2: -2 - 1
+            (b"TTACGT", b"ACGT", 5),
+            // 12 matches, two separate gaps of length 1: 2 * -2
+            (b"GATTACAGATTACA", b"GATACAGATACA", 20),
+        ];
+        
+        for &(seq1, seq2, expected) in cases {
+            let alignment = needleman_wunsch(seq1, seq2, &scoring).unwrap();
+            assert_eq!(alignment.score, expected, "{:?} vs {:?}", seq1, seq2);
+            
+            // Swapping the sequences does not change the score
+            let swapped = needleman_wunsch(seq2, seq1, &scoring).unwrap();
+            assert_eq!(swapped.score, expected);
+            
+            // A full-width band gives the same result
+            let banded = banded_global_align(seq1, seq2, &scoring, seq1.len().max(seq2.len())).unwrap();
+            assert_eq!(banded.score, expected);
+        }
+    }
+    
+    #[test]
+    fn test_hirschberg_alignment() {
+        let scoring = ScoringScheme::default();
+        let matrix = SubstitutionMatrix::blosum62();
+        
+        let cases: &[(&[u8], &[u8])] = &[
+            (b"ACGTACGT", b"ACGTCGT"),
+            (b"ACGTTTTACGT", b"ACGTACGT"),
+            (b"GATTACAGATTACA", b"GATACAGATACA"),
+            (b"A", b"TTTTAGGG"),
+            (b"TTTTTTTTAC", b"AC"),
+        ];
+        
+        for &(seq1, seq2) in cases {
+            let expected = needleman_wunsch(seq1, seq2, &scoring).unwrap();
+            let alignment = hirschberg_align(seq1, seq2, &scoring).unwrap();
+            assert_eq!(alignment.score, expected.score, "{:?} vs {:?}", seq1, seq2);
+            assert_eq!(alignment.seq1_aligned.len(), alignment.seq2_aligned.len());
+        }
+        
+        let alignment = hirschberg_align(b"HEAGAWGHEE", b"PAWHEAE", &matrix).unwrap();
+        assert_eq!(alignment.score, needleman_wunsch(b"HEAGAWGHEE", b"PAWHEAE", &matrix).unwrap().score);
+        
+        assert!(hirschberg_align(b"", b"ACGT", &scoring).is_err());
+        
+        let inverted = ScoringScheme { gap_open_penalty: -1, gap_extend_penalty: -2, ..ScoringScheme::default() };
+        assert!(hirschberg_align(b"ACGT", b"ACGT", &inverted).is_err());
+    }
+    
+    /// Count the distinct alignments reaching the optimal global score
+    ///
+    /// Follows the same state transitions as `needleman_wunsch`, so a count
+    /// of one means there is no tie for the traceback to break.
+    fn optimal_alignment_count(seq1: &[u8], seq2: &[u8], scoring: &Scoring) -> u64 {
+        const NEG_INF: i32 = i32::MIN / 2;
+        let (m, n) = (seq1.len(), seq2.len());
+        
+        // Best score and number of ways to reach it, per state (M, Ix, Iy)
+        let mut cells = vec![vec![[(NEG_INF, 0u64); 3]; n + 1]; m + 1];
+        cells[0][0][0] = (0, 1);
+        
+        let best = |options: [(i32, u64); 3]| {
+            let score = options.iter().map(|&(s, _)| s).max().unwrap();
+            let count = options.iter()
+                .filter(|&&(s, _)| s == score)
+                .fold(0u64, |total, &(_, c)| total.saturating_add(c));
+            (score, count)
+        };
+        let add = |(s, c): (i32, u64), delta: i32| (s + delta, c);
+        
+        for i in 0..=m {
+            for j in 0..=n {
+                if i > 0 && j > 0 {
+                    let [m_, x, y] = cells[i - 1][j - 1];
+                    let sub = scoring.substitution(seq1[i - 1], seq2[j - 1]);
+                    cells[i][j][0] = best([add(m_, sub), add(x, sub), add(y, sub)]);
+                }
+                if i > 0 {
+                    let [m_, x, y] = cells[i - 1][j];
+                    cells[i][j][1] = best([add(m_, scoring.gap_open()), add(x, scoring.gap_extend()), add(y, scoring.gap_open())]);
+                }
+                if j > 0 {
+                    let [m_, x, y] = cells[i][j - 1];
+                    cells[i][j][2] = best([add(m_, scoring.gap_open()), add(x, scoring.gap_open()), add(y, scoring.gap_extend())]);
+                }
+            }
+        }
+        
+        best(cells[m][n]).1
+    }
+    
+    proptest::proptest! {
+        #[test]
+        fn prop_hirschberg_matches_needleman_wunsch(
+            seq1 in "[ACGT]{1,40}",
+            seq2 in "[ACGT]{1,40}",
+            gap_open in -6i32..=-1,
+            extend_offset in 0i32..=6,
+        ) {
+            let gap_extend = cmp::min(gap_open + extend_offset, 0);
+            let scoring = ScoringScheme { gap_open_penalty: gap_open, gap_extend_penalty: gap_extend, ..ScoringScheme::default() };
+            let expected = needleman_wunsch(seq1.as_bytes(), seq2.as_bytes(), &scoring).unwrap();
+            let alignment = hirschberg_align(seq1.as_bytes(), seq2.as_bytes(), &scoring).unwrap();
+            
+            // The reported score is recomputed from the columns, so it must reach the optimum
+            proptest::prop_assert_eq!(alignment.score, expected.score);
+            
+            // Removing gaps gives back the inputs
+            let ungapped1: Vec<u8> = alignment.seq1_aligned.iter().copied().filter(|&b| b != b'-').collect();
+            let ungapped2: Vec<u8> = alignment.seq2_aligned.iter().copied().filter(|&b| b != b'-').collect();
+            proptest::prop_assert_eq!(ungapped1, seq1.as_bytes());
+            proptest::prop_assert_eq!(ungapped2, seq2.as_bytes());
+            
+            // Without a tie to break, both produce the same traceback
+            if optimal_alignment_count(seq1.as_bytes(), seq2.as_bytes(), &Scoring::from(&scoring)) == 1 {
+                proptest::prop_assert_eq!(&alignment.seq1_aligned, &expected.seq1_aligned);
+                proptest::prop_assert_eq!(&alignment.seq2_aligned, &expected.seq2_aligned);
+            }
+        }
+        
+        #[test]
+        fn prop_hirschberg_matches_needleman_wunsch_traceback(
+            seq1 in "[ACDEFGHIKLMNPQRSTVWY]{1,30}",
+            edits in proptest::collection::vec((0usize..30, "[ACDEFGHIKLMNPQRSTVWY]{0,3}", 0usize..3), 0..4),
+        ) {
+            // Related sequences under BLOSUM62 rarely tie; keep only the ones that don't
+            let mut seq2 = seq1.clone().into_bytes();
+            for (pos, insert, delete) in edits {
+                let pos = pos % (seq2.len() + 1);
+                let end = cmp::min(pos + delete, seq2.len());
+                seq2.splice(pos..end, insert.bytes());
+            }
+            proptest::prop_assume!(!seq2.is_empty());
+            
+            let matrix = SubstitutionMatrix::blosum62();
+            proptest::prop_assume!(optimal_alignment_count(seq1.as_bytes(), &seq2, &Scoring::from(&matrix)) == 1);
+            
+            let expected = needleman_wunsch(seq1.as_bytes(), &seq2, &matrix).unwrap();
+            let alignment = hirschberg_align(seq1.as_bytes(), &seq2, &matrix).unwrap();
+            proptest::prop_assert_eq!(alignment.score, expected.score);
+            proptest::prop_assert_eq!(alignment.seq1_aligned, expected.seq1_aligned);
+            proptest::prop_assert_eq!(alignment.seq2_aligned, expected.seq2_aligned);
+        }
+    }
+    
+    #[test]
+    fn test_align_batch() {
+        crate::engines::core::parallel::initialize_thread_pool();
+        
+        let scoring = ScoringScheme::default();
+        let seqs: Vec<Vec<u8>> = (0..1500)
+            .map(|i| crate::engines::compute::string_ops::random_dna(20 + i % 13))
+            .collect();
+        let pairs: Vec<(&[u8], &[u8])> = seqs
+            .windows(2)
+            .map(|w| (w[0].as_slice(), w[1].as_slice()))
+            .collect();
+        
+        // Batch results match aligning each pair on its own, in order
+        let batch = align_batch(&pairs, AlignmentType::Local, &scoring).unwrap();
+        assert_eq!(batch.len(), pairs.len());
+        for (alignment, &(seq1, seq2)) in batch.iter().zip(&pairs) {
+            assert_eq!(*alignment, smith_waterman(seq1, seq2, &scoring).unwrap());
+        }
+        
+        // Substitution matrices are shared across threads too
+        let matrix = SubstitutionMatrix::blosum62();
+        let proteins: Vec<(&[u8], &[u8])> = vec![(b"HEAGAWGHEE", b"PAWHEAE"); 20];
+        assert_eq!(align_batch(&proteins, AlignmentType::Global, &matrix).unwrap().len(), 20);
+        
+        // A failing pair reports its index
+        let mut bad = pairs.clone();
+        bad[1234] = (b"", b"ACGT");
+        let err = align_batch(&bad, AlignmentType::Local, &scoring).unwrap_err();
+        assert!(err.to_string().contains("pair 1234"), "{}", err);
     }
     
     #[test]
@@ -678,6 +1902,52 @@ mod tests {
         assert_eq!(alignment.score, 12); // 6 matches * 2 = 12
     }
     
+    #[test]
+    fn test_local_alignment_affine_gaps() {
+        let scoring = ScoringScheme::default();
+        let left = "GATTACACGT";
+        let right = "CCTAGGAATC";
+        let target = format!("{}{}", left, right);
+        
+        // One gap of length 3: 20 matches * 2 - 2 - 2 * 1
+        let long_gap = format!("{}AAA{}", left, right);
+        let alignment = smith_waterman(long_gap.as_bytes(), target.as_bytes(), &scoring).unwrap();
+        assert_eq!(alignment.score, 36);
+        assert_eq!(alignment.seq1_aligned, long_gap.as_bytes());
+        assert_eq!(alignment.seq2_aligned, format!("{}---{}", left, right).as_bytes());
+        
+        // Three separate gaps of length 1 cost more: 20 * 2 - 3 * 2
+        let short_gaps = format!("{}A{}A{}A{}", &left[..4], &left[4..8], &left[8..], right);
+        let alignment = smith_waterman(short_gaps.as_bytes(), target.as_bytes(), &scoring).unwrap();
+        assert_eq!(alignment.score, 34);
+        
+        // A gap right after the first residue is still an opened gap
+        let alignment = smith_waterman(b"GATTTTTTT", b"GTTTTTTT", &scoring).unwrap();
+        assert_eq!(alignment.score, 14);
+        assert_eq!(alignment.seq1_aligned, b"TTTTTTT");
+        
+        // Scores match the global alignment when the whole sequences align
+        let global = needleman_wunsch(long_gap.as_bytes(), target.as_bytes(), &scoring).unwrap();
+        assert_eq!(global.score, 36);
+    }
+    
+    #[test]
+    fn test_local_alignment_uniform() {
+        // Every cell ties along the diagonals; the traceback must still finish
+        let alignment = smith_waterman(b"AAAA", b"AAAA", &ScoringScheme::default()).unwrap();
+        assert_eq!(alignment.seq1_aligned, b"AAAA");
+        assert_eq!(alignment.seq2_aligned, b"AAAA");
+        assert_eq!(alignment.score, 8);
+        assert_eq!((alignment.seq1_start, alignment.seq1_end), (0, 4));
+        assert_eq!((alignment.seq2_start, alignment.seq2_end), (0, 4));
+        
+        // Uniform scores with free gaps make every move tie
+        let flat = ScoringScheme { match_score: 0, mismatch_penalty: 0, gap_open_penalty: 0, gap_extend_penalty: 0 };
+        let alignment = smith_waterman(b"AAAA", b"AAAA", &flat).unwrap();
+        assert_eq!(alignment.score, 0);
+        assert!(alignment.seq1_aligned.is_empty());
+    }
+    
     #[test]
     fn test_semi_global_alignment() {
         let seq1 = b"ACGTACGTACGT";
@@ -690,6 +1960,222 @@ mod tests {
         assert!(alignment.score >= 0);
     }
     
+    #[test]
+    fn test_semi_global_alignment_coordinates() {
+        // Adapter embedded in the middle of a read
+        let read = b"GCATGCTAGCAGATCGGAAGAGCTTGACCA";
+        let adapter = b"AGATCGGAAGAGC";
+        let scoring = ScoringScheme::default();
+        
+        let alignment = semi_global_align(read, adapter, &scoring).unwrap();
+        assert_eq!(alignment.score, 26);
+        assert_eq!((alignment.seq1_start, alignment.seq1_end), (10, 23));
+        assert_eq!((alignment.seq2_start, alignment.seq2_end), (0, adapter.len()));
+        assert_eq!(&read[alignment.seq1_start..alignment.seq1_end], adapter);
+        
+        // The flanking read bases are kept in order as end gaps
+        let ungapped: Vec<u8> = alignment.seq1_aligned.iter().copied().filter(|&b| b != b'-').collect();
+        assert_eq!(ungapped, read);
+        assert_eq!(alignment.seq1_aligned.len(), alignment.seq2_aligned.len());
+    }
+    
+    #[test]
+    fn test_cigar() {
+        let scoring = ScoringScheme::default();
+        
+        // All-match alignments are a single M run
+        let alignment = needleman_wunsch(b"ACGTACGT", b"ACGTACGT", &scoring).unwrap();
+        assert_eq!(alignment.to_cigar(), "8M");
+        assert_eq!(alignment.to_cigar_clipped(8), "8M");
+        
+        // Extra query bases are insertions, missing ones deletions
+        let alignment = Alignment {
+            seq1_aligned: b"ACG-TACGT".to_vec(),
+            seq2_aligned: b"ACGATA--T".to_vec(),
+            score: 0,
+            seq1_start: 0,
+            seq1_end: 8,
+            seq2_start: 0,
+            seq2_end: 7,
+            identity: 0.0,
+        };
+        assert_eq!(alignment.to_cigar(), "3M1I2M2D1M");
+        
+        // Local alignments soft-clip the unaligned flanks of seq1
+        let seq1 = b"TTTTACGTACGGGG";
+        let alignment = smith_waterman(seq1, b"CCACGTACCC", &scoring).unwrap();
+        assert_eq!((alignment.seq1_start, alignment.seq1_end), (4, 10));
+        assert_eq!(alignment.to_cigar(), "6M");
+        assert_eq!(alignment.to_cigar_clipped(seq1.len()), "4S6M4S");
+        
+        let alignment = smith_waterman(b"TTACGTAC", b"ACGTAC", &scoring).unwrap();
+        assert_eq!(alignment.to_cigar_clipped(8), "2S6M");
+        
+        // Terminal gaps of a global alignment are kept as I and D
+        let alignment = Alignment {
+            seq1_aligned: b"--ACGTAC".to_vec(),
+            seq2_aligned: b"GGACGT--".to_vec(),
+            score: 0,
+            seq1_start: 0,
+            seq1_end: 6,
+            seq2_start: 0,
+            seq2_end: 6,
+            identity: 0.0,
+        };
+        assert_eq!(alignment.to_cigar(), "2I4M2D");
+        assert_eq!(alignment.to_cigar_clipped(6), "2I4M2D");
+    }
+    
+    #[test]
+    fn test_clustal_output() {
+        let alignment = Alignment {
+            seq1_aligned: b"MKV-LSTAGF".to_vec(),
+            seq2_aligned: b"MRVALSQWGV".to_vec(),
+            score: 0,
+            seq1_start: 0,
+            seq1_end: 8,
+            seq2_start: 0,
+            seq2_end: 9,
+            identity: 0.0,
+        };
+        
+        let clustal = alignment.to_clustal("query", "subject 1");
+        let lines: Vec<&str> = clustal.lines().collect();
+        assert_eq!(lines[0], "CLUSTAL W multiple sequence alignment");
+        assert_eq!(lines[3], "query          MKV-LSTAGF");
+        assert_eq!(lines[4], "subject_1      MRVALSQWGV");
+        assert_eq!(lines[5], "               *:* **  *.");
+        
+        // Long alignments wrap into 60-column blocks separated by blank lines
+        let long = Alignment {
+            seq1_aligned: vec![b'A'; 130],
+            seq2_aligned: vec![b'A'; 130],
+            ..alignment
+        };
+        let clustal = long.to_clustal("a", "b");
+        let widths: Vec<usize> = clustal.lines()
+            .filter_map(|line| line.strip_prefix("a      "))
+            .map(|row| row.len())
+            .collect();
+        assert_eq!(widths, vec![60, 60, 10]);
+        assert_eq!(clustal.matches("\n\na      ").count(), 3);
+    }
+    
+    #[test]
+    fn test_substitution_matrix() {
+        let blosum62 = SubstitutionMatrix::blosum62();
+        assert_eq!(blosum62.score(b'W', b'W'), 11);
+        assert_eq!(blosum62.score(b'A', b'R'), -1);
+        assert_eq!(blosum62.score(b'r', b'a'), -1);
+        
+        // Unknown residues score like X instead of panicking
+        assert_eq!(blosum62.score(b'J', b'A'), 0);
+        assert_eq!(blosum62.score(b'*', b'*'), 1);
+        
+        assert_eq!(SubstitutionMatrix::blosum45().score(b'C', b'C'), 12);
+        assert_eq!(SubstitutionMatrix::pam250().score(b'W', b'W'), 17);
+        assert_eq!(SubstitutionMatrix::by_name("pam250").unwrap().name(), "PAM250");
+        assert!(SubstitutionMatrix::by_name("BLOSUM99").is_none());
+        
+        // Identical proteins score the sum of the diagonal: M5 K5 T5 A4 Y7 I4 A4 K5
+        let protein = b"MKTAYIAK";
+        let alignment = align(protein, protein, AlignmentType::Global, &blosum62).unwrap();
+        assert_eq!(alignment.score, 39);
+        
+        // Local alignment picks out the conserved core
+        let alignment = smith_waterman(b"GGGWWWGGG", b"PPWWWPP", &blosum62).unwrap();
+        assert_eq!(alignment.seq1_aligned, b"WWW");
+        assert_eq!(alignment.score, 33);
+        
+        // The flat scheme is still accepted
+        let alignment = align(b"ACGT", b"ACGT", AlignmentType::Global, &ScoringScheme::default()).unwrap();
+        assert_eq!(alignment.score, 8);
+    }
+    
+    #[test]
+    fn test_banded_alignment_auto_widening() {
+        // The optimal alignment runs five to six positions off the diagonal
+        let seq1 = b"ACGTTGCATCAGGGGGGCATGACTGAAC";
+        let seq2 = b"ACGTTGCATCAGCATGACTGAACTTTTTT";
+        let scoring = ScoringScheme::default();
+        
+        // A band covering the whole matrix is equivalent to full DP
+        let full = banded_global_align(seq1, seq2, &scoring, seq1.len()).unwrap();
+        let expected = needleman_wunsch(seq1, seq2, &scoring).unwrap();
+        assert_eq!(full.score, expected.score);
+        assert_eq!(full.seq1_aligned, expected.seq1_aligned);
+        assert_eq!(full.seq2_aligned, expected.seq2_aligned);
+        
+        // The band only needs to cover the optimal path, not the whole matrix
+        let covering = banded_global_align(seq1, seq2, &scoring, 6).unwrap();
+        assert_eq!(covering.score, expected.score);
+        assert_eq!(covering.seq1_aligned, expected.seq1_aligned);
+        assert_eq!(covering.seq2_aligned, expected.seq2_aligned);
+        
+        // Same on random pairs with a full-width band
+        for i in 0..50 {
+            let a = crate::engines::compute::string_ops::random_dna(10 + i % 17);
+            let b = crate::engines::compute::string_ops::random_dna(10 + i % 13);
+            let band = cmp::max(a.len(), b.len());
+            let banded = banded_global_align(&a, &b, &scoring, band).unwrap();
+            let expected = needleman_wunsch(&a, &b, &scoring).unwrap();
+            assert_eq!(banded.score, expected.score);
+            assert_eq!(banded.seq1_aligned, expected.seq1_aligned);
+            assert_eq!(banded.seq2_aligned, expected.seq2_aligned);
+        }
+        
+        // A band that is too narrow misses the optimal path
+        let narrow = banded_global_align(seq1, seq2, &scoring, 1).unwrap();
+        assert!(narrow.score < full.score);
+        
+        // Starting from the same narrow band, widening recovers the full-DP result
+        let widened = banded_align_widening(seq1, seq2, &scoring, 1).unwrap();
+        assert_eq!(widened.score, full.score);
+        assert_eq!(widened.seq1_aligned, full.seq1_aligned);
+        assert_eq!(widened.seq2_aligned, full.seq2_aligned);
+        
+        let auto = banded_global_align_auto(seq1, seq2, &scoring).unwrap();
+        assert_eq!(auto.score, full.score);
+        
+        // Bands narrower than the length difference are rejected
+        assert!(banded_global_align(b"ACGTACGT", b"ACG", &scoring, 2).is_err());
+    }
+    
+    #[test]
+    fn test_star_align() {
+        let scoring = ScoringScheme::default();
+        
+        // The exact copies of ACGTACGT tie as center; the first one wins
+        let rows = star_align(&[b"ACGTACGT", b"ACGTCGT", b"ACGTACGT"], &scoring).unwrap();
+        assert_eq!(rows, vec![b"ACGTACGT".to_vec(), b"ACGT-CGT".to_vec(), b"ACGTACGT".to_vec()]);
+        
+        // An insertion in one sequence opens a gap column in all the others
+        let seqs: [&[u8]; 4] = [b"ACGTACGT", b"ACGTAACGT", b"ACGACGT", b"ACGTACGT"];
+        let rows = star_align(&seqs, &scoring).unwrap();
+        assert!(rows.iter().all(|row| row.len() == 9));
+        for (row, seq) in rows.iter().zip(seqs) {
+            let residues: Vec<u8> = row.iter().copied().filter(|&c| c != b'-').collect();
+            assert_eq!(residues, seq);
+        }
+        assert_eq!(rows[0], rows[3]);
+        assert_eq!(rows[0].iter().filter(|&&c| c == b'-').count(), 1);
+        
+        // More pairs than the sequential cutoff of `align_batch`, without a thread pool
+        let seqs: [&[u8]; 6] = [b"ACGTACGT", b"ACGTAACGT", b"ACGACGT", b"ACGTACGT", b"TCGTACGT", b"ACGTACG"];
+        let rows = star_align(&seqs, &scoring).unwrap();
+        assert_eq!(rows.len(), 6);
+        assert!(rows.iter().all(|row| row.len() == rows[0].len()));
+        for (row, seq) in rows.iter().zip(seqs) {
+            let residues: Vec<u8> = row.iter().copied().filter(|&c| c != b'-').collect();
+            assert_eq!(residues, seq);
+        }
+        
+        // A single sequence is its own alignment
+        assert_eq!(star_align(&[b"ACGT"], &scoring).unwrap(), vec![b"ACGT".to_vec()]);
+        assert!(star_align(&[], &scoring).is_err());
+        assert!(star_align(&[b"ACGT", b""], &scoring).is_err());
+    }
+    
     #[test]
     fn test_edit_distance() {
         // Test cases
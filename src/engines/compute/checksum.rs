@@ -0,0 +1,153 @@
+//! Sequence checksums
+//!
+//! This module digests sequence content in the normalized form used by the
+//! SAM `M5` tag, so checksums match `samtools dict` and CRAM references.
+//! Storage is streamed in chunks, so memory-mapped sequences are never
+//! copied whole.
+
+use md5::{Digest, Md5};
+#[cfg(feature = "sha256")]
+use sha2::Sha256;
+
+use crate::engines::storage::StorableSequence;
+
+/// Bytes read at a time from storage without a contiguous slice
+const CHECKSUM_CHUNK_SIZE: usize = 1 << 20;
+
+/// Digest algorithm for sequence checksums
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChecksumAlgorithm {
+    /// MD5, as in the SAM `M5` tag
+    #[default]
+    Md5,
+    /// SHA-256
+    #[cfg(feature = "sha256")]
+    Sha256,
+}
+
+/// Running digest for one algorithm
+#[derive(Clone)]
+enum DigestState {
+    Md5(Md5),
+    #[cfg(feature = "sha256")]
+    Sha256(Sha256),
+}
+
+/// Incremental checksum of sequence bytes
+///
+/// Following the SAM specification for `M5`, bytes outside the printable
+/// range `!`..=`~` (whitespace, line breaks, control characters) are
+/// skipped and lowercase letters are uppercased before digesting. Gap
+/// characters are printable and therefore part of the checksum.
+#[derive(Clone)]
+pub struct SequenceHasher {
+    state: DigestState,
+    /// Normalized copy of the current chunk
+    buffer: Vec<u8>,
+}
+
+impl SequenceHasher {
+    /// Create a hasher for the given algorithm
+    pub fn new(algorithm: ChecksumAlgorithm) -> Self {
+        let state = match algorithm {
+            ChecksumAlgorithm::Md5 => DigestState::Md5(Md5::new()),
+            #[cfg(feature = "sha256")]
+            ChecksumAlgorithm::Sha256 => DigestState::Sha256(Sha256::new()),
+        };
+        Self { state, buffer: Vec::new() }
+    }
+    
+    /// Add a chunk of sequence
+    pub fn update(&mut self, chunk: &[u8]) {
+        self.buffer.clear();
+        self.buffer.extend(
+            chunk
+                .iter()
+                .filter(|&&c| (b'!'..=b'~').contains(&c))
+                .map(|c| c.to_ascii_uppercase()),
+        );
+        
+        match &mut self.state {
+            DigestState::Md5(digest) => digest.update(&self.buffer),
+            #[cfg(feature = "sha256")]
+            DigestState::Sha256(digest) => digest.update(&self.buffer),
+        }
+    }
+    
+    /// Finish the digest as a lowercase hex string
+    pub fn finalize(self) -> String {
+        match self.state {
+            DigestState::Md5(digest) => to_hex(&digest.finalize()),
+            #[cfg(feature = "sha256")]
+            DigestState::Sha256(digest) => to_hex(&digest.finalize()),
+        }
+    }
+}
+
+/// Checksum a sequence held in memory
+pub fn checksum(seq: &[u8], algorithm: ChecksumAlgorithm) -> String {
+    let mut hasher = SequenceHasher::new(algorithm);
+    hasher.update(seq);
+    hasher.finalize()
+}
+
+/// Checksum sequence storage, reading it in chunks if it has no slice
+pub fn checksum_storage(storage: &dyn StorableSequence, algorithm: ChecksumAlgorithm) -> String {
+    if let Some(slice) = storage.as_slice() {
+        return checksum(slice, algorithm);
+    }
+    
+    let mut hasher = SequenceHasher::new(algorithm);
+    let len = storage.len();
+    for start in (0..len).step_by(CHECKSUM_CHUNK_SIZE) {
+        let end = (start + CHECKSUM_CHUNK_SIZE).min(len);
+        hasher.update(&storage.subsequence(start, end));
+    }
+    hasher.finalize()
+}
+
+/// Format digest bytes as lowercase hex
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::core::memory::PackedDnaStorage;
+    
+    #[test]
+    fn test_md5_checksum() {
+        // md5 of "ACGTN", as `samtools dict` reports it
+        let expected = "252fe4e1c9aa67ce660443056dfa3799";
+        assert_eq!(checksum(b"ACGTN", ChecksumAlgorithm::Md5), expected);
+        
+        // Case and whitespace do not change the checksum
+        assert_eq!(checksum(b"acg t\nN\r\n", ChecksumAlgorithm::Md5), expected);
+        assert_eq!(checksum(b"", ChecksumAlgorithm::default()), "d41d8cd98f00b204e9800998ecf8427e");
+        
+        // Chunked updates give the same digest
+        let mut hasher = SequenceHasher::new(ChecksumAlgorithm::Md5);
+        hasher.update(b"AC");
+        hasher.update(b"gtn");
+        assert_eq!(hasher.finalize(), expected);
+        
+        // Storage without a slice is streamed
+        let mut packed = PackedDnaStorage::with_capacity(10);
+        packed.pack(b"ACGTACGTAC");
+        assert!(packed.as_slice().is_none());
+        assert_eq!(
+            checksum_storage(&packed, ChecksumAlgorithm::Md5),
+            checksum(b"ACGTACGTAC", ChecksumAlgorithm::Md5)
+        );
+    }
+    
+    #[cfg(feature = "sha256")]
+    #[test]
+    fn test_sha256_checksum() {
+        assert_eq!(
+            checksum(b"acgtn", ChecksumAlgorithm::Sha256),
+            "d254552eaf2579aa2ecb2a56439c41472f8e7de08ab3f15e898705eada76fc2d"
+        );
+    }
+}
@@ -5,6 +5,8 @@
 
 pub mod string_ops;
 pub mod alignment;
+pub mod pssm;
+pub mod checksum;
 
 use crate::engines::core::parallel::ParallelChunkProcessor;
 
@@ -39,7 +41,7 @@ pub trait ParallelCompute<T, R> {
 /// Base implementation for parallel compute operations
 impl<T, R, F> ParallelCompute<T, R> for F
 where
-    T: Clone + Send + Sync + 'static,
+    T: Send + Sync + 'static,
     R: Send + 'static,
     F: Fn(&T) -> ComputeResult<R> + Sync + Send + Clone + 'static,
 {
@@ -50,19 +52,13 @@ where
             ));
         }
         
-        // Create chunks for parallel processing
+        // Borrow chunks of the input for parallel processing
         let chunk_size = chunk_size.unwrap_or(1024);
-        let chunks: Vec<Vec<T>> = data
-            .chunks(chunk_size)
-            .map(|chunk| chunk.to_vec())
-            .collect();
-        
-        // Create parallel processor
-        let processor = ParallelChunkProcessor::new(chunks);
+        let processor = ParallelChunkProcessor::from_slice(data, chunk_size);
         
         // Process chunks in parallel
         let f = self.clone();
-        let results = processor.process(move |chunk| {
+        let results = processor.process(move |chunk: &[T]| {
             chunk
                 .iter()
                 .map(|item| f(item))
@@ -0,0 +1,255 @@
+//! Position-specific scoring matrices
+//!
+//! This module builds log-odds scoring matrices for DNA motifs, such as
+//! transcription factor binding sites, and scans sequences with them on
+//! both strands.
+
+use super::{ComputeError, ComputeResult};
+
+/// Position-specific scoring matrix for a DNA motif
+///
+/// Each column holds log2-odds scores for `A`, `C`, `G` and `T` against a
+/// background distribution.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pssm {
+    /// Scores for each motif position, in `ACGT` order
+    forward: Vec<[f64; 4]>,
+    /// The same matrix for the reverse-complement strand
+    reverse: Vec<[f64; 4]>,
+}
+
+/// Best-scoring occurrence of a motif found by [`Pssm::best_match`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PssmMatch {
+    /// Start of the window in the scanned sequence (0-indexed)
+    pub position: usize,
+    /// Log-odds score of the window
+    pub score: f64,
+    /// Whether the motif matched the reverse-complement strand
+    pub reverse: bool,
+}
+
+impl Pssm {
+    /// Build a PSSM from per-position base counts
+    ///
+    /// `counts` and `background` are in `ACGT` order; counts may be
+    /// fractional, e.g. weighted. `pseudocount` is added to every count
+    /// before frequencies are taken, so that bases never seen at a position
+    /// get a finite score when it is positive. Background frequencies must be
+    /// positive and sum to 1.
+    pub fn new(counts: &[[f64; 4]], pseudocount: f64, background: [f64; 4]) -> ComputeResult<Self> {
+        if counts.is_empty() {
+            return Err(ComputeError::InvalidInput("PSSM needs at least one position".to_string()));
+        }
+        if pseudocount.is_nan() || pseudocount < 0.0 {
+            return Err(ComputeError::InvalidInput(format!("Invalid pseudocount {}", pseudocount)));
+        }
+        if background.iter().any(|&p| p.is_nan() || p <= 0.0) || (background.iter().sum::<f64>() - 1.0).abs() > 1e-6 {
+            return Err(ComputeError::InvalidInput(format!(
+                "Background frequencies {:?} must be positive and sum to 1",
+                background
+            )));
+        }
+        
+        let mut forward = Vec::with_capacity(counts.len());
+        for (position, column) in counts.iter().enumerate() {
+            if column.iter().any(|&count| count.is_nan() || count < 0.0) {
+                return Err(ComputeError::InvalidInput(format!(
+                    "Invalid counts {:?} at position {}",
+                    column, position
+                )));
+            }
+            
+            let total = column.iter().sum::<f64>() + 4.0 * pseudocount;
+            if total <= 0.0 {
+                return Err(ComputeError::InvalidInput(format!(
+                    "Position {} has no counts and no pseudocount",
+                    position
+                )));
+            }
+            
+            let mut scores = [0.0; 4];
+            for (score, (&count, &expected)) in scores.iter_mut().zip(column.iter().zip(&background)) {
+                *score = ((count + pseudocount) / total / expected).log2();
+            }
+            forward.push(scores);
+        }
+        
+        // Reading the reverse strand reverses the positions and complements the bases
+        let reverse = forward
+            .iter()
+            .rev()
+            .map(|&[a, c, g, t]| [t, g, c, a])
+            .collect();
+        
+        Ok(Self { forward, reverse })
+    }
+    
+    /// Build a PSSM from aligned binding sites
+    ///
+    /// All sites must have the same length. Bases are counted
+    /// case-insensitively with `U` as `T`; other characters, such as `N`,
+    /// are not counted.
+    pub fn from_sites(sites: &[&[u8]], pseudocount: f64, background: [f64; 4]) -> ComputeResult<Self> {
+        let width = match sites.first() {
+            Some(site) => site.len(),
+            None => return Err(ComputeError::InvalidInput("No sites to build a PSSM from".to_string())),
+        };
+        
+        let mut counts = vec![[0.0; 4]; width];
+        for (i, site) in sites.iter().enumerate() {
+            if site.len() != width {
+                return Err(ComputeError::InvalidInput(format!(
+                    "Site {} has length {}, expected {}",
+                    i, site.len(), width
+                )));
+            }
+            for (column, &base) in counts.iter_mut().zip(site.iter()) {
+                if let Some(index) = base_index(base) {
+                    column[index] += 1.0;
+                }
+            }
+        }
+        
+        Self::new(&counts, pseudocount, background)
+    }
+    
+    /// Get the motif length
+    pub fn len(&self) -> usize {
+        self.forward.len()
+    }
+    
+    /// Check if the motif has no positions (never true for a built PSSM)
+    pub fn is_empty(&self) -> bool {
+        self.forward.is_empty()
+    }
+    
+    /// Get the score of a base at a motif position, or `None` for a non-ACGT base
+    pub fn score(&self, position: usize, base: u8) -> Option<f64> {
+        base_index(base).map(|index| self.forward[position][index])
+    }
+    
+    /// Score every window of a sequence on the forward strand
+    ///
+    /// Element `i` is the score of the window starting at `i`, so a
+    /// sequence shorter than the motif gives no scores. Windows containing
+    /// a base other than `ACGTU` score `NaN`.
+    pub fn scan(&self, seq: &[u8]) -> Vec<f64> {
+        scan_with(&self.forward, seq)
+    }
+    
+    /// Score every window of a sequence on the reverse-complement strand
+    ///
+    /// Element `i` is the score of the reverse complement of the window
+    /// starting at forward position `i`, so results line up with
+    /// [`scan`](Self::scan).
+    pub fn scan_reverse_complement(&self, seq: &[u8]) -> Vec<f64> {
+        scan_with(&self.reverse, seq)
+    }
+    
+    /// Find the highest-scoring window on either strand
+    ///
+    /// Ties go to the leftmost window, and to the forward strand at the same
+    /// position. Returns `None` if no window can be scored.
+    pub fn best_match(&self, seq: &[u8]) -> Option<PssmMatch> {
+        let forward = self.scan(seq);
+        let reverse = self.scan_reverse_complement(seq);
+        
+        let mut best: Option<PssmMatch> = None;
+        for (position, (&forward, &reverse)) in forward.iter().zip(&reverse).enumerate() {
+            for (score, is_reverse) in [(forward, false), (reverse, true)] {
+                if !score.is_nan() && best.is_none_or(|b| score > b.score) {
+                    best = Some(PssmMatch { position, score, reverse: is_reverse });
+                }
+            }
+        }
+        best
+    }
+}
+
+/// Map a nucleotide to its `ACGT` index
+fn base_index(base: u8) -> Option<usize> {
+    match base {
+        b'A' | b'a' => Some(0),
+        b'C' | b'c' => Some(1),
+        b'G' | b'g' => Some(2),
+        b'T' | b't' | b'U' | b'u' => Some(3),
+        _ => None,
+    }
+}
+
+/// Score every window of a sequence against one strand's matrix
+fn scan_with(matrix: &[[f64; 4]], seq: &[u8]) -> Vec<f64> {
+    if seq.len() < matrix.len() {
+        return Vec::new();
+    }
+    
+    seq.windows(matrix.len())
+        .map(|window| {
+            window
+                .iter()
+                .zip(matrix)
+                .map(|(&base, scores)| base_index(base).map_or(f64::NAN, |index| scores[index]))
+                .sum()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const UNIFORM: [f64; 4] = [0.25; 4];
+    
+    #[test]
+    fn test_pssm_scores() {
+        let pssm = Pssm::from_sites(&[b"ACGT", b"ACGA", b"acgt", b"TCGT"], 0.0, UNIFORM).unwrap();
+        assert_eq!(pssm.len(), 4);
+        
+        // 3 of 4 sites have A first: log2(0.75 / 0.25)
+        assert!((pssm.score(0, b'A').unwrap() - 3f64.log2()).abs() < 1e-12);
+        assert_eq!(pssm.score(1, b'C'), Some(2.0));
+        assert_eq!(pssm.score(1, b'G'), Some(f64::NEG_INFINITY));
+        assert_eq!(pssm.score(0, b'N'), None);
+        
+        // Pseudocounts keep unseen bases finite
+        let smoothed = Pssm::from_sites(&[b"ACGT"], 1.0, UNIFORM).unwrap();
+        assert!((smoothed.score(0, b'C').unwrap() - (0.2f64 / 0.25).log2()).abs() < 1e-12);
+        
+        assert!(Pssm::from_sites(&[], 1.0, UNIFORM).is_err());
+        assert!(Pssm::from_sites(&[b"ACGT", b"ACG"], 1.0, UNIFORM).is_err());
+        assert!(Pssm::new(&[[0.0; 4]], 0.0, UNIFORM).is_err());
+        assert!(Pssm::new(&[[1.0; 4]], 1.0, [0.5, 0.5, 0.5, 0.5]).is_err());
+    }
+    
+    #[test]
+    fn test_pssm_scan() {
+        let pssm = Pssm::from_sites(&[b"AACG"], 0.25, UNIFORM).unwrap();
+        let seq = b"TTAACGTTCGTTNAACG";
+        
+        let forward = pssm.scan(seq);
+        assert_eq!(forward.len(), seq.len() - 3);
+        let reverse = pssm.scan_reverse_complement(seq);
+        assert_eq!(reverse.len(), forward.len());
+        
+        // CGTT is the reverse complement of AACG, so both score the same maximum
+        assert_eq!(forward[2], reverse[8]);
+        assert!(forward.iter().filter(|s| !s.is_nan()).all(|&s| s <= forward[2]));
+        
+        // Windows with N can't be scored
+        assert!(forward[12].is_nan() && forward[9].is_nan());
+        assert!(!forward[13].is_nan());
+        
+        // The forward strand wins ties at the leftmost position
+        let best = pssm.best_match(seq).unwrap();
+        assert_eq!((best.position, best.reverse), (2, false));
+        let best = pssm.best_match(b"GGCGTTGG").unwrap();
+        assert_eq!((best.position, best.reverse), (2, true));
+        assert_eq!(best.score, forward[2]);
+        
+        // Sequences shorter than the motif have no windows
+        assert!(pssm.scan(b"AAC").is_empty());
+        assert!(pssm.best_match(b"AAC").is_none());
+        assert!(pssm.best_match(b"NNNN").is_none());
+    }
+}
@@ -6,6 +6,8 @@
 use super::{ComputeResult, ComputeError};
 use crate::engines::core::simd;
 use std::cmp;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
 
 /// Perform a substring search with the Knuth-Morris-Pratt algorithm
 ///
@@ -16,15 +18,37 @@ pub fn kmp_search(text: &[u8], pattern: &[u8]) -> ComputeResult<Vec<usize>> {
         return Err(ComputeError::InvalidInput("Pattern cannot be empty".to_string()));
     }
     
+    let mut matches = Vec::new();
+    kmp_scan(text, pattern, |start| matches.push(start));
+    
+    Ok(matches)
+}
+
+/// Count the occurrences of a pattern with the Knuth-Morris-Pratt algorithm
+///
+/// Matches the overlapping occurrences `kmp_search` reports without
+/// allocating a vector of positions.
+pub fn kmp_count(text: &[u8], pattern: &[u8]) -> ComputeResult<usize> {
+    if pattern.is_empty() {
+        return Err(ComputeError::InvalidInput("Pattern cannot be empty".to_string()));
+    }
+    
+    let mut count = 0;
+    kmp_scan(text, pattern, |_| count += 1);
+    
+    Ok(count)
+}
+
+/// Run a KMP scan over a text, calling `on_match` with each match start
+fn kmp_scan(text: &[u8], pattern: &[u8], mut on_match: impl FnMut(usize)) {
     if text.is_empty() {
-        return Ok(Vec::new());
+        return;
     }
     
     // Compute the failure function (partial match table)
     let failure_table = compute_kmp_failure_table(pattern);
     
     // Perform the search
-    let mut matches = Vec::new();
     let mut j = 0; // position in pattern
     
     for (i, &c) in text.iter().enumerate() {
@@ -40,12 +64,10 @@ pub fn kmp_search(text: &[u8], pattern: &[u8]) -> ComputeResult<Vec<usize>> {
         
         // If we reached the end of the pattern, we found a match
         if j == pattern.len() {
-            matches.push(i + 1 - j);
+            on_match(i + 1 - j);
             j = failure_table[j - 1];
         }
     }
-    
-    Ok(matches)
 }
 
 /// Compute the failure function table for KMP algorithm
@@ -81,7 +103,8 @@ pub fn boyer_moore_search(text: &[u8], pattern: &[u8]) -> ComputeResult<Vec<usiz
         return Err(ComputeError::InvalidInput("Pattern cannot be empty".to_string()));
     }
     
-    if text.is_empty() {
+    // A pattern longer than the text can't match, and would underflow `n - m`
+    if text.is_empty() || pattern.len() > text.len() {
         return Ok(Vec::new());
     }
     
@@ -113,8 +136,10 @@ pub fn boyer_moore_search(text: &[u8], pattern: &[u8]) -> ComputeResult<Vec<usiz
             matches.push(i);
             i += good_suffix[0];
         } else {
-            // Otherwise, shift based on the maximum of bad character and good suffix heuristics
-            let bc_shift = bad_char[text[i + j] as usize].max(1);
+            // Otherwise, shift based on the maximum of bad character and good suffix heuristics.
+            // The bad character table is relative to the last pattern position, so
+            // discount the part of the pattern already matched to the right of `j`
+            let bc_shift = bad_char[text[i + j] as usize].saturating_sub(m - 1 - j).max(1);
             let gs_shift = good_suffix[j];
             i += cmp::max(bc_shift, gs_shift);
         }
@@ -169,32 +194,446 @@ fn compute_good_suffix_table(pattern: &[u8]) -> Vec<usize> {
 }
 
 /// Compute the suffix table for Boyer-Moore algorithm
+///
+/// `suffix[i]` is the length of the longest substring ending at `i` that is
+/// also a suffix of the pattern.
 fn compute_suffix_table(pattern: &[u8]) -> Vec<usize> {
     let m = pattern.len();
     let mut suffix = vec![0; m];
     
     suffix[m - 1] = m;
-    let mut g = m - 1;
+    
+    // `g` is the start of the rightmost suffix match found so far, exclusive;
+    // it reaches -1 when a match runs to the start of the pattern, so it is signed
+    let mut g = m as isize - 1;
+    let mut f = m - 1;
     
     for i in (0..m - 1).rev() {
-        if i > g && suffix[m - 1 - (m - 1 - i)] < i - g {
-            suffix[i] = suffix[m - 1 - (m - 1 - i)];
+        if i as isize > g && suffix[i + m - 1 - f] < (i as isize - g) as usize {
+            suffix[i] = suffix[i + m - 1 - f];
         } else {
-            if i < g {
-                g = i;
+            if (i as isize) < g {
+                g = i as isize;
             }
-            let mut j = 0;
-            while g >= 0 && pattern[g] == pattern[m - 1 - j] {
+            f = i;
+            while g >= 0 && pattern[g as usize] == pattern[g as usize + m - 1 - f] {
                 g -= 1;
-                j += 1;
             }
-            suffix[i] = j;
+            suffix[i] = (f as isize - g) as usize;
         }
     }
     
     suffix
 }
 
+/// Modulus of the default rolling hash (the Mersenne prime 2^61 - 1)
+const ROLLING_HASH_MODULUS: u64 = (1 << 61) - 1;
+
+/// Base of the default rolling hash
+const ROLLING_HASH_BASE: u64 = 0x100_0000_01b3;
+
+/// Polynomial rolling hash over a fixed-size window
+///
+/// The hash of `w[0..k]` is `w[0] * base^(k-1) + ... + w[k-1]` modulo the
+/// modulus. [`roll`](Self::roll) slides the window by one byte in O(1), so
+/// one hasher can scan a whole text, and [`reset`](Self::reset) reuses it
+/// for another scan with the same window size.
+#[derive(Debug, Clone)]
+pub struct RollingHash {
+    base: u64,
+    modulus: u64,
+    /// base^(window - 1), the weight of the outgoing byte
+    high_power: u64,
+    window: usize,
+    value: u64,
+}
+
+impl RollingHash {
+    /// Hash a window with the default 61-bit modulus
+    pub fn new(window: &[u8]) -> Self {
+        Self::with_params(window, ROLLING_HASH_BASE, ROLLING_HASH_MODULUS)
+    }
+    
+    /// Hash a window with a custom base and modulus
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulus` is zero.
+    pub fn with_params(window: &[u8], base: u64, modulus: u64) -> Self {
+        assert!(modulus > 0, "rolling hash modulus must be non-zero");
+        
+        let base = base % modulus;
+        let high_power = (1..window.len()).fold(1 % modulus, |power, _| mul_mod(power, base, modulus));
+        let mut hash = Self {
+            base,
+            modulus,
+            high_power,
+            window: window.len(),
+            value: 0,
+        };
+        hash.reset(window);
+        hash
+    }
+    
+    /// Recompute the hash for a new window of the same size
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window` has a different length.
+    pub fn reset(&mut self, window: &[u8]) {
+        assert_eq!(window.len(), self.window, "window size changed");
+        self.value = window.iter().fold(0, |hash, &byte| {
+            add_mod(mul_mod(hash, self.base, self.modulus), byte as u64, self.modulus)
+        });
+    }
+    
+    /// Slide the window one byte: drop `outgoing` and append `incoming`
+    pub fn roll(&mut self, outgoing: u8, incoming: u8) {
+        let removed = mul_mod(outgoing as u64 % self.modulus, self.high_power, self.modulus);
+        let hash = add_mod(self.value, self.modulus - removed, self.modulus);
+        self.value = add_mod(mul_mod(hash, self.base, self.modulus), incoming as u64, self.modulus);
+    }
+    
+    /// Current hash value
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+    
+    /// Size of the hashed window
+    pub fn window_len(&self) -> usize {
+        self.window
+    }
+}
+
+/// Multiply two values modulo `modulus` without overflow
+fn mul_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    ((a as u128 * b as u128) % modulus as u128) as u64
+}
+
+/// Add two values modulo `modulus` without overflow, even above 2^63
+fn add_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    ((a as u128 + b as u128) % modulus as u128) as u64
+}
+
+/// Find all occurrences of a pattern with the Rabin-Karp algorithm
+///
+/// Uses a 64-bit polynomial rolling hash, so each text position costs O(1)
+/// regardless of the pattern length, which suits very long probes. Windows
+/// whose hash matches are compared byte for byte, so every reported match is
+/// exact even when hashes collide.
+pub fn rabin_karp_search(text: &[u8], pattern: &[u8]) -> ComputeResult<Vec<usize>> {
+    rabin_karp_search_with(text, pattern, ROLLING_HASH_BASE, ROLLING_HASH_MODULUS)
+}
+
+/// Rabin-Karp search with an explicit hash base and modulus
+fn rabin_karp_search_with(text: &[u8], pattern: &[u8], base: u64, modulus: u64) -> ComputeResult<Vec<usize>> {
+    if pattern.is_empty() {
+        return Err(ComputeError::InvalidInput("Pattern cannot be empty".to_string()));
+    }
+    
+    let m = pattern.len();
+    if text.len() < m {
+        return Ok(Vec::new());
+    }
+    
+    let target = RollingHash::with_params(pattern, base, modulus).value();
+    let mut hash = RollingHash::with_params(&text[..m], base, modulus);
+    
+    let mut matches = Vec::new();
+    for start in 0..=text.len() - m {
+        if start > 0 {
+            hash.roll(text[start - 1], text[start + m - 1]);
+        }
+        
+        // Verify candidates so collisions never produce false matches
+        if hash.value() == target && &text[start..start + m] == pattern {
+            matches.push(start);
+        }
+    }
+    
+    Ok(matches)
+}
+
+/// Find all approximate occurrences of a pattern, allowing mismatches
+///
+/// Returns each match start with its Hamming distance to the pattern.
+/// Overlapping matches are all reported. Runs in O(n * m) time, giving up on
+/// a position as soon as it exceeds `max_mismatches`.
+pub fn approximate_search(text: &[u8], pattern: &[u8], max_mismatches: usize) -> ComputeResult<Vec<(usize, usize)>> {
+    if pattern.is_empty() {
+        return Err(ComputeError::InvalidInput("Pattern cannot be empty".to_string()));
+    }
+    
+    if text.len() < pattern.len() {
+        return Ok(Vec::new());
+    }
+    
+    let mut matches = Vec::new();
+    for (start, window) in text.windows(pattern.len()).enumerate() {
+        let mut mismatches = 0;
+        for (&a, &b) in window.iter().zip(pattern) {
+            if a != b {
+                mismatches += 1;
+                if mismatches > max_mismatches {
+                    break;
+                }
+            }
+        }
+        
+        if mismatches <= max_mismatches {
+            matches.push((start, mismatches));
+        }
+    }
+    
+    Ok(matches)
+}
+
+/// Bit set of the nucleotides (A=1, C=2, G=4, T/U=8) an IUPAC code stands for
+const fn iupac_mask(code: u8) -> u8 {
+    match code.to_ascii_uppercase() {
+        b'A' => 1,
+        b'C' => 2,
+        b'G' => 4,
+        b'T' | b'U' => 8,
+        b'R' => 1 | 4,
+        b'Y' => 2 | 8,
+        b'S' => 2 | 4,
+        b'W' => 1 | 8,
+        b'K' => 4 | 8,
+        b'M' => 1 | 2,
+        b'B' => 2 | 4 | 8,
+        b'D' => 1 | 4 | 8,
+        b'H' => 1 | 2 | 8,
+        b'V' => 1 | 2 | 4,
+        b'N' => 1 | 2 | 4 | 8,
+        _ => 0,
+    }
+}
+
+/// Build the table of which text bytes each pattern byte matches
+const fn build_iupac_compatibility() -> [[bool; 256]; 256] {
+    let mut table = [[false; 256]; 256];
+    let mut p = 0;
+    while p < 256 {
+        let allowed = iupac_mask(p as u8);
+        let mut t = 0;
+        while t < 256 {
+            // A text base matches if everything it may stand for is allowed
+            let base = iupac_mask(t as u8);
+            table[p][t] = base != 0 && base & !allowed == 0;
+            t += 1;
+        }
+        p += 1;
+    }
+    table
+}
+
+/// `IUPAC_COMPATIBILITY[pattern][text]` is true when `text` matches `pattern`
+static IUPAC_COMPATIBILITY: [[bool; 256]; 256] = build_iupac_compatibility();
+
+/// Find all occurrences of an IUPAC degenerate nucleotide pattern
+///
+/// Each pattern code matches any base it stands for (e.g. `W` matches `A`
+/// and `T`), case-insensitively and with `U` read as `T`. Ambiguous text
+/// bases match only if the pattern allows every base they stand for, so `N`
+/// in the text is matched only by `N` in the pattern.
+pub fn iupac_search(text: &[u8], pattern: &[u8]) -> ComputeResult<Vec<usize>> {
+    if pattern.is_empty() {
+        return Err(ComputeError::InvalidInput("Pattern cannot be empty".to_string()));
+    }
+    
+    if let Some(&invalid) = pattern.iter().find(|&&c| iupac_mask(c) == 0) {
+        return Err(ComputeError::InvalidInput(format!(
+            "Invalid IUPAC nucleotide code '{}' in pattern",
+            invalid as char
+        )));
+    }
+    
+    if text.len() < pattern.len() {
+        return Ok(Vec::new());
+    }
+    
+    let rows: Vec<&[bool; 256]> = pattern.iter().map(|&c| &IUPAC_COMPATIBILITY[c as usize]).collect();
+    
+    let matches = text
+        .windows(pattern.len())
+        .enumerate()
+        .filter(|(_, window)| window.iter().zip(&rows).all(|(&c, row)| row[c as usize]))
+        .map(|(start, _)| start)
+        .collect();
+    
+    Ok(matches)
+}
+
+/// Suffix array over a text, for answering many exact searches
+///
+/// Built once by prefix doubling with counting sorts in O(n log n); each
+/// search is then a binary search over the sorted suffixes, taking
+/// O(m log n) for a pattern of length m.
+#[derive(Debug, Clone)]
+pub struct SuffixArray {
+    /// The indexed text
+    text: Vec<u8>,
+    /// Start positions of the suffixes in lexicographic order
+    suffixes: Vec<usize>,
+}
+
+impl SuffixArray {
+    /// Build a suffix array over a text
+    pub fn new(text: &[u8]) -> Self {
+        Self {
+            suffixes: build_suffix_array(text),
+            text: text.to_vec(),
+        }
+    }
+    
+    /// Get the length of the indexed text
+    pub fn len(&self) -> usize {
+        self.text.len()
+    }
+    
+    /// Check whether the indexed text is empty
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+    
+    /// Get the suffix start positions in lexicographic order
+    pub fn suffixes(&self) -> &[usize] {
+        &self.suffixes
+    }
+    
+    /// Find every occurrence of a pattern
+    ///
+    /// Returns start positions in increasing order, as `kmp_search` does.
+    /// An empty pattern has no matches.
+    pub fn search(&self, pattern: &[u8]) -> Vec<usize> {
+        let (lower, upper) = self.match_range(pattern);
+        let mut positions = self.suffixes[lower..upper].to_vec();
+        positions.sort_unstable();
+        positions
+    }
+    
+    /// Count the occurrences of a pattern without collecting them
+    pub fn count(&self, pattern: &[u8]) -> usize {
+        let (lower, upper) = self.match_range(pattern);
+        upper - lower
+    }
+    
+    /// Compute the longest common prefix array with Kasai's algorithm
+    ///
+    /// Entry `i` is the length of the prefix shared by suffixes `i - 1` and
+    /// `i` in sorted order; entry 0 is always 0.
+    pub fn lcp_array(&self) -> Vec<usize> {
+        let n = self.text.len();
+        let mut rank = vec![0; n];
+        for (i, &suffix) in self.suffixes.iter().enumerate() {
+            rank[suffix] = i;
+        }
+        
+        let mut lcp = vec![0; n];
+        let mut h = 0;
+        for i in 0..n {
+            if rank[i] == 0 {
+                h = 0;
+                continue;
+            }
+            
+            // The shared prefix shrinks by at most one between consecutive positions
+            let previous = self.suffixes[rank[i] - 1];
+            while i + h < n && previous + h < n && self.text[i + h] == self.text[previous + h] {
+                h += 1;
+            }
+            lcp[rank[i]] = h;
+            h = h.saturating_sub(1);
+        }
+        
+        lcp
+    }
+    
+    /// Find the longest substring that occurs at least twice
+    ///
+    /// Occurrences may overlap. Returns `None` if no byte repeats.
+    pub fn longest_repeated_substring(&self) -> Option<&[u8]> {
+        let lcp = self.lcp_array();
+        let (index, &length) = lcp.iter().enumerate().max_by_key(|&(i, &l)| (l, cmp::Reverse(i)))?;
+        
+        if length == 0 {
+            return None;
+        }
+        
+        let start = self.suffixes[index];
+        Some(&self.text[start..start + length])
+    }
+    
+    /// Range of sorted suffixes that begin with a pattern
+    fn match_range(&self, pattern: &[u8]) -> (usize, usize) {
+        if pattern.is_empty() {
+            return (0, 0);
+        }
+        
+        let prefix = |suffix: usize| &self.text[suffix..cmp::min(suffix + pattern.len(), self.text.len())];
+        let lower = self.suffixes.partition_point(|&s| prefix(s) < pattern);
+        let upper = lower + self.suffixes[lower..].partition_point(|&s| prefix(s) == pattern);
+        (lower, upper)
+    }
+}
+
+/// Sort the suffixes of a text by prefix doubling
+fn build_suffix_array(text: &[u8]) -> Vec<usize> {
+    let n = text.len();
+    let mut suffixes: Vec<usize> = (0..n).collect();
+    if n <= 1 {
+        return suffixes;
+    }
+    
+    // Start from the order and rank of the first byte of each suffix
+    suffixes.sort_unstable_by_key(|&i| text[i]);
+    let mut rank: Vec<usize> = text.iter().map(|&b| b as usize).collect();
+    let mut next_rank = vec![0; n];
+    let mut by_second = Vec::with_capacity(n);
+    let mut counts = vec![0; cmp::max(256, n)];
+    let mut k = 1;
+    
+    loop {
+        // Order by the rank of the second half; suffixes without one sort first
+        by_second.clear();
+        by_second.extend(n.saturating_sub(k)..n);
+        by_second.extend(suffixes.iter().filter(|&&s| s >= k).map(|&s| s - k));
+        
+        // Stable counting sort by the rank of the first half
+        counts.iter_mut().for_each(|c| *c = 0);
+        for &i in &by_second {
+            counts[rank[i]] += 1;
+        }
+        let mut total = 0;
+        for count in counts.iter_mut() {
+            let start = total;
+            total += *count;
+            *count = start;
+        }
+        for &i in &by_second {
+            suffixes[counts[rank[i]]] = i;
+            counts[rank[i]] += 1;
+        }
+        
+        // Re-rank by the first 2k bytes
+        next_rank[suffixes[0]] = 0;
+        for j in 1..n {
+            let (a, b) = (suffixes[j - 1], suffixes[j]);
+            let same = rank[a] == rank[b] && rank.get(a + k) == rank.get(b + k);
+            next_rank[b] = next_rank[a] + usize::from(!same);
+        }
+        std::mem::swap(&mut rank, &mut next_rank);
+        
+        if rank[suffixes[n - 1]] == n - 1 {
+            break;
+        }
+        k *= 2;
+    }
+    
+    suffixes
+}
+
 /// Reverse a sequence in-place
 pub fn reverse_in_place(sequence: &mut [u8]) {
     let len = sequence.len();
@@ -215,6 +654,10 @@ pub fn reverse(sequence: &[u8]) -> Vec<u8> {
 }
 
 /// Complement a DNA sequence in-place
+///
+/// Handles the full IUPAC nucleotide set (RNA `U` complements to `A`), and
+/// writes uppercase bases. Characters that are not nucleotide codes, such as
+/// gaps, are passed through unchanged.
 pub fn complement_dna_in_place(sequence: &mut [u8]) {
     for base in sequence.iter_mut() {
         *base = match *base {
@@ -224,6 +667,17 @@ pub fn complement_dna_in_place(sequence: &mut [u8]) {
             b'T' | b't' => b'A',
             b'U' | b'u' => b'A', // Handle RNA as well
             b'N' | b'n' => b'N',
+            // IUPAC ambiguity codes
+            b'R' | b'r' => b'Y',
+            b'Y' | b'y' => b'R',
+            b'S' | b's' => b'S',
+            b'W' | b'w' => b'W',
+            b'K' | b'k' => b'M',
+            b'M' | b'm' => b'K',
+            b'B' | b'b' => b'V',
+            b'V' | b'v' => b'B',
+            b'D' | b'd' => b'H',
+            b'H' | b'h' => b'D',
             _ => *base, // Keep other characters unchanged
         };
     }
@@ -249,6 +703,49 @@ pub fn reverse_complement_dna(sequence: &[u8]) -> Vec<u8> {
     result
 }
 
+/// Canonical form of a k-mer: the smaller of it and its reverse complement
+///
+/// Both strands are compared in uppercase, so the result is always
+/// uppercase. IUPAC codes are complemented as in [`complement_dna`]; other
+/// characters are compared as they are.
+pub fn canonical_kmer(kmer: &[u8]) -> Vec<u8> {
+    let forward = kmer.to_ascii_uppercase();
+    let reverse = reverse_complement_dna(kmer);
+    cmp::min(forward, reverse)
+}
+
+/// 2-bit encoded hash of a canonical k-mer
+///
+/// Encodes A=0, C=1, G=2, T=3 (case-insensitive) and returns the smaller of
+/// the k-mer's and its reverse complement's codes. The encoding preserves
+/// lexicographic order, so this is the code of [`canonical_kmer`] and is
+/// unique for every canonical k-mer up to k = 32. Returns `None` for k-mers
+/// containing anything other than ACGT, or longer than 32 bases, rather
+/// than falling back to a lossy byte hash.
+pub fn canonical_kmer_hash(kmer: &[u8]) -> Option<u64> {
+    if kmer.len() > 32 {
+        return None;
+    }
+    
+    let mut forward: u64 = 0;
+    let mut reverse: u64 = 0;
+    for (i, &base) in kmer.iter().enumerate() {
+        let code = match base {
+            b'A' | b'a' => 0,
+            b'C' | b'c' => 1,
+            b'G' | b'g' => 2,
+            b'T' | b't' => 3,
+            _ => return None,
+        };
+        
+        // The complement of base i is at position k - 1 - i of the reverse strand
+        forward = (forward << 2) | code;
+        reverse |= (3 - code) << (2 * i);
+    }
+    
+    Some(cmp::min(forward, reverse))
+}
+
 /// Count occurrences of each base in a DNA sequence
 pub fn count_bases(sequence: &[u8]) -> [usize; 5] {
     let mut counts = [0, 0, 0, 0, 0]; // A, C, G, T, N/Other
@@ -338,6 +835,168 @@ pub fn random_dna(length: usize) -> Vec<u8> {
         .collect()
 }
 
+/// Compute the (w, k)-minimizers of a sequence
+///
+/// Every window of `w` consecutive k-mers selects the k-mer with the smallest
+/// hash, breaking ties by the leftmost position. Returns `(hash, position)`
+/// pairs in position order; a position selected by several overlapping
+/// windows is reported once. Sequences with fewer than `w` k-mers are treated
+/// as a single window. Hashing is case-insensitive.
+pub fn minimizers(seq: &[u8], k: usize, w: usize) -> Vec<(u64, usize)> {
+    const BASE: u64 = 0x100000001b3;
+    
+    let mut result = Vec::new();
+    if k == 0 || w == 0 || seq.len() < k {
+        return result;
+    }
+    
+    // BASE^(k-1), used to drop the outgoing byte from the rolling hash
+    let mut high_power: u64 = 1;
+    for _ in 1..k {
+        high_power = high_power.wrapping_mul(BASE);
+    }
+    
+    let num_kmers = seq.len() - k + 1;
+    let w = cmp::min(w, num_kmers);
+    
+    // Monotonic queue of (hash, position) with increasing hashes; equal
+    // hashes keep the earlier entry so ties resolve to the leftmost k-mer
+    let mut window: VecDeque<(u64, usize)> = VecDeque::new();
+    let mut rolling: u64 = 0;
+    
+    for (i, &byte) in seq.iter().enumerate() {
+        if i >= k {
+            let outgoing = seq[i - k].to_ascii_uppercase() as u64;
+            rolling = rolling.wrapping_sub(outgoing.wrapping_mul(high_power));
+        }
+        rolling = rolling.wrapping_mul(BASE).wrapping_add(byte.to_ascii_uppercase() as u64);
+        
+        if i + 1 < k {
+            continue;
+        }
+        
+        let pos = i + 1 - k;
+        let hash = mix_hash(rolling);
+        
+        while window.back().is_some_and(|&(h, _)| h > hash) {
+            window.pop_back();
+        }
+        window.push_back((hash, pos));
+        
+        // Drop the entry that has slid out of the current window
+        if window.front().is_some_and(|&(_, p)| p + w <= pos) {
+            window.pop_front();
+        }
+        
+        // Emit once the first full window is available
+        if pos + 1 >= w {
+            let selected = window[0];
+            if result.last() != Some(&selected) {
+                result.push(selected);
+            }
+        }
+    }
+    
+    result
+}
+
+/// Scramble a rolling hash so minimizer order is not lexicographic
+fn mix_hash(mut h: u64) -> u64 {
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xff51afd7ed558ccd);
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xc4ceb9fe1a85ec53);
+    h ^ (h >> 33)
+}
+
+/// Find the `(i, j)` coordinates where `seq1[i..i + word_size]` equals
+/// `seq2[j..j + word_size]`
+///
+/// The k-mers of `seq2` are indexed in a hash map and `seq1` is scanned
+/// against it, so the cost is linear in the sequence lengths plus the number
+/// of hits. Matching is case-insensitive. Points are returned sorted by `i`,
+/// then `j`. An empty vector is returned when `word_size` is zero or longer
+/// than either sequence.
+pub fn dotplot(seq1: &[u8], seq2: &[u8], word_size: usize) -> Vec<(usize, usize)> {
+    let mut points = Vec::new();
+    if word_size == 0 || seq1.len() < word_size || seq2.len() < word_size {
+        return points;
+    }
+    
+    // Words borrow the inputs, so multi-megabase sequences are not copied
+    let mut index: HashMap<CaseInsensitiveWord, Vec<usize>> = HashMap::new();
+    for (j, word) in seq2.windows(word_size).enumerate() {
+        index.entry(CaseInsensitiveWord(word)).or_default().push(j);
+    }
+    
+    for (i, word) in seq1.windows(word_size).enumerate() {
+        if let Some(positions) = index.get(&CaseInsensitiveWord(word)) {
+            points.extend(positions.iter().map(|&j| (i, j)));
+        }
+    }
+    
+    points
+}
+
+/// A borrowed word that hashes and compares ignoring ASCII case
+struct CaseInsensitiveWord<'a>(&'a [u8]);
+
+impl Hash for CaseInsensitiveWord<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for &byte in self.0 {
+            state.write_u8(byte.to_ascii_uppercase());
+        }
+    }
+}
+
+impl PartialEq for CaseInsensitiveWord<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_ignore_ascii_case(other.0)
+    }
+}
+
+impl Eq for CaseInsensitiveWord<'_> {}
+
+/// Like [`dotplot`], but keep only points lying on a diagonal run of at least
+/// `min_run` consecutive word matches
+///
+/// Isolated short hits are the main source of noise in dot plots of long
+/// sequences; requiring a run filters them while keeping real alignments.
+/// A `min_run` of 0 or 1 keeps every point.
+pub fn dotplot_with_min_run(
+    seq1: &[u8],
+    seq2: &[u8],
+    word_size: usize,
+    min_run: usize,
+) -> Vec<(usize, usize)> {
+    let mut points = dotplot(seq1, seq2, word_size);
+    if min_run <= 1 {
+        return points;
+    }
+    
+    // Group points by diagonal (j - i), each diagonal in increasing i
+    points.sort_unstable_by_key(|&(i, j)| (j as isize - i as isize, i));
+    
+    let mut kept = Vec::new();
+    let mut start = 0;
+    for end in 1..=points.len() {
+        let run_continues = end < points.len() && {
+            let (pi, pj) = points[end - 1];
+            let (i, j) = points[end];
+            i == pi + 1 && j == pj + 1
+        };
+        if !run_continues {
+            if end - start >= min_run {
+                kept.extend_from_slice(&points[start..end]);
+            }
+            start = end;
+        }
+    }
+    
+    kept.sort_unstable();
+    kept
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -368,6 +1027,55 @@ mod tests {
         assert!(result.is_err());
     }
     
+    #[test]
+    fn test_kmp_count() {
+        assert_eq!(kmp_count(b"ACGTACGTACGTACGT", b"ACGT").unwrap(), 4);
+        assert_eq!(kmp_count(b"AAAAA", b"AA").unwrap(), 4);
+        assert_eq!(kmp_count(b"", b"A").unwrap(), 0);
+        assert!(kmp_count(b"ACGT", b"").is_err());
+    }
+    
+    #[test]
+    fn test_approximate_search() {
+        let text = b"ACGTACCTAGGTAAAA";
+        
+        // Zero mismatches is exact search
+        assert_eq!(approximate_search(text, b"ACGT", 0).unwrap(), vec![(0, 0)]);
+        
+        // One mismatch also finds ACCT and AGGT
+        assert_eq!(approximate_search(text, b"ACGT", 1).unwrap(), vec![(0, 0), (4, 1), (8, 1)]);
+        
+        // Overlapping matches are all reported
+        assert_eq!(approximate_search(b"AAAAA", b"AAA", 0).unwrap(), vec![(0, 0), (1, 0), (2, 0)]);
+        assert_eq!(approximate_search(b"AAAT", b"AA", 1).unwrap(), vec![(0, 0), (1, 0), (2, 1)]);
+        
+        // Edge cases
+        assert!(approximate_search(b"ACGT", b"ACGTA", 2).unwrap().is_empty());
+        assert!(approximate_search(text, b"", 1).is_err());
+    }
+    
+    #[test]
+    fn test_iupac_search() {
+        // W matches A or T
+        let text = b"GGAGGCGGTGGCGGCGG";
+        assert_eq!(iupac_search(text, b"GGWGG").unwrap(), vec![0, 6]);
+        
+        // Case-insensitive, with U read as T
+        assert_eq!(iupac_search(b"ggaggcgguGG", b"GGWGG").unwrap(), vec![0, 6]);
+        assert_eq!(iupac_search(b"ACGT", b"acgu").unwrap(), vec![0]);
+        
+        // Ambiguous text bases need a pattern code covering them
+        assert_eq!(iupac_search(b"ARA", b"ANA").unwrap(), vec![0]);
+        assert!(iupac_search(b"ANA", b"ARA").unwrap().is_empty());
+        
+        // Overlapping matches
+        assert_eq!(iupac_search(b"AAAA", b"RR").unwrap(), vec![0, 1, 2]);
+        
+        // Invalid patterns
+        assert!(iupac_search(text, b"").is_err());
+        assert!(iupac_search(text, b"GGXGG").is_err());
+    }
+    
     #[test]
     fn test_boyer_moore_search() {
         // Test simple patterns
@@ -392,6 +1100,108 @@ mod tests {
         let pattern = b"";
         let result = boyer_moore_search(text, pattern);
         assert!(result.is_err());
+        
+        // Patterns longer than the text don't match
+        assert!(boyer_moore_search(b"AC", b"ACGT").unwrap().is_empty());
+        assert_eq!(boyer_moore_search(b"ACGT", b"ACGT").unwrap(), vec![0]);
+        
+        // Single-base and self-overlapping patterns
+        assert_eq!(boyer_moore_search(b"AACA", b"A").unwrap(), vec![0, 1, 3]);
+        assert_eq!(boyer_moore_search(b"AAAAA", b"AAAA").unwrap(), vec![0, 1]);
+        assert_eq!(compute_suffix_table(b"A"), vec![1]);
+        assert_eq!(compute_suffix_table(b"AAAA"), vec![1, 2, 3, 4]);
+        
+        // Agrees with KMP on random text
+        let text = random_dna(2000);
+        for pattern in [&b"ACGTA"[..], b"AAA", b"GCGC", b"TATATA", b"CAGTCAG"] {
+            assert_eq!(boyer_moore_search(&text, pattern).unwrap(), kmp_search(&text, pattern).unwrap());
+        }
+    }
+    
+    #[test]
+    fn test_rabin_karp_search() {
+        assert_eq!(rabin_karp_search(b"ACGTACGTACGTACGT", b"ACGT").unwrap(), vec![0, 4, 8, 12]);
+        assert_eq!(rabin_karp_search(b"AAAAA", b"AAAA").unwrap(), vec![0, 1]);
+        assert!(rabin_karp_search(b"AC", b"ACGT").unwrap().is_empty());
+        assert!(rabin_karp_search(b"", b"A").unwrap().is_empty());
+        assert!(rabin_karp_search(b"ACGT", b"").is_err());
+        
+        // Long probes agree with KMP
+        let text = random_dna(20_000);
+        let probe = text[12_345..14_345].to_vec();
+        assert_eq!(rabin_karp_search(&text, &probe).unwrap(), kmp_search(&text, &probe).unwrap());
+        for pattern in [&b"ACGTA"[..], b"AAA", b"GCGC"] {
+            assert_eq!(rabin_karp_search(&text, pattern).unwrap(), kmp_search(&text, pattern).unwrap());
+        }
+        
+        // With a tiny modulus nearly every window collides; verification
+        // still reports only exact matches
+        let text = b"ACGTTGCAACGTAGCT";
+        let collisions = (0..=text.len() - 4)
+            .filter(|&i| RollingHash::with_params(&text[i..i + 4], 256, 3).value()
+                == RollingHash::with_params(b"ACGT", 256, 3).value())
+            .count();
+        assert!(collisions > 2);
+        assert_eq!(rabin_karp_search_with(text, b"ACGT", 256, 3).unwrap(), vec![0, 8]);
+        assert_eq!(rabin_karp_search_with(text, b"ACGT", 256, 1).unwrap(), vec![0, 8]);
+    }
+    
+    #[test]
+    fn test_rolling_hash() {
+        let text = b"GATTACAGATTACA";
+        let mut hash = RollingHash::new(&text[..5]);
+        assert_eq!(hash.window_len(), 5);
+        
+        // Rolling matches hashing each window from scratch
+        for start in 1..=text.len() - 5 {
+            hash.roll(text[start - 1], text[start + 4]);
+            assert_eq!(hash.value(), RollingHash::new(&text[start..start + 5]).value());
+        }
+        assert_eq!(RollingHash::new(b"GATTA").value(), RollingHash::new(&text[7..12]).value());
+        
+        // A hasher can be reset for another scan
+        hash.reset(b"CCCCC");
+        assert_eq!(hash.value(), RollingHash::new(b"CCCCC").value());
+        
+        // Moduli above 2^63 do not overflow the intermediate sums
+        let modulus = u64::MAX - 58;
+        let base = u64::MAX - 1;
+        let mut hash = RollingHash::with_params(&text[..5], base, modulus);
+        for start in 1..=text.len() - 5 {
+            hash.roll(text[start - 1], text[start + 4]);
+            assert_eq!(hash.value(), RollingHash::with_params(&text[start..start + 5], base, modulus).value());
+            assert!(hash.value() < modulus);
+        }
+    }
+    
+    #[test]
+    fn test_canonical_kmer() {
+        assert_eq!(canonical_kmer(b"ACG"), b"ACG");
+        assert_eq!(canonical_kmer(b"CGT"), b"ACG");
+        assert_eq!(canonical_kmer(b"cgt"), b"ACG");
+        assert_eq!(canonical_kmer(b"GGNT"), b"ANCC");
+        assert_eq!(canonical_kmer(b""), b"");
+        
+        // Both strands share a hash, which encodes the canonical k-mer
+        assert_eq!(canonical_kmer_hash(b"ACG"), Some(0b00_01_10));
+        assert_eq!(canonical_kmer_hash(b"CGT"), Some(0b00_01_10));
+        assert_eq!(canonical_kmer_hash(b"tTt"), canonical_kmer_hash(b"AAA"));
+        assert_eq!(canonical_kmer_hash(&[b'T'; 32]), Some(0));
+        assert_eq!(canonical_kmer_hash(b""), Some(0));
+        
+        // Non-ACGT and over-long k-mers have no 2-bit code
+        assert_eq!(canonical_kmer_hash(b"ACNG"), None);
+        assert_eq!(canonical_kmer_hash(&[b'A'; 33]), None);
+        
+        // Hash order matches canonical k-mer order
+        let text = random_dna(500);
+        let mut kmers: Vec<&[u8]> = text.windows(7).collect();
+        kmers.sort_by_key(|kmer| canonical_kmer(kmer));
+        let hashes: Vec<u64> = kmers.iter().map(|kmer| canonical_kmer_hash(kmer).unwrap()).collect();
+        assert!(hashes.windows(2).all(|pair| pair[0] <= pair[1]));
+        for kmer in &kmers {
+            assert_eq!(canonical_kmer_hash(kmer), canonical_kmer_hash(&reverse_complement_dna(kmer)));
+        }
     }
     
     #[test]
@@ -432,6 +1242,14 @@ mod tests {
         let mut seq_mut = b"ACGT".to_vec();
         reverse_complement_dna_in_place(&mut seq_mut);
         assert_eq!(seq_mut, b"ACGT");
+        
+        // IUPAC ambiguity codes complement to their partners
+        assert_eq!(reverse_complement_dna(b"RYSWKM"), b"KMWSRY");
+        assert_eq!(reverse_complement_dna(b"BDHVN"), b"NBDHV");
+        assert_eq!(reverse_complement_dna(b"acgu"), b"ACGT");
+        
+        // Unknown characters pass through
+        assert_eq!(reverse_complement_dna(b"AC-G*"), b"*C-GT");
     }
     
     #[test]
@@ -498,4 +1316,88 @@ mod tests {
             assert!(base == b'A' || base == b'C' || base == b'G' || base == b'T');
         }
     }
+    
+    #[test]
+    fn test_minimizers() {
+        let seq = b"ACGTTGCATGCAGTCAGGCTAACGTTAGC";
+        let (k, w) = (5, 4);
+        let result = minimizers(seq, k, w);
+        
+        // Compare against a brute-force scan of every window
+        let hashes: Vec<u64> = seq.windows(k).map(|kmer| minimizers(kmer, k, 1)[0].0).collect();
+        let mut expected: Vec<(u64, usize)> = Vec::new();
+        for start in 0..=hashes.len() - w {
+            let pos = (start..start + w).min_by_key(|&p| (hashes[p], p)).unwrap();
+            if expected.last().map(|&(_, p)| p) != Some(pos) {
+                expected.push((hashes[pos], pos));
+            }
+        }
+        assert_eq!(result, expected);
+        
+        // Ties resolve to the leftmost position and are emitted once
+        let repeat = minimizers(b"AAAAAAAAAA", 3, 4);
+        assert_eq!(repeat.iter().map(|&(_, p)| p).collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+        
+        // Short sequences form a single window; too-short ones have none
+        assert_eq!(minimizers(b"ACGTAC", 4, 10).len(), 1);
+        assert!(minimizers(b"ACG", 4, 2).is_empty());
+        assert_eq!(minimizers(b"acgtac", 4, 2), minimizers(b"ACGTAC", 4, 2));
+    }
+    
+    #[test]
+    fn test_suffix_array() {
+        let banana = SuffixArray::new(b"banana");
+        assert_eq!(banana.suffixes(), &[5, 3, 1, 0, 4, 2]);
+        assert_eq!(banana.lcp_array(), vec![0, 1, 3, 0, 0, 2]);
+        assert_eq!(banana.search(b"ana"), vec![1, 3]);
+        assert_eq!(banana.count(b"an"), 2);
+        assert!(banana.search(b"nab").is_empty());
+        assert!(banana.search(b"").is_empty());
+        assert_eq!(banana.longest_repeated_substring(), Some(&b"ana"[..]));
+        
+        // Matches agree with KMP on a random genome
+        let genome = random_dna(5000);
+        let index = SuffixArray::new(&genome);
+        for start in (0..4900).step_by(97) {
+            for len in [1, 3, 8, 20] {
+                let pattern = &genome[start..start + len];
+                assert_eq!(index.search(pattern), kmp_search(&genome, pattern).unwrap());
+            }
+        }
+        assert!(index.search(b"ACGN").is_empty());
+        
+        // Runs and texts without repeats
+        let run = SuffixArray::new(b"AAAAA");
+        assert_eq!(run.suffixes(), &[4, 3, 2, 1, 0]);
+        assert_eq!(run.longest_repeated_substring(), Some(&b"AAAA"[..]));
+        assert_eq!(SuffixArray::new(b"ACGT").longest_repeated_substring(), None);
+        assert!(SuffixArray::new(b"").search(b"A").is_empty());
+    }
+    
+    #[test]
+    fn test_dotplot() {
+        assert_eq!(
+            dotplot(b"ACGTA", b"cgta", 3),
+            vec![(1, 0), (2, 1)]
+        );
+        assert_eq!(
+            dotplot(b"AAAA", b"AAA", 2),
+            vec![(0, 0), (0, 1), (1, 0), (1, 1), (2, 0), (2, 1)]
+        );
+        assert_eq!(dotplot(b"acGT", b"ACgt", 4), vec![(0, 0)]);
+        assert!(dotplot(b"ACGT", b"ACGT", 0).is_empty());
+        assert!(dotplot(b"ACGT", b"AC", 3).is_empty());
+        
+        // The shared ACGTAC block forms a run of 4 words; the lone TTG hit does not
+        let seq1 = b"ACGTACTTGCC";
+        let seq2 = b"GGACGTACAATTGA";
+        let all = dotplot(seq1, seq2, 3);
+        assert!(all.contains(&(6, 10)));
+        assert_eq!(
+            dotplot_with_min_run(seq1, seq2, 3, 3),
+            vec![(0, 2), (1, 3), (2, 4), (3, 5)]
+        );
+        assert_eq!(dotplot_with_min_run(seq1, seq2, 3, 1), all);
+        assert!(dotplot_with_min_run(seq1, seq2, 3, 5).is_empty());
+    }
 }
\ No newline at end of file
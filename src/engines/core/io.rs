@@ -6,6 +6,8 @@
 use std::fs::{File, OpenOptions};
 use std::io::{self, Read, Write, BufReader, BufWriter, SeekFrom, Seek};
 use std::path::Path;
+use std::cell::Cell;
+use std::marker::PhantomData;
 use memmap2::{Mmap, MmapOptions};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use crate::engines::core::memory::MemoryMapped;
@@ -18,6 +20,24 @@ const DEFAULT_WRITE_BUFFER_SIZE: usize = 1024 * 1024; // 1MB
 static TOTAL_BYTES_READ: AtomicUsize = AtomicUsize::new(0);
 static TOTAL_BYTES_WRITTEN: AtomicUsize = AtomicUsize::new(0);
 
+thread_local! {
+    // Per-thread counters backing `IoStats` scopes
+    static THREAD_BYTES_READ: Cell<usize> = const { Cell::new(0) };
+    static THREAD_BYTES_WRITTEN: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Count bytes read in the global and per-thread statistics
+fn record_read(bytes: usize) {
+    TOTAL_BYTES_READ.fetch_add(bytes, Ordering::SeqCst);
+    THREAD_BYTES_READ.with(|count| count.set(count.get() + bytes));
+}
+
+/// Count bytes written in the global and per-thread statistics
+fn record_written(bytes: usize) {
+    TOTAL_BYTES_WRITTEN.fetch_add(bytes, Ordering::SeqCst);
+    THREAD_BYTES_WRITTEN.with(|count| count.set(count.get() + bytes));
+}
+
 /// Initialize the I/O subsystem
 pub fn initialize() {
     // Reset I/O counters
@@ -52,7 +72,7 @@ impl FastReader {
         let bytes_read = self.reader.read_to_end(&mut buffer)?;
         
         // Update read statistics
-        TOTAL_BYTES_READ.fetch_add(bytes_read, Ordering::SeqCst);
+        record_read(bytes_read);
         
         Ok(buffer)
     }
@@ -62,7 +82,7 @@ impl FastReader {
         let bytes_read = self.reader.read(buffer)?;
         
         // Update read statistics
-        TOTAL_BYTES_READ.fetch_add(bytes_read, Ordering::SeqCst);
+        record_read(bytes_read);
         
         Ok(bytes_read)
     }
@@ -90,6 +110,12 @@ impl FastReader {
         self.reader.seek(SeekFrom::Start(0))?;
         Ok(())
     }
+    
+    /// Move the reader to the given byte offset in the file
+    pub fn seek_to(&mut self, offset: u64) -> io::Result<()> {
+        self.reader.seek(SeekFrom::Start(offset))?;
+        Ok(())
+    }
 }
 
 /// Iterator over lines in a file
@@ -107,7 +133,7 @@ impl<'a> Iterator for Lines<'a> {
             Ok(0) => None, // EOF
             Ok(bytes) => {
                 // Update read statistics
-                TOTAL_BYTES_READ.fetch_add(bytes, Ordering::SeqCst);
+                record_read(bytes);
                 
                 // Trim the trailing newline
                 if self.buffer.ends_with('\n') {
@@ -173,7 +199,7 @@ impl FastWriter {
         let bytes_written = self.writer.write(data)?;
         
         // Update write statistics
-        TOTAL_BYTES_WRITTEN.fetch_add(bytes_written, Ordering::SeqCst);
+        record_written(bytes_written);
         
         Ok(bytes_written)
     }
@@ -184,7 +210,7 @@ impl FastWriter {
         let newline_written = self.writer.write(b"\n")?;
         
         // Update write statistics
-        TOTAL_BYTES_WRITTEN.fetch_add(bytes_written + newline_written, Ordering::SeqCst);
+        record_written(bytes_written + newline_written);
         
         Ok(bytes_written + newline_written)
     }
@@ -292,6 +318,37 @@ pub fn split_file_into_chunks<P: AsRef<Path>>(
     Ok(chunks)
 }
 
+/// Split data into chunks that each start at a record boundary
+///
+/// A record starts with `marker` (e.g. `>` for FASTA) at the beginning of a
+/// line. Each chunk edge is moved back to the nearest record start, or
+/// forward to the next one if a single record is longer than `chunk_size`,
+/// so every record lies in exactly one chunk.
+pub fn split_at_record_boundaries(data: &[u8], chunk_size: usize, marker: u8) -> Vec<(usize, usize)> {
+    let is_record_start = |i: usize| data[i] == marker && (i == 0 || data[i - 1] == b'\n');
+    
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    
+    while start < data.len() {
+        let edge = start + chunk_size.max(1);
+        if edge >= data.len() {
+            chunks.push((start, data.len()));
+            break;
+        }
+        
+        // Scan backward from the edge, then forward if the record is too long
+        let end = (start + 1..=edge).rev().find(|&i| is_record_start(i))
+            .or_else(|| (edge + 1..data.len()).find(|&i| is_record_start(i)))
+            .unwrap_or(data.len());
+        
+        chunks.push((start, end));
+        start = end;
+    }
+    
+    chunks
+}
+
 /// Process a file in parallel using memory-mapped I/O
 pub fn process_file_parallel<P, F, R>(
     path: P,
@@ -332,6 +389,60 @@ pub fn get_io_stats() -> (usize, usize) {
     )
 }
 
+/// I/O statistics scoped to one operation on the current thread
+///
+/// Snapshots this thread's counters on creation, so the delta only includes
+/// bytes read and written by this thread since then and is unaffected by
+/// concurrent readers elsewhere. Work handed off to other threads (such as
+/// the parallel parsers) is not included. The delta is logged at debug
+/// level when the guard is dropped.
+#[derive(Debug)]
+pub struct IoStats {
+    bytes_read: usize,
+    bytes_written: usize,
+    // The snapshot is only meaningful on the thread that took it
+    _not_send: PhantomData<*const ()>,
+}
+
+impl IoStats {
+    /// Start measuring from the current thread's counters
+    pub fn new() -> Self {
+        Self {
+            bytes_read: THREAD_BYTES_READ.with(Cell::get),
+            bytes_written: THREAD_BYTES_WRITTEN.with(Cell::get),
+            _not_send: PhantomData,
+        }
+    }
+    
+    /// Bytes read and written since this scope started
+    pub fn delta(&self) -> (usize, usize) {
+        (self.bytes_read(), self.bytes_written())
+    }
+    
+    /// Bytes read since this scope started
+    pub fn bytes_read(&self) -> usize {
+        THREAD_BYTES_READ.with(Cell::get) - self.bytes_read
+    }
+    
+    /// Bytes written since this scope started
+    pub fn bytes_written(&self) -> usize {
+        THREAD_BYTES_WRITTEN.with(Cell::get) - self.bytes_written
+    }
+}
+
+impl Default for IoStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for IoStats {
+    fn drop(&mut self) {
+        let (bytes_read, bytes_written) = self.delta();
+        log::debug!("I/O scope read {} bytes and wrote {} bytes", bytes_read, bytes_written);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -346,6 +457,7 @@ mod tests {
         
         // Write test data
         let test_data = b"Line 1\nLine 2\nLine 3";
+        let stats = IoStats::new();
         {
             let mut writer = FastWriter::new(&file_path, None)?;
             writer.write(test_data)?;
@@ -364,10 +476,53 @@ mod tests {
         assert_eq!(lines, vec!["Line 1", "Line 2", "Line 3"]);
         
         // Check I/O statistics
-        let (bytes_read, bytes_written) = get_io_stats();
+        let (bytes_read, bytes_written) = stats.delta();
         assert_eq!(bytes_written, test_data.len());
         assert_eq!(bytes_read, test_data.len() * 2); // read_all + read_lines
         
+        // The global counters include at least this test's I/O
+        let (total_read, total_written) = get_io_stats();
+        assert!(total_read >= bytes_read && total_written >= bytes_written);
+        
+        Ok(())
+    }
+    
+    #[test]
+    fn test_io_stats_scopes() -> io::Result<()> {
+        let dir = tempdir()?;
+        let barrier = std::sync::Arc::new(std::sync::Barrier::new(2));
+        
+        // Two threads read and write interleaved; each scope sees only its own bytes
+        let handles: Vec<_> = [100usize, 2500].into_iter().map(|size| {
+            let path = dir.path().join(format!("scope_{}.txt", size));
+            let barrier = barrier.clone();
+            std::thread::spawn(move || -> io::Result<(usize, usize)> {
+                let stats = IoStats::new();
+                barrier.wait();
+                let mut writer = FastWriter::new(&path, None)?;
+                writer.write(&vec![b'A'; size])?;
+                writer.flush()?;
+                barrier.wait();
+                FastReader::new(&path, None)?.read_all()?;
+                Ok(stats.delta())
+            })
+        }).collect();
+        
+        let deltas: Vec<(usize, usize)> = handles.into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect::<io::Result<_>>()?;
+        assert_eq!(deltas, vec![(100, 100), (2500, 2500)]);
+        
+        // Nested scopes on one thread
+        let path = dir.path().join("nested.txt");
+        let outer = IoStats::new();
+        FastWriter::new(&path, None)?.write(b"ACGT")?;
+        let inner = IoStats::new();
+        assert_eq!(inner.delta(), (0, 0));
+        FastReader::new(&path, None)?.read_all()?;
+        assert_eq!(inner.delta(), (4, 0));
+        assert_eq!(outer.delta(), (4, 4));
+        
         Ok(())
     }
     
@@ -427,4 +582,29 @@ mod tests {
         
         Ok(())
     }
+    
+    #[test]
+    fn test_split_at_record_boundaries() {
+        let data = b">a\nACGT\n>b\nGG\n>c\nTTTTTTTTTTTT\n>d\nA\n";
+        
+        // Chunks tile the data and each starts with a header
+        for chunk_size in 1..data.len() + 2 {
+            let chunks = split_at_record_boundaries(data, chunk_size, b'>');
+            assert_eq!(chunks.first().unwrap().0, 0);
+            assert_eq!(chunks.last().unwrap().1, data.len());
+            for pair in chunks.windows(2) {
+                assert_eq!(pair[0].1, pair[1].0);
+            }
+            for &(start, _) in &chunks {
+                assert_eq!(data[start], b'>');
+            }
+        }
+        
+        // A header exactly at the edge starts the next chunk
+        assert_eq!(split_at_record_boundaries(data, 8, b'>')[..2], [(0, 8), (8, 14)]);
+        
+        // A record longer than the chunk size stays whole
+        assert_eq!(split_at_record_boundaries(data, 4, b'>')[2], (14, 30));
+        assert!(split_at_record_boundaries(b"", 4, b'>').is_empty());
+    }
 }
\ No newline at end of file
@@ -80,11 +80,23 @@ impl MemoryMapped {
 }
 
 /// Packed 2-bit encoding for DNA sequences
+///
+/// Like the UCSC 2bit format, runs of bases other than A, C, G and T (e.g.
+/// `N` or IUPAC codes) and runs of soft-masked lowercase bases are kept as
+/// blocks, so unpacking restores the original bytes exactly.
+///
+/// This is the 2-bit store behind `Sequence::to_packed_storage`: it
+/// implements `StorableSequence`, decodes subsequences on demand and uses
+/// about a quarter of a byte per base.
 pub struct PackedDnaStorage {
     /// The packed sequence data (2 bits per base)
     data: Vec<u8>,
     /// The length of the sequence in bases
     len: usize,
+    /// Runs of a repeated non-ACGT base as (start, length, uppercase base)
+    n_blocks: Vec<(usize, usize, u8)>,
+    /// Runs of lowercase bases as (start, length)
+    mask_blocks: Vec<(usize, usize)>,
 }
 
 impl PackedDnaStorage {
@@ -95,6 +107,8 @@ impl PackedDnaStorage {
         Self {
             data: Vec::with_capacity(byte_capacity),
             len: 0,
+            n_blocks: Vec::new(),
+            mask_blocks: Vec::new(),
         }
     }
     
@@ -105,9 +119,11 @@ impl PackedDnaStorage {
         let required_bytes = (sequence.len() + 3) / 4;
         self.data.clear();
         self.data.reserve(required_bytes);
+        self.n_blocks.clear();
+        self.mask_blocks.clear();
         
         // Pack 4 bases per byte
-        for chunk in sequence.chunks(4) {
+        for (chunk_idx, chunk) in sequence.chunks(4).enumerate() {
             let mut byte = 0u8;
             for (i, &base) in chunk.iter().enumerate() {
                 let bits = match base {
@@ -118,9 +134,27 @@ impl PackedDnaStorage {
                     _ => 0b00, // Default to 'A' for invalid bases
                 };
                 byte |= bits << (6 - i * 2);
+                
+                // Record anything the 2-bit code can't reproduce
+                let pos = chunk_idx * 4 + i;
+                let upper = base.to_ascii_uppercase();
+                if !matches!(upper, b'A' | b'C' | b'G' | b'T') {
+                    match self.n_blocks.last_mut() {
+                        Some((start, len, b)) if *b == upper && *start + *len == pos => *len += 1,
+                        _ => self.n_blocks.push((pos, 1, upper)),
+                    }
+                }
+                if base.is_ascii_lowercase() {
+                    match self.mask_blocks.last_mut() {
+                        Some((start, len)) if *start + *len == pos => *len += 1,
+                        _ => self.mask_blocks.push((pos, 1)),
+                    }
+                }
             }
             self.data.push(byte);
         }
+        self.n_blocks.shrink_to_fit();
+        self.mask_blocks.shrink_to_fit();
         
         self.len = sequence.len();
         
@@ -146,9 +180,46 @@ impl PackedDnaStorage {
             };
         }
         
+        self.apply_blocks(&mut buffer[..unpack_len], 0);
+        
         unpack_len
     }
     
+    /// Unpack the bases in `start..end` into a new vector
+    pub fn unpack_range(&self, start: usize, end: usize) -> Vec<u8> {
+        let end = end.min(self.len);
+        let start = start.min(end);
+        
+        let mut result = Vec::with_capacity(end - start);
+        for i in start..end {
+            let bits = (self.data[i / 4] >> (6 - (i % 4) * 2)) & 0b11;
+            result.push(b"ACGT"[bits as usize]);
+        }
+        
+        self.apply_blocks(&mut result, start);
+        result
+    }
+    
+    /// Restore non-ACGT and lowercase blocks in `buffer`, which holds bases from `offset`
+    fn apply_blocks(&self, buffer: &mut [u8], offset: usize) {
+        let end = offset + buffer.len();
+        
+        // Blocks are sorted by position, so only visit the overlapping ones
+        let first = self.n_blocks.partition_point(|&(start, len, _)| start + len <= offset);
+        for &(start, len, base) in self.n_blocks[first..].iter().take_while(|&&(start, _, _)| start < end) {
+            for pos in start.max(offset)..(start + len).min(end) {
+                buffer[pos - offset] = base;
+            }
+        }
+        
+        let first = self.mask_blocks.partition_point(|&(start, len)| start + len <= offset);
+        for &(start, len) in self.mask_blocks[first..].iter().take_while(|&&(start, _)| start < end) {
+            for pos in start.max(offset)..(start + len).min(end) {
+                buffer[pos - offset].make_ascii_lowercase();
+            }
+        }
+    }
+    
     /// Get the length of the sequence in bases
     pub fn len(&self) -> usize {
         self.len
@@ -162,50 +233,62 @@ impl PackedDnaStorage {
     /// Get the memory usage in bytes
     pub fn memory_usage(&self) -> usize {
         self.data.capacity()
+            + self.n_blocks.capacity() * std::mem::size_of::<(usize, usize, u8)>()
+            + self.mask_blocks.capacity() * std::mem::size_of::<(usize, usize)>()
     }
 }
 
-/// Packed 4-bit encoding for protein sequences
+/// Packed 5-bit encoding for protein sequences
+///
+/// Stores the 20 standard amino acids, the ambiguity codes B, Z and X and
+/// the stop `*`, so unpacking restores any uppercase protein exactly.
+/// Residues straddle byte boundaries; lowercase residues are unpacked as
+/// uppercase and any other character as `X`.
 pub struct PackedProteinStorage {
-    /// The packed sequence data (4 bits per amino acid)
+    /// The packed sequence data (5 bits per amino acid, most significant first)
     data: Vec<u8>,
     /// The length of the sequence in amino acids
     len: usize,
 }
 
+/// Bits used per packed amino acid
+const PROTEIN_CODE_BITS: usize = 5;
+
 impl PackedProteinStorage {
     /// Create a new packed protein storage with the given capacity
     pub fn with_capacity(capacity: usize) -> Self {
-        // Calculate number of bytes needed for 4-bit encoding
-        let byte_capacity = (capacity + 1) / 2;
         Self {
-            data: Vec::with_capacity(byte_capacity),
+            data: Vec::with_capacity(Self::packed_bytes(capacity)),
             len: 0,
         }
     }
     
-    /// Pack a protein sequence into the storage (4 bits per amino acid)
+    /// Number of bytes needed to pack `len` amino acids
+    fn packed_bytes(len: usize) -> usize {
+        (len * PROTEIN_CODE_BITS).div_ceil(8)
+    }
+    
+    /// Pack a protein sequence into the storage (5 bits per amino acid)
     pub fn pack(&mut self, sequence: &[u8]) {
-        // Calculate required capacity
-        let required_bytes = (sequence.len() + 1) / 2;
+        // Size the buffer up front so a code spilling into the next byte
+        // always has a byte to spill into
         self.data.clear();
-        self.data.reserve(required_bytes);
+        self.data.resize(Self::packed_bytes(sequence.len()), 0);
         
-        // Pack 2 amino acids per byte
-        for chunk in sequence.chunks(2) {
-            let mut byte = 0u8;
+        for (i, &aa) in sequence.iter().enumerate() {
+            let code = encode_amino_acid(aa);
+            let bit = i * PROTEIN_CODE_BITS;
+            let (byte_idx, bit_offset) = (bit / 8, bit % 8);
             
-            // First amino acid in high nibble
-            let first = encode_amino_acid(chunk[0]);
-            byte |= first << 4;
-            
-            // Second amino acid in low nibble (if exists)
-            if chunk.len() > 1 {
-                let second = encode_amino_acid(chunk[1]);
-                byte |= second;
+            if bit_offset <= 8 - PROTEIN_CODE_BITS {
+                // The code fits in the current byte
+                self.data[byte_idx] |= code << (8 - PROTEIN_CODE_BITS - bit_offset);
+            } else {
+                // High bits end the current byte, low bits start the next one
+                let spill = bit_offset + PROTEIN_CODE_BITS - 8;
+                self.data[byte_idx] |= code >> spill;
+                self.data[byte_idx + 1] |= code << (8 - spill);
             }
-            
-            self.data.push(byte);
         }
         
         self.len = sequence.len();
@@ -214,26 +297,37 @@ impl PackedProteinStorage {
         update_memory_usage(self.data.capacity());
     }
     
+    /// Read the 5-bit code of amino acid `i`
+    fn code_at(&self, i: usize) -> u8 {
+        let bit = i * PROTEIN_CODE_BITS;
+        let (byte_idx, bit_offset) = (bit / 8, bit % 8);
+        
+        // Read two bytes so codes crossing a boundary come out whole
+        let high = self.data[byte_idx] as u16;
+        let low = self.data.get(byte_idx + 1).copied().unwrap_or(0) as u16;
+        let window = (high << 8) | low;
+        ((window >> (16 - PROTEIN_CODE_BITS - bit_offset)) & 0x1F) as u8
+    }
+    
     /// Unpack the protein sequence into the provided buffer
     pub fn unpack(&self, buffer: &mut [u8]) -> usize {
         let unpack_len = self.len.min(buffer.len());
         
-        for i in 0..unpack_len {
-            let byte_idx = i / 2;
-            let is_high_nibble = i % 2 == 0;
-            
-            let nibble = if is_high_nibble {
-                (self.data[byte_idx] >> 4) & 0x0F
-            } else {
-                self.data[byte_idx] & 0x0F
-            };
-            
-            buffer[i] = decode_amino_acid(nibble);
+        for (i, slot) in buffer[..unpack_len].iter_mut().enumerate() {
+            *slot = decode_amino_acid(self.code_at(i));
         }
         
         unpack_len
     }
     
+    /// Unpack the amino acids in `start..end` into a new vector
+    pub fn unpack_range(&self, start: usize, end: usize) -> Vec<u8> {
+        let end = end.min(self.len);
+        let start = start.min(end);
+        
+        (start..end).map(|i| decode_amino_acid(self.code_at(i))).collect()
+    }
+    
     /// Get the length of the sequence in amino acids
     pub fn len(&self) -> usize {
         self.len
@@ -273,48 +367,23 @@ pub fn get_memory_stats() -> (usize, usize) {
     )
 }
 
-/// Encode an amino acid to a 4-bit representation
+/// Amino acid for each 5-bit code
+const PROTEIN_CODES: &[u8; 24] = b"ACDEFGHIKLMNPQRSTVWYBZX*";
+
+/// Code used for characters outside [`PROTEIN_CODES`]
+const UNKNOWN_AMINO_ACID: u8 = 22;
+
+/// Encode an amino acid to a 5-bit representation
 fn encode_amino_acid(aa: u8) -> u8 {
-    match aa {
-        b'A' | b'a' => 0x0,
-        b'R' | b'r' => 0x1,
-        b'N' | b'n' => 0x2,
-        b'D' | b'd' => 0x3,
-        b'C' | b'c' => 0x4,
-        b'Q' | b'q' => 0x5,
-        b'E' | b'e' => 0x6,
-        b'G' | b'g' => 0x7,
-        b'H' | b'h' => 0x8,
-        b'I' | b'i' => 0x9,
-        b'L' | b'l' => 0xA,
-        b'K' | b'k' => 0xB,
-        b'M' | b'm' => 0xC,
-        b'F' | b'f' => 0xD,
-        b'P' | b'p' => 0xE,
-        _ => 0xF, // All other amino acids and stop codons
-    }
+    let aa = aa.to_ascii_uppercase();
+    PROTEIN_CODES.iter()
+        .position(|&code| code == aa)
+        .map_or(UNKNOWN_AMINO_ACID, |code| code as u8)
 }
 
-/// Decode a 4-bit representation to an amino acid
-fn decode_amino_acid(nibble: u8) -> u8 {
-    match nibble {
-        0x0 => b'A',
-        0x1 => b'R',
-        0x2 => b'N',
-        0x3 => b'D',
-        0x4 => b'C',
-        0x5 => b'Q',
-        0x6 => b'E',
-        0x7 => b'G',
-        0x8 => b'H',
-        0x9 => b'I',
-        0xA => b'L',
-        0xB => b'K',
-        0xC => b'M',
-        0xD => b'F',
-        0xE => b'P',
-        _ => b'X', // Unknown or stop codon
-    }
+/// Decode a 5-bit representation to an amino acid
+fn decode_amino_acid(code: u8) -> u8 {
+    PROTEIN_CODES.get(code as usize).copied().unwrap_or(b'X')
 }
 
 #[cfg(test)]
@@ -340,6 +409,22 @@ mod tests {
         assert_eq!(packed.memory_usage(), 2); // 8 bases = 2 bytes
     }
     
+    #[test]
+    fn test_dna_packing_blocks() {
+        let dna = b"ACGTNNacgtRYACG";
+        let mut packed = PackedDnaStorage::with_capacity(dna.len());
+        packed.pack(dna);
+        
+        // Non-ACGT bases round-trip exactly
+        let mut buffer = vec![0; dna.len()];
+        packed.unpack(&mut buffer);
+        assert_eq!(&buffer, dna);
+        
+        // Ranges restore exceptions at the right offsets
+        assert_eq!(packed.unpack_range(3, 9), b"TNNacg".to_vec());
+        assert_eq!(packed.unpack_range(12, 100), b"ACG".to_vec());
+    }
+    
     #[test]
     fn test_protein_packing() {
         let protein = b"ARNDCQEGHILKMFP";
@@ -356,7 +441,41 @@ mod tests {
         assert_eq!(&buffer, protein);
         
         // Check memory usage
-        assert_eq!(packed.memory_usage(), 8); // 15 amino acids = 8 bytes
+        assert_eq!(packed.memory_usage(), 10); // 15 amino acids * 5 bits = 10 bytes
+    }
+    
+    #[test]
+    fn test_protein_packing_round_trip() {
+        // Every code at every bit offset, for every length mod 8
+        for len in 1..=16 {
+            for shift in 0..PROTEIN_CODES.len() {
+                let protein: Vec<u8> = (0..len).map(|i| PROTEIN_CODES[(i + shift) % PROTEIN_CODES.len()]).collect();
+                let mut packed = PackedProteinStorage::with_capacity(len);
+                packed.pack(&protein);
+                assert_eq!(packed.memory_usage(), (len * 5).div_ceil(8));
+                
+                let mut buffer = vec![0; len];
+                assert_eq!(packed.unpack(&mut buffer), len);
+                assert_eq!(buffer, protein, "length {} shift {}", len, shift);
+                
+                // Ranges decode to the same substring
+                for start in 0..=len {
+                    for end in start..=len {
+                        assert_eq!(packed.unpack_range(start, end), &protein[start..end]);
+                    }
+                }
+            }
+        }
+        
+        // Codes in the final byte survive, including the all-ones pattern
+        let mut packed = PackedProteinStorage::with_capacity(2);
+        packed.pack(b"**");
+        assert_eq!(packed.unpack_range(0, 2), b"**");
+        
+        // Lowercase is restored as uppercase, unknown characters as X
+        let mut packed = PackedProteinStorage::with_capacity(4);
+        packed.pack(b"mkJv");
+        assert_eq!(packed.unpack_range(0, 10), b"MKXV");
     }
     
     #[test]
@@ -3,19 +3,19 @@
 //! This module provides a high-performance parallel processing framework
 //! for biological sequence data, using work-stealing and adaptive chunking.
 
-use std::sync::Once;
 use rayon::{ThreadPool, ThreadPoolBuilder};
-use std::sync::Mutex;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
-use parking_lot::RwLock;
+use std::cmp::Reverse;
+use std::time::{Duration, Instant};
 use rayon::prelude::*;
 
-// Initialize once
-static INIT: Once = Once::new();
+// Global thread pool for parallel operations, created once
+static GLOBAL_POOL: OnceLock<ThreadPool> = OnceLock::new();
 
-// Global thread pool for parallel operations
-static mut GLOBAL_POOL: Option<ThreadPool> = None;
+// Environment variable that caps the default thread count
+const NUM_THREADS_ENV: &str = "BIOSEQ_NUM_THREADS";
 
 // Default chunk size for adaptive chunking
 const DEFAULT_CHUNK_SIZE: usize = 1024 * 1024; // 1MB
@@ -23,11 +23,26 @@ const DEFAULT_CHUNK_SIZE: usize = 1024 * 1024; // 1MB
 // Default minimum chunks per thread
 const MIN_CHUNKS_PER_THREAD: usize = 4;
 
+// Minimum time between progress callbacks
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
 /// Initialize the thread pool for parallel processing
+///
+/// Does nothing if the pool has already been initialized.
 pub fn initialize_thread_pool() {
-    INIT.call_once(|| {
-        // Create a thread pool with the number of CPUs
-        let num_threads = default_num_threads();
+    initialize_thread_pool_with(default_num_threads());
+}
+
+/// Initialize the thread pool with a fixed number of threads
+///
+/// The pool is created only once; returns `false` if it already existed,
+/// in which case its thread count is left unchanged.
+pub fn initialize_thread_pool_with(num_threads: usize) -> bool {
+    let num_threads = num_threads.max(1);
+    let mut created = false;
+    
+    let pool = GLOBAL_POOL.get_or_init(|| {
+        created = true;
         
         let pool = ThreadPoolBuilder::new()
             .num_threads(num_threads)
@@ -35,25 +50,40 @@ pub fn initialize_thread_pool() {
             .build()
             .expect("Failed to create thread pool");
         
-        // Store in global variable
-        unsafe {
-            GLOBAL_POOL = Some(pool);
-        }
-        
         log::info!("Initialized thread pool with {} threads", num_threads);
+        pool
     });
+    
+    if !created && pool.current_num_threads() != num_threads {
+        log::debug!(
+            "Thread pool already initialized with {} threads, ignoring request for {}",
+            pool.current_num_threads(),
+            num_threads
+        );
+    }
+    
+    created
 }
 
 /// Get the default number of threads to use
+///
+/// Uses `BIOSEQ_NUM_THREADS` when set to a positive integer, otherwise the
+/// number of CPUs.
 pub fn default_num_threads() -> usize {
-    num_cpus::get()
+    parse_num_threads(std::env::var(NUM_THREADS_ENV).ok().as_deref())
+        .unwrap_or_else(num_cpus::get)
+}
+
+/// Parse a thread count override, ignoring empty, zero or invalid values
+fn parse_num_threads(value: Option<&str>) -> Option<usize> {
+    value
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .filter(|&n| n > 0)
 }
 
 /// Get a reference to the global thread pool
 pub fn global_pool() -> &'static ThreadPool {
-    unsafe {
-        GLOBAL_POOL.as_ref().expect("Thread pool not initialized")
-    }
+    GLOBAL_POOL.get().expect("Thread pool not initialized")
 }
 
 /// Execute a closure in parallel with the global thread pool
@@ -67,6 +97,9 @@ where
 }
 
 /// Calculate optimal chunk size for parallel processing
+///
+/// The result is a positive multiple of 1KB, even for empty input or a
+/// minimum of zero.
 pub fn calculate_chunk_size(total_size: usize, min_chunk_size: Option<usize>) -> usize {
     let num_threads = default_num_threads();
     let min_size = min_chunk_size.unwrap_or(1024); // Minimum 1KB
@@ -75,41 +108,163 @@ pub fn calculate_chunk_size(total_size: usize, min_chunk_size: Option<usize>) ->
     let chunks_per_thread = MIN_CHUNKS_PER_THREAD;
     let total_chunks = num_threads * chunks_per_thread;
     
-    // Ensure chunk size is at least min_size
-    let chunk_size = (total_size / total_chunks).max(min_size);
+    // Ensure chunk size is at least min_size, and never zero
+    let chunk_size = (total_size / total_chunks).max(min_size).max(1);
     
     // Round to the nearest multiple of 1KB for better memory alignment
     let alignment = 1024;
     ((chunk_size + alignment - 1) / alignment) * alignment
 }
 
+/// Scheduling priority of a work item
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Priority {
+    /// Started after all other items
+    Low,
+    /// Default priority
+    #[default]
+    Normal,
+    /// Started before all other items
+    High,
+}
+
+/// A work item tagged with its scheduling priority
+#[derive(Debug, Clone)]
+pub struct PrioritizedWork<T> {
+    /// The work item
+    pub item: T,
+    /// Priority of the item
+    pub priority: Priority,
+}
+
+impl<T> PrioritizedWork<T> {
+    /// Tag a work item with a priority
+    pub fn new(item: T, priority: Priority) -> Self {
+        Self { item, priority }
+    }
+}
+
 /// Work-stealing scheduler for balanced parallel execution
 pub struct WorkStealingScheduler<T> {
-    /// Work items to process
-    work_items: Mutex<Vec<T>>,
+    /// Work items to process with their submission index, taken from the back
+    work_items: Mutex<Vec<(usize, T)>>,
     /// Number of work items initially submitted
     total_items: usize,
     /// Number of completed work items
     completed: AtomicUsize,
+    /// Set to stop workers from taking new items
+    cancelled: Arc<AtomicBool>,
 }
 
-impl<T: Send + 'static> WorkStealingScheduler<T> {
+impl<T: Send> WorkStealingScheduler<T> {
     /// Create a new work-stealing scheduler with the given work items
     pub fn new(work_items: Vec<T>) -> Self {
+        Self::from_indexed(work_items.into_iter().enumerate().collect())
+    }
+    
+    /// Create a scheduler that starts higher-priority items first
+    ///
+    /// Items of equal priority start in submission order. Priority only
+    /// decides when an item starts; with several workers, a later item can
+    /// still finish first.
+    pub fn with_prioritized_items(work_items: Vec<PrioritizedWork<T>>) -> Self {
+        let mut indexed: Vec<(Priority, usize, T)> = work_items.into_iter()
+            .enumerate()
+            .map(|(index, work)| (work.priority, index, work.item))
+            .collect();
+        
+        // Workers take items from the back, so put the most urgent last
+        indexed.sort_by_key(|&(priority, index, _)| (priority, Reverse(index)));
+        Self::from_indexed(indexed.into_iter().map(|(_, index, item)| (index, item)).collect())
+    }
+    
+    /// Create a scheduler from items already tagged with submission indices
+    fn from_indexed(work_items: Vec<(usize, T)>) -> Self {
         let total_items = work_items.len();
         Self {
             work_items: Mutex::new(work_items),
             total_items,
             completed: AtomicUsize::new(0),
+            cancelled: Arc::new(AtomicBool::new(false)),
         }
     }
     
     /// Execute the work items in parallel using the given function
     pub fn execute<F>(&self, f: F)
     where
-        F: Fn(T) + Send + Sync + Clone + 'static,
+        F: Fn(T) + Send + Sync + Clone,
+    {
+        self.run_workers(move |_, item| f(item), || {});
+    }
+    
+    /// Execute the work items and collect their results in submission order
+    ///
+    /// Items skipped because of cancellation have no result.
+    pub fn execute_with_results<F, R>(&self, f: F) -> Vec<R>
+    where
+        F: Fn(T) -> R + Send + Sync + Clone,
+        R: Send,
+    {
+        let results = Mutex::new(Vec::with_capacity(self.total_items));
+        
+        // Items finish in any order, so tag each result with its index
+        self.run_workers(|index, item| {
+            let result = f(item);
+            results.lock().unwrap().push((index, result));
+        }, || {});
+        
+        let mut results = results.into_inner().unwrap();
+        results.sort_unstable_by_key(|&(index, _)| index);
+        results.into_iter().map(|(_, result)| result).collect()
+    }
+    
+    /// Execute the work items, calling `on_progress` with the progress (0.0-1.0)
+    ///
+    /// The callback runs on worker threads at most every 100ms, plus once
+    /// when execution finishes or is cancelled.
+    pub fn execute_with_progress<F, P>(&self, f: F, on_progress: P)
+    where
+        F: Fn(T) + Send + Sync + Clone,
+        P: Fn(f64) + Send + Sync,
+    {
+        let last_report = Mutex::new(Instant::now());
+        
+        self.run_workers(move |_, item| f(item), || {
+            // Skip the report if another worker is already reporting
+            if let Ok(mut last) = last_report.try_lock() {
+                if last.elapsed() >= PROGRESS_INTERVAL {
+                    *last = Instant::now();
+                    on_progress(self.progress());
+                }
+            }
+        });
+        
+        on_progress(self.progress());
+    }
+    
+    /// Get a token that cancels execution when set to `true`
+    pub fn cancellation_token(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.cancelled)
+    }
+    
+    /// Request cancellation; items already being processed still finish
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+    
+    /// Check if cancellation has been requested
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+    
+    /// Run workers until the items run out or cancellation is requested
+    fn run_workers<F, C>(&self, f: F, on_item_done: C)
+    where
+        F: Fn(usize, T) + Send + Sync + Clone,
+        C: Fn() + Send + Sync,
     {
         let pool = global_pool();
+        let on_item_done = &on_item_done;
         
         pool.install(|| {
             rayon::scope(|s| {
@@ -119,6 +274,11 @@ impl<T: Send + 'static> WorkStealingScheduler<T> {
                     s.spawn(move |_| {
                         // Worker loop: grab work items and process them
                         loop {
+                            // Stop taking work once cancelled
+                            if self.cancelled.load(Ordering::SeqCst) {
+                                break;
+                            }
+                            
                             // Try to get work
                             let work_item = {
                                 let mut guard = self.work_items.lock().unwrap();
@@ -128,12 +288,13 @@ impl<T: Send + 'static> WorkStealingScheduler<T> {
                                 guard.pop()
                             };
                             
-                            if let Some(item) = work_item {
+                            if let Some((index, item)) = work_item {
                                 // Process the work item
-                                f_clone(item);
+                                f_clone(index, item);
                                 
                                 // Update completed count
                                 self.completed.fetch_add(1, Ordering::SeqCst);
+                                on_item_done();
                             } else {
                                 break;
                             }
@@ -162,55 +323,58 @@ impl<T: Send + 'static> WorkStealingScheduler<T> {
 }
 
 /// Parallel sequence chunk processor
-pub struct ParallelChunkProcessor<T> {
+///
+/// Chunks are borrowed slices of the caller's data, so nothing is copied.
+pub struct ParallelChunkProcessor<'a, T> {
     /// Data divided into chunks
-    chunks: Vec<T>,
-    /// Results of processing
-    results: Arc<RwLock<Vec<usize>>>,
+    chunks: Vec<&'a [T]>,
 }
 
-impl<T: Send + Sync + 'static> ParallelChunkProcessor<T> {
+impl<'a, T: Sync> ParallelChunkProcessor<'a, T> {
     /// Create a new parallel chunk processor with the given chunks
-    pub fn new(chunks: Vec<T>) -> Self {
-        let num_chunks = chunks.len();
-        Self {
-            chunks,
-            results: Arc::new(RwLock::new(Vec::with_capacity(num_chunks))),
-        }
+    ///
+    /// Chunks may overlap, e.g. to share k-mers across boundaries.
+    pub fn new(chunks: Vec<&'a [T]>) -> Self {
+        Self { chunks }
+    }
+    
+    /// Create a processor over consecutive chunks of `data`
+    pub fn from_slice(data: &'a [T], chunk_size: usize) -> Self {
+        Self::new(data.chunks(chunk_size.max(1)).collect())
+    }
+    
+    /// Get the number of chunks
+    pub fn num_chunks(&self) -> usize {
+        self.chunks.len()
     }
     
     /// Process the chunks in parallel using the given function
+    ///
+    /// Results are returned in chunk order.
     pub fn process<F, R>(&self, f: F) -> Vec<R>
     where
-        F: Fn(&T) -> R + Send + Sync + Clone + 'static,
-        R: Send + 'static,
+        F: Fn(&[T]) -> R + Send + Sync,
+        R: Send,
     {
         let pool = global_pool();
-        let results = Arc::new(Mutex::new(Vec::with_capacity(self.chunks.len())));
         
         pool.install(|| {
-            let chunks_ref = &self.chunks;
-            let results_ref = Arc::clone(&results);
-            
-            // Process chunks in parallel
-            chunks_ref
+            self.chunks
                 .par_iter()
                 .map(|chunk| f(chunk))
-                .collect_into_vec(&mut *results_ref.lock().unwrap());
-        });
-        
-        // Return results
-        let guard = results.lock().unwrap();
-        guard.clone()
+                .collect()
+        })
     }
 }
 
 /// Adaptive parallel execution based on workload
+///
+/// Results are returned in the same order as `items`.
 pub fn adaptive_parallel_execute<T, F, R>(items: Vec<T>, f: F) -> Vec<R>
 where
-    T: Send + Sync + 'static,
-    F: Fn(&T) -> R + Send + Sync + Clone + 'static,
-    R: Send + Default + 'static,
+    T: Send + Sync,
+    F: Fn(&T) -> R + Send + Sync + Clone,
+    R: Send,
 {
     let num_items = items.len();
     
@@ -222,36 +386,27 @@ where
     // For small to medium workloads, use rayon's par_iter directly
     if num_items <= 1000 {
         let pool = global_pool();
-        let results = Arc::new(Mutex::new(Vec::with_capacity(num_items)));
         
-        pool.install(|| {
+        return pool.install(|| {
             items.par_iter()
                 .map(|item| f(item))
-                .collect_into_vec(&mut *results.lock().unwrap());
+                .collect()
         });
-        
-        let guard = results.lock().unwrap();
-        return guard.clone();
     }
     
     // For large workloads, use the work-stealing scheduler
-    let scheduler = WorkStealingScheduler::new(items);
-    let results = Arc::new(Mutex::new(Vec::with_capacity(num_items)));
-    let results_ref = Arc::clone(&results);
-    
-    scheduler.execute(move |item| {
-        let result = f(&item);
-        let mut guard = results_ref.lock().unwrap();
-        guard.push(result);
-    });
-    
-    let guard = results.lock().unwrap();
-    guard.clone()
+    WorkStealingScheduler::new(items).execute_with_results(|item| f(&item))
 }
 
 /// Chunk a slice into optimally sized chunks for parallel processing
+///
+/// An empty slice gives no chunks; a slice smaller than one chunk gives a
+/// single chunk holding all of it.
 pub fn chunk_slice<T>(slice: &[T], min_chunk_size: Option<usize>) -> Vec<&[T]> {
     let len = slice.len();
+    if len == 0 {
+        return Vec::new();
+    }
     let chunk_size = calculate_chunk_size(len, min_chunk_size);
     
     // Create chunks
@@ -286,12 +441,29 @@ where
         // Split the task
         let subtasks = split_func();
         
-        // Process subtasks in parallel
-        let results: Vec<R> = subtasks
-            .into_par_iter()
-            .map(|subtask| process_func(subtask))
+        // For very small number of subtasks, use simpler approach
+        if subtasks.len() <= 4 {
+            let results: Vec<R> = subtasks
+                .into_par_iter()
+                .map(|subtask| process_func(subtask))
+                .collect();
+            
+            return join_func(results);
+        }
+        
+        // First and last subtasks often hold the edge cases, so start them first
+        let last = subtasks.len() - 1;
+        let prioritized_tasks = subtasks.into_iter()
+            .enumerate()
+            .map(|(i, subtask)| {
+                let priority = if i == 0 || i == last { Priority::High } else { Priority::Normal };
+                PrioritizedWork::new(subtask, priority)
+            })
             .collect();
         
+        let scheduler = WorkStealingScheduler::with_prioritized_items(prioritized_tasks);
+        let results = scheduler.execute_with_results(&process_func);
+        
         // Join the results
         join_func(results)
     })
@@ -317,6 +489,48 @@ mod tests {
         // Should be a multiple of 1KB
         assert_eq!(chunk_1mb % 1024, 0);
         assert_eq!(chunk_1gb % 1024, 0);
+        
+        // Empty input and a zero minimum still give a usable size
+        assert_eq!(calculate_chunk_size(0, None), 1024);
+        assert_eq!(calculate_chunk_size(0, Some(0)), 1024);
+        assert_eq!(calculate_chunk_size(100, Some(0)), 1024);
+    }
+    
+    #[test]
+    fn test_chunk_slice_small_inputs() {
+        let empty: [u8; 0] = [];
+        assert!(chunk_slice(&empty, None).is_empty());
+        assert!(chunk_slice(&empty, Some(0)).is_empty());
+        
+        let one = [7u8];
+        assert_eq!(chunk_slice(&one, None), vec![&one[..]]);
+        assert_eq!(chunk_slice(&one, Some(0)), vec![&one[..]]);
+        
+        // Less than one chunk stays in a single chunk
+        let small = [1u8; 100];
+        assert_eq!(chunk_slice(&small, None), vec![&small[..]]);
+        
+        // Chunks cover larger slices in order without gaps
+        let large: Vec<u32> = (0..5000).collect();
+        let chunks = chunk_slice(&large, Some(1024));
+        assert!(chunks.iter().all(|chunk| !chunk.is_empty() && chunk.len() <= 5000));
+        assert_eq!(chunks.concat(), large);
+    }
+    
+    #[test]
+    fn test_thread_count_configuration() {
+        assert_eq!(parse_num_threads(Some("8")), Some(8));
+        assert_eq!(parse_num_threads(Some(" 4 ")), Some(4));
+        assert_eq!(parse_num_threads(Some("0")), None);
+        assert_eq!(parse_num_threads(Some("many")), None);
+        assert_eq!(parse_num_threads(None), None);
+        
+        // Once the pool exists, later initialization is a no-op
+        initialize_thread_pool();
+        let threads = global_pool().current_num_threads();
+        assert!(!initialize_thread_pool_with(threads + 1));
+        initialize_thread_pool();
+        assert_eq!(global_pool().current_num_threads(), threads);
     }
     
     #[test]
@@ -340,24 +554,98 @@ mod tests {
         assert!(scheduler.is_completed());
         
         // Check the result (sum of numbers 0-999)
-        let expected_sum = (0..1000).sum();
+        let expected_sum: usize = (0..1000).sum();
         assert_eq!(sum.load(Ordering::SeqCst), expected_sum);
     }
     
+    #[test]
+    fn test_work_stealing_progress_and_cancel() {
+        initialize_thread_pool();
+        
+        // Progress reports are monotonic and end at 1.0; each worker runs
+        // long enough for intermediate reports to be made
+        let items = global_pool().current_num_threads() * 150;
+        let scheduler = WorkStealingScheduler::new((0..items).collect::<Vec<usize>>());
+        let reports = Mutex::new(Vec::new());
+        scheduler.execute_with_progress(
+            |_| std::thread::sleep(Duration::from_millis(2)),
+            |p| reports.lock().unwrap().push(p),
+        );
+        let reports = reports.into_inner().unwrap();
+        assert!(reports.len() > 1);
+        assert!(reports.windows(2).all(|w| w[0] <= w[1]), "{:?}", reports);
+        assert_eq!(reports.last(), Some(&1.0));
+        assert!(reports.iter().all(|&p| (0.0..=1.0).contains(&p)));
+        
+        // Items are popped from the back, so cancelling near the end stops early
+        let scheduler = WorkStealingScheduler::new((0..10_000).collect::<Vec<usize>>());
+        let token = scheduler.cancellation_token();
+        let processed = Arc::new(AtomicUsize::new(0));
+        let processed_ref = Arc::clone(&processed);
+        
+        scheduler.execute(move |item| {
+            if item == 9_990 {
+                token.store(true, Ordering::SeqCst);
+            }
+            processed_ref.fetch_add(1, Ordering::SeqCst);
+        });
+        
+        assert!(scheduler.is_cancelled());
+        assert!(!scheduler.is_completed());
+        assert!(scheduler.progress() < 1.0);
+        assert_eq!(processed.load(Ordering::SeqCst), scheduler.completed.load(Ordering::SeqCst));
+    }
+    
+    #[test]
+    fn test_prioritized_scheduler() {
+        initialize_thread_pool();
+        
+        // High-priority items are taken first, ties in submission order
+        let scheduler = WorkStealingScheduler::with_prioritized_items(vec![
+            PrioritizedWork::new("a", Priority::Normal),
+            PrioritizedWork::new("b", Priority::High),
+            PrioritizedWork::new("c", Priority::Low),
+            PrioritizedWork::new("d", Priority::High),
+            PrioritizedWork::new("e", Priority::Normal),
+        ]);
+        let start_order: Vec<&str> = scheduler.work_items.lock().unwrap().iter().rev().map(|&(_, item)| item).collect();
+        assert_eq!(start_order, vec!["b", "d", "a", "e", "c"]);
+        
+        // Results come back in submission order regardless of priority
+        let results = scheduler.execute_with_results(|item| item.to_uppercase());
+        assert_eq!(results, vec!["A", "B", "C", "D", "E"]);
+        assert!(scheduler.is_completed());
+        
+        // Split-join keeps subtask order on the prioritized path
+        let joined = parallel_split_join(
+            || (0..20).collect::<Vec<usize>>(),
+            |i| vec![i * 2],
+            |parts| parts.concat(),
+        );
+        assert_eq!(joined, (0..20).map(|i| i * 2).collect::<Vec<_>>());
+    }
+    
+    #[test]
+    fn test_adaptive_parallel_execute_order() {
+        initialize_thread_pool();
+        
+        // Sequential, par_iter and work-stealing paths all keep input order
+        for n in [5, 500, 5000] {
+            let items: Vec<usize> = (0..n).collect();
+            let results = adaptive_parallel_execute(items, |&x| x * 2);
+            assert_eq!(results, (0..n).map(|x| x * 2).collect::<Vec<_>>());
+        }
+    }
+    
     #[test]
     fn test_parallel_chunk_processing() {
         // Initialize thread pool
         initialize_thread_pool();
         
         // Create data chunks
-        let data: Vec<Vec<usize>> = vec![
-            vec![1, 2, 3],
-            vec![4, 5, 6],
-            vec![7, 8, 9],
-            vec![10, 11, 12],
-        ];
+        let data: Vec<usize> = (1..=12).collect();
         
-        let processor = ParallelChunkProcessor::new(data);
+        let processor = ParallelChunkProcessor::from_slice(&data, 3);
         
         // Process chunks (sum each chunk)
         let results = processor.process(|chunk| chunk.iter().sum::<usize>());
@@ -365,4 +653,24 @@ mod tests {
         // Check results
         assert_eq!(results, vec![6, 15, 24, 33]);
     }
+    
+    #[test]
+    fn test_parallel_chunk_processing_order() {
+        initialize_thread_pool();
+        
+        // 10k chunks with uneven work must come back in input order
+        let data: Vec<usize> = (0..10_000).collect();
+        let processor = ParallelChunkProcessor::from_slice(&data, 1);
+        assert_eq!(processor.num_chunks(), 10_000);
+        
+        let results = processor.process(|chunk| {
+            let value = chunk[0];
+            if value % 97 == 0 {
+                std::thread::sleep(Duration::from_micros(50));
+            }
+            value
+        });
+        
+        assert_eq!(results, data);
+    }
 }
\ No newline at end of file
@@ -72,6 +72,20 @@ pub fn compare_slices(a: &[u8], b: &[u8]) -> bool {
     }
 }
 
+/// Count the positions at which two slices differ using the most efficient
+/// available SIMD instruction set
+///
+/// Only the first `min(a.len(), b.len())` bytes are compared.
+pub fn count_mismatches(a: &[u8], b: &[u8]) -> usize {
+    if has_avx2() {
+        unsafe { count_mismatches_avx2(a, b) }
+    } else if has_sse41() {
+        unsafe { count_mismatches_sse41(a, b) }
+    } else {
+        count_mismatches_scalar(a, b)
+    }
+}
+
 /// Convert a DNA sequence to a 2-bit packed representation using SIMD
 pub fn pack_dna_sequence(src: &[u8], dst: &mut [u8]) -> usize {
     if has_avx2() {
@@ -94,6 +108,32 @@ pub fn unpack_dna_sequence(src: &[u8], dst: &mut [u8], len: usize) -> usize {
     }
 }
 
+/// Convert ASCII letters to uppercase in place using SIMD
+///
+/// Non-letter bytes are left untouched.
+pub fn to_uppercase_ascii(buf: &mut [u8]) {
+    if has_avx2() {
+        unsafe { to_uppercase_ascii_avx2(buf) }
+    } else if has_sse41() {
+        unsafe { to_uppercase_ascii_sse41(buf) }
+    } else {
+        to_uppercase_ascii_scalar(buf)
+    }
+}
+
+/// Convert ASCII letters to lowercase in place using SIMD
+///
+/// Non-letter bytes are left untouched.
+pub fn to_lowercase_ascii(buf: &mut [u8]) {
+    if has_avx2() {
+        unsafe { to_lowercase_ascii_avx2(buf) }
+    } else if has_sse41() {
+        unsafe { to_lowercase_ascii_sse41(buf) }
+    } else {
+        to_lowercase_ascii_scalar(buf)
+    }
+}
+
 /// Scalar implementation for counting occurrences of a byte in a slice
 fn count_byte_scalar(slice: &[u8], byte: u8) -> usize {
     slice.iter().filter(|&&b| b == byte).count()
@@ -109,6 +149,11 @@ fn compare_slices_scalar(a: &[u8], b: &[u8]) -> bool {
     a == b
 }
 
+/// Scalar implementation for counting mismatched positions
+fn count_mismatches_scalar(a: &[u8], b: &[u8]) -> usize {
+    a.iter().zip(b).filter(|(x, y)| x != y).count()
+}
+
 /// Scalar implementation for packing a DNA sequence to 2-bit representation
 fn pack_dna_sequence_scalar(src: &[u8], dst: &mut [u8]) -> usize {
     let bytes_to_process = src.len();
@@ -164,6 +209,16 @@ fn unpack_dna_sequence_scalar(src: &[u8], dst: &mut [u8], len: usize) -> usize {
     bases_to_unpack
 }
 
+/// Scalar implementation for converting ASCII letters to uppercase
+fn to_uppercase_ascii_scalar(buf: &mut [u8]) {
+    buf.make_ascii_uppercase();
+}
+
+/// Scalar implementation for converting ASCII letters to lowercase
+fn to_lowercase_ascii_scalar(buf: &mut [u8]) {
+    buf.make_ascii_lowercase();
+}
+
 /// AVX2 implementation for counting occurrences of a byte in a slice
 #[target_feature(enable = "avx2")]
 unsafe fn count_byte_avx2(slice: &[u8], byte: u8) -> usize {
@@ -323,7 +378,8 @@ unsafe fn find_byte_sse41(slice: &[u8], byte: u8) -> Option<usize> {
 /// AVX2 implementation for comparing two slices
 #[target_feature(enable = "avx2")]
 unsafe fn compare_slices_avx2(a: &[u8], b: &[u8]) -> bool {
-    let len = a.len();
+    // Only load blocks both slices contain; the scalar tail catches a length mismatch
+    let len = a.len().min(b.len());
     let mut i = 0;
 
     // Process 32 bytes at a time
@@ -353,7 +409,8 @@ unsafe fn compare_slices_avx2(a: &[u8], b: &[u8]) -> bool {
 /// SSE4.1 implementation for comparing two slices
 #[target_feature(enable = "sse4.1")]
 unsafe fn compare_slices_sse41(a: &[u8], b: &[u8]) -> bool {
-    let len = a.len();
+    // Only load blocks both slices contain; the scalar tail catches a length mismatch
+    let len = a.len().min(b.len());
     let mut i = 0;
 
     // Process 16 bytes at a time
@@ -380,36 +437,389 @@ unsafe fn compare_slices_sse41(a: &[u8], b: &[u8]) -> bool {
     compare_slices_scalar(&a[i..], &b[i..])
 }
 
+/// AVX2 implementation for counting mismatched positions
+#[target_feature(enable = "avx2")]
+unsafe fn count_mismatches_avx2(a: &[u8], b: &[u8]) -> usize {
+    let len = a.len().min(b.len());
+    let mut count = 0;
+    let mut i = 0;
+
+    // Process 32 bytes at a time
+    while i + 32 <= len {
+        let a_data = _mm256_loadu_si256(a[i..].as_ptr() as *const __m256i);
+        let b_data = _mm256_loadu_si256(b[i..].as_ptr() as *const __m256i);
+        
+        // Equal bytes set their mask bit, so the rest are mismatches
+        let mask_bits = _mm256_movemask_epi8(_mm256_cmpeq_epi8(a_data, b_data)) as u32;
+        count += 32 - mask_bits.count_ones() as usize;
+        
+        i += 32;
+    }
+
+    // Process remaining bytes with scalar method
+    count + count_mismatches_scalar(&a[i..len], &b[i..len])
+}
+
+/// SSE4.1 implementation for counting mismatched positions
+#[target_feature(enable = "sse4.1")]
+unsafe fn count_mismatches_sse41(a: &[u8], b: &[u8]) -> usize {
+    let len = a.len().min(b.len());
+    let mut count = 0;
+    let mut i = 0;
+
+    // Process 16 bytes at a time
+    while i + 16 <= len {
+        let a_data = _mm_loadu_si128(a[i..].as_ptr() as *const __m128i);
+        let b_data = _mm_loadu_si128(b[i..].as_ptr() as *const __m128i);
+        
+        // Equal bytes set their mask bit, so the rest are mismatches
+        let mask_bits = _mm_movemask_epi8(_mm_cmpeq_epi8(a_data, b_data)) as u32;
+        count += 16 - mask_bits.count_ones() as usize;
+        
+        i += 16;
+    }
+
+    // Process remaining bytes with scalar method
+    count + count_mismatches_scalar(&a[i..len], &b[i..len])
+}
+
+/// AVX2 implementation for converting ASCII letters to uppercase
+#[target_feature(enable = "avx2")]
+unsafe fn to_uppercase_ascii_avx2(buf: &mut [u8]) {
+    let len = buf.len();
+    let mut i = 0;
+    
+    // Bytes >= 0x80 compare as negative, so they never fall in the letter range
+    let lower_bound = _mm256_set1_epi8((b'a' - 1) as i8);
+    let upper_bound = _mm256_set1_epi8((b'z' + 1) as i8);
+    let case_bit = _mm256_set1_epi8(0x20);
+
+    // Process 32 bytes at a time
+    while i + 32 <= len {
+        let ptr = buf[i..].as_mut_ptr() as *mut __m256i;
+        let data = _mm256_loadu_si256(ptr);
+        
+        // Select lowercase letters and subtract 32 from them
+        let is_lower = _mm256_and_si256(
+            _mm256_cmpgt_epi8(data, lower_bound),
+            _mm256_cmpgt_epi8(upper_bound, data),
+        );
+        let converted = _mm256_sub_epi8(data, _mm256_and_si256(is_lower, case_bit));
+        _mm256_storeu_si256(ptr, converted);
+        
+        i += 32;
+    }
+
+    // Process remaining bytes with scalar method
+    to_uppercase_ascii_scalar(&mut buf[i..]);
+}
+
+/// SSE4.1 implementation for converting ASCII letters to uppercase
+#[target_feature(enable = "sse4.1")]
+unsafe fn to_uppercase_ascii_sse41(buf: &mut [u8]) {
+    let len = buf.len();
+    let mut i = 0;
+    
+    let lower_bound = _mm_set1_epi8((b'a' - 1) as i8);
+    let upper_bound = _mm_set1_epi8((b'z' + 1) as i8);
+    let case_bit = _mm_set1_epi8(0x20);
+
+    // Process 16 bytes at a time
+    while i + 16 <= len {
+        let ptr = buf[i..].as_mut_ptr() as *mut __m128i;
+        let data = _mm_loadu_si128(ptr);
+        
+        let is_lower = _mm_and_si128(
+            _mm_cmpgt_epi8(data, lower_bound),
+            _mm_cmplt_epi8(data, upper_bound),
+        );
+        let converted = _mm_sub_epi8(data, _mm_and_si128(is_lower, case_bit));
+        _mm_storeu_si128(ptr, converted);
+        
+        i += 16;
+    }
+
+    // Process remaining bytes with scalar method
+    to_uppercase_ascii_scalar(&mut buf[i..]);
+}
+
+/// AVX2 implementation for converting ASCII letters to lowercase
+#[target_feature(enable = "avx2")]
+unsafe fn to_lowercase_ascii_avx2(buf: &mut [u8]) {
+    let len = buf.len();
+    let mut i = 0;
+    
+    // Bytes >= 0x80 compare as negative, so they never fall in the letter range
+    let lower_bound = _mm256_set1_epi8((b'A' - 1) as i8);
+    let upper_bound = _mm256_set1_epi8((b'Z' + 1) as i8);
+    let case_bit = _mm256_set1_epi8(0x20);
+
+    // Process 32 bytes at a time
+    while i + 32 <= len {
+        let ptr = buf[i..].as_mut_ptr() as *mut __m256i;
+        let data = _mm256_loadu_si256(ptr);
+        
+        // Select uppercase letters and add 32 to them
+        let is_upper = _mm256_and_si256(
+            _mm256_cmpgt_epi8(data, lower_bound),
+            _mm256_cmpgt_epi8(upper_bound, data),
+        );
+        let converted = _mm256_add_epi8(data, _mm256_and_si256(is_upper, case_bit));
+        _mm256_storeu_si256(ptr, converted);
+        
+        i += 32;
+    }
+
+    // Process remaining bytes with scalar method
+    to_lowercase_ascii_scalar(&mut buf[i..]);
+}
+
+/// SSE4.1 implementation for converting ASCII letters to lowercase
+#[target_feature(enable = "sse4.1")]
+unsafe fn to_lowercase_ascii_sse41(buf: &mut [u8]) {
+    let len = buf.len();
+    let mut i = 0;
+    
+    let lower_bound = _mm_set1_epi8((b'A' - 1) as i8);
+    let upper_bound = _mm_set1_epi8((b'Z' + 1) as i8);
+    let case_bit = _mm_set1_epi8(0x20);
+
+    // Process 16 bytes at a time
+    while i + 16 <= len {
+        let ptr = buf[i..].as_mut_ptr() as *mut __m128i;
+        let data = _mm_loadu_si128(ptr);
+        
+        let is_upper = _mm_and_si128(
+            _mm_cmpgt_epi8(data, lower_bound),
+            _mm_cmplt_epi8(data, upper_bound),
+        );
+        let converted = _mm_add_epi8(data, _mm_and_si128(is_upper, case_bit));
+        _mm_storeu_si128(ptr, converted);
+        
+        i += 16;
+    }
+
+    // Process remaining bytes with scalar method
+    to_lowercase_ascii_scalar(&mut buf[i..]);
+}
+
 /// AVX2 implementation for packing a DNA sequence to 2-bit representation
 #[target_feature(enable = "avx2")]
 unsafe fn pack_dna_sequence_avx2(src: &[u8], dst: &mut [u8]) -> usize {
-    // For simplicity, delegate to scalar implementation for now
-    // In a real implementation, you would optimize this with AVX2 instructions
-    pack_dna_sequence_scalar(src, dst)
+    let len = src.len();
+    let bytes_required = len.div_ceil(4);
+    
+    // Make sure destination has enough space
+    if dst.len() < bytes_required {
+        return 0;
+    }
+    
+    let case_mask = _mm256_set1_epi8(!0x20u8 as i8);
+    let base_c = _mm256_set1_epi8(b'C' as i8);
+    let base_g = _mm256_set1_epi8(b'G' as i8);
+    let base_t = _mm256_set1_epi8(b'T' as i8);
+    let base_u = _mm256_set1_epi8(b'U' as i8);
+    let code_1 = _mm256_set1_epi8(1);
+    let code_2 = _mm256_set1_epi8(2);
+    let code_3 = _mm256_set1_epi8(3);
+    // Weights combining four 2-bit codes into one byte, first base in the high bits
+    let pair_weights = _mm256_set1_epi16(0x0104);
+    let quad_weights = _mm256_set1_epi32(0x0001_0010);
+    let mut i = 0;
+    
+    // Process 32 bases (8 output bytes) at a time
+    while i + 32 <= len {
+        let data = _mm256_loadu_si256(src[i..].as_ptr() as *const __m256i);
+        let upper = _mm256_and_si256(data, case_mask);
+        
+        // Map A/C/G/T(U) to 0/1/2/3; anything else becomes 0 like the scalar path
+        let is_t = _mm256_or_si256(_mm256_cmpeq_epi8(upper, base_t), _mm256_cmpeq_epi8(upper, base_u));
+        let codes = _mm256_or_si256(
+            _mm256_or_si256(
+                _mm256_and_si256(_mm256_cmpeq_epi8(upper, base_c), code_1),
+                _mm256_and_si256(_mm256_cmpeq_epi8(upper, base_g), code_2),
+            ),
+            _mm256_and_si256(is_t, code_3),
+        );
+        
+        // Combine pairs then quads of codes; each 32-bit lane ends up holding one packed byte
+        let pairs = _mm256_maddubs_epi16(codes, pair_weights);
+        let quads = _mm256_madd_epi16(pairs, quad_weights);
+        let words = _mm256_packus_epi32(quads, quads);
+        let bytes = _mm256_packus_epi16(words, words);
+        
+        // The low 4 bytes of each 128-bit half hold the packed output
+        let low = _mm_cvtsi128_si32(_mm256_castsi256_si128(bytes)) as u32;
+        let high = _mm_cvtsi128_si32(_mm256_extracti128_si256::<1>(bytes)) as u32;
+        dst[i / 4..i / 4 + 4].copy_from_slice(&low.to_le_bytes());
+        dst[i / 4 + 4..i / 4 + 8].copy_from_slice(&high.to_le_bytes());
+        
+        i += 32;
+    }
+    
+    // Process remaining bases with scalar method
+    pack_dna_sequence_scalar(&src[i..], &mut dst[i / 4..]);
+    
+    bytes_required
 }
 
 /// SSE4.1 implementation for packing a DNA sequence to 2-bit representation
 #[target_feature(enable = "sse4.1")]
 unsafe fn pack_dna_sequence_sse41(src: &[u8], dst: &mut [u8]) -> usize {
-    // For simplicity, delegate to scalar implementation for now
-    // In a real implementation, you would optimize this with SSE4.1 instructions
-    pack_dna_sequence_scalar(src, dst)
+    let len = src.len();
+    let bytes_required = len.div_ceil(4);
+    
+    // Make sure destination has enough space
+    if dst.len() < bytes_required {
+        return 0;
+    }
+    
+    let case_mask = _mm_set1_epi8(!0x20u8 as i8);
+    let base_c = _mm_set1_epi8(b'C' as i8);
+    let base_g = _mm_set1_epi8(b'G' as i8);
+    let base_t = _mm_set1_epi8(b'T' as i8);
+    let base_u = _mm_set1_epi8(b'U' as i8);
+    let code_1 = _mm_set1_epi8(1);
+    let code_2 = _mm_set1_epi8(2);
+    let code_3 = _mm_set1_epi8(3);
+    // Weights combining four 2-bit codes into one byte, first base in the high bits
+    let pair_weights = _mm_set1_epi16(0x0104);
+    let quad_weights = _mm_set1_epi32(0x0001_0010);
+    let mut i = 0;
+    
+    // Process 16 bases (4 output bytes) at a time
+    while i + 16 <= len {
+        let data = _mm_loadu_si128(src[i..].as_ptr() as *const __m128i);
+        let upper = _mm_and_si128(data, case_mask);
+        
+        // Map A/C/G/T(U) to 0/1/2/3; anything else becomes 0 like the scalar path
+        let is_t = _mm_or_si128(_mm_cmpeq_epi8(upper, base_t), _mm_cmpeq_epi8(upper, base_u));
+        let codes = _mm_or_si128(
+            _mm_or_si128(
+                _mm_and_si128(_mm_cmpeq_epi8(upper, base_c), code_1),
+                _mm_and_si128(_mm_cmpeq_epi8(upper, base_g), code_2),
+            ),
+            _mm_and_si128(is_t, code_3),
+        );
+        
+        // Combine pairs then quads of codes; each 32-bit lane ends up holding one packed byte
+        let pairs = _mm_maddubs_epi16(codes, pair_weights);
+        let quads = _mm_madd_epi16(pairs, quad_weights);
+        let words = _mm_packus_epi32(quads, quads);
+        let bytes = _mm_packus_epi16(words, words);
+        
+        let packed = _mm_cvtsi128_si32(bytes) as u32;
+        dst[i / 4..i / 4 + 4].copy_from_slice(&packed.to_le_bytes());
+        
+        i += 16;
+    }
+    
+    // Process remaining bases with scalar method
+    pack_dna_sequence_scalar(&src[i..], &mut dst[i / 4..]);
+    
+    bytes_required
 }
 
 /// AVX2 implementation for unpacking a 2-bit DNA sequence to ASCII
 #[target_feature(enable = "avx2")]
 unsafe fn unpack_dna_sequence_avx2(src: &[u8], dst: &mut [u8], len: usize) -> usize {
-    // For simplicity, delegate to scalar implementation for now
-    // In a real implementation, you would optimize this with AVX2 instructions
-    unpack_dna_sequence_scalar(src, dst, len)
+    let bases_to_unpack = len.min(dst.len());
+    
+    // Spread each packed byte over four lanes; the shuffle works per 128-bit half
+    let spread = _mm256_setr_epi8(
+        0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3,
+        4, 4, 4, 4, 5, 5, 5, 5, 6, 6, 6, 6, 7, 7, 7, 7,
+    );
+    // Lanes taking the 2-bit field at each shift, first base in the high bits
+    let select_6 = _mm256_set1_epi32(0x0000_00FF);
+    let select_4 = _mm256_set1_epi32(0x0000_FF00);
+    let select_2 = _mm256_set1_epi32(0x00FF_0000);
+    let select_0 = _mm256_set1_epi32(0xFF00_0000u32 as i32);
+    let two_bits = _mm256_set1_epi8(0b11);
+    let alphabet = _mm256_setr_epi8(
+        b'A' as i8, b'C' as i8, b'G' as i8, b'T' as i8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        b'A' as i8, b'C' as i8, b'G' as i8, b'T' as i8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    );
+    let mut i = 0;
+    
+    // Process 32 bases (8 packed bytes) at a time
+    while i + 32 <= bases_to_unpack {
+        let mut word = [0u8; 8];
+        word.copy_from_slice(&src[i / 4..i / 4 + 8]);
+        let packed = _mm256_set1_epi64x(i64::from_le_bytes(word));
+        let spread_bytes = _mm256_shuffle_epi8(packed, spread);
+        
+        // 16-bit shifts are safe here: the masked low bits always come from the lane's own byte
+        let codes = _mm256_or_si256(
+            _mm256_or_si256(
+                _mm256_and_si256(_mm256_and_si256(_mm256_srli_epi16::<6>(spread_bytes), two_bits), select_6),
+                _mm256_and_si256(_mm256_and_si256(_mm256_srli_epi16::<4>(spread_bytes), two_bits), select_4),
+            ),
+            _mm256_or_si256(
+                _mm256_and_si256(_mm256_and_si256(_mm256_srli_epi16::<2>(spread_bytes), two_bits), select_2),
+                _mm256_and_si256(_mm256_and_si256(spread_bytes, two_bits), select_0),
+            ),
+        );
+        
+        let ascii = _mm256_shuffle_epi8(alphabet, codes);
+        _mm256_storeu_si256(dst[i..].as_mut_ptr() as *mut __m256i, ascii);
+        
+        i += 32;
+    }
+    
+    // Process remaining bases with scalar method
+    unpack_dna_sequence_scalar(&src[i / 4..], &mut dst[i..bases_to_unpack], bases_to_unpack - i);
+    
+    bases_to_unpack
 }
 
 /// SSE4.1 implementation for unpacking a 2-bit DNA sequence to ASCII
 #[target_feature(enable = "sse4.1")]
 unsafe fn unpack_dna_sequence_sse41(src: &[u8], dst: &mut [u8], len: usize) -> usize {
-    // For simplicity, delegate to scalar implementation for now
-    // In a real implementation, you would optimize this with SSE4.1 instructions
-    unpack_dna_sequence_scalar(src, dst, len)
+    let bases_to_unpack = len.min(dst.len());
+    
+    // Spread each packed byte over four lanes
+    let spread = _mm_setr_epi8(0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3);
+    // Lanes taking the 2-bit field at each shift, first base in the high bits
+    let select_6 = _mm_set1_epi32(0x0000_00FF);
+    let select_4 = _mm_set1_epi32(0x0000_FF00);
+    let select_2 = _mm_set1_epi32(0x00FF_0000);
+    let select_0 = _mm_set1_epi32(0xFF00_0000u32 as i32);
+    let two_bits = _mm_set1_epi8(0b11);
+    let alphabet = _mm_setr_epi8(
+        b'A' as i8, b'C' as i8, b'G' as i8, b'T' as i8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    );
+    let mut i = 0;
+    
+    // Process 16 bases (4 packed bytes) at a time
+    while i + 16 <= bases_to_unpack {
+        let mut word = [0u8; 4];
+        word.copy_from_slice(&src[i / 4..i / 4 + 4]);
+        let packed = _mm_cvtsi32_si128(i32::from_le_bytes(word));
+        let spread_bytes = _mm_shuffle_epi8(packed, spread);
+        
+        // 16-bit shifts are safe here: the masked low bits always come from the lane's own byte
+        let codes = _mm_or_si128(
+            _mm_or_si128(
+                _mm_and_si128(_mm_and_si128(_mm_srli_epi16::<6>(spread_bytes), two_bits), select_6),
+                _mm_and_si128(_mm_and_si128(_mm_srli_epi16::<4>(spread_bytes), two_bits), select_4),
+            ),
+            _mm_or_si128(
+                _mm_and_si128(_mm_and_si128(_mm_srli_epi16::<2>(spread_bytes), two_bits), select_2),
+                _mm_and_si128(_mm_and_si128(spread_bytes, two_bits), select_0),
+            ),
+        );
+        
+        let ascii = _mm_shuffle_epi8(alphabet, codes);
+        _mm_storeu_si128(dst[i..].as_mut_ptr() as *mut __m128i, ascii);
+        
+        i += 16;
+    }
+    
+    // Process remaining bases with scalar method
+    unpack_dna_sequence_scalar(&src[i / 4..], &mut dst[i..bases_to_unpack], bases_to_unpack - i);
+    
+    bases_to_unpack
 }
 
 #[cfg(test)]
@@ -472,6 +882,160 @@ mod tests {
         assert!(!compare_slices(a, &c[0..30]));
     }
     
+    #[test]
+    fn test_count_mismatches() {
+        let a = b"ACGTACGTACGTACGTACGTACGTACGTACGTACGTACGT";
+        let mut b = *a;
+        assert_eq!(count_mismatches(a, &b), 0);
+        
+        // Mismatches inside a full block and in the tail
+        b[3] = b'A';
+        b[20] = b'N';
+        b[38] = b'a';
+        assert_eq!(count_mismatches(a, &b), 3);
+        
+        // Only the common prefix is compared
+        assert_eq!(count_mismatches(a, &b[..10]), 1);
+        assert_eq!(count_mismatches(b"", a), 0);
+    }
+    
+    #[test]
+    fn test_simd_matches_scalar_on_tails() {
+        // Lengths around and between the 16/32-byte blocks, at unaligned offsets
+        let buffer: Vec<u8> = (0..200).map(|i| b"ACGTNacgt"[(i * 7 + i / 3) % 9]).collect();
+        let avx2 = is_x86_feature_detected!("avx2");
+        let sse41 = is_x86_feature_detected!("sse4.1");
+        
+        for offset in 0..4 {
+            for len in 0..=128 {
+                let slice = &buffer[offset..offset + len];
+                
+                for &byte in b"ACGTNa$" {
+                    let count = count_byte_scalar(slice, byte);
+                    let found = find_byte_scalar(slice, byte);
+                    assert_eq!(count_byte(slice, byte), count);
+                    assert_eq!(find_byte(slice, byte), found);
+                    if avx2 {
+                        assert_eq!(unsafe { count_byte_avx2(slice, byte) }, count, "AVX2 count, length {}", len);
+                        assert_eq!(unsafe { find_byte_avx2(slice, byte) }, found, "AVX2 find, length {}", len);
+                    }
+                    if sse41 {
+                        assert_eq!(unsafe { count_byte_sse41(slice, byte) }, count, "SSE4.1 count, length {}", len);
+                        assert_eq!(unsafe { find_byte_sse41(slice, byte) }, found, "SSE4.1 find, length {}", len);
+                    }
+                }
+                
+                // A match placed at each position, including the last byte
+                for pos in 0..len {
+                    let mut data = slice.to_vec();
+                    data[pos] = b'#';
+                    assert_eq!(find_byte(&data, b'#'), Some(pos));
+                    if avx2 {
+                        assert_eq!(unsafe { find_byte_avx2(&data, b'#') }, Some(pos));
+                    }
+                    if sse41 {
+                        assert_eq!(unsafe { find_byte_sse41(&data, b'#') }, Some(pos));
+                    }
+                }
+                
+                // Equal slices, a single difference anywhere, and mismatched lengths
+                let copy = slice.to_vec();
+                let mut variants = vec![(copy.clone(), true)];
+                for pos in 0..len {
+                    let mut changed = copy.clone();
+                    changed[pos] ^= 0x20;
+                    variants.push((changed, false));
+                }
+                if len > 0 {
+                    variants.push((copy[..len - 1].to_vec(), false));
+                }
+                
+                for (other, equal) in &variants {
+                    let mismatches = count_mismatches_scalar(slice, other);
+                    assert_eq!(count_mismatches(slice, other), mismatches);
+                    if avx2 {
+                        assert_eq!(unsafe { count_mismatches_avx2(slice, other) }, mismatches, "AVX2 mismatches, length {}", len);
+                    }
+                    if sse41 {
+                        assert_eq!(unsafe { count_mismatches_sse41(slice, other) }, mismatches, "SSE4.1 mismatches, length {}", len);
+                    }
+                    
+                    assert_eq!(compare_slices(slice, other), *equal);
+                    if avx2 {
+                        assert_eq!(unsafe { compare_slices_avx2(slice, other) }, *equal, "AVX2 compare, length {}", len);
+                        assert_eq!(unsafe { compare_slices_avx2(other, slice) }, *equal, "AVX2 compare, length {}", len);
+                    }
+                    if sse41 {
+                        assert_eq!(unsafe { compare_slices_sse41(slice, other) }, *equal, "SSE4.1 compare, length {}", len);
+                        assert_eq!(unsafe { compare_slices_sse41(other, slice) }, *equal, "SSE4.1 compare, length {}", len);
+                    }
+                }
+                
+                // Case conversion touches only the given bytes
+                let mut expected = slice.to_vec();
+                to_uppercase_ascii_scalar(&mut expected);
+                if avx2 {
+                    let mut upper = buffer.clone();
+                    unsafe { to_uppercase_ascii_avx2(&mut upper[offset..offset + len]) };
+                    assert_eq!(&upper[offset..offset + len], &expected[..]);
+                    assert_eq!(&upper[offset + len..], &buffer[offset + len..]);
+                }
+                if sse41 {
+                    let mut upper = buffer.clone();
+                    unsafe { to_uppercase_ascii_sse41(&mut upper[offset..offset + len]) };
+                    assert_eq!(&upper[offset..offset + len], &expected[..]);
+                    assert_eq!(&upper[offset + len..], &buffer[offset + len..]);
+                }
+            }
+        }
+    }
+    
+    #[test]
+    fn test_case_conversion() {
+        // Mixed-case letters interleaved with digits, punctuation and non-ASCII bytes
+        let mut original: Vec<u8> = b"acgtNNnnACGTmask-ED*[`{@]0123456789acgtACGTacgtACGTxyzXYZ".to_vec();
+        original.extend_from_slice(&[0x80, 0xC1, 0xE1, 0xFA, 0xFF]);
+        
+        let mut expected_upper = original.clone();
+        to_uppercase_ascii_scalar(&mut expected_upper);
+        let mut expected_lower = original.clone();
+        to_lowercase_ascii_scalar(&mut expected_lower);
+        
+        // Non-letters are untouched by the scalar reference
+        assert_eq!(&expected_upper[12..30], b"MASK-ED*[`{@]01234");
+        assert_eq!(&expected_upper[original.len() - 5..], &[0x80, 0xC1, 0xE1, 0xFA, 0xFF]);
+        
+        // Dispatching implementation
+        let mut upper = original.clone();
+        to_uppercase_ascii(&mut upper);
+        assert_eq!(upper, expected_upper);
+        
+        let mut lower = original.clone();
+        to_lowercase_ascii(&mut lower);
+        assert_eq!(lower, expected_lower);
+        
+        // Vectorized implementations when supported by the CPU
+        if is_x86_feature_detected!("avx2") {
+            let mut upper = original.clone();
+            unsafe { to_uppercase_ascii_avx2(&mut upper) };
+            assert_eq!(upper, expected_upper);
+            
+            let mut lower = original.clone();
+            unsafe { to_lowercase_ascii_avx2(&mut lower) };
+            assert_eq!(lower, expected_lower);
+        }
+        
+        if is_x86_feature_detected!("sse4.1") {
+            let mut upper = original.clone();
+            unsafe { to_uppercase_ascii_sse41(&mut upper) };
+            assert_eq!(upper, expected_upper);
+            
+            let mut lower = original.clone();
+            unsafe { to_lowercase_ascii_sse41(&mut lower) };
+            assert_eq!(lower, expected_lower);
+        }
+    }
+    
     #[test]
     fn test_pack_unpack_dna() {
         let dna = b"ACGTACGTACGTACGTACGTACGTACGTACGT";
@@ -489,4 +1053,37 @@ mod tests {
         // Check that unpacked sequence matches original
         assert_eq!(&unpacked, dna);
     }
+    
+    #[test]
+    fn test_pack_unpack_dna_simd_matches_scalar() {
+        // Mixed case, RNA and invalid bases; lengths around the 16/32-base vector widths
+        let pattern = b"ACGTacgtUuNnGGCATTAGxCA-TGCAAGCTTGACGTacgTTAGGCCATGCAAATTTGGGCCC";
+        
+        for len in 0..=pattern.len() {
+            let dna = &pattern[..len];
+            let packed_len = len.div_ceil(4);
+            
+            let mut expected_packed = vec![0u8; packed_len];
+            assert_eq!(pack_dna_sequence_scalar(dna, &mut expected_packed), packed_len);
+            let mut expected_unpacked = vec![0u8; len];
+            unpack_dna_sequence_scalar(&expected_packed, &mut expected_unpacked, len);
+            
+            let mut packed = vec![0u8; packed_len];
+            let mut unpacked = vec![0u8; len];
+            
+            if is_x86_feature_detected!("avx2") {
+                assert_eq!(unsafe { pack_dna_sequence_avx2(dna, &mut packed) }, packed_len);
+                assert_eq!(packed, expected_packed, "AVX2 pack, length {}", len);
+                assert_eq!(unsafe { unpack_dna_sequence_avx2(&packed, &mut unpacked, len) }, len);
+                assert_eq!(unpacked, expected_unpacked, "AVX2 unpack, length {}", len);
+            }
+            
+            if is_x86_feature_detected!("sse4.1") {
+                assert_eq!(unsafe { pack_dna_sequence_sse41(dna, &mut packed) }, packed_len);
+                assert_eq!(packed, expected_packed, "SSE4.1 pack, length {}", len);
+                assert_eq!(unsafe { unpack_dna_sequence_sse41(&packed, &mut unpacked, len) }, len);
+                assert_eq!(unpacked, expected_unpacked, "SSE4.1 unpack, length {}", len);
+            }
+        }
+    }
 }
\ No newline at end of file
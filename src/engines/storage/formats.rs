@@ -3,13 +3,24 @@
 //! This module provides high-performance parsers and writers for various
 //! bioinformatics file formats, including FASTA, FASTQ, etc.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::io::{self, BufRead, Write};
 use std::collections::HashMap;
+use std::sync::Arc;
 use crate::engines::EngineResult;
 use crate::engines::EngineError;
 use crate::engines::core::io::{FastReader, FastWriter};
+#[cfg(feature = "parallel")]
+use crate::engines::core::io::split_at_record_boundaries;
+#[cfg(feature = "parallel")]
+use crate::engines::core::parallel;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use crate::engines::core::memory::{MemoryMapped, MemoryMapMode};
 use crate::engines::storage::{StorableSequence, InMemoryStorage, StorageFactory, StorageMode};
+use crate::engines::compute::string_ops;
+use crate::engines::compute::checksum::{self, ChecksumAlgorithm};
+use crate::modules::seq::alphabet::{detect_alphabet, is_gap, Alphabet, ProteinAlphabet};
 
 /// Trait for sequence record parsers
 pub trait SequenceParser: Send + Sync {
@@ -35,6 +46,30 @@ pub trait SequenceWriter: Send + Sync {
     fn format_name(&self) -> &str;
 }
 
+/// Trait for feature (annotation) parsers such as GFF3
+pub trait FeatureParser: Send + Sync {
+    /// Parse a file and create features
+    fn parse_file<P: AsRef<Path>>(&self, path: P) -> EngineResult<Vec<GffFeature>>;
+    
+    /// Parse a string and create features
+    fn parse_string(&self, content: &str) -> EngineResult<Vec<GffFeature>>;
+    
+    /// Get the format name
+    fn format_name(&self) -> &str;
+}
+
+/// Trait for feature (annotation) writers such as GFF3
+pub trait FeatureWriter: Send + Sync {
+    /// Write features to a file
+    fn write_file<P: AsRef<Path>>(&self, features: &[GffFeature], path: P) -> EngineResult<()>;
+    
+    /// Write features to a string
+    fn write_string(&self, features: &[GffFeature]) -> EngineResult<String>;
+    
+    /// Get the format name
+    fn format_name(&self) -> &str;
+}
+
 /// A sequence record with ID, description, and sequence data
 #[derive(Debug, Clone)]
 pub struct SequenceRecord {
@@ -92,6 +127,19 @@ impl SequenceRecord {
         self.sequence.is_empty()
     }
     
+    /// Get the approximate memory used by this record in bytes
+    ///
+    /// Includes the sequence and quality storage (mapped files are not
+    /// counted) plus the id, description and metadata.
+    pub fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.id.capacity()
+            + self.description.as_ref().map_or(0, |desc| desc.capacity())
+            + self.sequence.memory_usage()
+            + self.quality.as_ref().map_or(0, |quality| quality.memory_usage())
+            + self.metadata.iter().map(|(key, value)| key.capacity() + value.capacity()).sum::<usize>()
+    }
+    
     /// Get a subsequence
     pub fn subsequence(&self, start: usize, end: usize) -> Vec<u8> {
         self.sequence.subsequence(start, end)
@@ -115,6 +163,14 @@ impl SequenceRecord {
         })
     }
     
+    /// Compute a checksum of the record's sequence
+    ///
+    /// With [`ChecksumAlgorithm::Md5`] this is the SAM `M5` value; the
+    /// sequence is streamed from its storage rather than copied whole.
+    pub fn checksum(&self, algorithm: ChecksumAlgorithm) -> String {
+        checksum::checksum_storage(self.sequence.as_ref(), algorithm)
+    }
+    
     /// Add metadata to the record
     pub fn add_metadata(&mut self, key: &str, value: &str) {
         self.metadata.insert(key.to_string(), value.to_string());
@@ -124,15 +180,277 @@ impl SequenceRecord {
     pub fn get_metadata(&self, key: &str) -> Option<&String> {
         self.metadata.get(key)
     }
+    
+    /// Decode the quality string into Phred scores using an ASCII offset
+    ///
+    /// Returns `None` if the record has no quality scores or if any character
+    /// lies below `offset`, which means the wrong encoding was chosen.
+    pub fn phred_scores(&self, offset: u8) -> Option<Vec<u8>> {
+        let quality = self.quality_as_vec()?;
+        quality.iter().map(|&q| q.checked_sub(offset)).collect()
+    }
+    
+    /// Get the mean Phred score of the record
+    ///
+    /// The encoding is detected from the record's own quality characters.
+    /// Returns `None` if there are no quality scores or they cannot be decoded.
+    pub fn mean_quality(&self) -> Option<f64> {
+        let quality = self.quality_as_vec()?;
+        let encoding = detect_quality_encoding(&quality)?;
+        let scores = self.phred_scores(encoding.offset())?;
+        
+        if scores.is_empty() {
+            return None;
+        }
+        
+        let total: u64 = scores.iter().map(|&q| q as u64).sum();
+        Some(total as f64 / scores.len() as f64)
+    }
+    
+    /// Trim low-quality bases from both ends of the record
+    ///
+    /// Uses the BWA running-sum algorithm from each end: the sum of
+    /// `min_q - q` is accumulated inward until it turns negative, and the read
+    /// is cut where the sum peaked. Quality characters below `offset` count as
+    /// Q0. The sequence and quality are trimmed together; records without
+    /// quality scores are returned unchanged, and a read that is low quality
+    /// throughout yields an empty record. If the sequence and quality lengths
+    /// differ, only the bases that have a quality score are kept.
+    pub fn trim_quality(&self, min_q: u8, offset: u8) -> SequenceRecord {
+        let sequence = self.sequence_as_vec();
+        let quality = self.quality_as_vec();
+        
+        let (start, end) = match &quality {
+            Some(quality) => {
+                let scores: Vec<i32> = quality[..quality.len().min(sequence.len())]
+                    .iter()
+                    .map(|&q| q.saturating_sub(offset) as i32)
+                    .collect();
+                let end = scores.len() - quality_trim_count(scores.iter().rev(), min_q as i32);
+                let start = quality_trim_count(scores[..end].iter(), min_q as i32);
+                (start, end)
+            },
+            None => (0, sequence.len()),
+        };
+        
+        SequenceRecord {
+            id: self.id.clone(),
+            description: self.description.clone(),
+            sequence: Box::new(InMemoryStorage::new(sequence[start..end].to_vec())),
+            quality: quality.map(|q| {
+                Box::new(InMemoryStorage::new(q[start..end].to_vec())) as Box<dyn StorableSequence>
+            }),
+            metadata: self.metadata.clone(),
+        }
+    }
+    
+    /// Trim amplicon primers from the record
+    ///
+    /// The forward primer is searched near the 5' end and the reverse primer
+    /// (as its reverse complement) near the 3' end, each allowing up to
+    /// `max_mismatch` mismatches. A primer may start at most
+    /// [`PRIMER_END_SLACK`] bases from its end of the read, so primer-like
+    /// sequence inside the insert is never mistaken for a primer. Returns the
+    /// insert between the two primers, with quality scores trimmed to match,
+    /// or `None` if either primer is not found.
+    pub fn trim_primers(&self, fwd: &[u8], rev: &[u8], max_mismatch: usize) -> Option<SequenceRecord> {
+        if fwd.is_empty() || rev.is_empty() {
+            return None;
+        }
+        
+        let sequence = self.sequence_as_vec();
+        let rev_rc = string_ops::reverse_complement_dna(rev);
+        
+        // Locate the forward primer closest to the 5' end
+        let fwd_pos = find_primer(&sequence, fwd, max_mismatch, false, PRIMER_END_SLACK)?;
+        let insert_start = fwd_pos + fwd.len();
+        
+        // Locate the reverse primer closest to the 3' end, after the forward primer
+        let insert_end = insert_start + find_primer(&sequence[insert_start..], &rev_rc, max_mismatch, true, PRIMER_END_SLACK)?;
+        
+        let quality = self.quality_as_vec().map(|q| {
+            Box::new(InMemoryStorage::new(q[insert_start..insert_end].to_vec())) as Box<dyn StorableSequence>
+        });
+        
+        Some(SequenceRecord {
+            id: self.id.clone(),
+            description: self.description.clone(),
+            sequence: Box::new(InMemoryStorage::new(sequence[insert_start..insert_end].to_vec())),
+            quality,
+            metadata: self.metadata.clone(),
+        })
+    }
+}
+
+/// Serialized form of a [`SequenceRecord`]
+///
+/// The sequence and quality are written as plain strings, whatever storage
+/// backs the record.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SerializedRecord {
+    id: String,
+    #[serde(default)]
+    description: Option<String>,
+    sequence: String,
+    #[serde(default)]
+    quality: Option<String>,
+    #[serde(default)]
+    metadata: HashMap<String, String>,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for SequenceRecord {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::Error;
+        
+        // Materialize the storage into text
+        let sequence = String::from_utf8(self.sequence_as_vec())
+            .map_err(|_| S::Error::custom(format!("sequence of record '{}' is not valid UTF-8", self.id)))?;
+        let quality = self.quality_as_vec()
+            .map(|q| String::from_utf8(q)
+                .map_err(|_| S::Error::custom(format!("quality of record '{}' is not valid UTF-8", self.id))))
+            .transpose()?;
+        
+        SerializedRecord {
+            id: self.id.clone(),
+            description: self.description.clone(),
+            sequence,
+            quality,
+            metadata: self.metadata.clone(),
+        }.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SequenceRecord {
+    /// Deserialized records always use in-memory storage
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let record = SerializedRecord::deserialize(deserializer)?;
+        
+        Ok(SequenceRecord {
+            id: record.id,
+            description: record.description,
+            sequence: Box::new(InMemoryStorage::new(record.sequence.into_bytes())),
+            quality: record.quality.map(|q| Box::new(InMemoryStorage::new(q.into_bytes())) as Box<dyn StorableSequence>),
+            metadata: record.metadata,
+        })
+    }
+}
+
+/// Count the bases to trim from one end of a read
+///
+/// `scores` runs inward from the end being trimmed.
+fn quality_trim_count<'a>(scores: impl Iterator<Item = &'a i32>, min_q: i32) -> usize {
+    let mut sum = 0;
+    let mut best = 0;
+    let mut trimmed = 0;
+    
+    for (i, &score) in scores.enumerate() {
+        sum += min_q - score;
+        if sum < 0 {
+            break;
+        }
+        if sum > best {
+            best = sum;
+            trimmed = i + 1;
+        }
+    }
+    
+    trimmed
+}
+
+/// ASCII encoding of FASTQ quality scores
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QualityEncoding {
+    /// Phred+33 (Sanger, Illumina 1.8+)
+    Phred33,
+    /// Phred+64 (Illumina 1.3 to 1.7)
+    Phred64,
+}
+
+impl QualityEncoding {
+    /// Get the ASCII offset of the encoding
+    pub fn offset(&self) -> u8 {
+        match self {
+            QualityEncoding::Phred33 => 33,
+            QualityEncoding::Phred64 => 64,
+        }
+    }
+}
+
+/// Guess the quality encoding from the observed quality characters
+///
+/// Characters below `@` can only be Phred+33, and characters above `K`
+/// (Phred+33 Q42) indicate Phred+64. Ranges that fit both are reported as
+/// Phred+33, the modern default. Returns `None` for empty input or
+/// characters outside the printable range, and when the range fits neither.
+pub fn detect_quality_encoding(quality: &[u8]) -> Option<QualityEncoding> {
+    let min = *quality.iter().min()?;
+    let max = *quality.iter().max()?;
+    
+    if min < b'!' || max > b'~' {
+        return None;
+    }
+    
+    let needs_phred33 = min < b'@';
+    let needs_phred64 = max > b'K';
+    
+    match (needs_phred33, needs_phred64) {
+        (true, true) => None,
+        (false, true) => Some(QualityEncoding::Phred64),
+        _ => Some(QualityEncoding::Phred33),
+    }
+}
+
+/// Number of bases allowed before a primer at either end of a read
+///
+/// Covers leftover adapter or barcode bases in front of the primer in
+/// [`SequenceRecord::trim_primers`].
+pub const PRIMER_END_SLACK: usize = 10;
+
+/// Find a primer in a sequence allowing up to `max_mismatch` mismatches
+///
+/// Only matches starting within `slack` bases of the searched end are
+/// considered. Returns the start of the first match, or the last match if
+/// `from_end` is set.
+fn find_primer(sequence: &[u8], primer: &[u8], max_mismatch: usize, from_end: bool, slack: usize) -> Option<usize> {
+    if primer.len() > sequence.len() {
+        return None;
+    }
+    
+    let matches_at = |pos: usize| {
+        sequence[pos..pos + primer.len()]
+            .iter()
+            .zip(primer)
+            .filter(|(a, b)| !a.eq_ignore_ascii_case(b))
+            .count() <= max_mismatch
+    };
+    
+    let last = sequence.len() - primer.len();
+    if from_end {
+        (last.saturating_sub(slack)..=last).rev().find(|&pos| matches_at(pos))
+    } else {
+        (0..=slack.min(last)).find(|&pos| matches_at(pos))
+    }
 }
 
+/// Metadata key for the line width a FASTA record was read with
+pub const FASTA_LINE_WIDTH_KEY: &str = "fasta.line_width";
+
 /// FASTA format parser
+///
+/// A header with no sequence lines gives a record with an empty sequence.
 #[derive(Debug, Clone)]
 pub struct FastaParser {
     /// Storage mode to use for sequences
     storage_mode: StorageMode,
     /// Buffer size for reading
     buffer_size: usize,
+    /// Whether to record each record's line width in its metadata
+    record_line_width: bool,
+    /// Whether to move `key=value` tokens from descriptions into metadata
+    description_metadata: bool,
 }
 
 impl FastaParser {
@@ -141,6 +459,8 @@ impl FastaParser {
         Self {
             storage_mode: StorageMode::default(),
             buffer_size: 1024 * 1024, // 1MB
+            record_line_width: false,
+            description_metadata: false,
         }
     }
     
@@ -149,6 +469,8 @@ impl FastaParser {
         Self {
             storage_mode,
             buffer_size: 1024 * 1024, // 1MB
+            record_line_width: false,
+            description_metadata: false,
         }
     }
     
@@ -157,6 +479,192 @@ impl FastaParser {
         self.buffer_size = buffer_size;
         self
     }
+    
+    /// Record the width of each record's first sequence line
+    ///
+    /// The width is stored under [`FASTA_LINE_WIDTH_KEY`] so that a
+    /// [`FastaWriter`] preserving line widths can reproduce the layout.
+    pub fn with_line_width_metadata(mut self, record_line_width: bool) -> Self {
+        self.record_line_width = record_line_width;
+        self
+    }
+    
+    /// Parse `key=value` tokens in descriptions into record metadata
+    ///
+    /// This reads headers written by [`FastaWriter::with_metadata_keys`].
+    /// Values may be double-quoted to hold spaces. The pairs are removed from
+    /// the description, and a description left empty becomes `None`.
+    pub fn with_description_metadata(mut self, parse: bool) -> Self {
+        self.description_metadata = parse;
+        self
+    }
+    
+    /// Parse a file by splitting it at record boundaries and parsing the
+    /// pieces in parallel on the global thread pool
+    ///
+    /// Records are returned in file order. `chunk_size` defaults to one
+    /// chosen from the file size and thread count. The thread pool must
+    /// already be initialized.
+    #[cfg(feature = "parallel")]
+    pub fn parse_file_parallel<P: AsRef<Path>>(&self, path: P, chunk_size: Option<usize>) -> EngineResult<Vec<SequenceRecord>> {
+        let path = path.as_ref();
+        let mmap = MemoryMapped::new(path, MemoryMapMode::ReadOnly)?;
+        let data = mmap.as_slice();
+        
+        let chunk_size = chunk_size.unwrap_or_else(|| parallel::calculate_chunk_size(data.len(), Some(self.buffer_size)));
+        let chunks = split_at_record_boundaries(data, chunk_size, b'>');
+        
+        // Parse each chunk on its own, keeping chunk order
+        let parsed: Vec<EngineResult<Vec<SequenceRecord>>> = parallel::execute(|pool| {
+            pool.install(|| {
+                chunks
+                    .par_iter()
+                    .map(|&(start, end)| self.parse_chunk(&data[start..end], path))
+                    .collect()
+            })
+        });
+        
+        let mut records = Vec::new();
+        for chunk_records in parsed {
+            records.extend(chunk_records?);
+        }
+        Ok(records)
+    }
+    
+    /// Parse one chunk of a FASTA file with this parser's storage mode
+    #[cfg(feature = "parallel")]
+    fn parse_chunk(&self, chunk: &[u8], path: &Path) -> EngineResult<Vec<SequenceRecord>> {
+        let content = std::str::from_utf8(chunk).map_err(|e| {
+            EngineError::InvalidSequenceData(format!("FASTA file is not valid UTF-8: {}", e))
+        })?;
+        let mut records = self.parse_string(content)?;
+        
+        if self.storage_mode != StorageMode::InMemory {
+            for record in &mut records {
+                let sequence = record.sequence_as_vec();
+                let len = sequence.len();
+                record.sequence = StorageFactory::create_storage(Some(sequence), Some(path), Some(len), Some(self.storage_mode))?;
+            }
+        }
+        
+        Ok(records)
+    }
+    
+    /// Open a streaming reader using this parser's settings
+    pub fn reader<P: AsRef<Path>>(&self, path: P) -> EngineResult<FastaReader> {
+        Ok(FastaReader {
+            reader: FastReader::new(path.as_ref(), Some(self.buffer_size))?,
+            path: path.as_ref().to_path_buf(),
+            storage_mode: self.storage_mode,
+            record_line_width: self.record_line_width,
+            description_metadata: self.description_metadata,
+            next_header: None,
+            line_number: 0,
+            finished: false,
+        })
+    }
+}
+
+/// Build record metadata, including the line width if it should be recorded
+fn fasta_metadata(record_line_width: bool, line_width: Option<usize>) -> HashMap<String, String> {
+    let mut metadata = HashMap::new();
+    if let (true, Some(width)) = (record_line_width, line_width) {
+        metadata.insert(FASTA_LINE_WIDTH_KEY.to_string(), width.to_string());
+    }
+    metadata
+}
+
+/// Move `key=value` tokens from a record's description into its metadata
+fn extract_description_metadata(record: &mut SequenceRecord) {
+    if let Some(description) = record.description.take() {
+        let (description, pairs) = split_description_metadata(&description);
+        record.description = description;
+        record.metadata.extend(pairs);
+    }
+}
+
+/// Split a FASTA description into its `key=value` pairs and remaining text
+///
+/// Quoted values use `\"` and `\\` escapes. Tokens that are not well-formed
+/// pairs, such as an unterminated quoted value, stay in the text.
+fn split_description_metadata(description: &str) -> (Option<String>, Vec<(String, String)>) {
+    let mut text = Vec::new();
+    let mut pairs = Vec::new();
+    let mut rest = description.trim_start();
+    
+    while !rest.is_empty() {
+        let token_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let token = &rest[..token_end];
+        
+        match token.split_once('=') {
+            Some((key, value)) if !key.is_empty() && !key.contains('"') => {
+                if !value.starts_with('"') {
+                    pairs.push((key.to_string(), value.to_string()));
+                } else if let Some((value, consumed)) = parse_quoted_value(&rest[key.len() + 1..]) {
+                    // A quoted value may run past whitespace
+                    pairs.push((key.to_string(), value));
+                    rest = rest[key.len() + 1 + consumed..].trim_start();
+                    continue;
+                } else {
+                    text.push(token);
+                }
+            },
+            _ => text.push(token),
+        }
+        
+        rest = rest[token_end..].trim_start();
+    }
+    
+    let text = text.join(" ");
+    ((!text.is_empty()).then_some(text), pairs)
+}
+
+/// Read a double-quoted value, returning it unescaped with the bytes consumed
+///
+/// The closing quote must end the token.
+fn parse_quoted_value(input: &str) -> Option<(String, usize)> {
+    let mut value = String::new();
+    let mut chars = input.char_indices().skip(1);
+    
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => value.push(chars.next()?.1),
+            '"' => {
+                let consumed = i + 1;
+                let ends_token = input[consumed..].chars().next().is_none_or(char::is_whitespace);
+                return ends_token.then_some((value, consumed));
+            },
+            c => value.push(c),
+        }
+    }
+    
+    None
+}
+
+/// Format a metadata pair for a FASTA description
+///
+/// Values containing whitespace or quotes are quoted and escaped. Keys that
+/// could not be parsed back, and values spanning lines, are rejected.
+fn format_description_metadata(key: &str, value: &str) -> EngineResult<String> {
+    if key.is_empty() || key.contains(|c: char| c.is_whitespace() || c == '=' || c == '"') {
+        return Err(EngineError::InvalidSequenceData(format!(
+            "Metadata key '{}' cannot be written to a FASTA header: keys must be non-empty without spaces, '=' or quotes",
+            key
+        )));
+    }
+    if value.contains(['\n', '\r']) {
+        return Err(EngineError::InvalidSequenceData(format!(
+            "Metadata value for key '{}' cannot be written to a FASTA header: it contains a line break",
+            key
+        )));
+    }
+    
+    if value.contains(|c: char| c.is_whitespace() || c == '"') {
+        let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+        Ok(format!("{}=\"{}\"", key, escaped))
+    } else {
+        Ok(format!("{}={}", key, value))
+    }
 }
 
 impl Default for FastaParser {
@@ -173,19 +681,20 @@ impl SequenceParser for FastaParser {
         let mut current_id = String::new();
         let mut current_desc = None;
         let mut current_seq = Vec::new();
+        let mut current_width = None;
         
         for line_result in reader.read_lines() {
             let line = line_result?;
             
-            // Skip empty lines
-            if line.is_empty() {
+            // Skip empty and whitespace-only lines
+            if line.trim().is_empty() {
                 continue;
             }
             
             // Header line
             if line.starts_with('>') {
                 // Save the previous record if any
-                if !current_id.is_empty() && !current_seq.is_empty() {
+                if !current_id.is_empty() {
                     // Create storage according to the chosen mode
                     let sequence = StorageFactory::create_storage(
                         Some(current_seq.clone()),
@@ -199,11 +708,12 @@ impl SequenceParser for FastaParser {
                         description: current_desc.clone(),
                         sequence,
                         quality: None,
-                        metadata: HashMap::new(),
+                        metadata: fasta_metadata(self.record_line_width, current_width),
                     });
                     
                     current_seq.clear();
                 }
+                current_width = None;
                 
                 // Parse header
                 let header = &line[1..];
@@ -213,12 +723,14 @@ impl SequenceParser for FastaParser {
                 current_desc = parts.get(1).map(|s| s.to_string());
             } else {
                 // Sequence line (add to current sequence)
-                current_seq.extend(line.trim().as_bytes());
+                let line = line.trim();
+                current_width.get_or_insert(line.len());
+                current_seq.extend(line.as_bytes());
             }
         }
         
         // Add the last record if any
-        if !current_id.is_empty() && !current_seq.is_empty() {
+        if !current_id.is_empty() {
             // Create storage according to the chosen mode
             let sequence = StorageFactory::create_storage(
                 Some(current_seq.clone()),
@@ -232,10 +744,14 @@ impl SequenceParser for FastaParser {
                 description: current_desc,
                 sequence,
                 quality: None,
-                metadata: HashMap::new(),
+                metadata: fasta_metadata(self.record_line_width, current_width),
             });
         }
         
+        if self.description_metadata {
+            records.iter_mut().for_each(extract_description_metadata);
+        }
+        
         Ok(records)
     }
     
@@ -244,6 +760,7 @@ impl SequenceParser for FastaParser {
         let mut current_id = String::new();
         let mut current_desc = None;
         let mut current_seq = Vec::new();
+        let mut current_width = None;
         
         for line in content.lines() {
             let line = line.trim();
@@ -256,15 +773,18 @@ impl SequenceParser for FastaParser {
             // Header line
             if line.starts_with('>') {
                 // Save the previous record if any
-                if !current_id.is_empty() && !current_seq.is_empty() {
-                    records.push(SequenceRecord::new(
+                if !current_id.is_empty() {
+                    let mut record = SequenceRecord::new(
                         current_id.clone(),
                         current_desc.clone(),
                         current_seq.clone(),
-                    ));
+                    );
+                    record.metadata = fasta_metadata(self.record_line_width, current_width);
+                    records.push(record);
                     
                     current_seq.clear();
                 }
+                current_width = None;
                 
                 // Parse header
                 let header = &line[1..];
@@ -274,17 +794,24 @@ impl SequenceParser for FastaParser {
                 current_desc = parts.get(1).map(|s| s.to_string());
             } else {
                 // Sequence line (add to current sequence)
+                current_width.get_or_insert(line.len());
                 current_seq.extend(line.as_bytes());
             }
         }
         
         // Add the last record if any
-        if !current_id.is_empty() && !current_seq.is_empty() {
-            records.push(SequenceRecord::new(
+        if !current_id.is_empty() {
+            let mut record = SequenceRecord::new(
                 current_id,
                 current_desc,
                 current_seq,
-            ));
+            );
+            record.metadata = fasta_metadata(self.record_line_width, current_width);
+            records.push(record);
+        }
+        
+        if self.description_metadata {
+            records.iter_mut().for_each(extract_description_metadata);
         }
         
         Ok(records)
@@ -295,13 +822,157 @@ impl SequenceParser for FastaParser {
     }
 }
 
+/// Streaming FASTA reader
+///
+/// Yields one record at a time, holding at most the record being assembled
+/// and the header of the next one.
+pub struct FastaReader {
+    reader: FastReader,
+    path: PathBuf,
+    storage_mode: StorageMode,
+    record_line_width: bool,
+    description_metadata: bool,
+    next_header: Option<String>,
+    line_number: usize,
+    finished: bool,
+}
+
+impl FastaReader {
+    /// Open a FASTA file with the default storage mode
+    pub fn open<P: AsRef<Path>>(path: P) -> EngineResult<Self> {
+        FastaParser::new().reader(path)
+    }
+    
+    /// Read the next line, counting line numbers
+    fn next_line(&mut self) -> EngineResult<Option<String>> {
+        match self.reader.read_lines().next() {
+            Some(line) => {
+                self.line_number += 1;
+                Ok(Some(line?))
+            },
+            None => Ok(None),
+        }
+    }
+    
+    /// Assemble the next record, skipping headers with no id
+    fn read_record(&mut self) -> EngineResult<Option<SequenceRecord>> {
+        // Find the header, either left over from the last record or the first in the file
+        let mut header = match self.next_header.take() {
+            Some(header) => header,
+            None => loop {
+                match self.next_line()? {
+                    Some(line) if line.is_empty() => continue,
+                    Some(line) if line.starts_with('>') => break line,
+                    Some(_) => {
+                        return Err(EngineError::InvalidSequenceData(format!(
+                            "FASTA sequence data before the first header at line {}",
+                            self.line_number
+                        )));
+                    },
+                    None => return Ok(None),
+                }
+            },
+        };
+        
+        loop {
+            // Collect sequence lines up to the next header or end of file
+            let mut seq = Vec::new();
+            let mut line_width = None;
+            while let Some(line) = self.next_line()? {
+                if line.starts_with('>') {
+                    self.next_header = Some(line);
+                    break;
+                }
+                let line = line.trim();
+                if !line.is_empty() {
+                    line_width.get_or_insert(line.len());
+                }
+                seq.extend(line.as_bytes());
+            }
+            
+            let parts: Vec<&str> = header[1..].splitn(2, ' ').collect();
+            let id = parts[0].to_string();
+            
+            // Records without an id are skipped, as in `FastaParser`; empty
+            // sequences are kept as zero-length records
+            if !id.is_empty() {
+                let sequence = StorageFactory::create_storage(
+                    Some(seq.clone()),
+                    Some(&self.path),
+                    Some(seq.len()),
+                    Some(self.storage_mode),
+                )?;
+                
+                let mut record = SequenceRecord {
+                    id,
+                    description: parts.get(1).map(|s| s.to_string()),
+                    sequence,
+                    quality: None,
+                    metadata: fasta_metadata(self.record_line_width, line_width),
+                };
+                if self.description_metadata {
+                    extract_description_metadata(&mut record);
+                }
+                return Ok(Some(record));
+            }
+            
+            header = match self.next_header.take() {
+                Some(header) => header,
+                None => return Ok(None),
+            };
+        }
+    }
+}
+
+impl Iterator for FastaReader {
+    type Item = EngineResult<SequenceRecord>;
+    
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        
+        let result = self.read_record().transpose();
+        if !matches!(result, Some(Ok(_))) {
+            self.finished = true;
+        }
+        result
+    }
+}
+
+/// Line terminator written after each line of output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Terminator {
+    /// `\n`
+    #[default]
+    Unix,
+    /// `\r\n`
+    Windows,
+}
+
+impl Terminator {
+    /// Get the terminator as a string
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Terminator::Unix => "\n",
+            Terminator::Windows => "\r\n",
+        }
+    }
+}
+
 /// FASTA format writer
 #[derive(Debug, Clone)]
 pub struct FastaWriter {
-    /// Line width for sequence output
+    /// Line width for sequence output, or 0 to write each sequence on one line
     line_width: usize,
+    /// Line terminator
+    terminator: Terminator,
     /// Buffer size for writing
     buffer_size: usize,
+    /// Whether to use the line width recorded in each record's metadata
+    preserve_line_width: bool,
+    /// Metadata keys to write into the description as `key=value` pairs
+    metadata_keys: Vec<String>,
 }
 
 impl FastaWriter {
@@ -309,23 +980,123 @@ impl FastaWriter {
     pub fn new() -> Self {
         Self {
             line_width: 60,
+            terminator: Terminator::default(),
             buffer_size: 1024 * 1024, // 1MB
+            preserve_line_width: false,
+            metadata_keys: Vec::new(),
         }
     }
     
     /// Create a new FASTA writer with the specified line width
+    ///
+    /// A width of 0 disables wrapping, writing each sequence on one line.
     pub fn with_line_width(line_width: usize) -> Self {
         Self {
             line_width,
+            terminator: Terminator::default(),
             buffer_size: 1024 * 1024, // 1MB
+            preserve_line_width: false,
+            metadata_keys: Vec::new(),
         }
     }
     
+    /// Set the line terminator
+    pub fn with_line_terminator(mut self, terminator: Terminator) -> Self {
+        self.terminator = terminator;
+        self
+    }
+    
     /// Set the buffer size
     pub fn with_buffer_size(mut self, buffer_size: usize) -> Self {
         self.buffer_size = buffer_size;
         self
     }
+    
+    /// Wrap each record at the width stored under [`FASTA_LINE_WIDTH_KEY`]
+    ///
+    /// Records without a recorded width use the writer's line width.
+    pub fn with_preserved_line_width(mut self, preserve: bool) -> Self {
+        self.preserve_line_width = preserve;
+        self
+    }
+    
+    /// Append the given metadata keys to each description as `key=value`
+    ///
+    /// Keys are written in the order given and skipped for records that
+    /// lack them. Values with spaces are quoted; keys with spaces, `=` or
+    /// quotes make writing fail. Read them back with
+    /// [`FastaParser::with_description_metadata`].
+    pub fn with_metadata_keys(mut self, keys: &[&str]) -> Self {
+        self.metadata_keys = keys.iter().map(|key| key.to_string()).collect();
+        self
+    }
+    
+    /// Build the header line for a record, including its line terminator
+    fn header_line(&self, record: &SequenceRecord) -> EngineResult<String> {
+        let mut header = format!(">{}", record.id);
+        if let Some(desc) = &record.description {
+            header.push(' ');
+            header.push_str(desc);
+        }
+        
+        for key in &self.metadata_keys {
+            if let Some(value) = record.get_metadata(key) {
+                header.push(' ');
+                header.push_str(&format_description_metadata(key, value)?);
+            }
+        }
+        
+        header.push_str(self.terminator.as_str());
+        Ok(header)
+    }
+    
+    /// Split a record's sequence into the lines it is written as
+    ///
+    /// Empty sequences have no lines, and without wrapping the whole
+    /// sequence is one line.
+    fn sequence_lines<'a>(&self, record: &SequenceRecord, sequence: &'a [u8]) -> std::slice::Chunks<'a, u8> {
+        let line_width = match self.line_width_for(record) {
+            0 => sequence.len().max(1),
+            width => width,
+        };
+        sequence.chunks(line_width)
+    }
+    
+    /// Get the line width to wrap a record at, or 0 for no wrapping
+    fn line_width_for(&self, record: &SequenceRecord) -> usize {
+        if self.preserve_line_width {
+            if let Some(width) = record.get_metadata(FASTA_LINE_WIDTH_KEY).and_then(|w| w.parse().ok()) {
+                if width > 0 {
+                    return width;
+                }
+            }
+        }
+        self.line_width
+    }
+    
+    /// Open a streaming writer using this writer's settings
+    pub fn stream_writer<P: AsRef<Path>>(&self, path: P) -> EngineResult<FastaStreamWriter> {
+        Ok(FastaStreamWriter {
+            writer: FastWriter::new(path, Some(self.buffer_size))?,
+            config: self.clone(),
+            records_written: 0,
+        })
+    }
+    
+    /// Write one record with its header and wrapped sequence
+    fn write_record_to(&self, writer: &mut FastWriter, record: &SequenceRecord) -> EngineResult<()> {
+        // Write header
+        writer.write(self.header_line(record)?.as_bytes())?;
+        
+        // Write sequence with line wrapping
+        let sequence = record.sequence_as_vec();
+        for line in self.sequence_lines(record, &sequence) {
+            writer.write(line)?;
+            writer.write(self.terminator.as_str().as_bytes())?;
+        }
+        
+        Ok(())
+    }
 }
 
 impl Default for FastaWriter {
@@ -339,18 +1110,7 @@ impl SequenceWriter for FastaWriter {
         let mut writer = FastWriter::new(path, Some(self.buffer_size))?;
         
         for record in records {
-            // Write header
-            let header = match &record.description {
-                Some(desc) => format!(">{} {}\n", record.id, desc),
-                None => format!(">{}\n", record.id),
-            };
-            writer.write(header.as_bytes())?;
-            
-            // Write sequence with line wrapping
-            for chunk in record.sequence_as_vec().chunks(self.line_width) {
-                writer.write(chunk)?;
-                writer.write(b"\n")?;
-            }
+            self.write_record_to(&mut writer, record)?;
         }
         
         writer.flush()?;
@@ -362,17 +1122,13 @@ impl SequenceWriter for FastaWriter {
         
         for record in records {
             // Write header
-            match &record.description {
-                Some(desc) => output.push_str(&format!(">{} {}\n", record.id, desc)),
-                None => output.push_str(&format!(">{}\n", record.id)),
-            };
+            output.push_str(&self.header_line(record)?);
             
             // Write sequence with line wrapping
             let sequence = record.sequence_as_vec();
-            for i in (0..sequence.len()).step_by(self.line_width) {
-                let end = (i + self.line_width).min(sequence.len());
-                output.push_str(&String::from_utf8_lossy(&sequence[i..end]));
-                output.push('\n');
+            for line in self.sequence_lines(record, &sequence) {
+                output.push_str(&String::from_utf8_lossy(line));
+                output.push_str(self.terminator.as_str());
             }
         }
         
@@ -384,6 +1140,84 @@ impl SequenceWriter for FastaWriter {
     }
 }
 
+/// Streaming FASTA writer
+///
+/// Writes records one at a time with the line wrapping of the
+/// [`FastaWriter`] it was opened from. Buffered output is flushed when the
+/// writer is dropped; call [`flush`](Self::flush) to see write errors.
+pub struct FastaStreamWriter {
+    writer: FastWriter,
+    config: FastaWriter,
+    records_written: usize,
+}
+
+impl FastaStreamWriter {
+    /// Create a FASTA file with the default writer settings
+    pub fn create<P: AsRef<Path>>(path: P) -> EngineResult<Self> {
+        FastaWriter::new().stream_writer(path)
+    }
+    
+    /// Write a single record
+    pub fn write_record(&mut self, record: &SequenceRecord) -> EngineResult<()> {
+        self.config.write_record_to(&mut self.writer, record)?;
+        self.records_written += 1;
+        Ok(())
+    }
+    
+    /// Get the number of records written so far
+    pub fn records_written(&self) -> usize {
+        self.records_written
+    }
+    
+    /// Flush buffered records to disk
+    pub fn flush(&mut self) -> EngineResult<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+impl Drop for FastaStreamWriter {
+    fn drop(&mut self) {
+        // Errors can't be reported from drop; callers who care flush explicitly
+        let _ = self.writer.flush();
+    }
+}
+
+/// Strip any carriage returns left on a line by mixed line endings
+fn trim_line_ending(line: &str) -> &str {
+    line.trim_end_matches(['\r', '\n'])
+}
+
+/// Check that a FASTQ quality line is as long as its sequence line
+///
+/// When the lengths differ by exactly the number of trailing control
+/// characters on the longer line, the error points at the line endings
+/// rather than at the data.
+fn check_fastq_lengths(seq: &[u8], qual: &[u8], record: &str) -> EngineResult<()> {
+    if seq.len() == qual.len() {
+        return Ok(());
+    }
+    
+    let longer = if qual.len() > seq.len() { qual } else { seq };
+    let trailing_controls = longer.iter().rev().take_while(|b| b.is_ascii_control()).count();
+    
+    let message = if trailing_controls == longer.len() - seq.len().min(qual.len()) {
+        format!(
+            "Quality length ({}) does not match sequence length ({}) for {}: the {} line ends with {} control character(s), which usually means inconsistent line endings (e.g. a stray '\\r')",
+            qual.len(), seq.len(), record,
+            if qual.len() > seq.len() { "quality" } else { "sequence" },
+            trailing_controls
+        )
+    } else {
+        format!(
+            "Quality length ({}) does not match sequence length ({}) for {}",
+            qual.len(), seq.len(), record
+        )
+    };
+    
+    Err(EngineError::InvalidSequenceData(message))
+}
+
 /// FASTQ format parser
 #[derive(Debug, Clone)]
 pub struct FastqParser {
@@ -415,6 +1249,17 @@ impl FastqParser {
         self.buffer_size = buffer_size;
         self
     }
+    
+    /// Open a streaming reader using this parser's settings
+    pub fn reader<P: AsRef<Path>>(&self, path: P) -> EngineResult<FastqReader> {
+        Ok(FastqReader {
+            reader: FastReader::new(path.as_ref(), Some(self.buffer_size))?,
+            path: path.as_ref().to_path_buf(),
+            storage_mode: self.storage_mode,
+            line_number: 0,
+            finished: false,
+        })
+    }
 }
 
 impl Default for FastqParser {
@@ -437,6 +1282,7 @@ impl SequenceParser for FastqParser {
         
         for line_result in reader.read_lines() {
             let line = line_result?;
+            let line = trim_line_ending(&line);
             let phase = line_counter % 4;
             
             match phase {
@@ -472,14 +1318,7 @@ impl SequenceParser for FastqParser {
                     current_qual = line.as_bytes().to_vec();
                     
                     // Validate quality length
-                    if current_qual.len() != current_seq.len() {
-                        return Err(EngineError::InvalidSequenceData(
-                            format!(
-                                "Quality length ({}) does not match sequence length ({}) for record {}",
-                                current_qual.len(), current_seq.len(), current_id
-                            )
-                        ));
-                    }
+                    check_fastq_lengths(&current_seq, &current_qual, &format!("record {}", current_id))?;
                     
                     // Create sequence storages
                     let sequence = StorageFactory::create_storage(
@@ -523,7 +1362,7 @@ impl SequenceParser for FastqParser {
     
     fn parse_string(&self, content: &str) -> EngineResult<Vec<SequenceRecord>> {
         let mut records = Vec::new();
-        let mut lines = content.lines();
+        let mut lines = content.lines().map(trim_line_ending);
         
         loop {
             // Header line
@@ -576,14 +1415,7 @@ impl SequenceParser for FastqParser {
             };
             
             // Validate quality length
-            if qual.len() != seq.len() {
-                return Err(EngineError::InvalidSequenceData(
-                    format!(
-                        "Quality length ({}) does not match sequence length ({}) for record {}",
-                        qual.len(), seq.len(), id
-                    )
-                ));
-            }
+            check_fastq_lengths(&seq, &qual, &format!("record {}", id))?;
             
             // Add the record
             records.push(SequenceRecord::with_quality(
@@ -602,11 +1434,156 @@ impl SequenceParser for FastqParser {
     }
 }
 
-/// FASTQ format writer
-#[derive(Debug, Clone)]
-pub struct FastqWriter {
-    /// Buffer size for writing
-    buffer_size: usize,
+/// Streaming FASTQ reader
+///
+/// Reads four lines per record and yields one record at a time.
+pub struct FastqReader {
+    reader: FastReader,
+    path: PathBuf,
+    storage_mode: StorageMode,
+    line_number: usize,
+    finished: bool,
+}
+
+impl FastqReader {
+    /// Open a FASTQ file with the default storage mode
+    pub fn open<P: AsRef<Path>>(path: P) -> EngineResult<Self> {
+        FastqParser::new().reader(path)
+    }
+    
+    /// Iterate over batches of up to `n` records
+    ///
+    /// Only one batch is held in memory at a time and the final batch may be
+    /// shorter. A parse error is yielded in place of the batch it occurs in
+    /// and ends the iteration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    pub fn batches(&mut self, n: usize) -> impl Iterator<Item = EngineResult<Vec<SequenceRecord>>> + '_ {
+        assert!(n > 0, "batch size must be non-zero");
+        
+        std::iter::from_fn(move || {
+            let mut batch = Vec::with_capacity(n);
+            for record in self.by_ref().take(n) {
+                match record {
+                    Ok(record) => batch.push(record),
+                    Err(e) => return Some(Err(e)),
+                }
+            }
+            
+            if batch.is_empty() {
+                None
+            } else {
+                Some(Ok(batch))
+            }
+        })
+    }
+    
+    /// Read the next line, counting line numbers
+    fn next_line(&mut self) -> EngineResult<Option<String>> {
+        match self.reader.read_lines().next() {
+            Some(line) => {
+                self.line_number += 1;
+                let mut line = line?;
+                line.truncate(trim_line_ending(&line).len());
+                Ok(Some(line))
+            },
+            None => Ok(None),
+        }
+    }
+    
+    /// Read the next line of a record, failing at end of file
+    fn expect_line(&mut self, id: &str) -> EngineResult<String> {
+        self.next_line()?.ok_or_else(|| {
+            EngineError::InvalidSequenceData(format!(
+                "Incomplete FASTQ record {} at line {}",
+                id, self.line_number
+            ))
+        })
+    }
+    
+    /// Read and validate the next four-line record
+    fn read_record(&mut self) -> EngineResult<Option<SequenceRecord>> {
+        // Header line, skipping blank lines between records
+        let header = loop {
+            match self.next_line()? {
+                Some(line) if line.is_empty() => continue,
+                Some(line) => break line,
+                None => return Ok(None),
+            }
+        };
+        
+        if !header.starts_with('@') {
+            return Err(EngineError::InvalidSequenceData(format!(
+                "Invalid FASTQ header at line {}: {}",
+                self.line_number, header
+            )));
+        }
+        
+        let parts: Vec<&str> = header[1..].splitn(2, ' ').collect();
+        let id = parts[0].to_string();
+        let description = parts.get(1).map(|s| s.to_string());
+        
+        // Sequence, separator and quality lines
+        let seq = self.expect_line(&id)?.into_bytes();
+        
+        let separator = self.expect_line(&id)?;
+        if !separator.starts_with('+') {
+            return Err(EngineError::InvalidSequenceData(format!(
+                "Invalid FASTQ separator for record {} at line {}: {}",
+                id, self.line_number, separator
+            )));
+        }
+        
+        let qual = self.expect_line(&id)?.into_bytes();
+        check_fastq_lengths(&seq, &qual, &format!("record {} at line {}", id, self.line_number))?;
+        
+        let sequence = StorageFactory::create_storage(
+            Some(seq.clone()),
+            Some(&self.path),
+            Some(seq.len()),
+            Some(self.storage_mode),
+        )?;
+        
+        let quality = StorageFactory::create_storage(
+            Some(qual.clone()),
+            Some(&self.path),
+            Some(qual.len()),
+            Some(self.storage_mode),
+        )?;
+        
+        Ok(Some(SequenceRecord {
+            id,
+            description,
+            sequence,
+            quality: Some(quality),
+            metadata: HashMap::new(),
+        }))
+    }
+}
+
+impl Iterator for FastqReader {
+    type Item = EngineResult<SequenceRecord>;
+    
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        
+        let result = self.read_record().transpose();
+        if !matches!(result, Some(Ok(_))) {
+            self.finished = true;
+        }
+        result
+    }
+}
+
+/// FASTQ format writer
+#[derive(Debug, Clone)]
+pub struct FastqWriter {
+    /// Buffer size for writing
+    buffer_size: usize,
 }
 
 impl FastqWriter {
@@ -622,6 +1599,37 @@ impl FastqWriter {
         self.buffer_size = buffer_size;
         self
     }
+    
+    /// Write one record as its four FASTQ lines
+    fn write_record_to(&self, writer: &mut FastWriter, record: &SequenceRecord) -> EngineResult<()> {
+        // Check if record has quality scores
+        let quality = match record.quality_as_vec() {
+            Some(q) => q,
+            None => return Err(EngineError::InvalidSequenceData(
+                format!("Record {} does not have quality scores (required for FASTQ)", record.id)
+            )),
+        };
+        
+        // Write header
+        let header = match &record.description {
+            Some(desc) => format!("@{} {}\n", record.id, desc),
+            None => format!("@{}\n", record.id),
+        };
+        writer.write(header.as_bytes())?;
+        
+        // Write sequence
+        writer.write(&record.sequence_as_vec())?;
+        writer.write(b"\n")?;
+        
+        // Write separator
+        writer.write(b"+\n")?;
+        
+        // Write quality
+        writer.write(&quality)?;
+        writer.write(b"\n")?;
+        
+        Ok(())
+    }
 }
 
 impl Default for FastqWriter {
@@ -635,31 +1643,7 @@ impl SequenceWriter for FastqWriter {
         let mut writer = FastWriter::new(path, Some(self.buffer_size))?;
         
         for record in records {
-            // Check if record has quality scores
-            let quality = match record.quality_as_vec() {
-                Some(q) => q,
-                None => return Err(EngineError::InvalidSequenceData(
-                    format!("Record {} does not have quality scores (required for FASTQ)", record.id)
-                )),
-            };
-            
-            // Write header
-            let header = match &record.description {
-                Some(desc) => format!("@{} {}\n", record.id, desc),
-                None => format!("@{}\n", record.id),
-            };
-            writer.write(header.as_bytes())?;
-            
-            // Write sequence
-            writer.write(&record.sequence_as_vec())?;
-            writer.write(b"\n")?;
-            
-            // Write separator
-            writer.write(b"+\n")?;
-            
-            // Write quality
-            writer.write(&quality)?;
-            writer.write(b"\n")?;
+            self.write_record_to(&mut writer, record)?;
         }
         
         writer.flush()?;
@@ -704,123 +1688,1947 @@ impl SequenceWriter for FastqWriter {
     }
 }
 
-/// Detect the format of a sequence file based on its content
-pub fn detect_format<P: AsRef<Path>>(path: P) -> EngineResult<&'static str> {
-    let mut reader = FastReader::new(path.as_ref(), None)?;
+/// Strand of a GFF feature
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GffStrand {
+    /// Forward strand (`+`)
+    Forward,
+    /// Reverse strand (`-`)
+    Reverse,
+    /// Not stranded (`.`)
+    Unstranded,
+    /// Stranded, but the strand is unknown (`?`)
+    Unknown,
+}
+
+impl GffStrand {
+    /// Get the GFF3 column value
+    pub fn as_char(&self) -> char {
+        match self {
+            GffStrand::Forward => '+',
+            GffStrand::Reverse => '-',
+            GffStrand::Unstranded => '.',
+            GffStrand::Unknown => '?',
+        }
+    }
+}
+
+/// A feature (one data line) from a GFF3 file
+///
+/// Coordinates are 1-based and inclusive, as in the file. Attributes keep
+/// the order they appeared in, so features round-trip unchanged.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GffFeature {
+    /// ID of the landmark (e.g. chromosome) the feature is on
+    pub seqid: String,
+    /// Program or database that produced the feature
+    pub source: String,
+    /// Feature type (e.g. `gene`, `mRNA`, `exon`)
+    pub feature_type: String,
+    /// Start position (1-based, inclusive)
+    pub start: usize,
+    /// End position (1-based, inclusive)
+    pub end: usize,
+    /// Optional score
+    pub score: Option<f64>,
+    /// Strand
+    pub strand: GffStrand,
+    /// Phase for CDS features (0, 1 or 2)
+    pub phase: Option<u8>,
+    /// Attributes as ordered key-value pairs (values are unescaped)
+    ///
+    /// Multi-value attributes such as `Parent=a,b` hold one entry per value.
+    pub attributes: Vec<(String, Vec<String>)>,
+}
+
+impl GffFeature {
+    /// Get the first value of an attribute
+    pub fn get_attribute(&self, key: &str) -> Option<&str> {
+        self.get_attribute_values(key).first().copied()
+    }
+    
+    /// Get all values of a multi-value attribute such as `Parent=a,b`
+    ///
+    /// A missing attribute gives an empty vector.
+    pub fn get_attribute_values(&self, key: &str) -> Vec<&str> {
+        self.attributes.iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, values)| values.iter().map(String::as_str).collect())
+            .unwrap_or_default()
+    }
+    
+    /// Set a multi-value attribute, replacing an existing one in place or appending a new one
+    pub fn set_attribute_values(&mut self, key: &str, values: &[&str]) {
+        let values: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+        match self.attributes.iter_mut().find(|(k, _)| k == key) {
+            Some((_, v)) => *v = values,
+            None => self.attributes.push((key.to_string(), values)),
+        }
+    }
+    
+    /// Set an attribute to a single value, replacing an existing one in place or appending a new one
+    pub fn set_attribute(&mut self, key: &str, value: &str) {
+        self.set_attribute_values(key, &[value]);
+    }
+    
+    /// Get the length of the feature in bases
+    pub fn len(&self) -> usize {
+        self.end + 1 - self.start
+    }
     
-    // Read the first line to determine the format
-    if let Some(first_line) = reader.read_lines().next() {
-        let line = first_line?;
+    /// Check if the feature is empty (never true for valid features)
+    pub fn is_empty(&self) -> bool {
+        self.end < self.start
+    }
+}
+
+/// GFF3 format parser
+///
+/// Comment lines and directives (including `##gzip`) are skipped, and
+/// parsing stops at a `##FASTA` directive. Gzip-compressed files must be
+/// decompressed first.
+#[derive(Debug, Clone)]
+pub struct Gff3Parser {
+    buffer_size: usize,
+}
+
+impl Gff3Parser {
+    /// Create a new GFF3 parser
+    pub fn new() -> Self {
+        Self {
+            buffer_size: 1024 * 1024, // 1MB buffer
+        }
+    }
+    
+    /// Set the buffer size for reading
+    pub fn with_buffer_size(mut self, buffer_size: usize) -> Self {
+        self.buffer_size = buffer_size;
+        self
+    }
+    
+    /// Parse one line, returning `Ok(None)` for comments, directives and blank lines
+    fn parse_line(line: &str, line_number: usize) -> EngineResult<Option<GffFeature>> {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            return Ok(None);
+        }
         
-        if line.starts_with('>') {
-            return Ok("FASTA");
-        } else if line.starts_with('@') {
-            // Check the third line to confirm it's FASTQ
-            for (i, line_result) in reader.read_lines().enumerate() {
-                if i == 1 { // Third line (after we've already read the first)
-                    let line = line_result?;
-                    if line.starts_with('+') {
-                        return Ok("FASTQ");
-                    }
-                    break;
-                }
+        let invalid = |reason: String| {
+            EngineError::InvalidSequenceData(format!("Invalid GFF3 line {}: {}", line_number, reason))
+        };
+        
+        let columns: Vec<&str> = line.split('\t').collect();
+        if columns.len() != 9 {
+            return Err(invalid(format!("expected 9 columns, found {}", columns.len())));
+        }
+        
+        let start: usize = columns[3].parse()
+            .map_err(|_| invalid(format!("invalid start '{}'", columns[3])))?;
+        let end: usize = columns[4].parse()
+            .map_err(|_| invalid(format!("invalid end '{}'", columns[4])))?;
+        if start == 0 || start > end {
+            return Err(invalid(format!("invalid range {}..{}", start, end)));
+        }
+        
+        let score = match columns[5] {
+            "." => None,
+            s => Some(s.parse().map_err(|_| invalid(format!("invalid score '{}'", s)))?),
+        };
+        
+        let strand = match columns[6] {
+            "+" => GffStrand::Forward,
+            "-" => GffStrand::Reverse,
+            "." => GffStrand::Unstranded,
+            "?" => GffStrand::Unknown,
+            s => return Err(invalid(format!("invalid strand '{}'", s))),
+        };
+        
+        let phase = match columns[7] {
+            "." => None,
+            "0" => Some(0),
+            "1" => Some(1),
+            "2" => Some(2),
+            s => return Err(invalid(format!("invalid phase '{}'", s))),
+        };
+        
+        // Attributes are `key=value` pairs separated by semicolons, with
+        // literal commas separating the values of multi-value attributes
+        let mut attributes = Vec::new();
+        if columns[8] != "." {
+            for pair in columns[8].split(';').filter(|p| !p.trim().is_empty()) {
+                let (key, value) = pair.split_once('=')
+                    .ok_or_else(|| invalid(format!("invalid attribute '{}'", pair)))?;
+                let values = value.split(',').map(gff_unescape).collect();
+                attributes.push((gff_unescape(key.trim()), values));
+            }
+        }
+        
+        Ok(Some(GffFeature {
+            seqid: gff_unescape(columns[0]),
+            source: gff_unescape(columns[1]),
+            feature_type: gff_unescape(columns[2]),
+            start,
+            end,
+            score,
+            strand,
+            phase,
+            attributes,
+        }))
+    }
+}
+
+impl Default for Gff3Parser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FeatureParser for Gff3Parser {
+    fn parse_file<P: AsRef<Path>>(&self, path: P) -> EngineResult<Vec<GffFeature>> {
+        // Reject gzip data up front rather than failing on invalid UTF-8
+        let mut magic = [0u8; 2];
+        let mut file = std::fs::File::open(path.as_ref())?;
+        if io::Read::read(&mut file, &mut magic)? == 2 && magic == [0x1f, 0x8b] {
+            return Err(EngineError::UnsupportedOperation(
+                "Gzip-compressed GFF3 files are not supported; decompress the file first".to_string()
+            ));
+        }
+        
+        let mut reader = FastReader::new(path.as_ref(), Some(self.buffer_size))?;
+        let mut features = Vec::new();
+        
+        for (index, line_result) in reader.read_lines().enumerate() {
+            let line = line_result?;
+            
+            // Embedded sequences follow a ##FASTA directive
+            if line.starts_with("##FASTA") {
+                break;
+            }
+            
+            if let Some(feature) = Self::parse_line(&line, index + 1)? {
+                features.push(feature);
             }
         }
+        
+        Ok(features)
     }
     
-    Err(EngineError::InvalidSequenceData(
-        "Could not determine file format".to_string()
-    ))
+    fn parse_string(&self, content: &str) -> EngineResult<Vec<GffFeature>> {
+        let mut features = Vec::new();
+        
+        for (index, line) in content.lines().enumerate() {
+            // Embedded sequences follow a ##FASTA directive
+            if line.starts_with("##FASTA") {
+                break;
+            }
+            
+            if let Some(feature) = Self::parse_line(line, index + 1)? {
+                features.push(feature);
+            }
+        }
+        
+        Ok(features)
+    }
+    
+    fn format_name(&self) -> &str {
+        "GFF3"
+    }
 }
 
-/// Create a parser for the specified format
-pub fn create_parser(format: &str) -> EngineResult<Box<dyn SequenceParser>> {
-    match format.to_uppercase().as_str() {
-        "FASTA" => Ok(Box::new(FastaParser::new())),
-        "FASTQ" => Ok(Box::new(FastqParser::new())),
-        _ => Err(EngineError::UnsupportedOperation(
-            format!("Unsupported format: {}", format)
-        )),
+/// GFF3 format writer
+#[derive(Debug, Clone)]
+pub struct Gff3Writer {
+    buffer_size: usize,
+}
+
+impl Gff3Writer {
+    /// Create a new GFF3 writer
+    pub fn new() -> Self {
+        Self {
+            buffer_size: 1024 * 1024, // 1MB buffer
+        }
+    }
+    
+    /// Set the buffer size for writing
+    pub fn with_buffer_size(mut self, buffer_size: usize) -> Self {
+        self.buffer_size = buffer_size;
+        self
+    }
+    
+    /// Format a feature as a GFF3 data line (without the trailing newline)
+    fn format_line(feature: &GffFeature) -> String {
+        let score = feature.score.map_or_else(|| ".".to_string(), |s| s.to_string());
+        let phase = feature.phase.map_or_else(|| ".".to_string(), |p| p.to_string());
+        let attributes = if feature.attributes.is_empty() {
+            ".".to_string()
+        } else {
+            feature.attributes.iter()
+                .map(|(k, values)| {
+                    let values: Vec<String> = values.iter().map(|v| gff_escape(v)).collect();
+                    format!("{}={}", gff_escape(k), values.join(","))
+                })
+                .collect::<Vec<_>>()
+                .join(";")
+        };
+        
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            gff_escape(&feature.seqid),
+            gff_escape(&feature.source),
+            gff_escape(&feature.feature_type),
+            feature.start,
+            feature.end,
+            score,
+            feature.strand.as_char(),
+            phase,
+            attributes,
+        )
     }
 }
 
-/// Create a writer for the specified format
-pub fn create_writer(format: &str) -> EngineResult<Box<dyn SequenceWriter>> {
-    match format.to_uppercase().as_str() {
-        "FASTA" => Ok(Box::new(FastaWriter::new())),
-        "FASTQ" => Ok(Box::new(FastqWriter::new())),
-        _ => Err(EngineError::UnsupportedOperation(
-            format!("Unsupported format: {}", format)
-        )),
+impl Default for Gff3Writer {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::io::Write;
-    use tempfile::tempdir;
-    
-    #[test]
-    fn test_fasta_parsing() -> std::io::Result<()> {
-        // Create a temporary FASTA file
-        let dir = tempdir()?;
-        let file_path = dir.path().join("test.fasta");
+impl FeatureWriter for Gff3Writer {
+    fn write_file<P: AsRef<Path>>(&self, features: &[GffFeature], path: P) -> EngineResult<()> {
+        let mut writer = FastWriter::new(path, Some(self.buffer_size))?;
         
-        let fasta_content = ">seq1 First sequence\nACGTACGT\n>seq2 Second sequence\nGTACGTAC\n";
-        {
-            let mut file = std::fs::File::create(&file_path)?;
-            file.write_all(fasta_content.as_bytes())?;
+        writer.write(b"##gff-version 3\n")?;
+        for feature in features {
+            writer.write(Self::format_line(feature).as_bytes())?;
+            writer.write(b"\n")?;
         }
         
-        // Parse the file
-        let parser = FastaParser::new();
-        let records = parser.parse_file(&file_path).unwrap();
+        writer.flush()?;
+        Ok(())
+    }
+    
+    fn write_string(&self, features: &[GffFeature]) -> EngineResult<String> {
+        let mut output = String::from("##gff-version 3\n");
         
-        // Check the parsed records
-        assert_eq!(records.len(), 2);
-        assert_eq!(records[0].id, "seq1");
-        assert_eq!(records[0].description, Some("First sequence".to_string()));
-        assert_eq!(records[0].sequence_as_vec(), b"ACGTACGT");
+        for feature in features {
+            output.push_str(&Self::format_line(feature));
+            output.push('\n');
+        }
+        
+        Ok(output)
+    }
+    
+    fn format_name(&self) -> &str {
+        "GFF3"
+    }
+}
+
+/// Percent-encode characters with special meaning in GFF3 columns
+///
+/// Commas inside a value are escaped too; the writer joins the values of
+/// multi-value attributes with literal commas.
+fn gff_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            ';' | '=' | '&' | ',' | '%' | '\t' | '\n' | '\r' => {
+                escaped.push_str(&format!("%{:02X}", c as u32));
+            },
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Decode percent-encoded characters in a GFF3 column
+fn gff_unescape(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 3 <= bytes.len() {
+            let hex = |b: u8| (b as char).to_digit(16);
+            if let (Some(hi), Some(lo)) = (hex(bytes[i + 1]), hex(bytes[i + 2])) {
+                decoded.push((hi * 16 + lo) as u8);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// An interval from a BED file
+///
+/// Coordinates are 0-based and half-open, as in the file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BedInterval {
+    /// Chromosome (sequence) name
+    pub chrom: String,
+    /// Start position (0-based, inclusive)
+    pub start: usize,
+    /// End position (0-based, exclusive)
+    pub end: usize,
+    /// Optional interval name (BED4+)
+    pub name: Option<String>,
+    /// Optional score (BED5+)
+    pub score: Option<f64>,
+    /// Strand (BED6+); unstranded if absent
+    pub strand: GffStrand,
+}
+
+impl BedInterval {
+    /// Get the length of the interval
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+    
+    /// Check if the interval is empty
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+/// BED format parser
+///
+/// Reads BED3 through BED6 columns; any further columns (e.g. BED12 blocks)
+/// are ignored. `track`, `browser` and `#` comment lines are skipped.
+#[derive(Debug, Clone)]
+pub struct BedParser {
+    /// Buffer size for reading
+    buffer_size: usize,
+}
+
+impl BedParser {
+    /// Create a new BED parser
+    pub fn new() -> Self {
+        Self {
+            buffer_size: 1024 * 1024, // 1MB buffer
+        }
+    }
+    
+    /// Set the buffer size for reading
+    pub fn with_buffer_size(mut self, buffer_size: usize) -> Self {
+        self.buffer_size = buffer_size;
+        self
+    }
+    
+    /// Parse a BED file
+    pub fn parse_file<P: AsRef<Path>>(&self, path: P) -> EngineResult<Vec<BedInterval>> {
+        let mut reader = FastReader::new(path.as_ref(), Some(self.buffer_size))?;
+        let mut intervals = Vec::new();
+        
+        for (index, line_result) in reader.read_lines().enumerate() {
+            if let Some(interval) = Self::parse_line(&line_result?, index + 1)? {
+                intervals.push(interval);
+            }
+        }
+        
+        Ok(intervals)
+    }
+    
+    /// Parse BED content from a string
+    pub fn parse_string(&self, content: &str) -> EngineResult<Vec<BedInterval>> {
+        let mut intervals = Vec::new();
+        
+        for (index, line) in content.lines().enumerate() {
+            if let Some(interval) = Self::parse_line(line, index + 1)? {
+                intervals.push(interval);
+            }
+        }
+        
+        Ok(intervals)
+    }
+    
+    /// Parse one line, returning `Ok(None)` for headers, comments and blank lines
+    fn parse_line(line: &str, line_number: usize) -> EngineResult<Option<BedInterval>> {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            return Ok(None);
+        }
+        
+        // Header lines start with a `track` or `browser` token; a chromosome
+        // merely named e.g. `track1` is data
+        if matches!(line.split_whitespace().next(), Some("track" | "browser")) {
+            return Ok(None);
+        }
+        
+        let invalid = |reason: String| {
+            EngineError::InvalidSequenceData(format!("Invalid BED line {}: {}", line_number, reason))
+        };
+        
+        let columns: Vec<&str> = line.split_whitespace().collect();
+        if columns.len() < 3 {
+            return Err(invalid(format!("expected at least 3 columns, found {}", columns.len())));
+        }
+        
+        let start: usize = columns[1].parse()
+            .map_err(|_| invalid(format!("invalid start '{}'", columns[1])))?;
+        let end: usize = columns[2].parse()
+            .map_err(|_| invalid(format!("invalid end '{}'", columns[2])))?;
+        if start > end {
+            return Err(invalid(format!("start {} is after end {}", start, end)));
+        }
+        
+        let score = match columns.get(4) {
+            None | Some(&".") => None,
+            Some(s) => Some(s.parse().map_err(|_| invalid(format!("invalid score '{}'", s)))?),
+        };
+        
+        let strand = match columns.get(5) {
+            Some(&"+") => GffStrand::Forward,
+            Some(&"-") => GffStrand::Reverse,
+            None | Some(&".") => GffStrand::Unstranded,
+            Some(s) => return Err(invalid(format!("invalid strand '{}'", s))),
+        };
+        
+        Ok(Some(BedInterval {
+            chrom: columns[0].to_string(),
+            start,
+            end,
+            name: columns.get(3).filter(|&&name| name != ".").map(|name| name.to_string()),
+            score,
+            strand,
+        }))
+    }
+}
+
+impl Default for BedParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Extract the subsequence of a record for each interval
+///
+/// Intervals on the `-` strand are reverse-complemented. Fails if an interval
+/// names a different chromosome than the record's id or runs past its end.
+pub fn extract_intervals(record: &SequenceRecord, intervals: &[BedInterval]) -> EngineResult<Vec<Vec<u8>>> {
+    intervals.iter().map(|interval| {
+        if interval.chrom != record.id {
+            return Err(EngineError::InvalidSequenceData(format!(
+                "Interval on '{}' does not match record '{}'",
+                interval.chrom, record.id
+            )));
+        }
+        
+        if interval.end > record.len() {
+            return Err(EngineError::InvalidSequenceData(format!(
+                "Interval {}:{}-{} exceeds record length {}",
+                interval.chrom, interval.start, interval.end, record.len()
+            )));
+        }
+        
+        let subsequence = record.subsequence(interval.start, interval.end);
+        Ok(match interval.strand {
+            GffStrand::Reverse => string_ops::reverse_complement_dna(&subsequence),
+            _ => subsequence,
+        })
+    }).collect()
+}
+
+/// A feature from the FEATURES table of a GenBank record
+///
+/// Ranges are 1-based and inclusive, listed in the order the location
+/// gives them (so reverse-strand joins run from the 5' end of the
+/// transcript). Partial markers (`<`, `>`) are dropped. Locations that
+/// [`parse_genbank_location`] does not support, such as references to other
+/// records or `one-of()`, keep their string with empty `ranges`, an
+/// `Unknown` strand and the reason in `location_error` rather than failing
+/// the record.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenBankFeature {
+    /// Feature key (e.g. `gene`, `CDS`)
+    pub feature_type: String,
+    /// Location string as written in the file
+    pub location: String,
+    /// Coordinate ranges parsed from the location
+    pub ranges: Vec<(usize, usize)>,
+    /// Strand of the location; `Unknown` if its parts disagree
+    pub strand: GffStrand,
+    /// Why the location could not be parsed, if it could not
+    pub location_error: Option<String>,
+    /// Qualifiers as ordered key-value pairs, with quotes removed
+    pub qualifiers: Vec<(String, String)>,
+}
+
+impl GenBankFeature {
+    /// Check whether the location was parsed into ranges
+    pub fn is_location_parsed(&self) -> bool {
+        self.location_error.is_none()
+    }
+    
+    /// Get the value of a qualifier (empty for flags such as `/pseudo`)
+    pub fn get_qualifier(&self, key: &str) -> Option<&str> {
+        self.qualifiers.iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+    
+    /// Convert to GFF features, one per location range
+    pub fn to_gff_features(&self, seqid: &str) -> Vec<GffFeature> {
+        self.ranges.iter().map(|&(start, end)| GffFeature {
+            seqid: seqid.to_string(),
+            source: "GenBank".to_string(),
+            feature_type: self.feature_type.clone(),
+            start,
+            end,
+            score: None,
+            strand: self.strand,
+            phase: None,
+            attributes: self.qualifiers.iter()
+                .map(|(key, value)| (key.clone(), vec![value.clone()]))
+                .collect(),
+        }).collect()
+    }
+}
+
+/// An annotated record from a GenBank flat file
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenBankRecord {
+    /// Locus name from the LOCUS line
+    pub name: String,
+    /// Sequence length from the LOCUS line
+    pub length: usize,
+    /// Molecule type from the LOCUS line (e.g. `DNA`, `mRNA`)
+    pub molecule_type: String,
+    /// `linear` or `circular`, if given
+    pub topology: Option<String>,
+    /// DEFINITION text
+    pub definition: Option<String>,
+    /// Primary accession
+    pub accession: Option<String>,
+    /// ORGANISM name (without the taxonomy lineage)
+    pub organism: Option<String>,
+    /// Features in file order
+    pub features: Vec<GenBankFeature>,
+    /// Sequence from the ORIGIN section
+    pub sequence: Vec<u8>,
+}
+
+impl GenBankRecord {
+    /// Convert to a sequence record, keeping the header fields as metadata
+    pub fn to_sequence_record(&self) -> SequenceRecord {
+        let mut record = SequenceRecord::new(self.name.clone(), self.definition.clone(), self.sequence.clone());
+        record.add_metadata("molecule_type", &self.molecule_type);
+        if let Some(topology) = &self.topology {
+            record.add_metadata("topology", topology);
+        }
+        if let Some(accession) = &self.accession {
+            record.add_metadata("accession", accession);
+        }
+        if let Some(organism) = &self.organism {
+            record.add_metadata("organism", organism);
+        }
+        record
+    }
+}
+
+/// Section of a GenBank record being parsed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GenBankSection {
+    Header,
+    Features,
+    Origin,
+}
+
+/// GenBank flat-file parser
+///
+/// Reads the LOCUS, DEFINITION, ACCESSION and ORGANISM fields, the FEATURES
+/// table and the ORIGIN sequence of each record. Other fields are skipped.
+#[derive(Debug, Clone)]
+pub struct GenBankParser {
+    /// Buffer size for reading
+    buffer_size: usize,
+}
+
+impl GenBankParser {
+    /// Create a new GenBank parser
+    pub fn new() -> Self {
+        Self {
+            buffer_size: 1024 * 1024, // 1MB buffer
+        }
+    }
+    
+    /// Set the buffer size for reading
+    pub fn with_buffer_size(mut self, buffer_size: usize) -> Self {
+        self.buffer_size = buffer_size;
+        self
+    }
+    
+    /// Parse a GenBank file
+    pub fn parse_file<P: AsRef<Path>>(&self, path: P) -> EngineResult<Vec<GenBankRecord>> {
+        let mut reader = FastReader::new(path.as_ref(), Some(self.buffer_size))?;
+        Self::parse_lines(reader.read_lines().map(|line| line.map_err(EngineError::from)))
+    }
+    
+    /// Parse GenBank content from a string
+    pub fn parse_string(&self, content: &str) -> EngineResult<Vec<GenBankRecord>> {
+        Self::parse_lines(content.lines().map(|line| Ok(line.to_string())))
+    }
+    
+    /// Parse records from a sequence of lines
+    fn parse_lines<I: Iterator<Item = EngineResult<String>>>(lines: I) -> EngineResult<Vec<GenBankRecord>> {
+        let mut records = Vec::new();
+        let mut record: Option<GenBankRecord> = None;
+        let mut section = GenBankSection::Header;
+        let mut header_key = String::new();
+        let mut feature: Option<GenBankFeature> = None;
+        
+        for (index, line_result) in lines.enumerate() {
+            let line = line_result?;
+            let line = line.trim_end();
+            let line_number = index + 1;
+            if line.is_empty() {
+                continue;
+            }
+            
+            let invalid = |reason: String| {
+                EngineError::InvalidSequenceData(format!("Invalid GenBank line {}: {}", line_number, reason))
+            };
+            
+            if line.starts_with("LOCUS") {
+                if record.is_some() {
+                    return Err(invalid("LOCUS line before the end of the previous record".to_string()));
+                }
+                record = Some(Self::parse_locus(line).map_err(invalid)?);
+                section = GenBankSection::Header;
+                continue;
+            }
+            
+            let current = record.as_mut()
+                .ok_or_else(|| invalid("data before the LOCUS line".to_string()))?;
+            
+            if line == "//" {
+                if let Some(done) = feature.take() {
+                    current.features.push(Self::finish_feature(done));
+                }
+                let done = record.take().unwrap();
+                if !done.sequence.is_empty() && done.sequence.len() != done.length {
+                    return Err(invalid(format!(
+                        "record {} has {} bases but its LOCUS line gives {}",
+                        done.name, done.sequence.len(), done.length
+                    )));
+                }
+                records.push(done);
+                continue;
+            }
+            
+            match section {
+                GenBankSection::Features if line.starts_with(' ') => {
+                    Self::parse_feature_line(line, &mut feature, &mut current.features).map_err(invalid)?;
+                },
+                GenBankSection::Origin if line.starts_with(' ') => {
+                    current.sequence.extend(line.bytes().filter(u8::is_ascii_alphabetic));
+                },
+                _ => {
+                    if let Some(done) = feature.take() {
+                        current.features.push(Self::finish_feature(done));
+                    }
+                    
+                    // Keywords occupy the first 12 columns; blank keywords continue the previous one
+                    let (key, value) = match (line.get(..12), line.get(12..)) {
+                        (Some(key), Some(value)) => (key.trim(), value.trim()),
+                        _ => (line.trim(), ""),
+                    };
+                    
+                    section = match key {
+                        "FEATURES" => GenBankSection::Features,
+                        "ORIGIN" => GenBankSection::Origin,
+                        _ => GenBankSection::Header,
+                    };
+                    
+                    match (key, header_key.as_str()) {
+                        ("", "DEFINITION") => {
+                            if let Some(definition) = current.definition.as_mut() {
+                                definition.push(' ');
+                                definition.push_str(value);
+                            }
+                        },
+                        ("DEFINITION", _) => current.definition = Some(value.to_string()),
+                        ("ACCESSION", _) => {
+                            current.accession = value.split_whitespace().next().map(|s| s.to_string());
+                        },
+                        ("ORGANISM", _) => current.organism = Some(value.to_string()),
+                        _ => {},
+                    }
+                    
+                    if !key.is_empty() {
+                        header_key = key.to_string();
+                    }
+                },
+            }
+        }
+        
+        // Accept a final record without the `//` terminator
+        if let Some(mut done) = record {
+            if let Some(last) = feature.take() {
+                done.features.push(Self::finish_feature(last));
+            }
+            records.push(done);
+        }
+        
+        Ok(records)
+    }
+    
+    /// Parse the LOCUS line into an empty record
+    fn parse_locus(line: &str) -> Result<GenBankRecord, String> {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        if tokens.len() < 5 {
+            return Err(format!("incomplete LOCUS line '{}'", line));
+        }
+        
+        let length = tokens[2].parse()
+            .map_err(|_| format!("invalid sequence length '{}'", tokens[2]))?;
+        
+        Ok(GenBankRecord {
+            name: tokens[1].to_string(),
+            length,
+            molecule_type: tokens[4].to_string(),
+            topology: tokens.get(5)
+                .filter(|&&t| t == "linear" || t == "circular")
+                .map(|t| t.to_string()),
+            definition: None,
+            accession: None,
+            organism: None,
+            features: Vec::new(),
+            sequence: Vec::new(),
+        })
+    }
+    
+    /// Handle one indented line of the FEATURES table
+    fn parse_feature_line(
+        line: &str,
+        feature: &mut Option<GenBankFeature>,
+        features: &mut Vec<GenBankFeature>,
+    ) -> Result<(), String> {
+        // Compare bytes so non-ASCII text near column 22 cannot split a character
+        let continuation = line.len() > 21 && line.as_bytes()[..21].iter().all(u8::is_ascii_whitespace);
+        
+        if !continuation {
+            // A new feature key starts in column 6, its location in column 22
+            let key = line.get(5..21).map(str::trim).unwrap_or("");
+            if key.is_empty() || !line.starts_with("     ") {
+                return Err(format!("malformed feature line '{}'", line.trim()));
+            }
+            if let Some(done) = feature.take() {
+                features.push(Self::finish_feature(done));
+            }
+            *feature = Some(GenBankFeature {
+                feature_type: key.to_string(),
+                location: line.get(21..).unwrap_or("").trim().to_string(),
+                ranges: Vec::new(),
+                strand: GffStrand::Unstranded,
+                location_error: None,
+                qualifiers: Vec::new(),
+            });
+            return Ok(());
+        }
+        
+        let current = feature.as_mut()
+            .ok_or_else(|| "qualifier before the first feature".to_string())?;
+        let content = line[21..].trim();
+        
+        if let Some(qualifier) = content.strip_prefix('/') {
+            let (key, value) = qualifier.split_once('=').unwrap_or((qualifier, ""));
+            current.qualifiers.push((key.to_string(), value.to_string()));
+        } else if let Some((key, value)) = current.qualifiers.last_mut() {
+            // Translations are wrapped without spaces; free text is joined with one
+            if key != "translation" {
+                value.push(' ');
+            }
+            value.push_str(content);
+        } else {
+            current.location.push_str(content);
+        }
+        
+        Ok(())
+    }
+    
+    /// Parse the location and unquote the qualifiers of a completed feature
+    ///
+    /// An unsupported location leaves the feature with no ranges.
+    fn finish_feature(mut feature: GenBankFeature) -> GenBankFeature {
+        match parse_genbank_location(&feature.location) {
+            Ok((ranges, strand)) => {
+                feature.ranges = ranges;
+                feature.strand = strand;
+            },
+            Err(err) => {
+                feature.ranges = Vec::new();
+                feature.strand = GffStrand::Unknown;
+                feature.location_error = Some(err.to_string());
+            },
+        }
+        
+        for (_, value) in feature.qualifiers.iter_mut() {
+            if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+                *value = value[1..value.len() - 1].replace("\"\"", "\"");
+            }
+        }
+        
+        feature
+    }
+}
+
+impl Default for GenBankParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parse a GenBank location string into 1-based inclusive ranges and a strand
+///
+/// Supports single bases, `a..b` and `a^b` spans, partial markers (`<`,
+/// `>`) and nested `complement()`, `join()` and `order()` operators.
+/// References to other records (`J00194.1:100..202`) are not supported.
+pub fn parse_genbank_location(location: &str) -> EngineResult<(Vec<(usize, usize)>, GffStrand)> {
+    let location: String = location.chars().filter(|c| !c.is_whitespace()).collect();
+    let parts = parse_location_parts(&location).map_err(EngineError::InvalidSequenceData)?;
+    
+    let strand = if parts.iter().all(|&(_, _, reverse)| reverse) {
+        GffStrand::Reverse
+    } else if parts.iter().all(|&(_, _, reverse)| !reverse) {
+        GffStrand::Forward
+    } else {
+        GffStrand::Unknown
+    };
+    
+    Ok((parts.into_iter().map(|(start, end, _)| (start, end)).collect(), strand))
+}
+
+/// Parse a location into `(start, end, reverse)` parts
+fn parse_location_parts(location: &str) -> Result<Vec<(usize, usize, bool)>, String> {
+    if let Some(inner) = location.strip_prefix("complement(").and_then(|s| s.strip_suffix(')')) {
+        // The complement reads the inner parts in reverse order
+        let mut parts = parse_location_parts(inner)?;
+        parts.reverse();
+        parts.iter_mut().for_each(|part| part.2 = !part.2);
+        return Ok(parts);
+    }
+    
+    let list = location.strip_prefix("join(")
+        .or_else(|| location.strip_prefix("order("))
+        .and_then(|s| s.strip_suffix(')'));
+    if let Some(inner) = list {
+        // Split on commas that are not inside nested operators
+        let mut parts = Vec::new();
+        let mut depth = 0usize;
+        let mut start = 0;
+        for (i, c) in inner.char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth = depth.checked_sub(1).ok_or_else(|| format!("unbalanced location '{}'", location))?,
+                ',' if depth == 0 => {
+                    parts.extend(parse_location_parts(&inner[start..i])?);
+                    start = i + 1;
+                },
+                _ => {},
+            }
+        }
+        parts.extend(parse_location_parts(&inner[start..])?);
+        return Ok(parts);
+    }
+    
+    let position = |s: &str| -> Result<usize, String> {
+        s.trim_start_matches('<').trim_start_matches('>')
+            .parse::<usize>()
+            .ok()
+            .filter(|&p| p > 0)
+            .ok_or_else(|| format!("unsupported location '{}'", location))
+    };
+    
+    let (start, end) = match location.split_once("..").or_else(|| location.split_once('^')) {
+        Some((start, end)) => (position(start)?, position(end)?),
+        None => {
+            let base = position(location)?;
+            (base, base)
+        },
+    };
+    
+    if start > end {
+        return Err(format!("location '{}' starts after it ends", location));
+    }
+    
+    Ok(vec![(start, end, false)])
+}
+
+/// Signature at the start of a `.2bit` file, in the file's byte order
+const TWO_BIT_SIGNATURE: u32 = 0x1A41_2743;
+
+/// Upper bound on capacity reserved from counts read out of a `.2bit` file
+///
+/// Counts come from the file itself, so a corrupt header must not be able to
+/// make us allocate arbitrarily large buffers up front.
+const TWO_BIT_MAX_PREALLOCATION: usize = 1 << 16;
+
+/// Base order of the 2-bit codes in `.2bit` files
+const TWO_BIT_BASES: &[u8; 4] = b"TCAG";
+
+/// Reader for UCSC `.2bit` files
+///
+/// The file is memory-mapped; opening it only parses the header and the
+/// sequence index, and each sequence's blocks are read when it is requested.
+pub struct TwoBitReader {
+    mmap: Arc<MemoryMapped>,
+    swapped: bool,
+    index: Vec<(String, usize)>,
+}
+
+impl TwoBitReader {
+    /// Open a `.2bit` file and read its sequence index
+    pub fn open<P: AsRef<Path>>(path: P) -> EngineResult<Self> {
+        let mmap = Arc::new(MemoryMapped::new(path, MemoryMapMode::ReadOnly)?);
+        let data = mmap.as_slice();
+        
+        // The signature tells us the byte order of every other integer
+        let signature = read_u32_at(data, 0, false)?;
+        let swapped = if signature == TWO_BIT_SIGNATURE {
+            false
+        } else if signature.swap_bytes() == TWO_BIT_SIGNATURE {
+            true
+        } else {
+            return Err(EngineError::InvalidSequenceData("Not a 2bit file: bad signature".to_string()));
+        };
+        
+        // Version 1 files use 64-bit sequence offsets
+        let version = read_u32_at(data, 4, swapped)?;
+        if version > 1 {
+            return Err(EngineError::UnsupportedOperation(format!("Unsupported 2bit version {}", version)));
+        }
+        let sequence_count = read_u32_at(data, 8, swapped)? as usize;
+        
+        let mut index = Vec::with_capacity(sequence_count.min(TWO_BIT_MAX_PREALLOCATION));
+        let mut pos = 16;
+        for _ in 0..sequence_count {
+            let name_len = *data.get(pos).ok_or_else(truncated_two_bit)? as usize;
+            let name = data.get(pos + 1..pos + 1 + name_len).ok_or_else(truncated_two_bit)?;
+            pos += 1 + name_len;
+            
+            let offset = if version == 1 {
+                let low = read_u32_at(data, pos, swapped)? as u64;
+                let high = read_u32_at(data, pos + 4, swapped)? as u64;
+                pos += 8;
+                if swapped { (low << 32) | high } else { (high << 32) | low }
+            } else {
+                pos += 4;
+                read_u32_at(data, pos - 4, swapped)? as u64
+            };
+            
+            index.push((String::from_utf8_lossy(name).into_owned(), offset as usize));
+        }
+        
+        Ok(Self { mmap, swapped, index })
+    }
+    
+    /// Get the names of the sequences in file order
+    pub fn names(&self) -> Vec<&str> {
+        self.index.iter().map(|(name, _)| name.as_str()).collect()
+    }
+    
+    /// Get random-access storage for a named sequence
+    pub fn sequence(&self, name: &str) -> EngineResult<TwoBitSequence> {
+        let offset = self.index.iter()
+            .find(|(n, _)| n == name)
+            .map(|&(_, offset)| offset)
+            .ok_or_else(|| EngineError::InvalidSequenceData(format!("Sequence '{}' not found in 2bit file", name)))?;
+        
+        let data = self.mmap.as_slice();
+        let length = read_u32_at(data, offset, self.swapped)? as usize;
+        let mut pos = offset + 4;
+        
+        let n_blocks = read_two_bit_blocks(data, &mut pos, self.swapped)?;
+        let mask_blocks = read_two_bit_blocks(data, &mut pos, self.swapped)?;
+        
+        // Skip the reserved word before the packed bases
+        let dna_offset = pos + 4;
+        if dna_offset + length.div_ceil(4) > data.len() {
+            return Err(truncated_two_bit());
+        }
+        
+        Ok(TwoBitSequence {
+            mmap: Arc::clone(&self.mmap),
+            dna_offset,
+            length,
+            n_blocks,
+            mask_blocks,
+        })
+    }
+    
+    /// Read a named sequence into a record backed by the memory map
+    pub fn read_record(&self, name: &str) -> EngineResult<SequenceRecord> {
+        Ok(SequenceRecord {
+            id: name.to_string(),
+            description: None,
+            sequence: Box::new(self.sequence(name)?),
+            quality: None,
+            metadata: HashMap::new(),
+        })
+    }
+}
+
+/// A sequence inside a memory-mapped `.2bit` file
+///
+/// Subsequences are decoded on request, restoring `N` runs and lowercase
+/// masked regions.
+pub struct TwoBitSequence {
+    mmap: Arc<MemoryMapped>,
+    dna_offset: usize,
+    length: usize,
+    /// Runs of `N` as `(start, end)`, sorted by start
+    n_blocks: Vec<(usize, usize)>,
+    /// Soft-masked (lowercase) runs as `(start, end)`, sorted by start
+    mask_blocks: Vec<(usize, usize)>,
+}
+
+impl TwoBitSequence {
+    /// Apply runs overlapping `start..end` to the decoded bases
+    fn apply_blocks(blocks: &[(usize, usize)], start: usize, end: usize, bases: &mut [u8], f: impl Fn(&mut u8)) {
+        let first = blocks.partition_point(|&(_, block_end)| block_end <= start);
+        for &(block_start, block_end) in blocks[first..].iter().take_while(|&&(block_start, _)| block_start < end) {
+            for base in &mut bases[block_start.max(start) - start..block_end.min(end) - start] {
+                f(base);
+            }
+        }
+    }
+}
+
+impl StorableSequence for TwoBitSequence {
+    fn len(&self) -> usize {
+        self.length
+    }
+    
+    fn subsequence(&self, start: usize, end: usize) -> Vec<u8> {
+        let end = end.min(self.length);
+        let start = start.min(end);
+        let packed = &self.mmap.as_slice()[self.dna_offset..];
+        
+        let mut bases: Vec<u8> = (start..end)
+            .map(|pos| {
+                let code = (packed[pos / 4] >> (6 - 2 * (pos % 4))) & 0b11;
+                TWO_BIT_BASES[code as usize]
+            })
+            .collect();
+        
+        Self::apply_blocks(&self.n_blocks, start, end, &mut bases, |base| *base = b'N');
+        Self::apply_blocks(&self.mask_blocks, start, end, &mut bases, |base| base.make_ascii_lowercase());
+        
+        bases
+    }
+    
+    fn as_slice(&self) -> Option<&[u8]> {
+        // Bases are packed, so there is no contiguous ASCII slice
+        None
+    }
+    
+    fn storage_mode(&self) -> StorageMode {
+        StorageMode::MemoryMapped
+    }
+    
+    fn memory_usage(&self) -> usize {
+        // Only count metadata and blocks, not the mapped file
+        std::mem::size_of::<Self>()
+            + (self.n_blocks.capacity() + self.mask_blocks.capacity()) * std::mem::size_of::<(usize, usize)>()
+    }
+}
+
+/// Read a 32-bit integer from a `.2bit` file
+fn read_u32_at(data: &[u8], pos: usize, swapped: bool) -> EngineResult<u32> {
+    let bytes: [u8; 4] = data.get(pos..pos + 4)
+        .ok_or_else(truncated_two_bit)?
+        .try_into()
+        .map_err(|_| truncated_two_bit())?;
+    
+    let value = u32::from_le_bytes(bytes);
+    Ok(if swapped { value.swap_bytes() } else { value })
+}
+
+/// Read a block list (count, starts, sizes) as `(start, end)` runs
+fn read_two_bit_blocks(data: &[u8], pos: &mut usize, swapped: bool) -> EngineResult<Vec<(usize, usize)>> {
+    let count = read_u32_at(data, *pos, swapped)? as usize;
+    let starts_pos = *pos + 4;
+    let sizes_pos = starts_pos + 4 * count;
+    *pos = sizes_pos + 4 * count;
+    
+    let mut blocks = Vec::with_capacity(count.min(TWO_BIT_MAX_PREALLOCATION));
+    for i in 0..count {
+        let start = read_u32_at(data, starts_pos + 4 * i, swapped)? as usize;
+        let size = read_u32_at(data, sizes_pos + 4 * i, swapped)? as usize;
+        blocks.push((start, start + size));
+    }
+    
+    blocks.sort_unstable();
+    Ok(blocks)
+}
+
+fn truncated_two_bit() -> EngineError {
+    EngineError::InvalidSequenceData("Truncated 2bit file".to_string())
+}
+
+/// Supported sequence file formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileFormat {
+    /// FASTA format
+    Fasta,
+    /// FASTQ format
+    Fastq,
+}
+
+impl FileFormat {
+    /// Get the format name
+    pub fn name(&self) -> &'static str {
+        match self {
+            FileFormat::Fasta => "FASTA",
+            FileFormat::Fastq => "FASTQ",
+        }
+    }
+}
+
+/// Count the records in a sequence file without parsing them
+///
+/// FASTA records are counted by their header lines, FASTQ records by
+/// dividing the line count by four. The file is streamed, so this is
+/// cheap enough to run before a full parse (e.g. for progress reporting).
+pub fn count_records<P: AsRef<Path>>(path: P, format: FileFormat) -> EngineResult<usize> {
+    let mut reader = FastReader::new(path.as_ref(), None)?;
+    
+    match format {
+        FileFormat::Fasta => {
+            let mut count = 0;
+            for line_result in reader.read_lines() {
+                if line_result?.starts_with('>') {
+                    count += 1;
+                }
+            }
+            Ok(count)
+        },
+        FileFormat::Fastq => {
+            let mut line_count = 0;
+            for line_result in reader.read_lines() {
+                line_result?;
+                line_count += 1;
+            }
+            
+            if line_count % 4 != 0 {
+                return Err(EngineError::InvalidSequenceData(
+                    format!("FASTQ line count ({}) is not a multiple of 4", line_count)
+                ));
+            }
+            
+            Ok(line_count / 4)
+        },
+    }
+}
+
+/// Kinds of problems reported by [`validate`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationIssueKind {
+    /// Missing or malformed header line
+    BadHeader,
+    /// Missing or malformed FASTQ separator line
+    BadSeparator,
+    /// FASTA sequence line wrapped at a different width than the rest of the record
+    InconsistentLineLength,
+    /// Character that is not a valid residue
+    InvalidCharacter,
+    /// FASTQ quality string length differs from the sequence length
+    QualityLengthMismatch,
+    /// Record without any sequence
+    EmptyRecord,
+    /// FASTQ record truncated at end of file
+    IncompleteRecord,
+}
+
+/// A problem found while validating a sequence file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationIssue {
+    /// Line number (1-based) where the problem was found
+    pub line: usize,
+    /// Kind of problem
+    pub kind: ValidationIssueKind,
+    /// Human-readable description
+    pub message: String,
+}
+
+impl ValidationIssue {
+    fn new(line: usize, kind: ValidationIssueKind, message: String) -> Self {
+        Self { line, kind, message }
+    }
+}
+
+/// Report the first residue on each sequence line of a record that is not valid in its alphabet
+///
+/// Without an explicit alphabet the record's alphabet is detected from its
+/// residues, ignoring alignment gaps. A record that fits no alphabet is
+/// checked against the protein alphabet, the widest one.
+fn check_residues(lines: &[(usize, String)], alphabet: Option<&dyn Alphabet>, issues: &mut Vec<ValidationIssue>) {
+    let fallback;
+    let alphabet: &dyn Alphabet = match alphabet {
+        Some(alphabet) => alphabet,
+        None => {
+            let residues: Vec<u8> = lines
+                .iter()
+                .flat_map(|(_, line)| line.bytes())
+                .filter(|&b| !is_gap(b))
+                .collect();
+            if detect_alphabet(&residues).is_some() {
+                return;
+            }
+            fallback = ProteinAlphabet::default().with_gaps();
+            &fallback
+        },
+    };
+    
+    for (line_number, line) in lines {
+        if let Some((col, b)) = line.bytes().enumerate().find(|&(_, b)| !alphabet.is_valid_char(b)) {
+            issues.push(ValidationIssue::new(
+                *line_number,
+                ValidationIssueKind::InvalidCharacter,
+                format!("Invalid {} character {:?} at column {}", alphabet.name(), b as char, col + 1),
+            ));
+        }
+    }
+}
+
+/// Validate a sequence file, collecting every problem instead of stopping at the first
+///
+/// The file is streamed, so this is suitable for pre-flight checks on large
+/// uploads. An empty result means the file is well formed. I/O errors are
+/// still returned as `Err`.
+///
+/// Residues are checked against each record's detected alphabet; use
+/// [`validate_with_alphabet`] when the alphabet is known in advance.
+pub fn validate<P: AsRef<Path>>(path: P, format: FileFormat) -> EngineResult<Vec<ValidationIssue>> {
+    validate_records(path.as_ref(), format, None)
+}
+
+/// Validate a sequence file whose residues must all belong to `alphabet`
+///
+/// Wrap the alphabet with [`Alphabet::with_gaps`] to accept aligned records.
+pub fn validate_with_alphabet<P: AsRef<Path>>(
+    path: P,
+    format: FileFormat,
+    alphabet: &dyn Alphabet,
+) -> EngineResult<Vec<ValidationIssue>> {
+    validate_records(path.as_ref(), format, Some(alphabet))
+}
+
+fn validate_records(path: &Path, format: FileFormat, alphabet: Option<&dyn Alphabet>) -> EngineResult<Vec<ValidationIssue>> {
+    let mut reader = FastReader::new(path, None)?;
+    let mut issues = Vec::new();
+    
+    match format {
+        FileFormat::Fasta => {
+            // Header line of the current record, its line width and residue count
+            let mut header_line: Option<usize> = None;
+            let mut line_width: Option<usize> = None;
+            let mut last_line_short = false;
+            let mut residues = 0;
+            // Sequence lines of the current record, checked once its alphabet is known
+            let mut record_lines: Vec<(usize, String)> = Vec::new();
+            
+            for (index, line_result) in reader.read_lines().enumerate() {
+                let line = line_result?;
+                let line_number = index + 1;
+                
+                if line.is_empty() {
+                    continue;
+                }
+                
+                if let Some(header) = line.strip_prefix('>') {
+                    // Close the previous record
+                    check_residues(&record_lines, alphabet, &mut issues);
+                    record_lines.clear();
+                    if let Some(start) = header_line {
+                        if residues == 0 {
+                            issues.push(ValidationIssue::new(
+                                start,
+                                ValidationIssueKind::EmptyRecord,
+                                "Record has no sequence".to_string(),
+                            ));
+                        }
+                    }
+                    
+                    if header.split_whitespace().next().is_none() {
+                        issues.push(ValidationIssue::new(
+                            line_number,
+                            ValidationIssueKind::BadHeader,
+                            "Header has no identifier".to_string(),
+                        ));
+                    }
+                    
+                    header_line = Some(line_number);
+                    line_width = None;
+                    last_line_short = false;
+                    residues = 0;
+                    continue;
+                }
+                
+                if header_line.is_none() {
+                    issues.push(ValidationIssue::new(
+                        line_number,
+                        ValidationIssueKind::BadHeader,
+                        "Sequence data before the first '>' header".to_string(),
+                    ));
+                    // Treat the data as an anonymous record to avoid repeating the issue
+                    header_line = Some(line_number);
+                }
+                
+                // Every line but the last must share the record's line width
+                match line_width {
+                    None => line_width = Some(line.len()),
+                    Some(width) => {
+                        if last_line_short || line.len() > width {
+                            issues.push(ValidationIssue::new(
+                                line_number,
+                                ValidationIssueKind::InconsistentLineLength,
+                                format!("Line length {} does not match record line width {}", line.len(), width),
+                            ));
+                        }
+                        last_line_short = line.len() < width;
+                    },
+                }
+                
+                residues += line.len();
+                record_lines.push((line_number, line));
+            }
+            
+            check_residues(&record_lines, alphabet, &mut issues);
+            if let Some(start) = header_line {
+                if residues == 0 {
+                    issues.push(ValidationIssue::new(
+                        start,
+                        ValidationIssueKind::EmptyRecord,
+                        "Record has no sequence".to_string(),
+                    ));
+                }
+            }
+        },
+        FileFormat::Fastq => {
+            let mut line_count = 0;
+            let mut record_start = 0;
+            let mut seq_len = 0;
+            
+            for (index, line_result) in reader.read_lines().enumerate() {
+                let line = line_result?;
+                let line_number = index + 1;
+                line_count = line_number;
+                
+                match index % 4 {
+                    0 => {
+                        record_start = line_number;
+                        let valid = line
+                            .strip_prefix('@')
+                            .is_some_and(|header| header.split_whitespace().next().is_some());
+                        if !valid {
+                            issues.push(ValidationIssue::new(
+                                line_number,
+                                ValidationIssueKind::BadHeader,
+                                format!("Invalid FASTQ header: {}", line),
+                            ));
+                        }
+                    },
+                    1 => {
+                        seq_len = line.len();
+                        if line.is_empty() {
+                            issues.push(ValidationIssue::new(
+                                record_start,
+                                ValidationIssueKind::EmptyRecord,
+                                "Record has no sequence".to_string(),
+                            ));
+                        }
+                        check_residues(&[(line_number, line)], alphabet, &mut issues);
+                    },
+                    2 => {
+                        if !line.starts_with('+') {
+                            issues.push(ValidationIssue::new(
+                                line_number,
+                                ValidationIssueKind::BadSeparator,
+                                format!("Invalid FASTQ separator: {}", line),
+                            ));
+                        }
+                    },
+                    _ => {
+                        if line.len() != seq_len {
+                            issues.push(ValidationIssue::new(
+                                line_number,
+                                ValidationIssueKind::QualityLengthMismatch,
+                                format!(
+                                    "Quality length ({}) does not match sequence length ({})",
+                                    line.len(), seq_len
+                                ),
+                            ));
+                        }
+                    },
+                }
+            }
+            
+            if line_count % 4 != 0 {
+                issues.push(ValidationIssue::new(
+                    record_start,
+                    ValidationIssueKind::IncompleteRecord,
+                    "Incomplete FASTQ record at end of file".to_string(),
+                ));
+            }
+        },
+    }
+    
+    // Residues are only checked once a record is complete
+    issues.sort_by_key(|issue| issue.line);
+    Ok(issues)
+}
+
+/// Convert a sequence file to another format
+///
+/// The input format is detected from the file content. Records are streamed
+/// from the reader to the writer one at a time, so memory use does not grow
+/// with the file. Record ids and descriptions are preserved; quality scores
+/// are dropped when converting to FASTA. Converting a file without quality
+/// scores to FASTQ fails before anything is written.
+pub fn convert<P: AsRef<Path>>(input: P, output: P, to: FileFormat) -> EngineResult<()> {
+    let format = detect_format(input.as_ref())?;
+    
+    // Quality scores cannot be invented, so refuse up front
+    if to == FileFormat::Fastq && format != "FASTQ" {
+        return Err(EngineError::UnsupportedOperation(format!(
+            "Cannot convert {} to FASTQ: the input has no quality scores",
+            format
+        )));
+    }
+    
+    let records: Box<dyn Iterator<Item = EngineResult<SequenceRecord>>> = match format {
+        "FASTQ" => Box::new(FastqParser::new().reader(input.as_ref())?),
+        _ => Box::new(FastaParser::new().reader(input.as_ref())?),
+    };
+    
+    match to {
+        FileFormat::Fasta => {
+            let mut writer = FastaWriter::new().stream_writer(output)?;
+            for record in records {
+                writer.write_record(&record?)?;
+            }
+            writer.flush()
+        },
+        FileFormat::Fastq => {
+            let fastq = FastqWriter::new();
+            let mut writer = FastWriter::new(output, Some(fastq.buffer_size))?;
+            for record in records {
+                fastq.write_record_to(&mut writer, &record?)?;
+            }
+            writer.flush()?;
+            Ok(())
+        },
+    }
+}
+
+/// Detect the format of a sequence file based on its content
+pub fn detect_format<P: AsRef<Path>>(path: P) -> EngineResult<&'static str> {
+    let mut reader = FastReader::new(path.as_ref(), None)?;
+    
+    // Read the first record's worth of lines once, then classify them
+    let mut lines = Vec::with_capacity(4);
+    for line_result in reader.read_lines().take(4) {
+        lines.push(line_result?);
+    }
+    
+    if let Some(first_line) = lines.first() {
+        if first_line.starts_with('>') {
+            return Ok("FASTA");
+        } else if first_line.trim_end() == "##gff-version 3" || first_line.starts_with("##gff-version 3.") {
+            return Ok("GFF3");
+        } else if first_line.starts_with('@') {
+            // FASTQ needs a '+' separator on the third line and, when present,
+            // a quality line as long as the sequence
+            let separator_ok = lines.get(2).is_some_and(|line| line.starts_with('+'));
+            let quality_ok = match (lines.get(1), lines.get(3)) {
+                (Some(seq), Some(qual)) => seq.len() == qual.len(),
+                _ => true,
+            };
+            
+            if separator_ok && quality_ok {
+                return Ok("FASTQ");
+            }
+        }
+    }
+    
+    Err(EngineError::InvalidSequenceData(
+        "Could not determine file format".to_string()
+    ))
+}
+
+/// Create a parser for the specified format
+pub fn create_parser(format: &str) -> EngineResult<Box<dyn SequenceParser>> {
+    match format.to_uppercase().as_str() {
+        "FASTA" => Ok(Box::new(FastaParser::new())),
+        "FASTQ" => Ok(Box::new(FastqParser::new())),
+        _ => Err(EngineError::UnsupportedOperation(
+            format!("Unsupported format: {}", format)
+        )),
+    }
+}
+
+/// Create a writer for the specified format
+pub fn create_writer(format: &str) -> EngineResult<Box<dyn SequenceWriter>> {
+    match format.to_uppercase().as_str() {
+        "FASTA" => Ok(Box::new(FastaWriter::new())),
+        "FASTQ" => Ok(Box::new(FastqWriter::new())),
+        _ => Err(EngineError::UnsupportedOperation(
+            format!("Unsupported format: {}", format)
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::tempdir;
+    
+    #[test]
+    fn test_fasta_parsing() -> std::io::Result<()> {
+        // Create a temporary FASTA file
+        let dir = tempdir()?;
+        let file_path = dir.path().join("test.fasta");
+        
+        let fasta_content = ">seq1 First sequence\nACGTACGT\n>seq2 Second sequence\nGTACGTAC\n";
+        {
+            let mut file = std::fs::File::create(&file_path)?;
+            file.write_all(fasta_content.as_bytes())?;
+        }
+        
+        // Parse the file
+        let parser = FastaParser::new();
+        let records = parser.parse_file(&file_path).unwrap();
+        
+        // Check the parsed records
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].id, "seq1");
+        assert_eq!(records[0].description, Some("First sequence".to_string()));
+        assert_eq!(records[0].sequence_as_vec(), b"ACGTACGT");
+        
+        assert_eq!(records[1].id, "seq2");
+        assert_eq!(records[1].description, Some("Second sequence".to_string()));
+        assert_eq!(records[1].sequence_as_vec(), b"GTACGTAC");
+        
+        Ok(())
+    }
+    
+    #[test]
+    fn test_fasta_reader() -> std::io::Result<()> {
+        let dir = tempdir()?;
+        let file_path = dir.path().join("stream.fasta");
+        
+        let fasta_content = ">seq1 First sequence\nACGT\nACGT\n\n>empty\n>seq2\nGTAC\n";
+        std::fs::write(&file_path, fasta_content)?;
+        
+        // Streaming matches the bulk parser
+        let streamed: Vec<SequenceRecord> = FastaReader::open(&file_path)
+            .unwrap()
+            .collect::<EngineResult<_>>()
+            .unwrap();
+        let parsed = FastaParser::new().parse_file(&file_path).unwrap();
+        
+        assert_eq!(streamed.len(), 3);
+        assert_eq!(streamed.len(), parsed.len());
+        for (a, b) in streamed.iter().zip(parsed.iter()) {
+            assert_eq!(a.id, b.id);
+            assert_eq!(a.description, b.description);
+            assert_eq!(a.sequence_as_vec(), b.sequence_as_vec());
+        }
+        
+        // Data before the first header is an error, reported once
+        std::fs::write(&file_path, "ACGT\n>seq1\nACGT\n")?;
+        let mut reader = FastaReader::open(&file_path).unwrap();
+        assert!(reader.next().unwrap().is_err());
+        assert!(reader.next().is_none());
+        
+        Ok(())
+    }
+    
+    #[test]
+    fn test_fasta_empty_records() -> std::io::Result<()> {
+        let dir = tempdir()?;
+        let file_path = dir.path().join("placeholders.fasta");
+        
+        // Empty records between headers and at the end, with no trailing newline
+        let fasta_content = ">first\n>second desc\nACGT\n>last";
+        std::fs::write(&file_path, fasta_content)?;
+        
+        let parsed = FastaParser::new().parse_string(fasta_content).unwrap();
+        let from_file = FastaParser::new().parse_file(&file_path).unwrap();
+        let streamed: Vec<SequenceRecord> = FastaReader::open(&file_path)
+            .unwrap()
+            .collect::<EngineResult<_>>()
+            .unwrap();
+        
+        for records in [&parsed, &from_file, &streamed] {
+            let ids: Vec<&str> = records.iter().map(|record| record.id.as_str()).collect();
+            assert_eq!(ids, ["first", "second", "last"]);
+            assert!(records[0].is_empty());
+            assert_eq!(records[1].sequence_as_vec(), b"ACGT");
+            assert_eq!(records[1].description.as_deref(), Some("desc"));
+            assert!(records[2].is_empty());
+        }
+        
+        Ok(())
+    }
+    
+    #[test]
+    fn test_fasta_writing() -> std::io::Result<()> {
+        // Create records
+        let records = vec![
+            SequenceRecord::new(
+                "seq1".to_string(),
+                Some("First sequence".to_string()),
+                b"ACGTACGT".to_vec(),
+            ),
+            SequenceRecord::new(
+                "seq2".to_string(),
+                Some("Second sequence".to_string()),
+                b"GTACGTAC".to_vec(),
+            ),
+        ];
+        
+        // Create a temporary file
+        let dir = tempdir()?;
+        let file_path = dir.path().join("output.fasta");
+        
+        // Write the records
+        let writer = FastaWriter::new();
+        writer.write_file(&records, &file_path).unwrap();
+        
+        // Read the file and check the content
+        let content = std::fs::read_to_string(&file_path)?;
+        
+        assert!(content.contains(">seq1 First sequence"));
+        assert!(content.contains("ACGTACGT"));
+        assert!(content.contains(">seq2 Second sequence"));
+        assert!(content.contains("GTACGTAC"));
+        
+        Ok(())
+    }
+    
+    #[test]
+    fn test_fasta_line_width_round_trip() -> std::io::Result<()> {
+        let dir = tempdir()?;
+        let file_path = dir.path().join("wide.fasta");
+        let output_path = dir.path().join("copy.fasta");
+        
+        // Records wrapped at different widths
+        let content = format!(">wide\n{}\n{}\nAC\n>narrow desc\nACGTA\nCGT\n", "A".repeat(70), "C".repeat(70));
+        std::fs::write(&file_path, &content)?;
+        
+        // Widths are only recorded when asked for
+        let plain = FastaParser::new().parse_file(&file_path).unwrap();
+        assert!(plain[0].get_metadata(FASTA_LINE_WIDTH_KEY).is_none());
+        
+        let parser = FastaParser::new().with_line_width_metadata(true);
+        let records = parser.parse_file(&file_path).unwrap();
+        assert_eq!(records[0].get_metadata(FASTA_LINE_WIDTH_KEY), Some(&"70".to_string()));
+        assert_eq!(records[1].get_metadata(FASTA_LINE_WIDTH_KEY), Some(&"5".to_string()));
+        
+        // The string parser and streaming reader record the same widths
+        let from_string = parser.parse_string(&content).unwrap();
+        let streamed: Vec<SequenceRecord> = parser.reader(&file_path).unwrap().collect::<EngineResult<_>>().unwrap();
+        for other in [&from_string, &streamed] {
+            let widths: Vec<_> = other.iter().map(|r| r.get_metadata(FASTA_LINE_WIDTH_KEY).cloned()).collect();
+            assert_eq!(widths, vec![Some("70".to_string()), Some("5".to_string())]);
+        }
+        
+        // Blank and whitespace-only lines before the sequence don't count as a width
+        let spaced_path = dir.path().join("spaced.fasta");
+        let spaced = ">spaced\n\n   \n\t\nACGTA\nCG\n";
+        std::fs::write(&spaced_path, spaced)?;
+        let from_file = parser.parse_file(&spaced_path).unwrap();
+        let from_string = parser.parse_string(spaced).unwrap();
+        let streamed: Vec<SequenceRecord> = parser.reader(&spaced_path).unwrap().collect::<EngineResult<_>>().unwrap();
+        for other in [&from_file, &from_string, &streamed] {
+            assert_eq!(other[0].get_metadata(FASTA_LINE_WIDTH_KEY), Some(&"5".to_string()));
+            assert_eq!(other[0].sequence_as_vec(), b"ACGTACG");
+        }
+        
+        // Preserving widths reproduces the file byte for byte
+        let writer = FastaWriter::new().with_preserved_line_width(true);
+        writer.write_file(&records, &output_path).unwrap();
+        assert_eq!(std::fs::read_to_string(&output_path)?, content);
+        assert_eq!(writer.write_string(&records).unwrap(), content);
+        
+        // The default writer still wraps at 60
+        let rewrapped = FastaWriter::new().write_string(&records).unwrap();
+        assert!(rewrapped.lines().all(|line| line.len() <= 60 || line.starts_with('>')));
+        
+        Ok(())
+    }
+    
+    #[test]
+    fn test_fasta_unwrapped_and_crlf() -> std::io::Result<()> {
+        let dir = tempdir()?;
+        let output_path = dir.path().join("unwrapped.fasta");
+        
+        let long = "ACGT".repeat(50);
+        let records = vec![
+            SequenceRecord::new("long".to_string(), Some("200 bp".to_string()), long.as_bytes().to_vec()),
+            SequenceRecord::new("empty".to_string(), None, Vec::new()),
+            SequenceRecord::new("short".to_string(), None, b"GG".to_vec()),
+        ];
+        
+        // Width 0 writes each sequence on a single line
+        let writer = FastaWriter::with_line_width(0);
+        let expected = format!(">long 200 bp\n{}\n>empty\n>short\nGG\n", long);
+        assert_eq!(writer.write_string(&records).unwrap(), expected);
+        writer.write_file(&records, &output_path).unwrap();
+        assert_eq!(std::fs::read_to_string(&output_path)?, expected);
+        
+        // Windows line endings, including on headers
+        let crlf = FastaWriter::with_line_width(0).with_line_terminator(Terminator::Windows);
+        let content = crlf.write_string(&records).unwrap();
+        assert_eq!(content, expected.replace('\n', "\r\n"));
+        crlf.stream_writer(&output_path).unwrap().write_record(&records[2]).unwrap();
+        assert_eq!(std::fs::read_to_string(&output_path)?, ">short\r\nGG\r\n");
+        
+        // Both layouts parse back to the same records
+        for written in [expected, content] {
+            std::fs::write(&output_path, &written)?;
+            for parsed in [
+                FastaParser::new().parse_file(&output_path).unwrap(),
+                FastaParser::new().parse_string(&written).unwrap(),
+            ] {
+                assert_eq!(parsed.len(), records.len());
+                for (a, b) in parsed.iter().zip(&records) {
+                    assert_eq!(a.id, b.id);
+                    assert_eq!(a.description, b.description);
+                    assert_eq!(a.sequence_as_vec(), b.sequence_as_vec());
+                }
+            }
+        }
+        
+        Ok(())
+    }
+    
+    #[test]
+    fn test_fasta_stream_writer() -> std::io::Result<()> {
+        let dir = tempdir()?;
+        let input_path = dir.path().join("input.fasta");
+        let output_path = dir.path().join("output.fasta");
+        std::fs::write(&input_path, ">seq1 first\nACGTACGT\nAC\n>seq2\nGGGG\n")?;
+        
+        // Transform records while streaming from reader to writer
+        let writer_config = FastaWriter::with_line_width(4);
+        let mut expected = Vec::new();
+        {
+            let mut writer = writer_config.stream_writer(&output_path).unwrap();
+            for record in FastaReader::open(&input_path).unwrap() {
+                let mut record = record.unwrap();
+                record.id = format!("{}_copy", record.id);
+                writer.write_record(&record).unwrap();
+                expected.push(record);
+            }
+            assert_eq!(writer.records_written(), 2);
+        }
+        
+        // Dropping the writer flushes the same output as the batch writer
+        let written = std::fs::read_to_string(&output_path)?;
+        assert_eq!(written, writer_config.write_string(&expected).unwrap());
+        assert_eq!(written, ">seq1_copy first\nACGT\nACGT\nAC\n>seq2_copy\nGGGG\n");
+        
+        // Explicit flushes make records visible while the writer is open
+        let mut writer = FastaStreamWriter::create(&output_path).unwrap();
+        writer.write_record(&expected[1]).unwrap();
+        writer.flush().unwrap();
+        assert_eq!(std::fs::read_to_string(&output_path)?, ">seq2_copy\nGGGG\n");
+        
+        Ok(())
+    }
+    
+    #[test]
+    fn test_fasta_description_metadata() -> std::io::Result<()> {
+        let mut first = SequenceRecord::new("contig1".to_string(), Some("assembled contig".to_string()), b"ACGTACGT".to_vec());
+        first.add_metadata("coverage", "35.2");
+        first.add_metadata("note", "has \"quotes\" and spaces");
+        first.add_metadata("unlisted", "dropped");
+        let mut second = SequenceRecord::new("contig2".to_string(), None, b"GGCC".to_vec());
+        second.add_metadata("coverage", "12");
+        
+        let writer = FastaWriter::new().with_metadata_keys(&["coverage", "length", "note"]);
+        let output = writer.write_string(&[first, second]).unwrap();
+        assert!(output.starts_with(">contig1 assembled contig coverage=35.2 note=\"has \\\"quotes\\\" and spaces\"\n"));
+        assert!(output.contains(">contig2 coverage=12\n"));
+        
+        // Pairs move back into metadata, leaving the description text
+        let parser = FastaParser::new().with_description_metadata(true);
+        let records = parser.parse_string(&output).unwrap();
+        assert_eq!(records[0].description.as_deref(), Some("assembled contig"));
+        assert_eq!(records[0].get_metadata("coverage").unwrap(), "35.2");
+        assert_eq!(records[0].get_metadata("note").unwrap(), "has \"quotes\" and spaces");
+        assert!(records[0].get_metadata("unlisted").is_none());
+        assert_eq!(records[1].description, None);
+        assert_eq!(records[1].get_metadata("coverage").unwrap(), "12");
+        
+        // The streaming reader does the same
+        let dir = tempdir()?;
+        let file_path = dir.path().join("meta.fasta");
+        std::fs::write(&file_path, &output)?;
+        let streamed: Vec<SequenceRecord> = parser.reader(&file_path).unwrap().collect::<EngineResult<_>>().unwrap();
+        assert_eq!(streamed[0].get_metadata("note"), records[0].get_metadata("note"));
         
-        assert_eq!(records[1].id, "seq2");
-        assert_eq!(records[1].description, Some("Second sequence".to_string()));
-        assert_eq!(records[1].sequence_as_vec(), b"GTACGTAC");
+        // Without the option descriptions are untouched
+        let plain = FastaParser::new().parse_string(&output).unwrap();
+        assert!(plain[1].metadata.is_empty());
+        assert_eq!(plain[1].description.as_deref(), Some("coverage=12"));
+        
+        // Malformed tokens stay in the description
+        let (text, pairs) = split_description_metadata("x=\"open quote =y a=b");
+        assert_eq!(text.as_deref(), Some("x=\"open quote =y"));
+        assert_eq!(pairs, vec![("a".to_string(), "b".to_string())]);
+        
+        // Keys that could not be read back are rejected
+        let mut bad = SequenceRecord::new("seq".to_string(), None, b"ACGT".to_vec());
+        bad.add_metadata("read depth", "10");
+        let message = FastaWriter::new().with_metadata_keys(&["read depth"]).write_string(&[bad]).unwrap_err().to_string();
+        assert!(message.contains("read depth"), "{}", message);
         
         Ok(())
     }
     
     #[test]
-    fn test_fasta_writing() -> std::io::Result<()> {
-        // Create records
-        let records = vec![
-            SequenceRecord::new(
-                "seq1".to_string(),
-                Some("First sequence".to_string()),
-                b"ACGTACGT".to_vec(),
-            ),
-            SequenceRecord::new(
-                "seq2".to_string(),
-                Some("Second sequence".to_string()),
-                b"GTACGTAC".to_vec(),
-            ),
-        ];
+    #[cfg(feature = "parallel")]
+    fn test_fasta_parallel_parsing() -> std::io::Result<()> {
+        crate::engines::core::parallel::initialize_thread_pool();
         
-        // Create a temporary file
         let dir = tempdir()?;
-        let file_path = dir.path().join("output.fasta");
+        let file_path = dir.path().join("many.fasta");
         
-        // Write the records
-        let writer = FastaWriter::new();
-        writer.write_file(&records, &file_path).unwrap();
+        let mut content = String::new();
+        for i in 0..200 {
+            content.push_str(&format!(">seq{} record {}\n", i, i));
+            for _ in 0..(i % 5 + 1) {
+                content.push_str(&"ACGT".repeat(i % 7 + 1));
+                content.push('\n');
+            }
+        }
+        std::fs::write(&file_path, &content)?;
         
-        // Read the file and check the content
-        let content = std::fs::read_to_string(&file_path)?;
+        let parser = FastaParser::new();
+        let expected = parser.parse_file(&file_path).unwrap();
         
-        assert!(content.contains(">seq1 First sequence"));
-        assert!(content.contains("ACGTACGT"));
-        assert!(content.contains(">seq2 Second sequence"));
-        assert!(content.contains("GTACGTAC"));
+        // Small chunks force many boundaries, including ones on headers
+        for chunk_size in [1, 7, 64, 1000, 1 << 20] {
+            let records = parser.parse_file_parallel(&file_path, Some(chunk_size)).unwrap();
+            assert_eq!(records.len(), expected.len());
+            for (record, original) in records.iter().zip(&expected) {
+                assert_eq!(record.id, original.id);
+                assert_eq!(record.description, original.description);
+                assert_eq!(record.sequence_as_vec(), original.sequence_as_vec());
+            }
+        }
+        
+        let records = parser.parse_file_parallel(&file_path, None).unwrap();
+        assert_eq!(records.len(), 200);
         
         Ok(())
     }
@@ -856,6 +3664,121 @@ mod tests {
         Ok(())
     }
     
+    #[test]
+    fn test_fastq_reader() -> std::io::Result<()> {
+        let dir = tempdir()?;
+        let file_path = dir.path().join("stream.fastq");
+        
+        std::fs::write(&file_path, "@seq1 First\nACGT\n+\nHHHH\n@seq2\nGTAC\n+seq2\nIIII\n")?;
+        let records: Vec<SequenceRecord> = FastqReader::open(&file_path)
+            .unwrap()
+            .collect::<EngineResult<_>>()
+            .unwrap();
+        
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].id, "seq1");
+        assert_eq!(records[0].description, Some("First".to_string()));
+        assert_eq!(records[1].sequence_as_vec(), b"GTAC");
+        assert_eq!(records[1].quality_as_vec().unwrap(), b"IIII");
+        
+        // Errors name the record and line, and end the iteration
+        std::fs::write(&file_path, "@seq1\nACGT\n+\nHHHH\n@seq2\nGTAC\n+\nIII\n")?;
+        let mut reader = FastqReader::open(&file_path).unwrap();
+        assert!(reader.next().unwrap().is_ok());
+        let message = reader.next().unwrap().unwrap_err().to_string();
+        assert!(message.contains("seq2") && message.contains("line 8"));
+        assert!(reader.next().is_none());
+        
+        std::fs::write(&file_path, "@seq1\nACGT\n-\nHHHH\n")?;
+        let message = FastqReader::open(&file_path).unwrap().next().unwrap().unwrap_err().to_string();
+        assert!(message.contains("separator") && message.contains("line 3"));
+        
+        std::fs::write(&file_path, "@seq1\nACGT\n")?;
+        let message = FastqReader::open(&file_path).unwrap().next().unwrap().unwrap_err().to_string();
+        assert!(message.contains("Incomplete") && message.contains("seq1"));
+        
+        Ok(())
+    }
+    
+    #[test]
+    fn test_fastq_batches() -> std::io::Result<()> {
+        let dir = tempdir()?;
+        let file_path = dir.path().join("batches.fastq");
+        
+        let content: String = (0..5).map(|i| format!("@read{}\nACGT\n+\nIIII\n", i)).collect();
+        std::fs::write(&file_path, &content)?;
+        
+        // The final batch holds the remainder
+        let mut reader = FastqReader::open(&file_path).unwrap();
+        let batches: Vec<Vec<SequenceRecord>> = reader.batches(2).collect::<EngineResult<_>>().unwrap();
+        let sizes: Vec<usize> = batches.iter().map(|b| b.len()).collect();
+        assert_eq!(sizes, vec![2, 2, 1]);
+        assert_eq!(batches[2][0].id, "read4");
+        
+        let mut reader = FastqReader::open(&file_path).unwrap();
+        assert_eq!(reader.batches(5).count(), 1);
+        
+        // Errors inside a batch are reported instead of a short batch
+        std::fs::write(&file_path, format!("{}@bad\nACGT\n+\nII\n@read6\nACGT\n+\nIIII\n", content))?;
+        let mut reader = FastqReader::open(&file_path).unwrap();
+        let mut batches = reader.batches(4);
+        assert_eq!(batches.next().unwrap().unwrap().len(), 4);
+        let message = batches.next().unwrap().unwrap_err().to_string();
+        assert!(message.contains("bad"));
+        assert!(batches.next().is_none());
+        
+        Ok(())
+    }
+    
+    #[test]
+    fn test_record_memory_usage() {
+        let record = SequenceRecord::new("seq1".to_string(), None, vec![b'A'; 1000]);
+        let base = record.memory_usage();
+        assert!(base >= 1000 + std::mem::size_of::<SequenceRecord>());
+        
+        // Quality scores are included
+        let mut with_quality = SequenceRecord::with_quality("seq1".to_string(), None, vec![b'A'; 1000], vec![b'I'; 1000]);
+        assert!(with_quality.memory_usage() >= base + 1000);
+        
+        let before = with_quality.memory_usage();
+        with_quality.add_metadata("sample", "S1");
+        assert!(with_quality.memory_usage() >= before + 8);
+    }
+    
+    #[test]
+    fn test_fastq_line_endings() -> std::io::Result<()> {
+        let dir = tempdir()?;
+        let file_path = dir.path().join("mixed.fastq");
+        
+        // Mixed and doubled carriage returns parse the same on every path
+        let content = "@seq1 First\r\nACGT\r\n+\r\nHHHH\r\r\n@seq2\nGTAC\n+\nIIII\r\n";
+        std::fs::write(&file_path, content)?;
+        
+        let parser = FastqParser::new();
+        let from_file = parser.parse_file(&file_path).unwrap();
+        let from_string = parser.parse_string(content).unwrap();
+        let streamed: Vec<SequenceRecord> = FastqReader::open(&file_path)
+            .unwrap()
+            .collect::<EngineResult<_>>()
+            .unwrap();
+        
+        for records in [&from_file, &from_string, &streamed] {
+            assert_eq!(records.len(), 2);
+            assert_eq!(records[0].description, Some("First".to_string()));
+            assert_eq!(records[0].quality_as_vec().unwrap(), b"HHHH");
+            assert_eq!(records[1].quality_as_vec().unwrap(), b"IIII");
+        }
+        
+        // Leftover control characters get a targeted message
+        let message = parser.parse_string("@seq1\nACGT\n+\nHHHH\x0b\n").unwrap_err().to_string();
+        assert!(message.contains("line endings"), "{}", message);
+        
+        let message = parser.parse_string("@seq1\nACGT\n+\nHHHHH\n").unwrap_err().to_string();
+        assert!(!message.contains("line endings"), "{}", message);
+        
+        Ok(())
+    }
+    
     #[test]
     fn test_format_detection() -> std::io::Result<()> {
         // Create temporary files
@@ -879,6 +3802,504 @@ mod tests {
         assert_eq!(detect_format(&fasta_path).unwrap(), "FASTA");
         assert_eq!(detect_format(&fastq_path).unwrap(), "FASTQ");
         
+        // A FASTA description containing '@' is still FASTA
+        std::fs::write(&fasta_path, ">seq1 user@example.org\nACGT\n>seq2\n+CGT\n")?;
+        assert_eq!(detect_format(&fasta_path).unwrap(), "FASTA");
+        
+        // A FASTQ separator may repeat the record id
+        std::fs::write(&fastq_path, "@seq1 run@1\nACGT\n+seq1 run@1\nHHHH\n")?;
+        assert_eq!(detect_format(&fastq_path).unwrap(), "FASTQ");
+        
+        // An '@' header whose second line starts with '+' is not FASTQ
+        std::fs::write(&fastq_path, "@seq1\n+ACGT\nACGT\nHHHH\n")?;
+        assert!(detect_format(&fastq_path).is_err());
+        
+        Ok(())
+    }
+    
+    #[test]
+    fn test_validate() -> std::io::Result<()> {
+        let dir = tempdir()?;
+        let file_path = dir.path().join("upload.fasta");
+        
+        {
+            let mut file = std::fs::File::create(&file_path)?;
+            writeln!(file, ">seq1 good record")?;
+            writeln!(file, "ACGTACGT")?;
+            writeln!(file, "ACG7ACGT")?;
+            writeln!(file, "ACG")?;
+            writeln!(file, ">seq2 no sequence")?;
+            writeln!(file, ">seq3")?;
+            writeln!(file, "GGCC")?;
+        }
+        
+        // Both problems are reported, with their line numbers
+        let issues = validate(&file_path, FileFormat::Fasta).unwrap();
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].line, 3);
+        assert_eq!(issues[0].kind, ValidationIssueKind::InvalidCharacter);
+        assert_eq!(issues[1].line, 5);
+        assert_eq!(issues[1].kind, ValidationIssueKind::EmptyRecord);
+        
+        // A FASTQ quality length mismatch
+        let fastq_path = dir.path().join("upload.fastq");
+        {
+            let mut file = std::fs::File::create(&fastq_path)?;
+            writeln!(file, "@read1")?;
+            writeln!(file, "ACGT")?;
+            writeln!(file, "+")?;
+            writeln!(file, "III")?;
+        }
+        
+        let issues = validate(&fastq_path, FileFormat::Fastq).unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, 4);
+        assert_eq!(issues[0].kind, ValidationIssueKind::QualityLengthMismatch);
+        
+        Ok(())
+    }
+    
+    #[test]
+    fn test_validate_uses_record_alphabet() -> std::io::Result<()> {
+        let dir = tempdir()?;
+        let file_path = dir.path().join("mixed.fasta");
+        
+        {
+            let mut file = std::fs::File::create(&file_path)?;
+            writeln!(file, ">protein")?;
+            writeln!(file, "MKVLE*")?;
+            writeln!(file, ">aligned")?;
+            writeln!(file, "ACG--T")?;
+            writeln!(file, ">mixed T and U")?;
+            writeln!(file, "ACGTU")?;
+        }
+        
+        // Letters that fit no single alphabet are invalid
+        let issues = validate(&file_path, FileFormat::Fasta).unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, 6);
+        assert_eq!(issues[0].kind, ValidationIssueKind::InvalidCharacter);
+        assert!(issues[0].message.contains("column 5"));
+        
+        // A known alphabet rejects residues outside it
+        let dna = crate::modules::seq::alphabet::DNAAlphabet::default().with_gaps();
+        let issues = validate_with_alphabet(&file_path, FileFormat::Fasta, &dna).unwrap();
+        let lines: Vec<usize> = issues.iter().map(|issue| issue.line).collect();
+        assert_eq!(lines, vec![2, 6]);
+        assert!(issues.iter().all(|issue| issue.kind == ValidationIssueKind::InvalidCharacter));
+        
+        Ok(())
+    }
+    
+    #[test]
+    fn test_gff3_round_trip() -> std::io::Result<()> {
+        let content = "##gff-version 3\n\
+            ##gzip\n\
+            # a comment\n\
+            chr1\tRefSeq\tgene\t1000\t9000\t.\t+\t.\tID=gene1;Name=ABC1;Note=kinase%3B putative\n\
+            chr1\tRefSeq\tCDS\t1201\t3902\t0.5\t-\t2\tParent=gene1;ID=cds1\n\
+            ##FASTA\n\
+            >chr1\n\
+            ACGT\n";
+        
+        let features = Gff3Parser::new().parse_string(content).unwrap();
+        assert_eq!(features.len(), 2);
+        
+        let gene = &features[0];
+        assert_eq!(gene.seqid, "chr1");
+        assert_eq!(gene.feature_type, "gene");
+        assert_eq!((gene.start, gene.end), (1000, 9000));
+        assert_eq!(gene.strand, GffStrand::Forward);
+        assert_eq!(gene.score, None);
+        assert_eq!(gene.get_attribute("Note"), Some("kinase; putative"));
+        
+        let cds = &features[1];
+        assert_eq!(cds.score, Some(0.5));
+        assert_eq!(cds.strand, GffStrand::Reverse);
+        assert_eq!(cds.phase, Some(2));
+        
+        // Attribute order survives a round trip
+        let written = Gff3Writer::new().write_string(&features).unwrap();
+        assert!(written.contains("Parent=gene1;ID=cds1"));
+        assert_eq!(Gff3Parser::new().parse_string(&written).unwrap(), features);
+        
+        // Round trip through a file, which is detected as GFF3
+        let dir = tempdir()?;
+        let file_path = dir.path().join("annotations.gff3");
+        Gff3Writer::new().write_file(&features, &file_path).unwrap();
+        assert_eq!(detect_format(&file_path).unwrap(), "GFF3");
+        assert_eq!(Gff3Parser::new().parse_file(&file_path).unwrap(), features);
+        
+        // An escape at the very end of a value is decoded too
+        assert_eq!(gff_unescape("a%3Bb%3D"), "a;b=");
+        
+        // Multi-value attributes keep their literal commas
+        let content = "##gff-version 3\nchr1\tsrc\texon\t10\t20\t.\t+\t.\tID=e1;Parent=a,b\n";
+        let mut exons = Gff3Parser::new().parse_string(content).unwrap();
+        assert_eq!(exons[0].get_attribute_values("Parent"), vec!["a", "b"]);
+        let written = Gff3Writer::new().write_string(&exons).unwrap();
+        assert!(written.contains("Parent=a,b"));
+        assert_eq!(Gff3Parser::new().parse_string(&written).unwrap(), exons);
+        
+        exons[0].set_attribute_values("Parent", &["a", "b", "c"]);
+        assert_eq!(exons[0].get_attribute("Parent"), Some("a"));
+        assert!(exons[0].get_attribute_values("Name").is_empty());
+        
+        // Escaped commas stay inside a single value
+        let content = "##gff-version 3\nchr1\tsrc\tgene\t10\t20\t.\t+\t.\tID=g1;Note=a%2Cb\n";
+        let genes = Gff3Parser::new().parse_string(content).unwrap();
+        assert_eq!(genes[0].get_attribute_values("Note"), vec!["a,b"]);
+        let written = Gff3Writer::new().write_string(&genes).unwrap();
+        assert!(written.contains("Note=a%2Cb"), "{}", written);
+        assert_eq!(Gff3Parser::new().parse_string(&written).unwrap(), genes);
+        
+        // Malformed lines are reported with their line number
+        let err = Gff3Parser::new().parse_string("##gff-version 3\nchr1\tsrc\tgene\t10\n").unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+        
+        Ok(())
+    }
+    
+    const GENBANK_RECORD: &str = "\
+LOCUS       TEST0001                  72 bp    DNA     linear   BCT 01-JAN-2020
+DEFINITION  Synthetic test construct with a split
+            coding sequence.
+ACCESSION   TEST0001
+VERSION     TEST0001.1
+SOURCE      Escherichia coli
+  ORGANISM  Escherichia coli
+            Bacteria; Proteobacteria.
+FEATURES             Location/Qualifiers
+     source          1..72
+                     /organism=\"Escherichia coli\"
+     gene            <3..>30
+                     /gene=\"abcX\"
+     CDS             join(3..11,
+                     20..30)
+                     /gene=\"abcX\"
+                     /note=\"a note that spans
+                     two lines\"
+                     /translation=\"MKV
+                     LA\"
+     gene            complement(40..60)
+                     /gene=\"abcY\"
+                     /pseudo
+     CDS             complement(join(40..45,50..60))
+                     /product=\"say \"\"hi\"\"\"
+ORIGIN      
+        1 atgcatgcat gcatgcatgc atgcatgcat gcatgcatgc atgcatgcat gcatgcatgc
+       61 atgcatgcat gc
+//
+";
+    
+    #[test]
+    fn test_genbank_parsing() -> std::io::Result<()> {
+        let records = GenBankParser::new().parse_string(GENBANK_RECORD).unwrap();
+        assert_eq!(records.len(), 1);
+        
+        // Header fields
+        let record = &records[0];
+        assert_eq!(record.name, "TEST0001");
+        assert_eq!(record.length, 72);
+        assert_eq!(record.molecule_type, "DNA");
+        assert_eq!(record.topology.as_deref(), Some("linear"));
+        assert_eq!(record.definition.as_deref(), Some("Synthetic test construct with a split coding sequence."));
+        assert_eq!(record.accession.as_deref(), Some("TEST0001"));
+        assert_eq!(record.organism.as_deref(), Some("Escherichia coli"));
+        assert_eq!(record.sequence.len(), 72);
+        assert!(record.sequence.starts_with(b"atgcatgcat"));
+        
+        // Features, locations and qualifiers
+        let types: Vec<&str> = record.features.iter().map(|f| f.feature_type.as_str()).collect();
+        assert_eq!(types, vec!["source", "gene", "CDS", "gene", "CDS"]);
+        assert_eq!(record.features[1].ranges, vec![(3, 30)]);
+        
+        let cds = &record.features[2];
+        assert_eq!(cds.location, "join(3..11,20..30)");
+        assert_eq!(cds.ranges, vec![(3, 11), (20, 30)]);
+        assert_eq!(cds.strand, GffStrand::Forward);
+        assert_eq!(cds.get_qualifier("note"), Some("a note that spans two lines"));
+        assert_eq!(cds.get_qualifier("translation"), Some("MKVLA"));
+        
+        let gene = &record.features[3];
+        assert_eq!((gene.ranges.clone(), gene.strand), (vec![(40, 60)], GffStrand::Reverse));
+        assert_eq!(gene.get_qualifier("pseudo"), Some(""));
+        
+        let reverse_cds = &record.features[4];
+        assert_eq!(reverse_cds.ranges, vec![(50, 60), (40, 45)]);
+        assert_eq!(reverse_cds.get_qualifier("product"), Some("say \"hi\""));
+        
+        // Features convert to GFF, one per range
+        let gff = cds.to_gff_features("TEST0001");
+        assert_eq!(gff.len(), 2);
+        assert_eq!((gff[1].start, gff[1].end, gff[1].strand), (20, 30, GffStrand::Forward));
+        assert_eq!(gff[1].get_attribute("gene"), Some("abcX"));
+        
+        let sequence_record = record.to_sequence_record();
+        assert_eq!(sequence_record.id, "TEST0001");
+        assert_eq!(sequence_record.get_metadata("organism"), Some(&"Escherichia coli".to_string()));
+        
+        // Files parse the same, including several records
+        let dir = tempdir()?;
+        let file_path = dir.path().join("test.gb");
+        std::fs::write(&file_path, format!("{}{}", GENBANK_RECORD, GENBANK_RECORD))?;
+        let from_file = GenBankParser::new().parse_file(&file_path).unwrap();
+        assert_eq!(from_file, vec![record.clone(), record.clone()]);
+        
+        // A sequence that disagrees with the LOCUS length is an error
+        let truncated = GENBANK_RECORD.replace("       61 atgcatgcat gc\n", "");
+        assert!(GenBankParser::new().parse_string(&truncated).is_err());
+        
+        // Unsupported locations keep the feature and its raw text, without ranges
+        let remote = GENBANK_RECORD.replace(
+            "     gene            <3..>30\n",
+            "     misc_feature    J00194.1:100..202\n     variation       one-of(3,5)\n     gene            <3..>30\n",
+        );
+        let record = &GenBankParser::new().parse_string(&remote).unwrap()[0];
+        assert_eq!(record.features.len(), 7);
+        let misc = &record.features[1];
+        assert_eq!((misc.feature_type.as_str(), misc.location.as_str()), ("misc_feature", "J00194.1:100..202"));
+        assert!(misc.ranges.is_empty());
+        assert_eq!(misc.strand, GffStrand::Unknown);
+        assert!(!misc.is_location_parsed());
+        assert!(misc.location_error.as_deref().is_some_and(|err| err.contains("J00194.1")), "{:?}", misc.location_error);
+        let variation = &record.features[2];
+        assert_eq!(variation.location, "one-of(3,5)");
+        assert!(variation.ranges.is_empty());
+        assert!(!variation.is_location_parsed());
+        assert_eq!(record.features[3].ranges, vec![(3, 30)]);
+        assert!(record.features.iter().filter(|f| f.is_location_parsed()).all(|f| !f.ranges.is_empty()));
+        
+        // Non-ASCII text around column 22 is not split mid-character
+        let accented = GENBANK_RECORD.replace(
+            "                     two lines\"\n",
+            "                     caf\u{e9} cr\u{e8}me\"\n",
+        );
+        let record = &GenBankParser::new().parse_string(&accented).unwrap()[0];
+        assert_eq!(record.features[2].get_qualifier("note"), Some("a note that spans caf\u{e9} cr\u{e8}me"));
+        let misaligned = GENBANK_RECORD.replace("                     two lines", "                    \u{e9}two lines");
+        assert!(GenBankParser::new().parse_string(&misaligned).is_err());
+        
+        Ok(())
+    }
+    
+    #[test]
+    fn test_genbank_locations() {
+        assert_eq!(parse_genbank_location("7").unwrap(), (vec![(7, 7)], GffStrand::Forward));
+        assert_eq!(parse_genbank_location("<1..>200").unwrap(), (vec![(1, 200)], GffStrand::Forward));
+        assert_eq!(
+            parse_genbank_location("join(complement(5..10),complement(1..3))").unwrap(),
+            (vec![(5, 10), (1, 3)], GffStrand::Reverse)
+        );
+        assert_eq!(
+            parse_genbank_location("order(1..2,complement(5..6))").unwrap(),
+            (vec![(1, 2), (5, 6)], GffStrand::Unknown)
+        );
+        
+        assert!(parse_genbank_location("J00194.1:100..202").is_err());
+        assert!(parse_genbank_location("10..5").is_err());
+        assert!(parse_genbank_location("join(1..2").is_err());
+    }
+    
+    #[test]
+    fn test_bed_intervals() -> std::io::Result<()> {
+        let content = "track name=test\n\
+            chr1\t2\t6\n\
+            # comment\n\
+            chr1\t0\t3\tfirst\t960\t-\n\
+            chr1\t4\t4\tempty\t.\t+\n";
+        
+        let dir = tempdir()?;
+        let file_path = dir.path().join("regions.bed");
+        std::fs::write(&file_path, content)?;
+        
+        let intervals = BedParser::new().parse_file(&file_path).unwrap();
+        assert_eq!(intervals, BedParser::new().parse_string(content).unwrap());
+        assert_eq!(intervals.len(), 3);
+        assert_eq!((intervals[0].start, intervals[0].end), (2, 6));
+        assert_eq!(intervals[0].name, None);
+        assert_eq!(intervals[0].strand, GffStrand::Unstranded);
+        assert_eq!(intervals[1].name, Some("first".to_string()));
+        assert_eq!(intervals[1].score, Some(960.0));
+        assert_eq!(intervals[1].strand, GffStrand::Reverse);
+        assert!(intervals[2].is_empty());
+        
+        // Half-open coordinates, reverse-complementing the '-' strand
+        let record = SequenceRecord::new("chr1".to_string(), None, b"AACGTTGA".to_vec());
+        let extracted = extract_intervals(&record, &intervals).unwrap();
+        assert_eq!(extracted, vec![b"CGTT".to_vec(), b"GTT".to_vec(), Vec::new()]);
+        
+        // Out-of-range and mismatched intervals are rejected
+        let parser = BedParser::new();
+        assert!(extract_intervals(&record, &parser.parse_string("chr1\t4\t9\n").unwrap()).is_err());
+        assert!(extract_intervals(&record, &parser.parse_string("chr2\t0\t1\n").unwrap()).is_err());
+        
+        // Reversed coordinates are reported with their line number
+        let err = parser.parse_string("chr1\t0\t1\nchr1\t5\t3\n").unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+        
+        // Only whole `track`/`browser` tokens mark header lines
+        let intervals = parser.parse_string("browser position chr1:1-10\ntrack\nbrowser\tfull\ntrackA\t1\t2\nbrowser_2\t3\t4\n").unwrap();
+        assert_eq!(intervals.len(), 2);
+        assert_eq!(intervals[0].chrom, "trackA");
+        assert_eq!(intervals[1].chrom, "browser_2");
+        
+        Ok(())
+    }
+    
+    /// Encode sequences as a version 0, little-endian `.2bit` file
+    fn encode_two_bit(sequences: &[(&str, &[u8])]) -> Vec<u8> {
+        let push_u32 = |out: &mut Vec<u8>, value: usize| out.extend_from_slice(&(value as u32).to_le_bytes());
+        let runs = |seq: &[u8], pred: &dyn Fn(u8) -> bool| {
+            let mut runs = Vec::new();
+            let mut i = 0;
+            while i < seq.len() {
+                if pred(seq[i]) {
+                    let start = i;
+                    while i < seq.len() && pred(seq[i]) {
+                        i += 1;
+                    }
+                    runs.push((start, i - start));
+                } else {
+                    i += 1;
+                }
+            }
+            runs
+        };
+        
+        let mut records = Vec::new();
+        for &(_, seq) in sequences {
+            let mut record = Vec::new();
+            push_u32(&mut record, seq.len());
+            for blocks in [runs(seq, &|b| b.eq_ignore_ascii_case(&b'N')), runs(seq, &|b| b.is_ascii_lowercase())] {
+                push_u32(&mut record, blocks.len());
+                blocks.iter().for_each(|&(start, _)| push_u32(&mut record, start));
+                blocks.iter().for_each(|&(_, size)| push_u32(&mut record, size));
+            }
+            push_u32(&mut record, 0);
+            for chunk in seq.chunks(4) {
+                let mut byte = 0u8;
+                for (i, &base) in chunk.iter().enumerate() {
+                    let code = TWO_BIT_BASES.iter().position(|&b| b == base.to_ascii_uppercase()).unwrap_or(0);
+                    byte |= (code as u8) << (6 - 2 * i);
+                }
+                record.push(byte);
+            }
+            records.push(record);
+        }
+        
+        let mut out = Vec::new();
+        push_u32(&mut out, TWO_BIT_SIGNATURE as usize);
+        push_u32(&mut out, 0);
+        push_u32(&mut out, sequences.len());
+        push_u32(&mut out, 0);
+        
+        let index_len: usize = sequences.iter().map(|(name, _)| 1 + name.len() + 4).sum();
+        let mut offset = out.len() + index_len;
+        for (&(name, _), record) in sequences.iter().zip(&records) {
+            out.push(name.len() as u8);
+            out.extend_from_slice(name.as_bytes());
+            push_u32(&mut out, offset);
+            offset += record.len();
+        }
+        records.iter().for_each(|record| out.extend_from_slice(record));
+        out
+    }
+    
+    #[test]
+    fn test_two_bit_reader() -> std::io::Result<()> {
+        let chr1: &[u8] = b"ACGTNNNNNacgtacGGTTCAnnAC";
+        let chr2: &[u8] = b"TTTTGGGGC";
+        
+        let dir = tempdir()?;
+        let file_path = dir.path().join("genome.2bit");
+        std::fs::write(&file_path, encode_two_bit(&[("chr1", chr1), ("chr2", chr2)]))?;
+        
+        let reader = TwoBitReader::open(&file_path).unwrap();
+        assert_eq!(reader.names(), vec!["chr1", "chr2"]);
+        
+        // Full sequences restore N runs and soft-masking
+        let seq = reader.sequence("chr1").unwrap();
+        assert_eq!(seq.len(), chr1.len());
+        assert_eq!(seq.subsequence(0, chr1.len()), chr1);
+        assert_eq!(reader.read_record("chr2").unwrap().sequence_as_vec(), chr2);
+        
+        // Random access across block and byte boundaries
+        for (start, end) in [(3, 7), (6, 12), (10, 19), (20, 25), (5, 5)] {
+            assert_eq!(seq.subsequence(start, end), &chr1[start..end], "{}..{}", start, end);
+        }
+        
+        assert!(reader.sequence("chrM").is_err());
+        
+        // Other files are rejected by their signature
+        std::fs::write(&file_path, b">seq1\nACGT\n")?;
+        assert!(TwoBitReader::open(&file_path).is_err());
+        
+        // A header claiming billions of sequences fails as truncated
+        let mut header = encode_two_bit(&[]);
+        header[8..12].copy_from_slice(&u32::MAX.to_le_bytes());
+        std::fs::write(&file_path, header)?;
+        assert!(TwoBitReader::open(&file_path).is_err());
+        
+        Ok(())
+    }
+    
+    #[test]
+    fn test_convert() -> std::io::Result<()> {
+        let dir = tempdir()?;
+        let fastq_path = dir.path().join("reads.fastq");
+        let fasta_path = dir.path().join("reads.fasta");
+        
+        {
+            let mut file = std::fs::File::create(&fastq_path)?;
+            writeln!(file, "@read1 first read")?;
+            writeln!(file, "ACGTACGT")?;
+            writeln!(file, "+")?;
+            writeln!(file, "IIIIIIII")?;
+            writeln!(file, "@read2 second read")?;
+            writeln!(file, "GGCCTTAA")?;
+            writeln!(file, "+")?;
+            writeln!(file, "HHHHHHHH")?;
+        }
+        
+        // FASTQ to FASTA keeps ids and descriptions
+        convert(&fastq_path, &fasta_path, FileFormat::Fasta).unwrap();
+        let records = FastaParser::new().parse_file(&fasta_path).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].id, "read1");
+        assert_eq!(records[0].description, Some("first read".to_string()));
+        assert_eq!(records[0].sequence_as_vec(), b"ACGTACGT");
+        assert_eq!(records[1].id, "read2");
+        assert_eq!(records[1].description, Some("second read".to_string()));
+        assert!(records[1].quality.is_none());
+        
+        // FASTA to FASTQ fails without writing output
+        let fastq_out = dir.path().join("out.fastq");
+        assert!(convert(&fasta_path, &fastq_out, FileFormat::Fastq).is_err());
+        assert!(!fastq_out.exists());
+        
+        // FASTQ to FASTQ streams records through unchanged
+        convert(&fastq_path, &fastq_out, FileFormat::Fastq).unwrap();
+        assert_eq!(std::fs::read(&fastq_out)?, std::fs::read(&fastq_path)?);
+        
+        Ok(())
+    }
+    
+    #[test]
+    fn test_count_records() -> std::io::Result<()> {
+        let dir = tempdir()?;
+        let fasta_path = dir.path().join("count.fasta");
+        let fastq_path = dir.path().join("count.fastq");
+        let bad_fastq_path = dir.path().join("bad.fastq");
+        
+        std::fs::write(&fasta_path, ">seq1\nACGT\nACGT\n>seq2\nGG\n>seq3\nTT\n")?;
+        std::fs::write(&fastq_path, "@r1\nACGT\n+\nHHHH\n@r2\nGTAC\n+\nIIII\n")?;
+        std::fs::write(&bad_fastq_path, "@r1\nACGT\n+\nHHHH\n@r2\nGTAC\n")?;
+        
+        assert_eq!(count_records(&fasta_path, FileFormat::Fasta).unwrap(), 3);
+        assert_eq!(count_records(&fastq_path, FileFormat::Fastq).unwrap(), 2);
+        assert!(count_records(&bad_fastq_path, FileFormat::Fastq).is_err());
+        
         Ok(())
     }
     
@@ -962,6 +4383,9 @@ mod tests {
         let sub = record.subsequence(2, 6);
         assert_eq!(sub, b"GTAC");
         
+        // Test checksum
+        assert_eq!(record.checksum(ChecksumAlgorithm::Md5), "cc0af3a4fedb18378b4b57b98068e69f");
+        
         // Test metadata
         record.add_metadata("source", "test data");
         record.add_metadata("date", "2023-01-01");
@@ -970,4 +4394,166 @@ mod tests {
         assert_eq!(record.get_metadata("date"), Some(&"2023-01-01".to_string()));
         assert_eq!(record.get_metadata("missing"), None);
     }
+    
+    #[test]
+    fn test_quality_decoding() {
+        let record = SequenceRecord::with_quality(
+            "read1".to_string(),
+            None,
+            b"ACGT".to_vec(),
+            b"!+5I".to_vec(),
+        );
+        
+        assert_eq!(record.phred_scores(33), Some(vec![0, 10, 20, 40]));
+        assert_eq!(record.mean_quality(), Some(17.5));
+        
+        // Decoding with the wrong offset would go negative and is rejected
+        assert_eq!(record.phred_scores(64), None);
+        
+        // Records without quality have no scores
+        let fasta = SequenceRecord::new("seq1".to_string(), None, b"ACGT".to_vec());
+        assert_eq!(fasta.phred_scores(33), None);
+        assert_eq!(fasta.mean_quality(), None);
+        
+        // Encoding detection from the observed range
+        assert_eq!(detect_quality_encoding(b"!+5I"), Some(QualityEncoding::Phred33));
+        assert_eq!(detect_quality_encoding(b"@JTh"), Some(QualityEncoding::Phred64));
+        assert_eq!(detect_quality_encoding(b"FFJJ"), Some(QualityEncoding::Phred33));
+        assert_eq!(detect_quality_encoding(b"#h"), None);
+        assert_eq!(detect_quality_encoding(b""), None);
+        
+        let old_illumina = SequenceRecord::with_quality("read2".to_string(), None, b"ACGT".to_vec(), b"@JTh".to_vec());
+        assert_eq!(old_illumina.mean_quality(), Some(17.5));
+    }
+    
+    #[test]
+    fn test_trim_quality() {
+        // Phred+33: '#' = Q2, 'I' = Q40, '5' = Q20
+        let mut record = SequenceRecord::with_quality(
+            "read1".to_string(),
+            Some("sample".to_string()),
+            b"NNACGTACGTAN".to_vec(),
+            b"##IIII5IIII#".to_vec(),
+        );
+        record.add_metadata("lane", "1");
+        
+        let trimmed = record.trim_quality(20, 33);
+        assert_eq!(trimmed.sequence_as_vec(), b"ACGTACGTA");
+        assert_eq!(trimmed.quality_as_vec().unwrap(), b"IIII5IIII");
+        assert_eq!(trimmed.id, "read1");
+        assert_eq!(trimmed.description, Some("sample".to_string()));
+        assert_eq!(trimmed.get_metadata("lane"), Some(&"1".to_string()));
+        
+        // The 3' cut falls where the running sum peaks
+        let record = SequenceRecord::with_quality("read2".to_string(), None, b"ACGTACG".to_vec(), b"IIII##5".to_vec());
+        assert_eq!(record.trim_quality(30, 33).sequence_as_vec(), b"ACGT");
+        
+        // Entirely low-quality reads become empty without panicking
+        let record = SequenceRecord::with_quality("read3".to_string(), None, b"ACGT".to_vec(), b"####".to_vec());
+        let trimmed = record.trim_quality(20, 33);
+        assert!(trimmed.is_empty());
+        assert_eq!(trimmed.quality_as_vec().unwrap(), b"");
+        
+        // Records without quality are left as they are
+        let record = SequenceRecord::new("seq1".to_string(), None, b"ACGT".to_vec());
+        assert_eq!(record.trim_quality(20, 33).sequence_as_vec(), b"ACGT");
+        
+        // Mismatched lengths are trimmed over the bases that have a score
+        let record = SequenceRecord::with_quality("read4".to_string(), None, b"ACGT".to_vec(), b"IIIIII".to_vec());
+        let trimmed = record.trim_quality(20, 33);
+        assert_eq!(trimmed.sequence_as_vec(), b"ACGT");
+        assert_eq!(trimmed.quality_as_vec().unwrap(), b"IIII");
+        
+        let record = SequenceRecord::with_quality("read5".to_string(), None, b"ACGTAC".to_vec(), b"II#".to_vec());
+        let trimmed = record.trim_quality(20, 33);
+        assert_eq!(trimmed.sequence_as_vec(), b"AC");
+        assert_eq!(trimmed.quality_as_vec().unwrap(), b"II");
+    }
+    
+    #[test]
+    fn test_trim_primers() {
+        // Read layout: junk + forward primer + insert + revcomp(reverse primer) + junk
+        let fwd = b"ACGTTG";
+        let rev = b"GGCATC"; // reverse complement is GATGCC
+        let read = b"TTACGTTGCCCCAAAATTTTGATGCCAA";
+        let qual = b"0123456789ABCDEFGHIJKLMNOPQR";
+        
+        let record = SequenceRecord::with_quality(
+            "read1".to_string(),
+            Some("amplicon".to_string()),
+            read.to_vec(),
+            qual.to_vec(),
+        );
+        
+        // Exact primer match
+        let trimmed = record.trim_primers(fwd, rev, 0).unwrap();
+        assert_eq!(trimmed.id, "read1");
+        assert_eq!(trimmed.description, Some("amplicon".to_string()));
+        assert_eq!(trimmed.sequence_as_vec(), b"CCCCAAAATTTT");
+        assert_eq!(trimmed.quality_as_vec().unwrap(), b"89ABCDEFGHIJ");
+        
+        // One mismatch in the forward primer is tolerated only when allowed
+        let fwd_mismatch = b"ACGTAG";
+        assert!(record.trim_primers(fwd_mismatch, rev, 0).is_none());
+        let trimmed = record.trim_primers(fwd_mismatch, rev, 1).unwrap();
+        assert_eq!(trimmed.sequence_as_vec(), b"CCCCAAAATTTT");
+        
+        // Missing reverse primer
+        assert!(record.trim_primers(fwd, b"AAAAAA", 0).is_none());
+        
+        // Primer-like sequence in the middle of a long insert is ignored
+        let insert = b"CCCCAAAATTTTGATGCCACGTTGCCCCAAAATTTT";
+        let read = [&fwd[..], insert, b"GATGCC"].concat();
+        let record = SequenceRecord::new("read2".to_string(), None, read);
+        assert_eq!(record.trim_primers(fwd, rev, 0).unwrap().sequence_as_vec(), insert);
+        
+        let without_rev = SequenceRecord::new("read3".to_string(), None, [&fwd[..], insert].concat());
+        assert!(without_rev.trim_primers(fwd, rev, 0).is_none());
+        let without_fwd = SequenceRecord::new("read4".to_string(), None, [&insert[..], b"GATGCC"].concat());
+        assert!(without_fwd.trim_primers(fwd, rev, 0).is_none());
+    }
+    
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let mut record = SequenceRecord::with_quality(
+            "read1".to_string(),
+            Some("sample read".to_string()),
+            b"ACGTN".to_vec(),
+            b"IIII#".to_vec(),
+        );
+        record.add_metadata("sample", "S1");
+        
+        // Sequence and quality are plain strings
+        let json = serde_json::to_string(&record).unwrap();
+        assert!(json.contains("\"sequence\":\"ACGTN\""));
+        assert!(json.contains("\"quality\":\"IIII#\""));
+        
+        let decoded: SequenceRecord = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.id, "read1");
+        assert_eq!(decoded.description.as_deref(), Some("sample read"));
+        assert_eq!(decoded.sequence_as_vec(), b"ACGTN");
+        assert_eq!(decoded.quality_as_vec().unwrap(), b"IIII#");
+        assert_eq!(decoded.get_metadata("sample").map(String::as_str), Some("S1"));
+        
+        // Optional fields may be omitted
+        let decoded: SequenceRecord = serde_json::from_str(r#"{"id":"seq2","sequence":"GGCC"}"#).unwrap();
+        assert_eq!(decoded.sequence_as_vec(), b"GGCC");
+        assert!(decoded.quality.is_none());
+        
+        // GFF features derive their impls
+        let feature = GffFeature {
+            seqid: "chr1".to_string(),
+            source: "test".to_string(),
+            feature_type: "gene".to_string(),
+            start: 10,
+            end: 200,
+            score: None,
+            strand: GffStrand::Reverse,
+            phase: None,
+            attributes: vec![("ID".to_string(), vec!["gene1".to_string()])],
+        };
+        let json = serde_json::to_string(&feature).unwrap();
+        assert_eq!(serde_json::from_str::<GffFeature>(&json).unwrap(), feature);
+    }
 }
\ No newline at end of file
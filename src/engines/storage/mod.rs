@@ -5,8 +5,8 @@
 
 pub mod formats;
 
-use crate::engines::EngineResult;
-use crate::engines::core::memory::MemoryMapped;
+use crate::engines::{EngineError, EngineResult};
+use crate::engines::core::memory::{MemoryMapped, PackedDnaStorage};
 use std::path::Path;
 
 /// Storage mode for sequence data
@@ -18,6 +18,8 @@ pub enum StorageMode {
     MemoryMapped,
     /// On-demand loading (sequence loaded in chunks as needed)
     OnDemand,
+    /// Packed in-memory storage (2 bits per DNA base)
+    Packed,
 }
 
 impl Default for StorageMode {
@@ -39,6 +41,14 @@ pub trait StorableSequence: Send + Sync {
     /// Get a subsequence as a vector
     fn subsequence(&self, start: usize, end: usize) -> Vec<u8>;
     
+    /// Get a subsequence, reporting storage errors instead of hiding them
+    ///
+    /// Storage that holds its data can't fail, so the default delegates to
+    /// `subsequence`; backends that read lazily override it.
+    fn try_subsequence(&self, start: usize, end: usize) -> EngineResult<Vec<u8>> {
+        Ok(self.subsequence(start, end))
+    }
+    
     /// Get a slice of the sequence
     fn as_slice(&self) -> Option<&[u8]>;
     
@@ -104,6 +114,28 @@ impl StorableSequence for InMemoryStorage {
     }
 }
 
+impl StorableSequence for PackedDnaStorage {
+    fn len(&self) -> usize {
+        PackedDnaStorage::len(self)
+    }
+    
+    fn subsequence(&self, start: usize, end: usize) -> Vec<u8> {
+        self.unpack_range(start, end)
+    }
+    
+    fn as_slice(&self) -> Option<&[u8]> {
+        None // Bases must be decoded before they can be read
+    }
+    
+    fn storage_mode(&self) -> StorageMode {
+        StorageMode::Packed
+    }
+    
+    fn memory_usage(&self) -> usize {
+        PackedDnaStorage::memory_usage(self)
+    }
+}
+
 /// Memory-mapped sequence storage
 #[derive(Debug)]
 pub struct MemoryMappedStorage {
@@ -161,6 +193,8 @@ pub struct OnDemandStorage {
     chunk_size: usize,
     /// Currently loaded chunk
     current_chunk: Option<(usize, Vec<u8>)>,
+    /// Byte returned by `subsequence` for data that could not be read
+    placeholder: u8,
 }
 
 impl OnDemandStorage {
@@ -171,9 +205,46 @@ impl OnDemandStorage {
             length,
             chunk_size,
             current_chunk: None,
+            placeholder: b'N',
         })
     }
     
+    /// Set the byte `subsequence` substitutes for unreadable data
+    ///
+    /// Defaults to `N`, which suits nucleotides but is a valid amino acid;
+    /// use `X` for proteins, or `try_subsequence` to get the error instead.
+    pub fn with_placeholder(mut self, placeholder: u8) -> Self {
+        self.placeholder = placeholder;
+        self
+    }
+    
+    /// Append the bytes in `start..end` to `result`, stopping at the first error
+    fn read_range(&self, start: usize, end: usize, result: &mut Vec<u8>) -> EngineResult<()> {
+        // Need a mutable reference to load chunks
+        let mut storage = self.clone();
+        
+        // Load and copy each chunk that contains the requested subsequence
+        let mut pos = start;
+        while pos < end {
+            storage.load_chunk(pos)?;
+            
+            if let Some((chunk_pos, ref chunk)) = storage.current_chunk {
+                let offset = pos - chunk_pos;
+                let copy_end = (end - chunk_pos).min(chunk.len());
+                if copy_end <= offset {
+                    return Err(EngineError::InvalidSequenceData(format!(
+                        "{} ends at byte {} but the sequence length is {}",
+                        self.path, chunk_pos + chunk.len(), self.length
+                    )));
+                }
+                result.extend_from_slice(&chunk[offset..copy_end]);
+                pos += copy_end - offset;
+            }
+        }
+        
+        Ok(())
+    }
+    
     /// Load a chunk containing the given position
     fn load_chunk(&mut self, position: usize) -> EngineResult<()> {
         // Check if the position is already in the current chunk
@@ -188,10 +259,20 @@ impl OnDemandStorage {
         let chunk_start = (position / self.chunk_size) * self.chunk_size;
         let chunk_end = (chunk_start + self.chunk_size).min(self.length);
         
-        // Load the chunk from the file
-        let reader = crate::engines::core::io::FastReader::new(&self.path, None)?;
+        // Load the chunk from the file, starting at its offset
+        let mut reader = crate::engines::core::io::FastReader::new(&self.path, Some(self.chunk_size))?;
+        reader.seek_to(chunk_start as u64)?;
+        
         let mut buffer = vec![0; chunk_end - chunk_start];
-        let _ = reader.read_chunk(&mut buffer)?;
+        let mut filled = 0;
+        while filled < buffer.len() {
+            let bytes_read = reader.read_chunk(&mut buffer[filled..])?;
+            if bytes_read == 0 {
+                break;
+            }
+            filled += bytes_read;
+        }
+        buffer.truncate(filled);
         
         // Store the loaded chunk
         self.current_chunk = Some((chunk_start, buffer));
@@ -207,36 +288,29 @@ impl StorableSequence for OnDemandStorage {
     
     fn subsequence(&self, start: usize, end: usize) -> Vec<u8> {
         let start = start.min(self.length);
-        let end = end.min(self.length);
+        let end = end.min(self.length).max(start);
         let mut result = Vec::with_capacity(end - start);
         
-        // Need a mutable reference to load chunks
-        let mut storage = self.clone();
-        
-        // Load and copy each chunk that contains the requested subsequence
-        let mut pos = start;
-        while pos < end {
-            let chunk_start = (pos / storage.chunk_size) * storage.chunk_size;
-            let chunk_end = (chunk_start + storage.chunk_size).min(storage.length);
-            
-            if let Ok(()) = storage.load_chunk(pos) {
-                if let Some((chunk_pos, ref chunk)) = storage.current_chunk {
-                    let offset = pos - chunk_pos;
-                    let copy_end = (end - chunk_pos).min(chunk.len());
-                    result.extend_from_slice(&chunk[offset..copy_end]);
-                    pos += copy_end - offset;
-                }
-            } else {
-                // Error loading chunk, fill with placeholder value
-                let remaining = end - pos;
-                result.extend(vec![b'N'; remaining]);
-                break;
-            }
+        // Fill whatever could not be read with the placeholder, but don't hide why
+        if let Err(e) = self.read_range(start, end, &mut result) {
+            log::warn!(
+                "Failed to read {}..{} from {}, substituting '{}': {}",
+                start + result.len(), end, self.path, self.placeholder as char, e
+            );
+            result.resize(end - start, self.placeholder);
         }
         
         result
     }
     
+    fn try_subsequence(&self, start: usize, end: usize) -> EngineResult<Vec<u8>> {
+        let start = start.min(self.length);
+        let end = end.min(self.length).max(start);
+        let mut result = Vec::with_capacity(end - start);
+        self.read_range(start, end, &mut result)?;
+        Ok(result)
+    }
+    
     fn as_slice(&self) -> Option<&[u8]> {
         // On-demand storage doesn't provide direct slice access
         None
@@ -264,6 +338,7 @@ impl Clone for OnDemandStorage {
             length: self.length,
             chunk_size: self.chunk_size,
             current_chunk: self.current_chunk.clone(),
+            placeholder: self.placeholder,
         }
     }
 }
@@ -332,6 +407,17 @@ impl StorageFactory {
                     ))
                 }
             },
+            StorageMode::Packed => {
+                if let Some(data) = data {
+                    let mut storage = PackedDnaStorage::with_capacity(data.len());
+                    storage.pack(&data);
+                    Ok(Box::new(storage))
+                } else {
+                    Err(crate::engines::EngineError::InvalidSequenceData(
+                        "Cannot create packed storage without data".to_string(),
+                    ))
+                }
+            },
         }
     }
 }
@@ -393,6 +479,25 @@ mod tests {
         Ok(())
     }
     
+    #[test]
+    fn test_memory_mapped_storage_last_byte() -> std::io::Result<()> {
+        let dir = tempdir()?;
+        let file_path = dir.path().join("last.seq");
+        std::fs::write(&file_path, b"ACGTN")?;
+        
+        let storage = MemoryMappedStorage::new(&file_path).unwrap();
+        
+        // The length must cover the whole mapping, including the final byte
+        assert_eq!(storage.len(), 5);
+        assert_eq!(storage.subsequence(storage.len() - 1, storage.len()), b"N");
+        assert_eq!(storage.as_slice().unwrap().last(), Some(&b'N'));
+        
+        // Requests past the end are clamped to the mapped length
+        assert_eq!(storage.subsequence(3, 100), b"TN");
+        
+        Ok(())
+    }
+    
     #[test]
     fn test_storage_factory() -> std::io::Result<()> {
         // Create a temporary file
@@ -430,4 +535,41 @@ mod tests {
         
         Ok(())
     }
+    
+    #[test]
+    fn test_on_demand_storage() -> std::io::Result<()> {
+        // Create a temporary file
+        let dir = tempdir()?;
+        let file_path = dir.path().join("test.seq");
+        
+        let data = b"AAAACCCCGGGGTTTTAC";
+        std::fs::write(&file_path, data)?;
+        
+        let storage = OnDemandStorage::new(&file_path, data.len(), 4).unwrap();
+        
+        // Reads within and across chunk boundaries match the file
+        assert_eq!(storage.subsequence(4, 8), b"CCCC");
+        assert_eq!(storage.subsequence(6, 10), b"CCGG");
+        assert_eq!(storage.subsequence(2, 17), &data[2..17]);
+        assert_eq!(storage.subsequence(16, 18), b"AC");
+        assert_eq!(storage.subsequence(0, data.len()), data);
+        assert_eq!(storage.try_subsequence(2, 17).unwrap(), &data[2..17]);
+        
+        // A file shorter than the declared length is an error, not made-up data
+        let truncated = OnDemandStorage::new(&file_path, data.len() + 6, 4).unwrap();
+        assert!(truncated.try_subsequence(16, 22).is_err());
+        assert_eq!(truncated.try_subsequence(0, 4).unwrap(), b"AAAA");
+        
+        // The infallible accessor keeps the readable part and fills the rest
+        assert_eq!(truncated.subsequence(16, 22), b"ACNNNN");
+        let protein = truncated.clone().with_placeholder(b'X');
+        assert_eq!(protein.subsequence(14, 20), b"TTACXX");
+        
+        // Missing files are reported too
+        let missing = OnDemandStorage::new(dir.path().join("missing.seq"), 8, 4).unwrap();
+        assert!(missing.try_subsequence(0, 8).is_err());
+        assert_eq!(missing.subsequence(0, 3), b"NNN");
+        
+        Ok(())
+    }
 }
\ No newline at end of file
@@ -14,6 +14,9 @@
 pub mod engines;
 pub mod modules;
 
+#[cfg(feature = "python")]
+mod python;
+
 // Re-export commonly used items
 pub use modules::seq::sequence::{Sequence, SequenceView};
 pub use modules::seq::alphabet::{Alphabet, DNAAlphabet, RNAAlphabet, ProteinAlphabet};
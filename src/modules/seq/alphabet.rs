@@ -4,6 +4,7 @@
 //! biological sequence alphabets.
 
 use std::collections::HashSet;
+use std::fmt;
 
 /// Alphabet trait for sequence validation
 pub trait Alphabet: Send + Sync {
@@ -36,6 +37,19 @@ pub trait Alphabet: Send + Sync {
     /// Get the complement of a character (if applicable)
     fn complement(&self, c: u8) -> Option<u8>;
     
+    /// Check whether every character of the alphabet has a complement
+    fn supports_complement(&self) -> bool {
+        !self.valid_chars().is_empty() && self.valid_chars().iter().all(|&c| self.complement(c).is_some())
+    }
+    
+    /// Extend the alphabet with the gap characters `-` and `.`
+    fn with_gaps(self) -> Gapped<Self>
+    where
+        Self: Sized,
+    {
+        Gapped::new(self)
+    }
+    
     /// Get the complement of a sequence (if applicable)
     fn complement_sequence(&self, seq: &[u8]) -> Option<Vec<u8>> {
         let mut result = Vec::with_capacity(seq.len());
@@ -52,6 +66,30 @@ pub trait Alphabet: Send + Sync {
     }
 }
 
+impl fmt::Debug for dyn Alphabet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Alphabet")
+            .field("name", &self.name())
+            .field("valid_chars", &String::from_utf8_lossy(self.valid_chars()))
+            .finish()
+    }
+}
+
+/// Alphabets are equal when they have the same name and characters
+impl PartialEq for Box<dyn Alphabet> {
+    fn eq(&self, other: &Self) -> bool {
+        self.name() == other.name() && self.valid_chars() == other.valid_chars()
+    }
+}
+
+// `#[derive(PartialEq)]` on a struct with a boxed alphabet field compares
+// through this impl; without it the derive tries to move the box
+impl PartialEq<&Self> for Box<dyn Alphabet> {
+    fn eq(&self, other: &&Self) -> bool {
+        self == *other
+    }
+}
+
 /// DNA alphabet (A, C, G, T, N and lowercase)
 #[derive(Debug, Clone)]
 pub struct DNAAlphabet {
@@ -112,6 +150,67 @@ impl Alphabet for DNAAlphabet {
     }
 }
 
+/// IUPAC DNA alphabet (all 15 nucleotide codes, gaps and lowercase)
+///
+/// Reports its name as "DNA" so that DNA operations such as complement and
+/// transcription accept sequences using it.
+#[derive(Debug, Clone)]
+pub struct IupacDNAAlphabet {
+    valid_chars: Vec<u8>,
+    valid_set: HashSet<u8>,
+    complement_map: [u8; 256],
+}
+
+impl Default for IupacDNAAlphabet {
+    fn default() -> Self {
+        let valid_chars = b"ACGTRYSWKMBDHVNacgtryswkmbdhvn-".to_vec();
+        let mut obj = Self {
+            valid_set: valid_chars.iter().copied().collect(),
+            valid_chars,
+            complement_map: [0; 256],
+        };
+        
+        // Initialize complement map
+        for i in 0..256 {
+            obj.complement_map[i] = i as u8;
+        }
+        
+        // Set up complements (S, W, N and gaps are their own complement)
+        for &(base, comp) in &[
+            (b'A', b'T'), (b'C', b'G'), (b'G', b'C'), (b'T', b'A'),
+            (b'R', b'Y'), (b'Y', b'R'), (b'K', b'M'), (b'M', b'K'),
+            (b'B', b'V'), (b'V', b'B'), (b'D', b'H'), (b'H', b'D'),
+        ] {
+            obj.complement_map[base as usize] = comp;
+            obj.complement_map[base.to_ascii_lowercase() as usize] = comp.to_ascii_lowercase();
+        }
+        
+        obj
+    }
+}
+
+impl Alphabet for IupacDNAAlphabet {
+    fn name(&self) -> &str {
+        "DNA"
+    }
+    
+    fn is_valid_char(&self, c: u8) -> bool {
+        self.valid_set.contains(&c)
+    }
+    
+    fn valid_chars(&self) -> &[u8] {
+        &self.valid_chars
+    }
+    
+    fn complement(&self, c: u8) -> Option<u8> {
+        if self.is_valid_char(c) {
+            Some(self.complement_map[c as usize])
+        } else {
+            None
+        }
+    }
+}
+
 /// RNA alphabet (A, C, G, U, N and lowercase)
 #[derive(Debug, Clone)]
 pub struct RNAAlphabet {
@@ -212,7 +311,158 @@ impl Alphabet for ProteinAlphabet {
     }
 }
 
+/// Gap characters accepted by [`Gapped`] alphabets
+pub const GAP_CHARS: &[u8] = b"-.";
+
+/// Check whether a character is an alignment gap
+pub fn is_gap(c: u8) -> bool {
+    GAP_CHARS.contains(&c)
+}
+
+/// An alphabet extended with alignment gaps
+///
+/// Accepts everything the wrapped alphabet does plus `-` and `.`, and keeps
+/// its name so that operations such as complement and transcription still
+/// recognize it. Gaps complement to themselves.
+#[derive(Debug, Clone)]
+pub struct Gapped<A> {
+    inner: A,
+    valid_chars: Vec<u8>,
+    /// Gaps complement only when the wrapped alphabet has a complement
+    complements_gaps: bool,
+}
+
+impl<A: Alphabet> Gapped<A> {
+    /// Wrap an alphabet so that it accepts gaps
+    pub fn new(inner: A) -> Self {
+        let mut valid_chars = inner.valid_chars().to_vec();
+        for &gap in GAP_CHARS {
+            if !inner.is_valid_char(gap) {
+                valid_chars.push(gap);
+            }
+        }
+        
+        let complements_gaps = inner.supports_complement();
+        Self { inner, valid_chars, complements_gaps }
+    }
+    
+    /// Get the wrapped alphabet
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+}
+
+impl<A: Alphabet + Default> Default for Gapped<A> {
+    fn default() -> Self {
+        Self::new(A::default())
+    }
+}
+
+impl<A: Alphabet> Alphabet for Gapped<A> {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+    
+    fn is_valid_char(&self, c: u8) -> bool {
+        is_gap(c) || self.inner.is_valid_char(c)
+    }
+    
+    fn valid_chars(&self) -> &[u8] {
+        &self.valid_chars
+    }
+    
+    fn complement(&self, c: u8) -> Option<u8> {
+        if is_gap(c) {
+            self.complements_gaps.then_some(c)
+        } else {
+            self.inner.complement(c)
+        }
+    }
+}
+
+/// DNA alphabet that also accepts alignment gaps
+pub type GappedDNAAlphabet = Gapped<DNAAlphabet>;
+
+/// RNA alphabet that also accepts alignment gaps
+pub type GappedRNAAlphabet = Gapped<RNAAlphabet>;
+
+/// Protein alphabet that also accepts alignment gaps
+pub type GappedProteinAlphabet = Gapped<ProteinAlphabet>;
+
+/// Alphabet built from a user-provided character set
+///
+/// Useful for symbol sets the crate doesn't know about, such as structural
+/// alphabets or reduced amino-acid codes.
+#[derive(Debug, Clone)]
+pub struct CustomAlphabet {
+    name: String,
+    valid_chars: Vec<u8>,
+    valid_set: HashSet<u8>,
+    complement_map: Option<[u8; 256]>,
+}
+
+impl CustomAlphabet {
+    /// Create an alphabet from its valid characters
+    ///
+    /// Characters are case-sensitive; duplicates are ignored. Complement
+    /// pairs apply in both directions, and characters without a pair are
+    /// their own complement. Without pairs the alphabet has no complement.
+    pub fn new(name: &str, chars: &[u8], complement: Option<&[(u8, u8)]>) -> Self {
+        let mut valid_chars = Vec::with_capacity(chars.len());
+        let mut valid_set = HashSet::with_capacity(chars.len());
+        for &c in chars {
+            if valid_set.insert(c) {
+                valid_chars.push(c);
+            }
+        }
+        
+        let complement_map = complement.map(|pairs| {
+            let mut map = [0u8; 256];
+            for (i, entry) in map.iter_mut().enumerate() {
+                *entry = i as u8;
+            }
+            for &(a, b) in pairs {
+                map[a as usize] = b;
+                map[b as usize] = a;
+            }
+            map
+        });
+        
+        Self {
+            name: name.to_string(),
+            valid_chars,
+            valid_set,
+            complement_map,
+        }
+    }
+}
+
+impl Alphabet for CustomAlphabet {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    
+    fn is_valid_char(&self, c: u8) -> bool {
+        self.valid_set.contains(&c)
+    }
+    
+    fn valid_chars(&self) -> &[u8] {
+        &self.valid_chars
+    }
+    
+    fn complement(&self, c: u8) -> Option<u8> {
+        match &self.complement_map {
+            Some(map) if self.is_valid_char(c) => Some(map[c as usize]),
+            _ => None,
+        }
+    }
+}
+
 /// Detect the alphabet of a sequence
+///
+/// Sequences using IUPAC ambiguity codes are detected as DNA when at least
+/// half of their characters are unambiguous nucleotides (A, C, G, T, N);
+/// otherwise they are likely proteins made of the same letters.
 pub fn detect_alphabet(seq: &[u8]) -> Option<Box<dyn Alphabet>> {
     // Check for DNA
     let dna_alphabet = DNAAlphabet::default();
@@ -226,6 +476,17 @@ pub fn detect_alphabet(seq: &[u8]) -> Option<Box<dyn Alphabet>> {
         return Some(Box::new(rna_alphabet));
     }
     
+    // Check for DNA with ambiguity codes
+    let iupac_alphabet = IupacDNAAlphabet::default();
+    if iupac_alphabet.is_valid_sequence(seq) {
+        let unambiguous = seq.iter()
+            .filter(|&&c| matches!(c.to_ascii_uppercase(), b'A' | b'C' | b'G' | b'T' | b'N'))
+            .count();
+        if unambiguous * 2 >= seq.len() {
+            return Some(Box::new(iupac_alphabet));
+        }
+    }
+    
     // Check for protein
     let protein_alphabet = ProteinAlphabet::default();
     if protein_alphabet.is_valid_sequence(seq) {
@@ -235,6 +496,116 @@ pub fn detect_alphabet(seq: &[u8]) -> Option<Box<dyn Alphabet>> {
     None
 }
 
+/// Broad kind of sequence, used as a prior for alphabet detection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AlphabetKind {
+    /// DNA, including IUPAC ambiguity codes
+    Dna,
+    /// RNA
+    Rna,
+    /// Protein
+    Protein,
+}
+
+/// Characters that rule out the other kinds: T is never RNA, U is never DNA,
+/// and these residues are not nucleotide codes
+const DNA_SPECIFIC: &[u8] = b"T";
+const RNA_SPECIFIC: &[u8] = b"U";
+const PROTEIN_SPECIFIC: &[u8] = b"EFILPQ*";
+
+impl AlphabetKind {
+    /// Build the alphabet for this kind, if the sequence is valid in it
+    ///
+    /// DNA falls back to the IUPAC alphabet for ambiguity codes.
+    fn alphabet_for(self, seq: &[u8]) -> Option<Box<dyn Alphabet>> {
+        let alphabet: Box<dyn Alphabet> = match self {
+            AlphabetKind::Dna if DNAAlphabet::default().is_valid_sequence(seq) => Box::new(DNAAlphabet::default()),
+            AlphabetKind::Dna => Box::new(IupacDNAAlphabet::default()),
+            AlphabetKind::Rna => Box::new(RNAAlphabet::default()),
+            AlphabetKind::Protein => Box::new(ProteinAlphabet::default()),
+        };
+        alphabet.is_valid_sequence(seq).then_some(alphabet)
+    }
+    
+    /// Characters specific to this kind (uppercase)
+    fn specific_chars(self) -> &'static [u8] {
+        match self {
+            AlphabetKind::Dna => DNA_SPECIFIC,
+            AlphabetKind::Rna => RNA_SPECIFIC,
+            AlphabetKind::Protein => PROTEIN_SPECIFIC,
+        }
+    }
+}
+
+/// Result of [`detect_alphabet_with_hint`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlphabetGuess {
+    /// The detected alphabet
+    pub alphabet: Box<dyn Alphabet>,
+    /// Kind of the detected alphabet
+    pub kind: AlphabetKind,
+    /// How strongly the sequence itself supports the alphabet, from 0 to 1
+    pub confidence: f64,
+}
+
+/// Detect the alphabet of a sequence using an optional prior
+///
+/// A `hint` (e.g. from a file extension) is used whenever the sequence is
+/// valid in the hinted alphabet, so `ACGT` can be read as a protein
+/// fragment. Without a usable hint the result of [`detect_alphabet`] is
+/// only returned if its confidence is at least `min_specific_fraction`.
+///
+/// The confidence is 1.0 when no other kind of alphabet fits the sequence.
+/// Otherwise it is the fraction of characters specific to the alphabet
+/// (case-insensitive): `T` for DNA, `U` for RNA and residues that aren't
+/// nucleotide codes (`EFILPQ*`) for protein. Sequences like `AAAA` or
+/// `NNNN` have no evidence either way and get a confidence of 0.
+pub fn detect_alphabet_with_hint(
+    seq: &[u8],
+    hint: Option<AlphabetKind>,
+    min_specific_fraction: f64,
+) -> Option<AlphabetGuess> {
+    // A valid prior wins regardless of the evidence threshold
+    if let Some(kind) = hint {
+        if let Some(alphabet) = kind.alphabet_for(seq) {
+            let confidence = detection_confidence(seq, kind);
+            return Some(AlphabetGuess { alphabet, kind, confidence });
+        }
+    }
+    
+    let alphabet = detect_alphabet(seq)?;
+    let kind = match alphabet.name() {
+        "DNA" => AlphabetKind::Dna,
+        "RNA" => AlphabetKind::Rna,
+        _ => AlphabetKind::Protein,
+    };
+    
+    let confidence = detection_confidence(seq, kind);
+    if confidence < min_specific_fraction {
+        return None;
+    }
+    
+    Some(AlphabetGuess { alphabet, kind, confidence })
+}
+
+/// Confidence that a sequence valid in `kind` really is of that kind
+fn detection_confidence(seq: &[u8], kind: AlphabetKind) -> f64 {
+    let others_fit = [AlphabetKind::Dna, AlphabetKind::Rna, AlphabetKind::Protein]
+        .into_iter()
+        .filter(|&other| other != kind)
+        .any(|other| other.alphabet_for(seq).is_some());
+    if !others_fit {
+        return 1.0;
+    }
+    if seq.is_empty() {
+        return 0.0;
+    }
+    
+    let specific = kind.specific_chars();
+    let count = seq.iter().filter(|c| specific.contains(&c.to_ascii_uppercase())).count();
+    count as f64 / seq.len() as f64
+}
+
 /// Convert a DNA sequence to RNA
 pub fn dna_to_rna(seq: &[u8]) -> Vec<u8> {
     seq.iter()
@@ -309,6 +680,27 @@ mod tests {
         );
     }
     
+    #[test]
+    fn test_iupac_dna_alphabet() {
+        let alphabet = IupacDNAAlphabet::default();
+        
+        // All 15 codes, gaps and lowercase are valid
+        assert!(alphabet.is_valid_sequence(b"ACGTRYSWKMBDHVN-"));
+        assert!(alphabet.is_valid_sequence(b"acgtryswkmbdhvn"));
+        assert_eq!(alphabet.size(), 31);
+        
+        // Test invalid characters
+        assert!(!alphabet.is_valid_char(b'U'));
+        assert!(!alphabet.is_valid_char(b'X'));
+        
+        // Ambiguity codes complement to their counterparts
+        assert_eq!(
+            alphabet.complement_sequence(b"ACGTRYKMBVDHSWN-"),
+            Some(b"TGCAYRMKVBHDSWN-".to_vec())
+        );
+        assert_eq!(alphabet.complement(b'r'), Some(b'y'));
+    }
+    
     #[test]
     fn test_rna_alphabet() {
         let alphabet = RNAAlphabet::default();
@@ -364,6 +756,50 @@ mod tests {
         assert_eq!(alphabet.complement(b'A'), None);
     }
     
+    #[test]
+    fn test_custom_alphabet() {
+        // A reduced amino-acid code: hydrophobic, polar, charged
+        let reduced = CustomAlphabet::new("HPC", b"HPCHP", None);
+        assert_eq!(reduced.name(), "HPC");
+        assert_eq!(reduced.valid_chars(), b"HPC");
+        assert!(reduced.is_valid_sequence(b"HHPCP"));
+        assert!(!reduced.is_valid_sequence(b"HPX"));
+        assert!(!reduced.supports_complement());
+        assert_eq!(reduced.complement_sequence(b"HP"), None);
+        
+        // Complement pairs work both ways; unpaired symbols map to themselves
+        let paired = CustomAlphabet::new("Paired", b"XYZ", Some(&[(b'X', b'Y')]));
+        assert!(paired.supports_complement());
+        assert_eq!(paired.complement_sequence(b"XYZ"), Some(b"YXZ".to_vec()));
+        assert_eq!(paired.complement(b'W'), None);
+        
+        // Built-in alphabets report complement support as before
+        assert!(DNAAlphabet::default().supports_complement());
+        assert!(!ProteinAlphabet::default().supports_complement());
+    }
+    
+    #[test]
+    fn test_gapped_alphabets() {
+        let gapped = GappedDNAAlphabet::default();
+        assert_eq!(gapped.name(), "DNA");
+        assert!(gapped.is_valid_sequence(b"AC-GT..N"));
+        assert!(!gapped.is_valid_sequence(b"AC-GU"));
+        assert_eq!(gapped.size(), DNAAlphabet::default().size() + 2);
+        
+        // Gaps pass through complements unchanged
+        assert_eq!(gapped.complement_sequence(b"A-C.g"), Some(b"T-G.c".to_vec()));
+        assert!(gapped.supports_complement());
+        
+        // The adapter works on any alphabet, and doesn't add a complement
+        let protein = ProteinAlphabet::default().with_gaps();
+        assert!(protein.is_valid_sequence(b"MK-V"));
+        assert_eq!(protein.complement(b'-'), None);
+        assert!(!protein.supports_complement());
+        
+        // Alphabets that already accept `-` don't list it twice
+        assert_eq!(IupacDNAAlphabet::default().with_gaps().size(), 32);
+    }
+    
     #[test]
     fn test_detect_alphabet() {
         // Test DNA detection
@@ -381,11 +817,59 @@ mod tests {
         let alphabet = detect_alphabet(protein_seq).unwrap();
         assert_eq!(alphabet.name(), "Protein");
         
+        // Test DNA with ambiguity codes
+        let iupac_seq = b"ACGTRYACGTNNKM";
+        let alphabet = detect_alphabet(iupac_seq).unwrap();
+        assert_eq!(alphabet.name(), "DNA");
+        assert!(alphabet.is_valid_char(b'R'));
+        
+        // Mostly ambiguity letters is more likely a protein
+        let alphabet = detect_alphabet(b"MKVHDRYSW").unwrap();
+        assert_eq!(alphabet.name(), "Protein");
+        
         // Test unknown sequence
         let unknown_seq = b"ACGTJ123";
         assert!(detect_alphabet(unknown_seq).is_none());
     }
     
+    #[test]
+    fn test_detect_alphabet_with_hint() {
+        // Without a hint the evidence threshold decides
+        let guess = detect_alphabet_with_hint(b"ACGTacgt", None, 0.1).unwrap();
+        assert_eq!(guess.kind, AlphabetKind::Dna);
+        assert_eq!(guess.confidence, 0.25);
+        assert!(detect_alphabet_with_hint(b"AAAA", None, 0.1).is_none());
+        assert_eq!(detect_alphabet_with_hint(b"AAAA", None, 0.0).unwrap().kind, AlphabetKind::Dna);
+        assert!(detect_alphabet_with_hint(b"", None, 0.1).is_none());
+        
+        let guess = detect_alphabet_with_hint(b"acguUU", None, 0.5).unwrap();
+        assert_eq!(guess.kind, AlphabetKind::Rna);
+        assert_eq!(guess.alphabet.name(), "RNA");
+        assert_eq!(guess.clone(), guess);
+        assert!(format!("{:?}", guess).contains("RNA"));
+        
+        // Only protein fits, so it is certain
+        let guess = detect_alphabet_with_hint(b"MkvLE", None, 0.9).unwrap();
+        assert_eq!(guess.kind, AlphabetKind::Protein);
+        assert_eq!(guess.confidence, 1.0);
+        
+        // A valid hint overrides detection
+        let guess = detect_alphabet_with_hint(b"ACGT", Some(AlphabetKind::Protein), 0.5).unwrap();
+        assert_eq!(guess.kind, AlphabetKind::Protein);
+        assert_eq!(guess.alphabet.name(), "Protein");
+        assert_eq!(guess.confidence, 0.0);
+        assert_eq!(detect_alphabet_with_hint(b"AAAA", Some(AlphabetKind::Rna), 0.5).unwrap().kind, AlphabetKind::Rna);
+        
+        // Ambiguity codes use the IUPAC alphabet for DNA hints
+        let guess = detect_alphabet_with_hint(b"ACGRYT", Some(AlphabetKind::Dna), 0.0).unwrap();
+        assert!(guess.alphabet.is_valid_char(b'R'));
+        
+        // An invalid hint falls back to detection
+        let guess = detect_alphabet_with_hint(b"ACGU", Some(AlphabetKind::Dna), 0.0).unwrap();
+        assert_eq!(guess.kind, AlphabetKind::Rna);
+        assert!(detect_alphabet_with_hint(b"ACGTJ123", Some(AlphabetKind::Protein), 0.0).is_none());
+    }
+    
     #[test]
     fn test_dna_rna_conversion() {
         // Test DNA to RNA
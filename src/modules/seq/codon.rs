@@ -0,0 +1,487 @@
+//! Genetic code tables
+//!
+//! This module provides NCBI codon tables used to translate nucleotide
+//! sequences into proteins.
+
+/// Static definition of an NCBI translation table
+struct TableData {
+    id: u8,
+    name: &'static str,
+    /// Amino acid for each codon, with bases in `TCAG` order (NCBI layout)
+    amino_acids: &'static [u8; 64],
+    start_codons: &'static [[u8; 3]],
+}
+
+/// The NCBI translation tables, from the NCBI `gc.prt` definitions
+///
+/// Start codons follow NCBI, except that the standard code only uses `ATG`,
+/// which is what ORF finders conventionally expect for table 1.
+const NCBI_TABLES: &[TableData] = &[
+    TableData {
+        id: 1,
+        name: "Standard",
+        amino_acids: b"FFLLSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        start_codons: &[*b"ATG"],
+    },
+    TableData {
+        id: 2,
+        name: "Vertebrate Mitochondrial",
+        amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSS**VVVVAAAADDEEGGGG",
+        start_codons: &[*b"ATT", *b"ATC", *b"ATA", *b"ATG", *b"GTG"],
+    },
+    TableData {
+        id: 3,
+        name: "Yeast Mitochondrial",
+        amino_acids: b"FFLLSSSSYY**CCWWTTTTPPPPHHQQRRRRIIMMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        start_codons: &[*b"ATA", *b"ATG", *b"GTG"],
+    },
+    TableData {
+        id: 4,
+        name: "Mold, Protozoan, and Coelenterate Mitochondrial and Mycoplasma/Spiroplasma",
+        amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        start_codons: &[*b"TTA", *b"TTG", *b"CTG", *b"ATT", *b"ATC", *b"ATA", *b"ATG", *b"GTG"],
+    },
+    TableData {
+        id: 5,
+        name: "Invertebrate Mitochondrial",
+        amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSSSSVVVVAAAADDEEGGGG",
+        start_codons: &[*b"TTG", *b"ATT", *b"ATC", *b"ATA", *b"ATG", *b"GTG"],
+    },
+    TableData {
+        id: 6,
+        name: "Ciliate, Dasycladacean and Hexamita Nuclear",
+        amino_acids: b"FFLLSSSSYYQQCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        start_codons: &[*b"ATG"],
+    },
+    TableData {
+        id: 9,
+        name: "Echinoderm and Flatworm Mitochondrial",
+        amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNNKSSSSVVVVAAAADDEEGGGG",
+        start_codons: &[*b"ATG", *b"GTG"],
+    },
+    TableData {
+        id: 10,
+        name: "Euplotid Nuclear",
+        amino_acids: b"FFLLSSSSYY**CCCWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        start_codons: &[*b"ATG"],
+    },
+    TableData {
+        id: 11,
+        name: "Bacterial, Archaeal and Plant Plastid",
+        amino_acids: b"FFLLSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        start_codons: &[*b"TTG", *b"CTG", *b"ATT", *b"ATC", *b"ATA", *b"ATG", *b"GTG"],
+    },
+    TableData {
+        id: 12,
+        name: "Alternative Yeast Nuclear",
+        amino_acids: b"FFLLSSSSYY**CC*WLLLSPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        start_codons: &[*b"CTG", *b"ATG"],
+    },
+    TableData {
+        id: 13,
+        name: "Ascidian Mitochondrial",
+        amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSSGGVVVVAAAADDEEGGGG",
+        start_codons: &[*b"TTG", *b"ATA", *b"ATG", *b"GTG"],
+    },
+    TableData {
+        id: 14,
+        name: "Alternative Flatworm Mitochondrial",
+        amino_acids: b"FFLLSSSSYYY*CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNNKSSSSVVVVAAAADDEEGGGG",
+        start_codons: &[*b"ATG"],
+    },
+    TableData {
+        id: 16,
+        name: "Chlorophycean Mitochondrial",
+        amino_acids: b"FFLLSSSSYY*LCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        start_codons: &[*b"ATG"],
+    },
+    TableData {
+        id: 21,
+        name: "Trematode Mitochondrial",
+        amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNNKSSSSVVVVAAAADDEEGGGG",
+        start_codons: &[*b"ATG", *b"GTG"],
+    },
+    TableData {
+        id: 22,
+        name: "Scenedesmus obliquus Mitochondrial",
+        amino_acids: b"FFLLSS*SYY*LCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        start_codons: &[*b"ATG"],
+    },
+    TableData {
+        id: 23,
+        name: "Thraustochytrium Mitochondrial",
+        amino_acids: b"FF*LSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        start_codons: &[*b"ATT", *b"ATG", *b"GTG"],
+    },
+    TableData {
+        id: 24,
+        name: "Rhabdopleuridae Mitochondrial",
+        amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSSKVVVVAAAADDEEGGGG",
+        start_codons: &[*b"TTG", *b"CTG", *b"ATG", *b"GTG"],
+    },
+    TableData {
+        id: 25,
+        name: "Candidate Division SR1 and Gracilibacteria",
+        amino_acids: b"FFLLSSSSYY**CCGWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        start_codons: &[*b"TTG", *b"ATG", *b"GTG"],
+    },
+    TableData {
+        id: 26,
+        name: "Pachysolen tannophilus Nuclear",
+        amino_acids: b"FFLLSSSSYY**CC*WLLLAPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        start_codons: &[*b"CTG", *b"ATG"],
+    },
+];
+
+/// Codon bases in NCBI table order
+const TCAG: [u8; 4] = *b"TCAG";
+
+/// How to handle a trailing partial codon during translation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartialCodon {
+    /// Fail if the sequence length is not a multiple of three
+    Error,
+    /// Append an `X` for the trailing partial codon
+    Pad,
+}
+
+/// Usage of one codon in a coding sequence
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CodonUsage {
+    /// The codon, as uppercase DNA
+    pub codon: [u8; 3],
+    /// Amino acid the codon encodes in the table used (`*` for stop)
+    pub amino_acid: u8,
+    /// Number of times the codon was read
+    pub count: usize,
+    /// Fraction of the codons for the same amino acid that were this codon
+    pub fraction: f64,
+}
+
+/// A genetic code mapping codons to amino acids
+#[derive(Debug, Clone)]
+pub struct CodonTable {
+    id: u32,
+    name: &'static str,
+    amino_acids: &'static [u8; 64],
+    start_codons: Vec<[u8; 3]>,
+    partial_codon: PartialCodon,
+}
+
+impl CodonTable {
+    /// The standard genetic code (NCBI table 1)
+    pub fn standard() -> Self {
+        Self::by_id(1).expect("table 1 is built in")
+    }
+    
+    /// The vertebrate mitochondrial code (NCBI table 2)
+    pub fn vertebrate_mitochondrial() -> Self {
+        Self::by_id(2).expect("table 2 is built in")
+    }
+    
+    /// The bacterial, archaeal and plant plastid code (NCBI table 11)
+    ///
+    /// Uses the standard codon assignments with the alternative start codons
+    /// found in prokaryotes.
+    pub fn bacterial() -> Self {
+        Self::by_id(11).expect("table 11 is built in")
+    }
+    
+    /// Look up a built-in table by its NCBI translation table id
+    pub fn by_id(id: u8) -> Option<Self> {
+        NCBI_TABLES.iter().find(|table| table.id == id).map(|table| Self {
+            id: table.id as u32,
+            name: table.name,
+            amino_acids: table.amino_acids,
+            start_codons: table.start_codons.to_vec(),
+            partial_codon: PartialCodon::Error,
+        })
+    }
+    
+    /// Look up a table by its NCBI translation table id
+    pub fn from_ncbi_id(id: u32) -> Option<Self> {
+        u8::try_from(id).ok().and_then(Self::by_id)
+    }
+    
+    /// Get the ids of all built-in tables, in ascending order
+    pub fn available_ids() -> Vec<u8> {
+        NCBI_TABLES.iter().map(|table| table.id).collect()
+    }
+    
+    /// Set how a trailing partial codon is handled
+    pub fn with_partial_codon(mut self, partial_codon: PartialCodon) -> Self {
+        self.partial_codon = partial_codon;
+        self
+    }
+    
+    /// Set the codons that start an open reading frame
+    ///
+    /// Codons are given as DNA; RNA input is matched against them with `U`
+    /// read as `T`. Defaults to `ATG`.
+    pub fn with_start_codons(mut self, codons: &[&[u8; 3]]) -> Self {
+        self.start_codons = codons.iter().map(|codon| codon.map(|b| b.to_ascii_uppercase())).collect();
+        self
+    }
+    
+    /// Get the NCBI table id
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+    
+    /// Get the table name
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+    
+    /// Get the partial codon handling mode
+    pub fn partial_codon(&self) -> PartialCodon {
+        self.partial_codon
+    }
+    
+    /// Translate a single codon
+    ///
+    /// DNA and RNA codons are accepted in either case. Stop codons translate
+    /// to `*`. Codons with IUPAC ambiguity codes translate to the amino acid
+    /// shared by every codon they expand to (e.g. `TCN` is `S`), or to `X` if
+    /// the expansions disagree or a base is not a nucleotide code.
+    pub fn translate_codon(&self, codon: &[u8]) -> u8 {
+        if codon.len() != 3 {
+            return b'X';
+        }
+        
+        let masks = match (base_mask(codon[0]), base_mask(codon[1]), base_mask(codon[2])) {
+            (Some(a), Some(b), Some(c)) => [a, b, c],
+            _ => return b'X',
+        };
+        
+        // Every expansion of the codon must give the same amino acid
+        let mut amino_acid = None;
+        for first in (0..4).filter(|i| masks[0] & (1 << i) != 0) {
+            for second in (0..4).filter(|i| masks[1] & (1 << i) != 0) {
+                for third in (0..4).filter(|i| masks[2] & (1 << i) != 0) {
+                    let aa = self.amino_acids[first * 16 + second * 4 + third];
+                    match amino_acid {
+                        None => amino_acid = Some(aa),
+                        Some(previous) if previous != aa => return b'X',
+                        Some(_) => {}
+                    }
+                }
+            }
+        }
+        
+        amino_acid.unwrap_or(b'X')
+    }
+    
+    /// Check whether a codon is a stop codon in this table
+    pub fn is_stop_codon(&self, codon: &[u8]) -> bool {
+        self.translate_codon(codon) == b'*'
+    }
+    
+    /// Get the start codons of this table, as uppercase DNA
+    pub fn start_codons(&self) -> &[[u8; 3]] {
+        &self.start_codons
+    }
+    
+    /// Count codon usage in a coding sequence
+    ///
+    /// Codons are read in frame from the start; a trailing partial codon and
+    /// codons with bases other than `ACGTU` are skipped. All 64 codons are
+    /// returned in `TCAG` order, each with its share of the codons for the
+    /// same amino acid under this table.
+    pub fn codon_usage(&self, nucleotides: &[u8]) -> Vec<CodonUsage> {
+        let mut counts = [0usize; 64];
+        for codon in nucleotides.chunks_exact(3) {
+            if let Some(index) = codon_index(codon) {
+                counts[index] += 1;
+            }
+        }
+        
+        // Total codons read for each amino acid, for the synonymous fractions
+        let mut totals = [0usize; 256];
+        for (index, &count) in counts.iter().enumerate() {
+            totals[self.amino_acids[index] as usize] += count;
+        }
+        
+        (0..64)
+            .map(|index| {
+                let amino_acid = self.amino_acids[index];
+                let total = totals[amino_acid as usize];
+                CodonUsage {
+                    codon: [TCAG[index / 16], TCAG[index / 4 % 4], TCAG[index % 4]],
+                    amino_acid,
+                    count: counts[index],
+                    fraction: if total == 0 { 0.0 } else { counts[index] as f64 / total as f64 },
+                }
+            })
+            .collect()
+    }
+    
+    /// Check whether a codon starts an open reading frame
+    pub fn is_start_codon(&self, codon: &[u8]) -> bool {
+        if codon.len() != 3 {
+            return false;
+        }
+        
+        let normalized = [normalize_base(codon[0]), normalize_base(codon[1]), normalize_base(codon[2])];
+        self.start_codons.contains(&normalized)
+    }
+    
+    /// Translate the first codon of a reading frame
+    ///
+    /// With `treat_alt_start_as_met`, any start codon of this table becomes
+    /// `M`, as initiator tRNAs read `GTG` and `TTG` starts as methionine.
+    pub fn translate_start_codon(&self, codon: &[u8], treat_alt_start_as_met: bool) -> u8 {
+        if treat_alt_start_as_met && self.is_start_codon(codon) {
+            b'M'
+        } else {
+            self.translate_codon(codon)
+        }
+    }
+}
+
+impl Default for CodonTable {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+/// Uppercase a nucleotide, reading `U` as `T`
+fn normalize_base(base: u8) -> u8 {
+    match base.to_ascii_uppercase() {
+        b'U' => b'T',
+        b => b,
+    }
+}
+
+/// Get the table index of an unambiguous codon
+fn codon_index(codon: &[u8]) -> Option<usize> {
+    codon.iter().try_fold(0, |index, &base| {
+        TCAG.iter().position(|&b| b == normalize_base(base)).map(|position| index * 4 + position)
+    })
+}
+
+/// Get the nucleotides an IUPAC code stands for, as bits in `TCAG` order
+fn base_mask(base: u8) -> Option<u8> {
+    const T: u8 = 1;
+    const C: u8 = 2;
+    const A: u8 = 4;
+    const G: u8 = 8;
+    
+    let mask = match normalize_base(base) {
+        b'T' => T,
+        b'C' => C,
+        b'A' => A,
+        b'G' => G,
+        b'R' => A | G,
+        b'Y' => C | T,
+        b'S' => C | G,
+        b'W' => A | T,
+        b'K' => G | T,
+        b'M' => A | C,
+        b'B' => C | G | T,
+        b'D' => A | G | T,
+        b'H' => A | C | T,
+        b'V' => A | C | G,
+        b'N' => A | C | G | T,
+        _ => return None,
+    };
+    Some(mask)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_translate_codon() {
+        let standard = CodonTable::standard();
+        assert_eq!(standard.translate_codon(b"ATG"), b'M');
+        assert_eq!(standard.translate_codon(b"aug"), b'M');
+        assert_eq!(standard.translate_codon(b"TGA"), b'*');
+        assert_eq!(standard.translate_codon(b"AGA"), b'R');
+        assert_eq!(standard.translate_codon(b"ANA"), b'X');
+        
+        // Ambiguous codons resolve only when every expansion agrees
+        assert_eq!(standard.translate_codon(b"TCN"), b'S');
+        assert_eq!(standard.translate_codon(b"ucn"), b'S');
+        assert_eq!(standard.translate_codon(b"AAN"), b'X');
+        assert_eq!(standard.translate_codon(b"AAR"), b'K');
+        assert_eq!(standard.translate_codon(b"NNN"), b'X');
+        assert_eq!(standard.translate_codon(b"TAR"), b'*');
+        assert_eq!(standard.translate_codon(b"A-G"), b'X');
+        
+        // The mitochondrial code reassigns TGA, AGA and ATA
+        let mito = CodonTable::from_ncbi_id(2).unwrap();
+        assert_eq!(mito.translate_codon(b"TGA"), b'W');
+        assert_eq!(mito.translate_codon(b"AGA"), b'*');
+        assert_eq!(mito.translate_codon(b"ATA"), b'M');
+        
+        assert!(CodonTable::from_ncbi_id(99).is_none());
+        
+        // Start codons are configurable and match RNA
+        assert!(standard.is_start_codon(b"AUG"));
+        assert!(!standard.is_start_codon(b"GTG"));
+        let alternative = CodonTable::standard().with_start_codons(&[b"ATG", b"GTG"]);
+        assert!(alternative.is_start_codon(b"gtg"));
+        
+        // Alternative starts read as methionine only when asked
+        let bacterial = CodonTable::from_ncbi_id(11).unwrap();
+        assert!(bacterial.is_start_codon(b"TTG"));
+        assert_eq!(bacterial.translate_codon(b"TTG"), b'L');
+        assert_eq!(bacterial.translate_start_codon(b"TTG", true), b'M');
+        assert_eq!(bacterial.translate_start_codon(b"TTG", false), b'L');
+        assert_eq!(standard.translate_start_codon(b"TTG", true), b'L');
+    }
+    
+    #[test]
+    fn test_ncbi_tables() {
+        let ids = CodonTable::available_ids();
+        assert_eq!(&ids[..6], &[1, 2, 3, 4, 5, 6]);
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(CodonTable::by_id(7).is_none());
+        
+        // Every table translates ATG to M and lists it as a start
+        for id in ids {
+            let table = CodonTable::by_id(id).unwrap();
+            assert_eq!(table.id(), id as u32);
+            assert_eq!(table.translate_codon(b"ATG"), b'M');
+            assert!(table.is_start_codon(b"ATG"), "table {}", id);
+        }
+        
+        // Stop codons differ between tables
+        let standard = CodonTable::standard();
+        assert!(standard.is_stop_codon(b"TGA"));
+        assert!(!CodonTable::by_id(4).unwrap().is_stop_codon(b"TGA"));
+        assert!(CodonTable::by_id(2).unwrap().is_stop_codon(b"AGG"));
+        assert_eq!(CodonTable::by_id(6).unwrap().translate_codon(b"TAA"), b'Q');
+        assert_eq!(CodonTable::by_id(3).unwrap().translate_codon(b"CTT"), b'T');
+        assert_eq!(CodonTable::by_id(23).unwrap().translate_codon(b"TTA"), b'*');
+        
+        // So do alternative starts
+        assert!(CodonTable::by_id(4).unwrap().is_start_codon(b"TTA"));
+        assert!(!CodonTable::bacterial().is_start_codon(b"TTA"));
+        assert!(CodonTable::by_id(2).unwrap().is_start_codon(b"ATA"));
+        assert!(!CodonTable::by_id(6).unwrap().is_start_codon(b"GTG"));
+        assert_eq!(CodonTable::by_id(12).unwrap().start_codons(), &[*b"CTG", *b"ATG"]);
+    }
+    
+    #[test]
+    fn test_codon_usage() {
+        // Two leucine codons, one ATG, an ambiguous codon and a partial codon
+        let usage = CodonTable::standard().codon_usage(b"CTGCTGTTAATGNNNTA");
+        assert_eq!(usage.len(), 64);
+        assert_eq!(usage[0].codon, *b"TTT");
+        
+        let find = |codon: &[u8; 3]| usage.iter().find(|u| &u.codon == codon).unwrap();
+        assert_eq!(find(b"CTG").count, 2);
+        assert!((find(b"CTG").fraction - 2.0 / 3.0).abs() < 1e-12);
+        assert_eq!(find(b"ATG").fraction, 1.0);
+        assert_eq!(find(b"TGG").fraction, 0.0);
+        assert_eq!(usage.iter().map(|u| u.count).sum::<usize>(), 4);
+        
+        // Synonymous groups follow the table
+        let usage = CodonTable::vertebrate_mitochondrial().codon_usage(b"ATAATG");
+        assert_eq!(usage.iter().find(|u| &u.codon == b"ATA").unwrap().amino_acid, b'M');
+        assert_eq!(usage.iter().find(|u| &u.codon == b"ATA").unwrap().fraction, 0.5);
+    }
+}
@@ -0,0 +1,182 @@
+//! Melting temperature estimation
+//!
+//! This module estimates the melting temperature (Tm) of DNA oligos using
+//! either the Wallace rule or the nearest-neighbor thermodynamic model with
+//! the unified parameters of SantaLucia (1998).
+
+use super::sequence::{SequenceError, SequenceResult};
+
+/// Oligos shorter than this use the Wallace rule when the method is `Auto`
+pub const WALLACE_MAX_LENGTH: usize = 14;
+
+/// Gas constant in cal/(K·mol)
+const GAS_CONSTANT: f64 = 1.987;
+
+/// Nearest-neighbor enthalpy (kcal/mol) and entropy (cal/(K·mol)) for each
+/// dinucleotide stack, from SantaLucia (1998)
+const NN_PARAMS: [(&[u8; 2], f64, f64); 16] = [
+    (b"AA", -7.9, -22.2), (b"TT", -7.9, -22.2),
+    (b"AT", -7.2, -20.4),
+    (b"TA", -7.2, -21.3),
+    (b"CA", -8.5, -22.7), (b"TG", -8.5, -22.7),
+    (b"GT", -8.4, -22.4), (b"AC", -8.4, -22.4),
+    (b"CT", -7.8, -21.0), (b"AG", -7.8, -21.0),
+    (b"GA", -8.2, -22.2), (b"TC", -8.2, -22.2),
+    (b"CG", -10.6, -27.2),
+    (b"GC", -9.8, -24.4),
+    (b"GG", -8.0, -19.9), (b"CC", -8.0, -19.9),
+];
+
+/// Initiation terms for a terminal G·C or A·T pair
+const INIT_GC: (f64, f64) = (0.1, -2.8);
+const INIT_AT: (f64, f64) = (2.3, 4.1);
+
+/// Entropy penalty for self-complementary duplexes
+const SYMMETRY_ENTROPY: f64 = -1.4;
+
+/// Method used to estimate a melting temperature
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TmMethod {
+    /// Wallace rule below 14 nt, nearest-neighbor with default conditions otherwise
+    #[default]
+    Auto,
+    /// Wallace rule: 4·(G+C) + 2·(A+T)
+    Wallace,
+    /// Nearest-neighbor thermodynamics with a salt correction
+    NearestNeighbor {
+        /// Monovalent cation (Na+) concentration in mM
+        sodium_mm: f64,
+        /// Concentration of each strand in nM
+        strand_nm: f64,
+    },
+}
+
+impl TmMethod {
+    /// Nearest-neighbor method with 50 mM Na+ and 25 nM of each strand
+    pub fn nearest_neighbor() -> Self {
+        TmMethod::NearestNeighbor { sodium_mm: 50.0, strand_nm: 25.0 }
+    }
+}
+
+/// Estimate the melting temperature of a DNA oligo in °C
+///
+/// Bases are case-insensitive; anything other than `ACGT` is an error.
+pub fn melting_temperature(oligo: &[u8], method: TmMethod) -> SequenceResult<f64> {
+    let bases = normalize_oligo(oligo)?;
+    
+    match method {
+        TmMethod::Auto if bases.len() < WALLACE_MAX_LENGTH => Ok(wallace_tm(&bases)),
+        TmMethod::Auto => nearest_neighbor_tm(&bases, 50.0, 25.0),
+        TmMethod::Wallace => Ok(wallace_tm(&bases)),
+        TmMethod::NearestNeighbor { sodium_mm, strand_nm } => nearest_neighbor_tm(&bases, sodium_mm, strand_nm),
+    }
+}
+
+/// Uppercase an oligo, rejecting ambiguous or non-DNA bases
+fn normalize_oligo(oligo: &[u8]) -> SequenceResult<Vec<u8>> {
+    if oligo.is_empty() {
+        return Err(SequenceError::InvalidSequence(
+            "Cannot calculate the melting temperature of an empty sequence".to_string()
+        ));
+    }
+    
+    oligo.iter()
+        .map(|&b| match b.to_ascii_uppercase() {
+            base @ (b'A' | b'C' | b'G' | b'T') => Ok(base),
+            _ => Err(SequenceError::InvalidSequence(format!(
+                "Cannot calculate melting temperature with base '{}'",
+                b as char
+            ))),
+        })
+        .collect()
+}
+
+/// Wallace rule for short oligos
+fn wallace_tm(bases: &[u8]) -> f64 {
+    let gc = bases.iter().filter(|&&b| b == b'G' || b == b'C').count();
+    let at = bases.len() - gc;
+    (4 * gc + 2 * at) as f64
+}
+
+/// Nearest-neighbor Tm with the SantaLucia (1998) entropy salt correction
+fn nearest_neighbor_tm(bases: &[u8], sodium_mm: f64, strand_nm: f64) -> SequenceResult<f64> {
+    if bases.len() < 2 {
+        return Err(SequenceError::InvalidSequence(
+            "Nearest-neighbor melting temperature needs at least two bases".to_string()
+        ));
+    }
+    if sodium_mm <= 0.0 || strand_nm <= 0.0 {
+        return Err(SequenceError::InvalidSequence(
+            "Salt and strand concentrations must be positive".to_string()
+        ));
+    }
+    
+    let mut enthalpy = 0.0;
+    let mut entropy = 0.0;
+    
+    // Sum the stacking terms
+    for pair in bases.windows(2) {
+        let &(_, dh, ds) = NN_PARAMS.iter()
+            .find(|(stack, _, _)| stack[..] == *pair)
+            .expect("all ACGT dinucleotides have parameters");
+        enthalpy += dh;
+        entropy += ds;
+    }
+    
+    // Initiation terms for each terminal pair
+    for &end in &[bases[0], bases[bases.len() - 1]] {
+        let (dh, ds) = if end == b'G' || end == b'C' { INIT_GC } else { INIT_AT };
+        enthalpy += dh;
+        entropy += ds;
+    }
+    
+    // Self-complementary duplexes pair with themselves
+    let self_complementary = bases.iter().copied().eq(bases.iter().rev().map(|&b| match b {
+        b'A' => b'T',
+        b'T' => b'A',
+        b'C' => b'G',
+        _ => b'C',
+    }));
+    let strand_conc = strand_nm * 1e-9;
+    let effective_conc = if self_complementary {
+        entropy += SYMMETRY_ENTROPY;
+        strand_conc
+    } else {
+        strand_conc / 2.0
+    };
+    
+    // Salt correction on the entropy
+    entropy += 0.368 * (bases.len() - 1) as f64 * (sodium_mm / 1000.0).ln();
+    
+    Ok(enthalpy * 1000.0 / (entropy + GAS_CONSTANT * effective_conc.ln()) - 273.15)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_melting_temperature() {
+        let primer = b"CGTTCCAAAGATGTGGGCATGAGCTTAC";
+        
+        // Wallace rule: 14 G/C and 14 A/T
+        assert_eq!(melting_temperature(primer, TmMethod::Wallace).unwrap(), 84.0);
+        assert_eq!(melting_temperature(b"acgt", TmMethod::Wallace).unwrap(), 12.0);
+        
+        // Nearest-neighbor matches common primer tools
+        let tm = melting_temperature(primer, TmMethod::nearest_neighbor()).unwrap();
+        assert!((tm - 60.32).abs() < 0.01, "{}", tm);
+        
+        // Auto picks the method by length
+        assert_eq!(melting_temperature(b"ACGTACGT", TmMethod::Auto).unwrap(), 24.0);
+        assert_eq!(melting_temperature(primer, TmMethod::Auto).unwrap(), tm);
+        
+        // More salt stabilizes the duplex
+        let salty = melting_temperature(primer, TmMethod::NearestNeighbor { sodium_mm: 500.0, strand_nm: 25.0 }).unwrap();
+        assert!(salty > tm);
+        
+        // Ambiguous bases are errors
+        assert!(melting_temperature(b"ACGNT", TmMethod::Wallace).is_err());
+        assert!(melting_temperature(b"", TmMethod::Auto).is_err());
+    }
+}
@@ -4,6 +4,12 @@
 
 pub mod sequence;
 pub mod alphabet;
+pub mod codon;
+pub mod motif;
+pub mod melting;
+pub mod stats;
+pub mod msa;
+pub mod protein;
 
 use crate::engines;
 
@@ -13,8 +19,21 @@ pub fn initialize() {
 }
 
 /// Convenience re-exports
-pub use sequence::{Sequence, SequenceView, SequenceError};
-pub use alphabet::{Alphabet, DNAAlphabet, RNAAlphabet, ProteinAlphabet};
+pub use sequence::{Sequence, SequenceView, ReverseComplementView, SequenceError, Orf, Strand};
+pub use alphabet::{
+    Alphabet, AlphabetGuess, AlphabetKind, CustomAlphabet, DNAAlphabet, Gapped, GappedDNAAlphabet, GappedProteinAlphabet,
+    GappedRNAAlphabet, IupacDNAAlphabet, ProteinAlphabet, RNAAlphabet,
+};
+pub use codon::{CodonTable, CodonUsage, PartialCodon};
+pub use motif::Motif;
+pub use melting::TmMethod;
+pub use protein::{AmbiguousResidues, MassType};
+pub use stats::{assembly_stats, AssemblyStats};
+pub use msa::{consensus, position_frequency_matrix, PositionFrequencyMatrix};
+pub use crate::engines::compute::checksum::ChecksumAlgorithm;
+pub use crate::engines::compute::alignment::{
+    Alignment, AlignmentType, Scoring, ScoringScheme, SubstitutionMatrix,
+};
 
 #[cfg(test)]
 mod tests {
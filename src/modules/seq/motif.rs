@@ -0,0 +1,245 @@
+//! Sequence motif matching
+//!
+//! This module compiles a restricted, regular-expression-like motif syntax
+//! into a small NFA specialized for sequence data. Supported syntax:
+//!
+//! - literal residues (`A`, `C`, ...), matched case-insensitively
+//! - IUPAC nucleotide codes (`R`, `Y`, `N`, ...) for nucleotide motifs,
+//!   and `X` as a wildcard for protein motifs
+//! - character classes such as `[AG]`
+//! - bounded repeats such as `N{3}` or `N{3,5}`
+
+use super::sequence::{SequenceError, SequenceResult};
+
+/// A single NFA state: the residues it accepts and whether it can be skipped
+#[derive(Clone)]
+struct MotifState {
+    allowed: [bool; 256],
+    optional: bool,
+}
+
+/// A compiled sequence motif
+#[derive(Clone)]
+pub struct Motif {
+    states: Vec<MotifState>,
+}
+
+impl Motif {
+    /// Compile a motif pattern
+    ///
+    /// When `nucleotide` is true, letters are interpreted as IUPAC nucleotide
+    /// codes (with `T` and `U` interchangeable); otherwise letters are literal
+    /// amino acids and `X` matches any residue.
+    pub fn compile(pattern: &str, nucleotide: bool) -> SequenceResult<Self> {
+        let chars: Vec<char> = pattern.chars().collect();
+        let mut states = Vec::new();
+        let mut i = 0;
+        
+        while i < chars.len() {
+            // Parse a single element: a residue code or a character class
+            let allowed = match chars[i] {
+                '[' => {
+                    let close = chars[i..].iter().position(|&c| c == ']').ok_or_else(|| {
+                        invalid_pattern(pattern, "unclosed character class")
+                    })? + i;
+                    
+                    if close == i + 1 {
+                        return Err(invalid_pattern(pattern, "empty character class"));
+                    }
+                    
+                    let mut allowed = [false; 256];
+                    for &c in &chars[i + 1..close] {
+                        let member = residue_set(c, nucleotide)
+                            .ok_or_else(|| invalid_pattern(pattern, &format!("unexpected '{}' in character class", c)))?;
+                        for (slot, &ok) in allowed.iter_mut().zip(member.iter()) {
+                            *slot |= ok;
+                        }
+                    }
+                    
+                    i = close + 1;
+                    allowed
+                },
+                c => {
+                    let allowed = residue_set(c, nucleotide)
+                        .ok_or_else(|| invalid_pattern(pattern, &format!("unexpected '{}'", c)))?;
+                    i += 1;
+                    allowed
+                },
+            };
+            
+            // Parse an optional bounded repeat
+            let (min, max) = if chars.get(i) == Some(&'{') {
+                let close = chars[i..].iter().position(|&c| c == '}').ok_or_else(|| {
+                    invalid_pattern(pattern, "unclosed repeat")
+                })? + i;
+                
+                let spec: String = chars[i + 1..close].iter().collect();
+                i = close + 1;
+                parse_repeat(&spec).ok_or_else(|| {
+                    invalid_pattern(pattern, &format!("invalid repeat '{{{}}}'", spec))
+                })?
+            } else {
+                (1, 1)
+            };
+            
+            // Expand the repeat into required states followed by optional ones
+            for n in 0..max {
+                states.push(MotifState { allowed, optional: n >= min });
+            }
+        }
+        
+        if states.is_empty() {
+            return Err(invalid_pattern(pattern, "pattern matches nothing"));
+        }
+        
+        Ok(Self { states })
+    }
+    
+    /// Find non-overlapping matches as half-open `(start, end)` spans
+    ///
+    /// Matches are reported leftmost first, taking the longest match at
+    /// each start position.
+    pub fn find_all(&self, sequence: &[u8]) -> Vec<(usize, usize)> {
+        let mut matches = Vec::new();
+        let mut start = 0;
+        
+        while start < sequence.len() {
+            match self.longest_match_at(sequence, start) {
+                Some(end) if end > start => {
+                    matches.push((start, end));
+                    start = end;
+                },
+                _ => start += 1,
+            }
+        }
+        
+        matches
+    }
+    
+    /// Simulate the NFA from `start`, returning the end of the longest match
+    fn longest_match_at(&self, sequence: &[u8], start: usize) -> Option<usize> {
+        let accept = self.states.len();
+        let mut active = vec![false; accept + 1];
+        active[0] = true;
+        self.follow_optional(&mut active);
+        
+        let mut best = if active[accept] { Some(start) } else { None };
+        
+        for (pos, &byte) in sequence.iter().enumerate().skip(start) {
+            let mut next = vec![false; accept + 1];
+            for (s, state) in self.states.iter().enumerate() {
+                if active[s] && state.allowed[byte as usize] {
+                    next[s + 1] = true;
+                }
+            }
+            self.follow_optional(&mut next);
+            
+            if !next.iter().any(|&a| a) {
+                break;
+            }
+            
+            if next[accept] {
+                best = Some(pos + 1);
+            }
+            active = next;
+        }
+        
+        best
+    }
+    
+    /// Add the states reachable by skipping optional states
+    fn follow_optional(&self, active: &mut [bool]) {
+        for s in 0..self.states.len() {
+            if active[s] && self.states[s].optional {
+                active[s + 1] = true;
+            }
+        }
+    }
+}
+
+/// Get the set of bytes matched by a residue code
+fn residue_set(code: char, nucleotide: bool) -> Option<[bool; 256]> {
+    if !code.is_ascii_alphabetic() {
+        return None;
+    }
+    
+    let upper = code.to_ascii_uppercase();
+    let members: &str = if nucleotide {
+        match upper {
+            'A' => "A",
+            'C' => "C",
+            'G' => "G",
+            'T' | 'U' => "TU",
+            'R' => "AG",
+            'Y' => "CTU",
+            'S' => "GC",
+            'W' => "ATU",
+            'K' => "GTU",
+            'M' => "AC",
+            'B' => "CGTU",
+            'D' => "AGTU",
+            'H' => "ACTU",
+            'V' => "ACG",
+            'N' => "ACGTU",
+            _ => return None,
+        }
+    } else if upper == 'X' {
+        "ABCDEFGHIJKLMNOPQRSTUVWXYZ"
+    } else {
+        // Any other letter is a literal amino acid
+        let mut allowed = [false; 256];
+        allowed[upper as usize] = true;
+        allowed[code.to_ascii_lowercase() as usize] = true;
+        return Some(allowed);
+    };
+    
+    let mut allowed = [false; 256];
+    for b in members.bytes() {
+        allowed[b as usize] = true;
+        allowed[b.to_ascii_lowercase() as usize] = true;
+    }
+    Some(allowed)
+}
+
+/// Parse a repeat specification of the form `n` or `n,m`
+fn parse_repeat(spec: &str) -> Option<(usize, usize)> {
+    let (min, max) = match spec.split_once(',') {
+        Some((min, max)) => (min.trim().parse().ok()?, max.trim().parse().ok()?),
+        None => {
+            let n = spec.trim().parse().ok()?;
+            (n, n)
+        },
+    };
+    
+    if max == 0 || min > max {
+        return None;
+    }
+    
+    Some((min, max))
+}
+
+fn invalid_pattern(pattern: &str, reason: &str) -> SequenceError {
+    SequenceError::InvalidSequence(format!("Invalid motif pattern '{}': {}", pattern, reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_motif_compile() {
+        // Protein motif with a wildcard and a class
+        let motif = Motif::compile("CX{2}[DE]", false).unwrap();
+        assert_eq!(motif.find_all(b"MKCAAEW"), vec![(2, 6)]);
+        
+        // IUPAC codes expand for nucleotides
+        let motif = Motif::compile("GR", true).unwrap();
+        assert_eq!(motif.find_all(b"GAGCGG"), vec![(0, 2), (4, 6)]);
+        
+        // Malformed patterns are rejected
+        assert!(Motif::compile("", true).is_err());
+        assert!(Motif::compile("A[CG", true).is_err());
+        assert!(Motif::compile("N{5,3}", true).is_err());
+        assert!(Motif::compile("A*", true).is_err());
+    }
+}
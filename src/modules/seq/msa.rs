@@ -0,0 +1,237 @@
+//! Multiple sequence alignment summaries
+//!
+//! This module summarizes the columns of sequences that have already been
+//! aligned to a common length: per-column residue counts, for sequence
+//! logos and profiles, and a threshold consensus sequence.
+
+use super::alphabet::is_gap;
+use super::sequence::{Sequence, SequenceError, SequenceResult};
+
+/// Symbol under which all gap characters (`-` and `.`) are counted
+pub const GAP: u8 = b'-';
+
+/// Residue counts for each column of an alignment
+///
+/// Residues are counted case-insensitively as uppercase, and both gap
+/// characters are counted as [`GAP`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionFrequencyMatrix {
+    /// Symbols seen in any column, in ascending byte order
+    symbols: Vec<u8>,
+    /// Counts for each column, indexed like `symbols`
+    counts: Vec<Vec<usize>>,
+    /// Number of aligned sequences
+    depth: usize,
+}
+
+impl PositionFrequencyMatrix {
+    /// Get the number of columns
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+    
+    /// Check if the alignment has no columns
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+    
+    /// Get the number of aligned sequences
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+    
+    /// Get the symbols seen in the alignment, in ascending byte order
+    pub fn symbols(&self) -> &[u8] {
+        &self.symbols
+    }
+    
+    /// Get the counts of one column, indexed like [`symbols`](Self::symbols)
+    pub fn column(&self, column: usize) -> &[usize] {
+        &self.counts[column]
+    }
+    
+    /// Count occurrences of a symbol in a column
+    pub fn count(&self, column: usize, symbol: u8) -> usize {
+        let symbol = normalize(symbol);
+        match self.symbols.binary_search(&symbol) {
+            Ok(index) => self.counts[column][index],
+            Err(_) => 0,
+        }
+    }
+    
+    /// Get the fraction of sequences with a symbol in a column
+    pub fn frequency(&self, column: usize, symbol: u8) -> f64 {
+        if self.depth == 0 {
+            0.0
+        } else {
+            self.count(column, symbol) as f64 / self.depth as f64
+        }
+    }
+}
+
+/// Count the residues in each column of aligned sequences
+///
+/// All sequences must have the same length and alphabet.
+pub fn position_frequency_matrix(aligned: &[Sequence]) -> SequenceResult<PositionFrequencyMatrix> {
+    let width = check_aligned(aligned)?;
+    
+    let mut table = vec![[0usize; 256]; width];
+    for sequence in aligned {
+        for (column, &residue) in table.iter_mut().zip(sequence.as_bytes().iter()) {
+            column[normalize(residue) as usize] += 1;
+        }
+    }
+    
+    let symbols: Vec<u8> = (0..=255u8)
+        .filter(|&symbol| table.iter().any(|column| column[symbol as usize] > 0))
+        .collect();
+    let counts = table
+        .iter()
+        .map(|column| symbols.iter().map(|&symbol| column[symbol as usize]).collect())
+        .collect();
+    
+    Ok(PositionFrequencyMatrix { symbols, counts, depth: aligned.len() })
+}
+
+/// Build a consensus of aligned sequences
+///
+/// Each column gets its most frequent residue when that residue's share of
+/// all sequences, gaps included, is at least `threshold`, and otherwise `N`
+/// (`X` for proteins). A column where gaps are strictly the most frequent
+/// symbol gets a gap instead; gaps lose ties with residues, and ties between
+/// residues go to the lowest byte value. The consensus has the alphabet of
+/// the inputs.
+pub fn consensus(aligned: &[Sequence], threshold: f64) -> SequenceResult<Sequence> {
+    if !(0.0..=1.0).contains(&threshold) {
+        return Err(SequenceError::InvalidSequence(format!(
+            "Consensus threshold {} is not between 0 and 1",
+            threshold
+        )));
+    }
+    
+    let matrix = position_frequency_matrix(aligned)?;
+    let ambiguous = if aligned[0].alphabet_name() == "Protein" { b'X' } else { b'N' };
+    let gap_index = matrix.symbols.binary_search(&GAP).ok();
+    
+    let residues = matrix
+        .counts
+        .iter()
+        .map(|column| {
+            // Most frequent residue; `max_by_key` keeps the last maximum, so scan in reverse
+            let best = column
+                .iter()
+                .enumerate()
+                .rev()
+                .filter(|&(index, _)| Some(index) != gap_index)
+                .max_by_key(|&(_, &count)| count);
+            let gaps = gap_index.map_or(0, |index| column[index]);
+            
+            let (symbol, count) = match best {
+                Some((index, &count)) if count >= gaps => (matrix.symbols[index], count),
+                _ => (GAP, gaps),
+            };
+            if count as f64 >= threshold * matrix.depth as f64 {
+                symbol
+            } else {
+                ambiguous
+            }
+        })
+        .collect();
+    
+    Ok(aligned[0].with_same_alphabet(residues))
+}
+
+/// Check that sequences share a length and alphabet, returning the length
+fn check_aligned(aligned: &[Sequence]) -> SequenceResult<usize> {
+    let first = aligned.first().ok_or_else(|| {
+        SequenceError::InvalidSequence("No aligned sequences given".to_string())
+    })?;
+    
+    for (i, sequence) in aligned.iter().enumerate().skip(1) {
+        if sequence.len() != first.len() {
+            return Err(SequenceError::InvalidSequence(format!(
+                "Aligned sequence {} has length {}, expected {}",
+                i, sequence.len(), first.len()
+            )));
+        }
+        if sequence.alphabet_name() != first.alphabet_name() {
+            return Err(SequenceError::InvalidAlphabet(format!(
+                "Aligned sequence {} is {}, expected {}",
+                i, sequence.alphabet_name(), first.alphabet_name()
+            )));
+        }
+    }
+    
+    Ok(first.len())
+}
+
+/// Uppercase residues and fold both gap characters into [`GAP`]
+fn normalize(symbol: u8) -> u8 {
+    if is_gap(symbol) {
+        GAP
+    } else {
+        symbol.to_ascii_uppercase()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::seq::alphabet::{GappedDNAAlphabet, GappedProteinAlphabet};
+    
+    fn gapped_dna(data: &[u8]) -> Sequence {
+        Sequence::with_alphabet(data, GappedDNAAlphabet::default()).unwrap()
+    }
+    
+    #[test]
+    fn test_position_frequency_matrix() {
+        let aligned = [gapped_dna(b"ACG-"), gapped_dna(b"acT."), gapped_dna(b"AGT-")];
+        let matrix = position_frequency_matrix(&aligned).unwrap();
+        
+        assert_eq!(matrix.len(), 4);
+        assert_eq!(matrix.depth(), 3);
+        assert_eq!(matrix.symbols(), b"-ACGT");
+        assert_eq!(matrix.column(0), &[0, 3, 0, 0, 0]);
+        assert_eq!(matrix.count(1, b'c'), 2);
+        assert_eq!(matrix.count(3, b'.'), 3);
+        assert_eq!(matrix.count(0, b'N'), 0);
+        assert!((matrix.frequency(2, b'T') - 2.0 / 3.0).abs() < 1e-12);
+    }
+    
+    #[test]
+    fn test_consensus() {
+        let aligned = [
+            gapped_dna(b"ACGTA-"),
+            gapped_dna(b"ACGAC-"),
+            gapped_dna(b"ATCA--"),
+            gapped_dna(b"AT-TGG"),
+        ];
+        
+        // Columns: unanimous, 2/2 tie, 2/1/1 with a gap, 2/2 tie, all different, gap majority
+        assert_eq!(consensus(&aligned, 0.5).unwrap().as_string(), "ACGAN-");
+        assert_eq!(consensus(&aligned, 0.0).unwrap().as_string(), "ACGAA-");
+        assert_eq!(consensus(&aligned, 0.75).unwrap().as_string(), "ANNNN-");
+        assert_eq!(consensus(&aligned, 1.0).unwrap().as_string(), "ANNNNN");
+        
+        // Gaps lose ties with residues
+        let tied = [gapped_dna(b"A"), gapped_dna(b"-")];
+        assert_eq!(consensus(&tied, 0.5).unwrap().as_string(), "A");
+        
+        // Proteins fall back to X
+        let proteins = [
+            Sequence::with_alphabet(b"MK-", GappedProteinAlphabet::default()).unwrap(),
+            Sequence::with_alphabet(b"ML-", GappedProteinAlphabet::default()).unwrap(),
+        ];
+        assert_eq!(consensus(&proteins, 0.6).unwrap().as_string(), "MX-");
+    }
+    
+    #[test]
+    fn test_consensus_rejects_unaligned() {
+        assert!(consensus(&[], 0.5).is_err());
+        assert!(consensus(&[gapped_dna(b"ACGT")], 1.5).is_err());
+        assert!(consensus(&[gapped_dna(b"ACGT"), gapped_dna(b"ACG")], 0.5).is_err());
+        
+        let protein = Sequence::new_protein(b"MKLV").unwrap();
+        assert!(position_frequency_matrix(&[gapped_dna(b"ACGT"), protein]).is_err());
+    }
+}
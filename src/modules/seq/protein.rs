@@ -0,0 +1,241 @@
+//! Protein physicochemical properties
+//!
+//! This module computes the molecular weight and isoelectric point of
+//! protein sequences with the residue masses and Bjellqvist pK values used
+//! by ExPASy ProtParam.
+
+use super::sequence::{SequenceError, SequenceResult};
+
+/// Amino acid residue masses in Da: (residue, average, monoisotopic)
+///
+/// A residue is an amino acid less one water, as it sits in a peptide
+/// chain. Includes selenocysteine (`U`) and pyrrolysine (`O`).
+const RESIDUE_MASSES: [(u8, f64, f64); 22] = [
+    (b'A', 71.0788, 71.03711),
+    (b'C', 103.1388, 103.00919),
+    (b'D', 115.0886, 115.02694),
+    (b'E', 129.1155, 129.04259),
+    (b'F', 147.1766, 147.06841),
+    (b'G', 57.0519, 57.02146),
+    (b'H', 137.1411, 137.05891),
+    (b'I', 113.1594, 113.08406),
+    (b'K', 128.1741, 128.09496),
+    (b'L', 113.1594, 113.08406),
+    (b'M', 131.1926, 131.04049),
+    (b'N', 114.1038, 114.04293),
+    (b'O', 237.2982, 237.14773),
+    (b'P', 97.1167, 97.05276),
+    (b'Q', 128.1307, 128.05858),
+    (b'R', 156.1875, 156.10111),
+    (b'S', 87.0782, 87.03203),
+    (b'T', 101.1051, 101.04768),
+    (b'U', 150.0388, 150.95364),
+    (b'V', 99.1326, 99.06841),
+    (b'W', 186.2132, 186.07931),
+    (b'Y', 163.1760, 163.06333),
+];
+
+/// Mass of the water completing the chain termini: (average, monoisotopic)
+const WATER_MASS: (f64, f64) = (18.01524, 18.01056);
+
+/// The 20 standard amino acids, averaged over for `X`
+const STANDARD_AMINO_ACIDS: &[u8; 20] = b"ACDEFGHIKLMNPQRSTVWY";
+
+/// Side-chain pK values of basic residues
+const POSITIVE_PKS: [(u8, f64); 3] = [(b'K', 10.0), (b'R', 12.0), (b'H', 5.98)];
+
+/// Side-chain pK values of acidic residues
+const NEGATIVE_PKS: [(u8, f64); 4] = [(b'D', 4.05), (b'E', 4.45), (b'C', 9.0), (b'Y', 10.0)];
+
+/// Default pK of the N-terminal amine, and values for specific first residues
+const N_TERMINAL_PK: f64 = 7.5;
+const N_TERMINAL_PKS: [(u8, f64); 7] = [
+    (b'A', 7.59), (b'M', 7.0), (b'S', 6.93), (b'P', 8.36), (b'T', 6.82), (b'V', 7.44), (b'E', 7.7),
+];
+
+/// Default pK of the C-terminal carboxyl, and values for specific last residues
+const C_TERMINAL_PK: f64 = 3.55;
+const C_TERMINAL_PKS: [(u8, f64); 2] = [(b'D', 4.55), (b'E', 4.75)];
+
+/// Precision of the isoelectric point bisection, in pH units
+const PI_TOLERANCE: f64 = 1e-4;
+
+/// Which residue masses to use for molecular weights
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MassType {
+    /// Average masses over natural isotope abundances, as ProtParam reports
+    #[default]
+    Average,
+    /// Masses of the most abundant isotopes
+    Monoisotopic,
+}
+
+/// How to treat the ambiguous residues `B`, `Z`, `J` and `X`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AmbiguousResidues {
+    /// Reject sequences containing ambiguous residues
+    #[default]
+    Error,
+    /// Average over the residues each code stands for: `B` is D/N, `Z` is
+    /// E/Q, `J` is I/L and `X` is any of the 20 standard amino acids. For the
+    /// isoelectric point, `B` and `Z` carry half the charge of D and E, and
+    /// `X` none.
+    Average,
+}
+
+/// Calculate the molecular weight of a protein in Da
+///
+/// This is the sum of the residue masses plus one water for the termini.
+/// Residues are case-insensitive; characters that are not amino acid
+/// codes, including `*`, are an error.
+pub fn molecular_weight(protein: &[u8], mass_type: MassType, ambiguous: AmbiguousResidues) -> SequenceResult<f64> {
+    let residues = normalize_protein(protein, ambiguous)?;
+    
+    let mut weight = 0.0;
+    for &residue in &residues {
+        weight += match residue {
+            b'B' => (residue_mass(b'D', mass_type) + residue_mass(b'N', mass_type)) / 2.0,
+            b'Z' => (residue_mass(b'E', mass_type) + residue_mass(b'Q', mass_type)) / 2.0,
+            b'J' => (residue_mass(b'I', mass_type) + residue_mass(b'L', mass_type)) / 2.0,
+            b'X' => {
+                STANDARD_AMINO_ACIDS.iter().map(|&aa| residue_mass(aa, mass_type)).sum::<f64>()
+                    / STANDARD_AMINO_ACIDS.len() as f64
+            },
+            _ => residue_mass(residue, mass_type),
+        };
+    }
+    
+    let water = match mass_type {
+        MassType::Average => WATER_MASS.0,
+        MassType::Monoisotopic => WATER_MASS.1,
+    };
+    Ok(weight + water)
+}
+
+/// Calculate the isoelectric point of a protein
+///
+/// Finds the pH at which the net charge is zero by bisection, using the
+/// Bjellqvist pK values with terminal pKs that depend on the first and
+/// last residues.
+pub fn isoelectric_point(protein: &[u8], ambiguous: AmbiguousResidues) -> SequenceResult<f64> {
+    let residues = normalize_protein(protein, ambiguous)?;
+    
+    let mut counts = [0.0f64; 256];
+    for &residue in &residues {
+        match residue {
+            b'B' => counts[b'D' as usize] += 0.5,
+            b'Z' => counts[b'E' as usize] += 0.5,
+            _ => counts[residue as usize] += 1.0,
+        }
+    }
+    
+    let n_terminal = lookup_pk(&N_TERMINAL_PKS, residues[0]).unwrap_or(N_TERMINAL_PK);
+    let c_terminal = lookup_pk(&C_TERMINAL_PKS, residues[residues.len() - 1]).unwrap_or(C_TERMINAL_PK);
+    
+    // Net charge decreases monotonically with pH
+    let charge_at = |ph: f64| {
+        let positive = |pk: f64| 1.0 / (1.0 + 10f64.powf(ph - pk));
+        let negative = |pk: f64| 1.0 / (1.0 + 10f64.powf(pk - ph));
+        
+        let mut charge = positive(n_terminal) - negative(c_terminal);
+        for &(residue, pk) in &POSITIVE_PKS {
+            charge += counts[residue as usize] * positive(pk);
+        }
+        for &(residue, pk) in &NEGATIVE_PKS {
+            charge -= counts[residue as usize] * negative(pk);
+        }
+        charge
+    };
+    
+    let (mut low, mut high) = (0.0, 14.0);
+    while high - low > PI_TOLERANCE {
+        let mid = (low + high) / 2.0;
+        if charge_at(mid) > 0.0 {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    Ok((low + high) / 2.0)
+}
+
+/// Uppercase a protein, rejecting empty sequences and unknown residues
+fn normalize_protein(protein: &[u8], ambiguous: AmbiguousResidues) -> SequenceResult<Vec<u8>> {
+    if protein.is_empty() {
+        return Err(SequenceError::InvalidSequence(
+            "Cannot calculate properties of an empty protein".to_string()
+        ));
+    }
+    
+    protein.iter()
+        .map(|&b| match b.to_ascii_uppercase() {
+            residue if RESIDUE_MASSES.iter().any(|&(aa, _, _)| aa == residue) => Ok(residue),
+            residue @ (b'B' | b'Z' | b'J' | b'X') if ambiguous == AmbiguousResidues::Average => Ok(residue),
+            _ => Err(SequenceError::InvalidSequence(format!(
+                "Cannot calculate protein properties with residue '{}'",
+                b as char
+            ))),
+        })
+        .collect()
+}
+
+/// Get the mass of an unambiguous amino acid
+fn residue_mass(residue: u8, mass_type: MassType) -> f64 {
+    let &(_, average, monoisotopic) = RESIDUE_MASSES.iter()
+        .find(|&&(aa, _, _)| aa == residue)
+        .expect("residues are normalized before lookup");
+    match mass_type {
+        MassType::Average => average,
+        MassType::Monoisotopic => monoisotopic,
+    }
+}
+
+/// Look up the pK of a residue in a table
+fn lookup_pk(table: &[(u8, f64)], residue: u8) -> Option<f64> {
+    table.iter().find(|&&(aa, _)| aa == residue).map(|&(_, pk)| pk)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// Human ubiquitin
+    const PROTEIN: &[u8] = b"MQIFVKTLTGKTITLEVEPSDTIENVKAKIQDKEGIPPDQQRLIFAGKQLEDGRTLSDYNIQKESTLHLVLRLRGG";
+    
+    #[test]
+    fn test_molecular_weight() {
+        let weight = molecular_weight(PROTEIN, MassType::Average, AmbiguousResidues::Error).unwrap();
+        assert!((weight - 8564.84).abs() < 0.01, "{}", weight);
+        
+        // A single residue is the free amino acid
+        let glycine = molecular_weight(b"g", MassType::Monoisotopic, AmbiguousResidues::Error).unwrap();
+        assert!((glycine - 75.03202).abs() < 1e-9);
+        let dipeptide = molecular_weight(b"GG", MassType::Monoisotopic, AmbiguousResidues::Error).unwrap();
+        assert!((dipeptide - 132.05348).abs() < 1e-9);
+        
+        // Ambiguous residues are averaged only when asked
+        assert!(molecular_weight(b"MB", MassType::Average, AmbiguousResidues::Error).is_err());
+        let b = molecular_weight(b"B", MassType::Average, AmbiguousResidues::Average).unwrap();
+        assert!((b - ((115.0886 + 114.1038) / 2.0 + 18.01524)).abs() < 1e-9);
+        assert!(molecular_weight(b"MX", MassType::Average, AmbiguousResidues::Average).is_ok());
+        
+        assert!(molecular_weight(b"MA*", MassType::Average, AmbiguousResidues::Average).is_err());
+        assert!(molecular_weight(b"", MassType::Average, AmbiguousResidues::Error).is_err());
+    }
+    
+    #[test]
+    fn test_isoelectric_point() {
+        let pi = isoelectric_point(PROTEIN, AmbiguousResidues::Error).unwrap();
+        assert!((pi - 6.56).abs() < 0.01, "{}", pi);
+        
+        // Acidic and basic peptides
+        assert!(isoelectric_point(b"DDEEDD", AmbiguousResidues::Error).unwrap() < 4.0);
+        assert!(isoelectric_point(b"KKRRKK", AmbiguousResidues::Error).unwrap() > 11.0);
+        
+        // Ambiguous residues shift the charge only when averaged
+        assert!(isoelectric_point(b"KZ", AmbiguousResidues::Error).is_err());
+        let with_z = isoelectric_point(b"GKZG", AmbiguousResidues::Average).unwrap();
+        let with_x = isoelectric_point(b"GKXG", AmbiguousResidues::Average).unwrap();
+        assert!(with_z < with_x);
+    }
+}
@@ -3,6 +3,7 @@
 //! This module provides the core sequence types and operations for bioinformatics.
 
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::ops::{Index, Range, RangeBounds}
 
 /// A view into a sequence
@@ -48,6 +49,14 @@ impl<'a> SequenceView<'a> {
         self.sequence.data.subsequence(self.start, self.end)
     }
     
+    /// Borrow the viewed bytes without copying
+    ///
+    /// Returns `None` when the backing storage has no contiguous slice (such
+    /// as packed or on-demand storage); use `as_bytes` in that case.
+    pub fn as_slice(&self) -> Option<&'a [u8]> {
+        self.sequence.data.as_slice().map(|slice| &slice[self.start..self.end])
+    }
+    
     /// Slide the view to a new position
     pub fn slide(&self, offset: isize) -> SequenceResult<Self> {
         let new_start = if offset >= 0 {
@@ -114,6 +123,97 @@ impl<'a> SequenceView<'a> {
     }
 }
 
+/// A lazy reverse complement of a sequence
+///
+/// Residues are read from the underlying storage and complemented on demand,
+/// so a large reverse complement can be written out without holding a second
+/// copy of the sequence. Created by [`Sequence::reverse_complement_view`].
+pub struct ReverseComplementView<'a> {
+    sequence: &'a Sequence,
+}
+
+impl<'a> ReverseComplementView<'a> {
+    /// Get the length of the view
+    pub fn len(&self) -> usize {
+        self.sequence.len()
+    }
+    
+    /// Check if the view is empty
+    pub fn is_empty(&self) -> bool {
+        self.sequence.is_empty()
+    }
+    
+    /// Get residues `start..end` of the reverse complement
+    ///
+    /// The range is clamped to the length of the view.
+    pub fn subsequence(&self, start: usize, end: usize) -> Vec<u8> {
+        let len = self.len();
+        let end = end.min(len);
+        let start = start.min(end);
+        
+        // The range maps onto the mirrored range of the forward strand
+        let mut bytes = self.sequence.data.subsequence(len - end, len - start);
+        bytes.reverse();
+        for base in bytes.iter_mut() {
+            *base = self.sequence.alphabet.complement(*base).unwrap_or(*base);
+        }
+        bytes
+    }
+    
+    /// Iterate over the reverse complement in chunks of `chunk_size` residues
+    pub fn chunks(&self, chunk_size: usize) -> impl Iterator<Item = Vec<u8>> + '_ {
+        let chunk_size = chunk_size.max(1);
+        (0..self.len())
+            .step_by(chunk_size)
+            .map(move |start| self.subsequence(start, start + chunk_size))
+    }
+    
+    /// Iterate over the residues of the reverse complement
+    pub fn iter(&self) -> impl Iterator<Item = u8> + '_ {
+        self.chunks(STREAM_CHUNK_SIZE).flatten()
+    }
+    
+    /// Write the reverse complement to `writer` one chunk at a time
+    pub fn write_to<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        for chunk in self.chunks(STREAM_CHUNK_SIZE) {
+            writer.write_all(&chunk)?;
+        }
+        Ok(())
+    }
+    
+    /// Materialize the view, matching [`Sequence::reverse_complement`]
+    pub fn to_sequence(&self) -> Sequence {
+        Sequence {
+            data: Box::new(InMemoryStorage::new(self.subsequence(0, self.len()))),
+            alphabet: self.sequence.alphabet.clone(),
+            id: self.sequence.id.clone(),
+            description: self.sequence.description.clone().map(|desc| format!("{} (reverse complement)", desc)),
+        }
+    }
+}
+
+impl StorableSequence for ReverseComplementView<'_> {
+    fn len(&self) -> usize {
+        ReverseComplementView::len(self)
+    }
+    
+    fn subsequence(&self, start: usize, end: usize) -> Vec<u8> {
+        ReverseComplementView::subsequence(self, start, end)
+    }
+    
+    fn as_slice(&self) -> Option<&[u8]> {
+        None // Residues are complemented as they are read
+    }
+    
+    fn storage_mode(&self) -> StorageMode {
+        self.sequence.data.storage_mode()
+    }
+    
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>()
+    }
+}
+
 impl fmt::Display for Sequence {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         // Format ID and description if available
@@ -137,6 +237,11 @@ impl fmt::Display for Sequence {
     }
 }
 
+/// Index a single residue
+///
+/// Requires storage with a contiguous slice, such as in-memory storage, and
+/// panics otherwise (e.g. for packed or on-demand storage). Use
+/// [`Sequence::get`] to access any storage backend without panicking.
 impl Index<usize> for Sequence {
     type Output = u8;
     
@@ -144,11 +249,16 @@ impl Index<usize> for Sequence {
         if let Some(slice) = self.data.as_slice() {
             &slice[index]
         } else {
-            panic!("Index out of bounds: {}", index)
+            panic!("Cannot index sequence storage without a contiguous slice; use get({}) instead", index)
         }
     }
 }
 
+/// Index a range of residues
+///
+/// Requires storage with a contiguous slice, such as in-memory storage, and
+/// panics otherwise. Use [`Sequence::get_range`] to access any storage
+/// backend without panicking.
 impl Index<Range<usize>> for Sequence {
     type Output = [u8];
     
@@ -156,7 +266,7 @@ impl Index<Range<usize>> for Sequence {
         if let Some(slice) = self.data.as_slice() {
             &slice[range]
         } else {
-            panic!("Range out of bounds: {:?}", range)
+            panic!("Cannot index sequence storage without a contiguous slice; use get_range({:?}) instead", range)
         }
     }
 }
@@ -192,6 +302,23 @@ impl From<&str> for Sequence {
     }
 }
 
+/// Sequences are equal when they share an alphabet and residues; the id and
+/// description are ignored
+impl PartialEq for Sequence {
+    fn eq(&self, other: &Self) -> bool {
+        self.alphabet_name() == other.alphabet_name() && self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl Eq for Sequence {}
+
+impl Hash for Sequence {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.alphabet_name().hash(state);
+        self.as_bytes().hash(state);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -268,6 +395,15 @@ mod tests {
         // Test count
         let count = dna.count(b"AC");
         assert_eq!(count, 2);
+        
+        // Test approximate matching
+        assert_eq!(dna.find_approximate(b"ACGA", 1), vec![(0, 1), (4, 1)]);
+        assert!(dna.find_approximate(b"ACGA", 0).is_empty());
+        
+        // Test IUPAC pattern matching
+        assert_eq!(dna.find_iupac(b"RCGW").unwrap(), vec![0, 4]);
+        assert!(dna.find_iupac(b"ACGJ").is_err());
+        assert!(Sequence::new_protein(b"ACGT").unwrap().find_iupac(b"ACGT").is_err());
     }
     
     #[test]
@@ -292,374 +428,2535 @@ mod tests {
         let new_seq = view.to_sequence();
         assert_eq!(new_seq.as_bytes().as_ref(), b"GTACGTAC");
     }
-};
-use std::borrow::Cow;
-use thiserror::Error;
-
-use crate::engines::core::memory::PackedDnaStorage;
-use crate::engines::storage::{StorableSequence, InMemoryStorage};
-use crate::engines::compute::string_ops;
-use super::alphabet::{Alphabet, DNAAlphabet, RNAAlphabet, ProteinAlphabet};
-
-/// Error type for sequence operations
-#[derive(Error, Debug)]
-pub enum SequenceError {
-    #[error("Invalid sequence: {0}")]
-    InvalidSequence(String),
     
-    #[error("Invalid alphabet: {0}")]
-    InvalidAlphabet(String),
-    
-    #[error("Index out of bounds: {0}")]
-    IndexOutOfBounds(String),
-    
-    #[error("Operation not supported: {0}")]
-    UnsupportedOperation(String),
-    
-    #[error("Engine error: {0}")]
-    EngineError(#[from] crate::engines::EngineError),
-}
-
-/// Result type for sequence operations
-pub type SequenceResult<T> = Result<T, SequenceError>;
-
-/// Common sequence type for all biological sequences
-#[derive(Clone)]
-pub struct Sequence {
-    /// The sequence data
-    data: Box<dyn StorableSequence>,
-    /// The alphabet used for this sequence
-    alphabet: Box<dyn Alphabet>,
-    /// Identifier for the sequence (optional)
-    id: Option<String>,
-    /// Description of the sequence (optional)
-    description: Option<String>,
-}
-
-impl Sequence {
-    /// Create a new sequence from raw bytes
-    pub fn new(data: &[u8]) -> SequenceResult<Self> {
-        // Detect alphabet
-        let alphabet = super::alphabet::detect_alphabet(data)
-            .ok_or_else(|| SequenceError::InvalidSequence(
-                "Could not detect alphabet for sequence".to_string()
-            ))?;
+    #[test]
+    fn test_kmer_containment() {
+        let reference = Sequence::new_dna(b"TTTTACGTACGGATCCAAAA").unwrap();
+        let query = Sequence::new_dna(b"ACGTACGGATCC").unwrap();
         
-        Ok(Self {
-            data: Box::new(InMemoryStorage::new(data.to_vec())),
-            alphabet,
-            id: None,
-            description: None,
-        })
+        // A query fully contained in the reference has containment 1.0
+        assert_eq!(query.kmer_containment(&reference, 4).unwrap(), 1.0);
+        
+        // Containment is not symmetric
+        assert!(reference.kmer_containment(&query, 4).unwrap() < 1.0);
+        
+        // Unrelated sequences share no k-mers
+        let unrelated = Sequence::new_dna(b"GGGGGGGGGG").unwrap();
+        assert_eq!(query.kmer_containment(&unrelated, 4).unwrap(), 0.0);
+        
+        // k of zero is rejected
+        assert!(query.kmer_containment(&reference, 0).is_err());
     }
     
-    /// Create a new sequence with a specific alphabet
-    pub fn with_alphabet<A: Alphabet + 'static>(data: &[u8], alphabet: A) -> SequenceResult<Self> {
-        // Validate sequence against alphabet
-        if !alphabet.is_valid_sequence(data) {
-            return Err(SequenceError::InvalidSequence(
-                format!("Sequence contains invalid characters for {} alphabet", alphabet.name())
-            ));
-        }
+    #[test]
+    fn test_case_conversion() {
+        let dna = Sequence::new_dna(b"acgtNNACGTnn").unwrap().with_id("masked");
         
-        Ok(Self {
-            data: Box::new(InMemoryStorage::new(data.to_vec())),
-            alphabet: Box::new(alphabet),
-            id: None,
-            description: None,
-        })
+        let upper = dna.to_upper();
+        assert_eq!(upper.as_bytes().as_ref(), b"ACGTNNACGTNN");
+        assert_eq!(upper.id(), Some("masked"));
+        
+        let lower = dna.to_lower();
+        assert_eq!(lower.as_bytes().as_ref(), b"acgtnnacgtnn");
+        assert_eq!(lower.alphabet_name(), "DNA");
     }
     
-    /// Create a new DNA sequence
-    pub fn new_dna(data: &[u8]) -> SequenceResult<Self> {
-        Self::with_alphabet(data, DNAAlphabet::default())
+    #[test]
+    fn test_soft_masking() {
+        let dna = Sequence::new_dna(b"ACGTACGTAC").unwrap();
+        
+        // Overlapping intervals lowercase their union
+        let masked = dna.soft_mask(&[(1, 3), (2, 5), (9, 10)]).unwrap();
+        assert_eq!(masked.as_string(), "AcgtaCGTAc");
+        assert!((masked.masked_fraction() - 0.5).abs() < 1e-12);
+        assert_eq!(dna.masked_fraction(), 0.0);
+        assert_eq!(masked.to_upper().as_string(), "ACGTACGTAC");
+        
+        // Complement, reverse complement and transcription keep the mask
+        assert_eq!(masked.complement().unwrap().as_string(), "TgcatGCATg");
+        assert_eq!(masked.reverse_complement().unwrap().as_string(), "gTACGtacgT");
+        assert_eq!(masked.transcribe().unwrap().as_string(), "AcguaCGUAc");
+        
+        // Intervals must lie within the sequence
+        assert!(dna.soft_mask(&[(8, 11)]).is_err());
+        assert!(dna.soft_mask(&[(5, 4)]).is_err());
+        assert_eq!(Sequence::new_dna(b"").unwrap().masked_fraction(), 0.0);
     }
     
-    /// Create a new RNA sequence
-    pub fn new_rna(data: &[u8]) -> SequenceResult<Self> {
-        Self::with_alphabet(data, RNAAlphabet::default())
+    #[test]
+    fn test_find_regex_motif() {
+        let seq = Sequence::new_dna(b"AACTTAGTCC").unwrap();
+        
+        // Character class
+        assert_eq!(seq.find_regex_motif("A[CG]T").unwrap(), vec![(1, 4), (5, 8)]);
+        
+        // Bounded repeat takes the longest match at each position
+        let short = Sequence::new_dna(b"ACGTA").unwrap();
+        assert_eq!(short.find_regex_motif("N{2,3}").unwrap(), vec![(0, 3), (3, 5)]);
+        
+        // IUPAC codes
+        assert_eq!(seq.find_regex_motif("WAK").unwrap(), vec![(4, 7)]);
+        
+        // Invalid patterns are reported
+        assert!(seq.find_regex_motif("A[CG").is_err());
     }
     
-    /// Create a new protein sequence
-    pub fn new_protein(data: &[u8]) -> SequenceResult<Self> {
-        Self::with_alphabet(data, ProteinAlphabet::default())
+    #[test]
+    fn test_translate() {
+        let dna = Sequence::new_dna(b"ATGGCCTGAAGA").unwrap();
+        
+        // Standard code
+        let protein = dna.translate(CodonTable::standard(), false).unwrap();
+        assert_eq!(protein.as_string(), "MA*R");
+        assert_eq!(protein.alphabet_name(), "Protein");
+        
+        // Vertebrate mitochondrial code
+        let protein = dna.translate(CodonTable::vertebrate_mitochondrial(), false).unwrap();
+        assert_eq!(protein.as_string(), "MAW*");
+        
+        // RNA is translated directly
+        let protein = dna.transcribe().unwrap().translate(CodonTable::default(), false).unwrap();
+        assert_eq!(protein.as_string(), "MA*R");
+        
+        // Partial codons are an error by default, or padded with X
+        let partial = Sequence::new_dna(b"ATGGC").unwrap();
+        assert!(partial.translate(CodonTable::standard(), false).is_err());
+        let padded = partial
+            .translate(CodonTable::standard().with_partial_codon(PartialCodon::Pad), false)
+            .unwrap();
+        assert_eq!(padded.as_string(), "MX");
+        
+        // Codons with N translate to X unless they are synonymous
+        let reads = Sequence::new_dna(b"ATGAANNNNTCN").unwrap();
+        assert_eq!(reads.translate(CodonTable::standard(), false).unwrap().as_string(), "MXXS");
+        
+        // Proteins cannot be translated
+        let protein = Sequence::new_protein(b"MA").unwrap();
+        assert!(protein.translate(CodonTable::standard(), false).is_err());
     }
     
-    /// Set the sequence identifier
-    pub fn with_id(mut self, id: &str) -> Self {
-        self.id = Some(id.to_string());
-        self
+    #[test]
+    fn test_translate_to_stop_and_cds() {
+        let dna = Sequence::new_dna(b"ATGGCCTGAAGAGC").unwrap();
+        
+        // Translation halts before the first stop; the partial codon after it is ignored
+        let protein = dna.translate_to_stop(CodonTable::standard(), false).unwrap();
+        assert_eq!(protein.as_string(), "MA");
+        
+        // Without a stop the whole frame is translated
+        let no_stop = Sequence::new_dna(b"ATGGCC").unwrap();
+        assert_eq!(no_stop.translate_to_stop(CodonTable::standard(), false).unwrap().as_string(), "MA");
+        
+        // A CDS with an alternative start translates it as M and drops the stop
+        let cds = Sequence::new_dna(b"GTGAAACTGTAA").unwrap();
+        assert_eq!(cds.translate_cds(CodonTable::bacterial()).unwrap().as_string(), "MKL");
+        assert_eq!(cds.transcribe().unwrap().translate_cds(CodonTable::bacterial()).unwrap().as_string(), "MKL");
+        
+        // Each CDS requirement is checked
+        let table = CodonTable::standard();
+        assert!(cds.translate_cds(table.clone()).is_err()); // GTG is not a standard start
+        assert!(Sequence::new_dna(b"ATGAAATAAG").unwrap().translate_cds(table.clone()).is_err());
+        assert!(Sequence::new_dna(b"ATGAAACTG").unwrap().translate_cds(table.clone()).is_err());
+        assert!(Sequence::new_dna(b"ATGTAGAAATAA").unwrap().translate_cds(table.clone()).is_err());
+        assert!(Sequence::new_dna(b"ATG").unwrap().translate_cds(table.clone()).is_err());
+        assert!(Sequence::new_protein(b"MA").unwrap().translate_cds(table).is_err());
     }
     
-    /// Set the sequence description
-    pub fn with_description(mut self, description: &str) -> Self {
-        self.description = Some(description.to_string());
-        self
+    #[test]
+    fn test_translate_alternative_start() {
+        // TTG start with an internal TTG and GTG
+        let dna = Sequence::new_dna(b"TTGAAATTGGTGTAA").unwrap();
+        
+        let protein = dna.translate(CodonTable::bacterial(), true).unwrap();
+        assert_eq!(protein.as_string(), "MKLV*");
+        let protein = dna.translate(CodonTable::bacterial(), false).unwrap();
+        assert_eq!(protein.as_string(), "LKLV*");
+        
+        // TTG is not a start codon in the table configured here
+        let protein = dna.translate(CodonTable::standard(), true).unwrap();
+        assert_eq!(protein.as_string(), "LKLV*");
+        
+        // ORFs beginning at TTG in table 11
+        let orfs = dna.find_orfs(1, CodonTable::from_ncbi_id(11).unwrap(), true).unwrap();
+        assert_eq!(orfs.len(), 1);
+        assert_eq!((orfs[0].start, orfs[0].end), (0, 15));
+        assert_eq!(orfs[0].protein.as_string(), "MKLV");
+        
+        let orfs = dna.find_orfs(1, CodonTable::bacterial(), false).unwrap();
+        assert_eq!(orfs[0].protein.as_string(), "LKLV");
+        
+        // The standard table only starts at ATG
+        assert!(dna.find_orfs(1, CodonTable::standard(), true).unwrap().is_empty());
     }
     
-    /// Get the sequence length
-    pub fn len(&self) -> usize {
-        self.data.len()
+    #[test]
+    fn test_find_orfs() {
+        // Forward ORF with a nested in-frame ATG, followed by the reverse
+        // complement of ATG GGT TGA
+        let dna = Sequence::new_dna(b"CCATGAAAATGCCCTAAGGTCAACCCAT").unwrap();
+        
+        let orfs = dna.find_orfs(2, CodonTable::standard(), false).unwrap();
+        assert_eq!(orfs.len(), 2);
+        
+        // The nested ATG shares the outer ORF's stop and is not reported separately
+        assert_eq!(orfs[0].strand, Strand::Forward);
+        assert_eq!(orfs[0].frame, 2);
+        assert_eq!((orfs[0].start, orfs[0].end), (2, 17));
+        assert_eq!(orfs[0].protein.as_string(), "MKMP");
+        
+        // Reverse-strand coordinates refer to the forward sequence
+        assert_eq!(orfs[1].strand, Strand::Reverse);
+        assert_eq!((orfs[1].start, orfs[1].end), (19, 28));
+        assert_eq!(orfs[1].protein.as_string(), "MG");
+        
+        // Short proteins are filtered out
+        let orfs = dna.find_orfs(3, CodonTable::standard(), false).unwrap();
+        assert_eq!(orfs.len(), 1);
+        assert_eq!(orfs[0].protein.as_string(), "MKMP");
     }
     
-    /// Check if the sequence is empty
-    pub fn is_empty(&self) -> bool {
-        self.data.len() == 0
+    #[test]
+    fn test_to_packed_storage() {
+        // A run of Ns and a soft-masked region, as in assembled genomes
+        let mut data = b"ACGT".repeat(100);
+        data.extend_from_slice(&[b'N'; 100]);
+        data.extend_from_slice(&b"acgt".repeat(50));
+        data.extend_from_slice(&b"GATTACA".repeat(100));
+        let dna = Sequence::new_dna(&data).unwrap();
+        let packed = dna.to_packed_storage().unwrap();
+        
+        // Round-trips exactly, including N and lowercase bases
+        assert_eq!(packed.as_bytes().as_ref(), data.as_slice());
+        assert_eq!(packed.subsequence(3, 10).unwrap().as_bytes().as_ref(), &data[3..10]);
+        assert_eq!(packed.gc_content().unwrap(), dna.gc_content().unwrap());
+        
+        // Packed storage is far smaller than the original
+        assert_eq!(packed.data.storage_mode(), crate::engines::storage::StorageMode::Packed);
+        assert!(packed.data.memory_usage() * 3 < dna.data.memory_usage());
+        
+        // Plain ACGT packs to exactly a quarter of the size
+        let plain = Sequence::new_dna(&b"ACGT".repeat(100)).unwrap();
+        assert_eq!(plain.to_packed_storage().unwrap().data.memory_usage(), 100);
+        
+        // Only DNA can be packed
+        assert!(Sequence::new_protein(b"MKV").unwrap().to_packed_storage().is_err());
     }
     
-    /// Get the sequence as a string
-    pub fn as_string(&self) -> String {
-        String::from_utf8_lossy(self.as_bytes()).to_string()
+    #[test]
+    fn test_codons() {
+        let dna = Sequence::new_dna(b"ATGGCCTGAAG").unwrap();
+        let codons = |seq: &Sequence, frame| seq.codons(frame).unwrap().collect::<Vec<_>>();
+        
+        // Trailing partial codons are dropped
+        assert_eq!(codons(&dna, 0), vec![*b"ATG", *b"GCC", *b"TGA"]);
+        assert_eq!(codons(&dna, 1), vec![*b"TGG", *b"CCT", *b"GAA"]);
+        assert_eq!(codons(&dna, 2), vec![*b"GGC", *b"CTG", *b"AAG"]);
+        
+        // RNA and packed storage give the same triplets
+        let rna = dna.transcribe().unwrap();
+        assert_eq!(codons(&rna, 0), vec![*b"AUG", *b"GCC", *b"UGA"]);
+        let packed = dna.to_packed_storage().unwrap();
+        assert_eq!(codons(&packed, 1), codons(&dna, 1));
+        
+        // Too short for a codon in the frame
+        assert!(codons(&Sequence::new_dna(b"ACG").unwrap(), 1).is_empty());
+        
+        assert!(dna.codons(3).is_err());
+        assert!(Sequence::new_protein(b"MKV").unwrap().codons(0).is_err());
     }
     
-    /// Get the sequence as bytes
-    pub fn as_bytes(&self) -> Cow<[u8]> {
-        if let Some(slice) = self.data.as_slice() {
-            Cow::Borrowed(slice)
-        } else {
-            Cow::Owned(self.data.subsequence(0, self.data.len()))
+    #[test]
+    fn test_kmers_iter() {
+        let dna = Sequence::new_dna(b"ACGTNAC").unwrap();
+        let kmers: Vec<Cow<[u8]>> = dna.kmers_iter(3).collect();
+        assert_eq!(kmers.len(), 5);
+        assert_eq!(kmers[0].as_ref(), b"ACG");
+        assert_eq!(kmers[4].as_ref(), b"NAC");
+        assert!(kmers.iter().all(|kmer| matches!(kmer, Cow::Borrowed(_))));
+        
+        // Non-sliceable storage falls back to owned copies
+        let packed = dna.to_packed_storage().unwrap();
+        let packed_kmers: Vec<Cow<[u8]>> = packed.kmers_iter(3).collect();
+        assert_eq!(packed_kmers, kmers);
+        assert!(packed_kmers.iter().all(|kmer| matches!(kmer, Cow::Owned(_))));
+        
+        // Agrees with the counting API
+        let mut counts = HashMap::new();
+        for kmer in dna.kmers_iter(2) {
+            *counts.entry(kmer.into_owned()).or_insert(0) += 1;
         }
+        assert_eq!(counts, dna.kmer_counts(2).unwrap());
+        
+        assert_eq!(dna.kmers_iter(0).count(), 0);
+        assert_eq!(dna.kmers_iter(8).count(), 0);
+        assert_eq!(dna.kmers_iter(7).count(), 1);
     }
     
-    /// Get a subsequence
-    pub fn subsequence(&self, start: usize, end: usize) -> SequenceResult<Self> {
-        if start > end || end > self.len() {
-            return Err(SequenceError::IndexOutOfBounds(
-                format!("Invalid range {}..{} for sequence of length {}", start, end, self.len())
-            ));
-        }
+    #[test]
+    fn test_kmer_counts() {
+        let dna = Sequence::new_dna(b"ACGTACG").unwrap();
         
-        let subseq = self.data.subsequence(start, end);
+        let counts = dna.kmer_counts(3).unwrap();
+        assert_eq!(counts.len(), 4);
+        assert_eq!(counts[b"ACG".as_slice()], 2);
+        assert_eq!(counts[b"GTA".as_slice()], 1);
         
-        Ok(Self {
-            data: Box::new(InMemoryStorage::new(subseq)),
-            alphabet: self.alphabet.clone(),
-            id: self.id.clone(),
-            description: self.description.clone().map(|desc| format!("{} (subsequence {}..{})", desc, start, end)),
-        })
-    }
-    
-    /// Get a view of the sequence
-    pub fn view(&self) -> SequenceView {
-        SequenceView {
-            sequence: self,
-            start: 0,
-            end: self.len(),
-        }
+        // k larger than the sequence yields no k-mers
+        assert!(dna.kmer_counts(10).unwrap().is_empty());
+        assert!(dna.par_kmer_counts(10, false).unwrap().is_empty());
+        assert!(dna.kmer_counts(0).is_err());
+        
+        // AC and GT are reverse complements, CG is its own
+        let canonical = Sequence::new_dna(b"ACGT").unwrap().kmer_counts_canonical(2).unwrap();
+        assert_eq!(canonical.len(), 2);
+        assert_eq!(canonical[b"AC".as_slice()], 2);
+        assert_eq!(canonical[b"CG".as_slice()], 1);
+        assert!(Sequence::new_protein(b"MKV").unwrap().kmer_counts_canonical(2).is_err());
+        
+        // Parallel counts match serial counts across chunk boundaries
+        let mut state: u32 = 12345;
+        let long: Vec<u8> = (0..50_000)
+            .map(|_| {
+                state = state.wrapping_mul(1103515245).wrapping_add(12345);
+                b"ACGT"[(state >> 16) as usize % 4]
+            })
+            .collect();
+        let long = Sequence::new_dna(&long).unwrap();
+        assert_eq!(long.par_kmer_counts(7, false).unwrap(), long.kmer_counts(7).unwrap());
+        assert_eq!(long.par_kmer_counts(7, true).unwrap(), long.kmer_counts_canonical(7).unwrap());
     }
     
-    /// Get the alphabet name
-    pub fn alphabet_name(&self) -> &str {
-        self.alphabet.name()
+    #[test]
+    fn test_sequence_equality() {
+        let a = Sequence::new_dna(b"ACGT").unwrap().with_id("a");
+        let b = Sequence::new_dna(b"ACGT").unwrap().with_id("b");
+        let lower = Sequence::new_dna(b"acgt").unwrap();
+        let rna = Sequence::new_rna(b"ACGU").unwrap();
+        
+        // Metadata is ignored, residues and case are not
+        assert!(a == b);
+        assert!(a != lower);
+        assert!(a.eq_ignore_case(&lower));
+        assert!(!a.eq_ignore_case(&rna));
+        
+        // Equal sequences collapse in a set
+        let set: HashSet<Sequence> = vec![a.clone(), b, lower].into_iter().collect();
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&a));
     }
     
-    /// Get the identifier (if any)
-    pub fn id(&self) -> Option<&str> {
-        self.id.as_deref()
+    #[test]
+    fn test_gc_content_windows() {
+        let seq = Sequence::new_dna(b"GGCCAATTGCNN").unwrap();
+        
+        // The last window skips N in its denominator
+        let windows = seq.gc_content_windows(4, 4).unwrap();
+        assert_eq!(windows, vec![100.0, 0.0, 100.0]);
+        
+        // A partial trailing window is kept and divided by its actual length
+        let partial = Sequence::new_dna(b"GGCCAATTGA").unwrap();
+        let windows = partial.gc_content_windows(4, 4).unwrap();
+        assert_eq!(windows, vec![100.0, 0.0, 50.0]);
+        
+        let windows = seq.gc_content_windows(8, 2).unwrap();
+        assert_eq!(windows, vec![50.0, 50.0, 2.0 / 6.0 * 100.0]);
+        
+        // A step larger than the window samples non-overlapping windows
+        let windows = seq.gc_content_windows(2, 5).unwrap();
+        assert_eq!(windows, vec![100.0, 0.0, 0.0]);
+        
+        assert!(seq.gc_content_windows(0, 1).is_err());
+        assert!(seq.gc_content_windows(1, 0).is_err());
+        assert!(Sequence::new_protein(b"MKV").unwrap().gc_content_windows(2, 1).is_err());
     }
     
-    /// Get the description (if any)
-    pub fn description(&self) -> Option<&str> {
-        self.description.as_deref()
+    #[test]
+    fn test_windows() {
+        let seq = Sequence::new_dna(b"ACGTAC").unwrap();
+        
+        let windows: Vec<Vec<u8>> = seq.windows(4).map(|w| w.as_bytes()).collect();
+        assert_eq!(windows, vec![b"ACGT".to_vec(), b"CGTA".to_vec(), b"GTAC".to_vec()]);
+        assert_eq!(seq.windows(4).next().unwrap().as_slice(), Some(&b"ACGT"[..]));
+        assert_eq!(seq.windows(6).count(), 1);
+        assert_eq!(seq.windows(7).count(), 0);
+        assert_eq!(seq.windows(0).count(), 0);
+        
+        // Storage without a contiguous slice still yields bytes
+        let packed = seq.to_packed_storage().unwrap();
+        let view = packed.windows(3).nth(2).unwrap();
+        assert_eq!(view.as_slice(), None);
+        assert_eq!(view.as_bytes(), b"GTA");
     }
     
-    /// Get the base composition
-    pub fn base_composition(&self) -> std::collections::HashMap<u8, usize> {
-        let mut counts = std::collections::HashMap::new();
+    #[test]
+    fn test_with_alphabet_unchecked() -> std::io::Result<()> {
+        use crate::engines::storage::MemoryMappedStorage;
         
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("chr.raw");
+        std::fs::write(&path, b"GGATCCAT")?;
+        
+        // Memory-mapped data is wrapped as is and validated on request
+        let seq = Sequence::with_alphabet_unchecked(MemoryMappedStorage::new(&path).unwrap(), DNAAlphabet::default());
+        assert_eq!(seq.data.storage_mode(), StorageMode::MemoryMapped);
+        assert!(seq.validate().is_ok());
+        assert_eq!(seq.as_string(), "GGATCCAT");
+        
+        // Invalid characters are only reported by validate
+        let seq = Sequence::with_alphabet_unchecked(InMemoryStorage::new(b"ACGTXAC".to_vec()), DNAAlphabet::default());
+        assert_eq!(seq.len(), 7);
+        let message = seq.validate().unwrap_err().to_string();
+        assert!(message.contains("'X'") && message.contains("position 4"), "{}", message);
+        
+        // Validation streams over storage that can't be sliced
+        let packed = Sequence::new_dna(b"ACGTACGT").unwrap().to_packed_storage().unwrap();
+        assert!(packed.validate().is_ok());
+        
+        Ok(())
+    }
+    
+    #[test]
+    fn test_protein_properties() {
+        let protein = Sequence::new_protein(b"MKV").unwrap();
+        assert!(protein.molecular_weight().unwrap() > protein.molecular_weight_with(MassType::Monoisotopic, AmbiguousResidues::Error).unwrap());
+        assert!(protein.isoelectric_point().is_ok());
+        
+        // Only proteins are supported
+        let dna = Sequence::new_dna(b"ACGT").unwrap();
+        assert!(dna.molecular_weight().is_err());
+        assert!(dna.isoelectric_point().is_err());
+    }
+    
+    #[test]
+    fn test_bisulfite_convert() {
+        let seq = Sequence::new_dna(b"ACGTCCaCgTc").unwrap();
+        
+        // Every C converts unless CpG methylation is simulated
+        let converted = seq.bisulfite_convert(Strand::Forward, false).unwrap();
+        assert_eq!(converted.as_string(), "ATGTTTaTgTt");
+        let converted = seq.bisulfite_convert(Strand::Forward, true).unwrap();
+        assert_eq!(converted.as_string(), "ACGTTTaCgTt");
+        
+        // The reverse strand (gAcGtGGACGT) is converted 5' to 3'
+        let converted = seq.bisulfite_convert(Strand::Reverse, false).unwrap();
+        assert_eq!(converted.as_string(), "gAtGtGGATGT");
+        let converted = seq.bisulfite_convert(Strand::Reverse, true).unwrap();
+        assert_eq!(converted.as_string(), "gAcGtGGACGT");
+        
+        // Only DNA can be converted
+        let rna = Sequence::new_rna(b"ACGU").unwrap();
+        assert!(rna.bisulfite_convert(Strand::Forward, false).is_err());
+    }
+    
+    #[test]
+    fn test_slice() {
+        let seq = Sequence::new_dna(b"ACGTACGTAA").unwrap();
+        let slice = |start, end, step| seq.slice(start, end, step).unwrap().as_string();
+        
+        // Negative indices count from the end
+        assert_eq!(slice(Some(-4), None, None), "GTAA");
+        assert_eq!(slice(Some(2), Some(-2), None), "GTACGT");
+        assert_eq!(slice(None, Some(-8), None), "AC");
+        
+        // Steps, including backwards
+        assert_eq!(slice(None, None, Some(2)), "AGAGA");
+        assert_eq!(slice(Some(1), None, Some(3)), "CAT");
+        assert_eq!(slice(None, None, Some(-1)), "AATGCATGCA");
+        assert_eq!(slice(Some(-2), Some(1), Some(-3)), "ACG");
+        assert_eq!(slice(Some(5), None, Some(-2)), "CTC");
+        
+        // Out-of-range indices are clamped like Python
+        assert_eq!(slice(Some(-100), Some(3), None), "ACG");
+        assert_eq!(slice(Some(8), Some(100), None), "AA");
+        assert_eq!(slice(Some(100), None, Some(-4)), "ACC");
+        assert_eq!(slice(Some(6), Some(2), None), "");
+        assert_eq!(slice(Some(2), Some(6), Some(-1)), "");
+        
+        assert!(seq.slice(None, None, Some(0)).is_err());
+    }
+    
+    #[test]
+    fn test_md5() -> std::io::Result<()> {
+        use crate::engines::storage::MemoryMappedStorage;
+        
+        // md5 of "ACGTN"; soft-masked bases give the same checksum
+        let expected = "252fe4e1c9aa67ce660443056dfa3799";
+        assert_eq!(Sequence::new_dna(b"ACGTN").unwrap().md5(), expected);
+        assert_eq!(Sequence::new_dna(b"acgTn").unwrap().md5(), expected);
+        
+        // Memory-mapped and packed storage are digested in place
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("chr.raw");
+        std::fs::write(&path, b"ACGTN")?;
+        let mapped = Sequence::with_alphabet_unchecked(MemoryMappedStorage::new(&path).unwrap(), DNAAlphabet::default());
+        assert_eq!(mapped.md5(), expected);
+        
+        let packed = Sequence::new_dna(b"ACGTN").unwrap().to_packed_storage().unwrap();
+        assert_eq!(packed.checksum(ChecksumAlgorithm::Md5), expected);
+        
+        Ok(())
+    }
+    
+    #[test]
+    fn test_windows_memory_mapped() -> std::io::Result<()> {
+        use crate::engines::storage::MemoryMappedStorage;
+        
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("seq.raw");
+        std::fs::write(&path, b"GGATCCAT")?;
+        
+        let seq = Sequence {
+            data: Box::new(MemoryMappedStorage::new(&path).unwrap()),
+            alphabet: Box::new(DNAAlphabet::default()),
+            id: None,
+            description: None,
+        };
+        
+        // Memory-mapped windows borrow straight from the map
+        let windows: Vec<&[u8]> = seq.windows(6).map(|w| w.as_slice().unwrap()).collect();
+        assert_eq!(windows, vec![&b"GGATCC"[..], &b"GATCCA"[..], &b"ATCCAT"[..]]);
+        
+        Ok(())
+    }
+    
+    #[test]
+    fn test_sequence_alignment() {
+        use crate::modules::seq::{ScoringScheme, SubstitutionMatrix};
+        
+        let seq1 = Sequence::new_dna(b"ACGTACGT").unwrap();
+        let seq2 = Sequence::new_dna(b"ACGAACGT").unwrap();
+        
+        assert_eq!(seq1.edit_distance(&seq2), 1);
+        assert_eq!(seq1.edit_distance(&seq1), 0);
+        assert_eq!(seq1.levenshtein(&seq2), 1);
+        
+        // Hamming distance counts substitutions only
+        assert_eq!(seq1.hamming_distance(&seq2).unwrap(), 1);
+        let shifted = Sequence::new_dna(b"CGTACGTA").unwrap();
+        assert_eq!(seq1.hamming_distance(&shifted).unwrap(), 8);
+        assert_eq!(seq1.levenshtein(&shifted), 2);
+        assert!(seq1.hamming_distance(&Sequence::new_dna(b"ACGT").unwrap()).is_err());
+        
+        let scheme = ScoringScheme::default();
+        let aln = seq1.align(&seq2, AlignmentType::Global, &scheme).unwrap();
+        assert_eq!(aln.seq1_aligned, b"ACGTACGT");
+        assert_eq!(aln.seq2_aligned, b"ACGAACGT");
+        assert_eq!(aln.score, 7 * 2 - 1);
+        
+        // Substitution matrices work too
+        let protein1 = Sequence::new_protein(b"HEAGAWGHEE").unwrap();
+        let protein2 = Sequence::new_protein(b"HEAGAWGHEE").unwrap();
+        let matrix = SubstitutionMatrix::blosum62();
+        let aln = protein1.align(&protein2, AlignmentType::Local, &matrix).unwrap();
+        assert_eq!(aln.seq1_aligned, b"HEAGAWGHEE");
+        
+        // Mixed alphabets are rejected
+        assert!(matches!(
+            seq1.align(&protein1, AlignmentType::Global, &scheme),
+            Err(SequenceError::InvalidAlphabet(_))
+        ));
+    }
+    
+    #[test]
+    fn test_melting_temperature() {
+        let primer = Sequence::new_dna(b"CGTTCCAAAGATGTGGGCATGAGCTTAC").unwrap();
+        assert_eq!(primer.melting_temperature_with(TmMethod::Wallace).unwrap(), 84.0);
+        assert!((primer.melting_temperature().unwrap() - 60.32).abs() < 0.01);
+        
+        // Short oligos use the Wallace rule
+        assert_eq!(Sequence::new_dna(b"ACGTACGT").unwrap().melting_temperature().unwrap(), 24.0);
+        
+        // Ambiguous bases and non-DNA alphabets are errors
+        assert!(Sequence::new_dna(b"ACGTNACGT").unwrap().melting_temperature().is_err());
+        assert!(Sequence::new_rna(b"ACGUACGU").unwrap().melting_temperature().is_err());
+    }
+    
+    #[test]
+    fn test_base_composition_full() {
+        let dna = Sequence::new_dna(b"AACGa").unwrap();
+        assert_eq!(
+            dna.base_composition_full(),
+            vec![
+                (b'A', 2), (b'C', 1), (b'G', 1), (b'T', 0), (b'N', 0),
+                (b'a', 1), (b'c', 0), (b'g', 0), (b't', 0), (b'n', 0),
+            ]
+        );
+        
+        // Every sequence with the same alphabet gets the same layout
+        let empty = Sequence::new_dna(b"").unwrap();
+        let symbols: Vec<u8> = empty.base_composition_full().iter().map(|&(s, _)| s).collect();
+        assert_eq!(symbols, b"ACGTNacgtn");
+        assert!(empty.base_composition_full().iter().all(|&(_, n)| n == 0));
+    }
+    
+    #[test]
+    fn test_shannon_entropy() {
+        assert_eq!(Sequence::new_dna(b"").unwrap().shannon_entropy(), 0.0);
+        assert_eq!(Sequence::new_dna(b"AAAA").unwrap().shannon_entropy(), 0.0);
+        assert_eq!(Sequence::new_dna(b"ACGT").unwrap().shannon_entropy(), 2.0);
+        assert_eq!(Sequence::new_dna(b"AACC").unwrap().shannon_entropy(), 1.0);
+        
+        // Incremental windows agree with recomputing each window
+        let seq = Sequence::new_dna(b"AAAAAAACGTACGTTTTTGCA").unwrap();
+        let data = seq.as_bytes();
+        for (window, step) in [(4, 1), (5, 2), (3, 3), (4, 6), (30, 1)] {
+            let windows = seq.entropy_windows(window, step).unwrap();
+            let expected: Vec<f64> = (0..data.len())
+                .step_by(step)
+                .map(|start| (start, (start + window).min(data.len())))
+                .scan(false, |done, (start, end)| {
+                    if *done { return None; }
+                    *done = end == data.len();
+                    Some(Sequence::new_dna(&data[start..end]).unwrap().shannon_entropy())
+                })
+                .collect();
+            assert_eq!(windows.len(), expected.len(), "window {} step {}", window, step);
+            for (got, want) in windows.iter().zip(&expected) {
+                assert!((got - want).abs() < 1e-12, "window {} step {}", window, step);
+            }
+        }
+        assert_eq!(seq.entropy_windows(4, 1).unwrap()[0], 0.0);
+        assert!(seq.entropy_windows(0, 1).is_err());
+        
+        // The partial trailing window is kept, like in gc_content_windows
+        let partial = Sequence::new_dna(b"AACCGT").unwrap();
+        assert_eq!(partial.entropy_windows(4, 4).unwrap(), vec![1.0, 1.0]);
+    }
+    
+    #[test]
+    fn test_dust_score() {
+        // A homopolymer of length n scores (n - 2) / 2
+        assert_eq!(Sequence::new_dna(b"AAAAAAAAAA").unwrap().dust_score(), 4.0);
+        
+        // Distinct triplets score zero
+        assert_eq!(Sequence::new_dna(b"ACGTTG").unwrap().dust_score(), 0.0);
+        
+        // Dinucleotide repeats score above more complex sequence
+        let repeat = Sequence::new_dna(b"CACACACACACACACA").unwrap();
+        let complex = Sequence::new_dna(b"ACGTTGCAAGCTTAGC").unwrap();
+        assert!(repeat.dust_score() > complex.dust_score());
+        
+        assert_eq!(Sequence::new_dna(b"ACG").unwrap().dust_score(), 0.0);
+    }
+    
+    #[test]
+    fn test_split_on_runs() {
+        let scaffold = Sequence::new_dna(b"NNACGTNNNNNGGNCCNNNNNTTANN")
+            .unwrap()
+            .with_id("scaffold1")
+            .with_description("chr1");
+        
+        // Runs shorter than min_run stay inside contigs; end runs are dropped
+        let contigs = scaffold.split_on_runs(b'N', 3);
+        let parts: Vec<String> = contigs.iter().map(|c| c.as_string()).collect();
+        assert_eq!(parts, vec!["ACGT", "GGNCC", "TTA"]);
+        
+        let descriptions: Vec<_> = contigs.iter().map(|c| c.description().unwrap().to_string()).collect();
+        assert_eq!(descriptions, vec!["chr1 (offset 2)", "chr1 (offset 11)", "chr1 (offset 21)"]);
+        assert_eq!(contigs[0].id(), Some("scaffold1"));
+        
+        // Every run splits with min_run of one
+        let parts: Vec<String> = scaffold.split_on_runs(b'N', 1).iter().map(|c| c.as_string()).collect();
+        assert_eq!(parts, vec!["ACGT", "GG", "CC", "TTA"]);
+        
+        // No runs, or only runs
+        let plain = Sequence::new_dna(b"ACGT").unwrap();
+        assert_eq!(plain.split_on_runs(b'N', 2)[0].description(), Some("offset 0"));
+        assert!(Sequence::new_dna(b"NNNN").unwrap().split_on_runs(b'N', 2).is_empty());
+    }
+    
+    #[test]
+    fn test_get() -> std::io::Result<()> {
+        use crate::engines::storage::MemoryMappedStorage;
+        
+        // In-memory storage borrows
+        let dna = Sequence::new_dna(b"ACGTNACGT").unwrap();
+        assert_eq!(dna.get(4), Some(b'N'));
+        assert_eq!(dna.get(9), None);
+        assert!(matches!(dna.get_range(2..5), Some(Cow::Borrowed(b"GTN"))));
+        assert!(dna.get_range(5..10).is_none());
+        
+        // Packed storage has no slice, so bytes are copied out
+        let packed = dna.to_packed_storage().unwrap();
+        assert_eq!(packed.get(4), Some(b'N'));
+        assert_eq!(packed.get(100), None);
+        assert!(matches!(packed.get_range(2..5), Some(Cow::Owned(ref v)) if v == b"GTN"));
+        
+        // Memory-mapped storage
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("seq.raw");
+        std::fs::write(&path, b"GGATCCAT")?;
+        let mapped = Sequence {
+            data: Box::new(MemoryMappedStorage::new(&path).unwrap()),
+            alphabet: Box::new(DNAAlphabet::default()),
+            id: None,
+            description: None,
+        };
+        assert_eq!(mapped.get(0), Some(b'G'));
+        assert_eq!(mapped.get(7), Some(b'T'));
+        assert_eq!(mapped.get(8), None);
+        assert_eq!(mapped.get_range(2..6).as_deref(), Some(&b"ATCC"[..]));
+        assert_eq!(mapped.get_range(8..8).as_deref(), Some(&b""[..]));
+        
+        Ok(())
+    }
+    
+    #[test]
+    fn test_concat_all() {
+        let scaffolds: Vec<Sequence> = [&b"ACGT"[..], b"NNNN", b"TTGA"].iter()
+            .map(|data| Sequence::new_dna(data).unwrap())
+            .collect();
+        
+        let chromosome = Sequence::concat_all(&scaffolds).unwrap();
+        assert_eq!(chromosome.as_string(), "ACGTNNNNTTGA");
+        assert_eq!(chromosome.alphabet_name(), "DNA");
+        assert_eq!(chromosome.description(), Some("concatenation of 3 sequences"));
+        
+        // Ids come from the first sequence that has one
+        let mut named = Sequence::new_dna(b"GG").unwrap();
+        named.id = Some("chr1".to_string());
+        let joined = Sequence::concat_all(&[scaffolds[0].clone(), named.clone()]).unwrap();
+        assert_eq!(joined.id(), Some("chr1"));
+        
+        // A single sequence keeps its description
+        named.description = Some("scaffold 1".to_string());
+        let single = Sequence::concat_all(std::slice::from_ref(&named)).unwrap();
+        assert_eq!(single.description(), Some("scaffold 1"));
+        
+        // Mixed alphabets and empty input are errors
+        let protein = Sequence::new_protein(b"MK").unwrap();
+        assert!(Sequence::concat_all(&[scaffolds[0].clone(), protein]).is_err());
+        assert!(Sequence::concat_all(&[]).is_err());
+    }
+    
+    #[test]
+    fn test_cpg() {
+        let dna = Sequence::new_dna(b"ACGcgT").unwrap();
+        let counts = dna.dinucleotide_counts();
+        assert_eq!(counts[b"CG"], 2);
+        assert_eq!(counts[b"GC"], 1);
+        assert_eq!(counts.values().sum::<usize>(), 5);
+        
+        // Observed/expected CpG
+        assert_eq!(Sequence::new_dna(b"CGCG").unwrap().cpg_ratio().unwrap(), 2.0);
+        assert_eq!(Sequence::new_dna(b"CCGG").unwrap().cpg_ratio().unwrap(), 1.0);
+        assert_eq!(Sequence::new_dna(b"AAAA").unwrap().cpg_ratio().unwrap(), 0.0);
+        assert_eq!(Sequence::new_dna(b"").unwrap().cpg_ratio().unwrap(), 0.0);
+        assert!(Sequence::new_protein(b"MCG").unwrap().cpg_ratio().is_err());
+        
+        // A CpG-rich block at 200..300 in AT-rich flanks
+        let mut data = b"AT".repeat(100);
+        data.extend(b"CG".repeat(50));
+        data.extend(b"AT".repeat(100));
+        let dna = Sequence::new_dna(&data).unwrap();
+        
+        // Windows with at least 25 of their 50 bases in the block qualify
+        assert_eq!(dna.find_cpg_islands(50, 50.0, 0.6).unwrap(), vec![(175, 325)]);
+        assert_eq!(dna.find_cpg_islands(50, 90.0, 0.6).unwrap(), vec![(195, 305)]);
+        assert!(dna.find_cpg_islands(1000, 50.0, 0.6).unwrap().is_empty());
+        assert!(dna.find_cpg_islands(1, 50.0, 0.6).is_err());
+        
+        // GC-rich sequence without CpGs is not an island
+        let gc_rich = Sequence::new_dna(&b"GGCCA".repeat(40)).unwrap();
+        assert!(gc_rich.find_cpg_islands(50, 50.0, 0.6).unwrap().is_empty());
+    }
+    
+    #[test]
+    fn test_count_pattern() {
+        let dna = Sequence::new_dna(b"AAAACGTACGTTTTAAAACGNNACG").unwrap();
+        
+        // Counts agree with the positions find_all reports, overlaps included
+        for pattern in [&b"A"[..], b"N", b"AA", b"ACG", b"AAAA", b"TTTTT", b"X"] {
+            assert_eq!(dna.count_pattern(pattern), dna.find_all(pattern).len(), "{:?}", pattern);
+            assert_eq!(dna.count(pattern), dna.count_pattern(pattern));
+        }
+        assert_eq!(dna.count_pattern(b"AA"), 6);
+        
+        // Empty and over-long patterns match nothing
+        assert_eq!(dna.count_pattern(b""), 0);
+        assert_eq!(dna.count_pattern(&[b'A'; 30]), 0);
+    }
+    
+    #[test]
+    fn test_custom_alphabet_sequence() {
+        use crate::modules::seq::alphabet::CustomAlphabet;
+        
+        let alphabet = CustomAlphabet::new("Binary", b"01", Some(&[(b'0', b'1')]));
+        let seq = Sequence::with_alphabet(b"0011", alphabet.clone()).unwrap();
+        assert_eq!(seq.alphabet_name(), "Binary");
+        assert_eq!(seq.complement().unwrap().as_string(), "1100");
+        assert_eq!(seq.reverse_complement().unwrap().as_string(), "0011");
+        
+        // Validation uses the custom symbol set
+        assert!(Sequence::with_alphabet(b"012", alphabet).is_err());
+        
+        // Without pairs there is no complement
+        let plain = Sequence::with_alphabet(b"ab", CustomAlphabet::new("Plain", b"ab", None)).unwrap();
+        assert!(plain.complement().is_err());
+    }
+    
+    #[test]
+    fn test_gapped_sequence() {
+        use crate::modules::seq::alphabet::GappedDNAAlphabet;
+        
+        // Aligned rows can be stored as sequences
+        let row = Sequence::with_alphabet(b"AC--GT..", GappedDNAAlphabet::default()).unwrap();
+        assert_eq!(row.alphabet_name(), "DNA");
+        assert!(Sequence::new_dna(b"AC--GT").is_err());
+        
+        // Gaps are excluded from GC content and kept by complements
+        assert_eq!(row.gc_content().unwrap(), 50.0);
+        assert_eq!(row.complement().unwrap().as_string(), "TG--CA..");
+        assert_eq!(row.reverse_complement().unwrap().as_string(), "..AC--GT");
+        
+        let all_gaps = Sequence::with_alphabet(b"---", GappedDNAAlphabet::default()).unwrap();
+        assert_eq!(all_gaps.gc_content().unwrap(), 0.0);
+    }
+    
+    #[test]
+    fn test_memory_usage() {
+        let mut seq = Sequence::new(b"ACGTACGTACGT").unwrap();
+        let base = seq.memory_usage();
+        assert!(base >= 12 + std::mem::size_of::<Sequence>());
+        
+        // Metadata counts too
+        seq.id = Some("chr1".to_string());
+        seq.description = Some("a longer description".to_string());
+        assert!(seq.memory_usage() >= base + 4 + 20);
+        
+        // Larger sequences use more
+        let long = Sequence::new(&[b'A'; 10_000]).unwrap();
+        assert!(long.memory_usage() > base + 9_000);
+    }
+    
+    #[test]
+    fn test_streaming_composition() {
+        // Longer than one streaming chunk, with a ragged final chunk
+        let data: Vec<u8> = b"ACGTNacgtGGC".iter().copied().cycle().take(2 * STREAM_CHUNK_SIZE + 7).collect();
+        let dna = Sequence::new(&data).unwrap();
+        let packed = dna.to_packed_storage().unwrap();
+        assert!(packed.data.as_slice().is_none());
+        
+        assert_eq!(packed.base_composition(), dna.base_composition());
+        assert_eq!(packed.base_composition_full(), dna.base_composition_full());
+        assert_eq!(packed.gc_content().unwrap(), dna.gc_content().unwrap());
+        assert_eq!(dna.base_composition()[&b'N'], data.iter().filter(|&&b| b == b'N').count());
+    }
+    
+    #[test]
+    fn test_codon_usage() {
+        let rna = Sequence::new(b"AUGUGAUGG").unwrap();
+        let usage = rna.codon_usage(CodonTable::vertebrate_mitochondrial()).unwrap();
+        let tga = usage.iter().find(|u| &u.codon == b"TGA").unwrap();
+        assert_eq!((tga.amino_acid, tga.count, tga.fraction), (b'W', 1, 0.5));
+        
+        let protein = Sequence::new(b"MKLV").unwrap();
+        assert!(protein.codon_usage(CodonTable::standard()).is_err());
+    }
+    
+    #[test]
+    fn test_reverse_complement_view() {
+        let mut dna = Sequence::new(b"ACGTTgcaNNacgtAAGCTTGGA").unwrap();
+        dna.description = Some("reads".to_string());
+        let eager = dna.reverse_complement().unwrap();
+        let view = dna.reverse_complement_view().unwrap();
+        
+        assert_eq!(view.len(), dna.len());
+        assert_eq!(view.iter().collect::<Vec<u8>>(), eager.as_bytes().as_ref());
+        assert_eq!(view.subsequence(3, 9), &eager.as_bytes()[3..9]);
+        assert_eq!(view.chunks(5).map(|chunk| chunk.len()).collect::<Vec<_>>(), vec![5, 5, 5, 5, 3]);
+        assert_eq!(view.chunks(5).collect::<Vec<_>>().concat(), eager.as_bytes().as_ref());
+        assert_eq!(view.to_sequence().as_string(), eager.as_string());
+        assert_eq!(view.to_sequence().description, eager.description);
+        
+        let mut written = Vec::new();
+        view.write_to(&mut written).unwrap();
+        assert_eq!(written, eager.as_bytes().as_ref());
+        
+        // Storage without a contiguous slice reads through the same path
+        let packed = dna.to_packed_storage().unwrap();
+        let packed_view = packed.reverse_complement_view().unwrap();
+        assert_eq!(packed_view.iter().collect::<Vec<u8>>(), eager.as_bytes().as_ref());
+        assert_eq!(StorableSequence::subsequence(&packed_view, 0, 4), &eager.as_bytes()[..4]);
+        
+        // Sequences the eager method rejects are rejected up front
+        let protein = Sequence::new(b"MKLVWF").unwrap();
+        assert!(protein.reverse_complement().is_err());
+        assert!(protein.reverse_complement_view().is_err());
+    }
+};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use thiserror::Error;
+
+use crate::engines::core::memory::PackedDnaStorage;
+use crate::engines::storage::{StorableSequence, InMemoryStorage, StorageMode};
+use crate::engines::compute::{alignment, checksum, string_ops};
+use crate::engines::compute::alignment::{Alignment, AlignmentType, Scoring};
+use crate::engines::compute::checksum::ChecksumAlgorithm;
+use crate::engines::core::simd;
+use crate::engines::core::parallel::{self, ParallelChunkProcessor};
+use super::motif::Motif;
+use super::melting::{self, TmMethod};
+use super::protein::{self, AmbiguousResidues, MassType};
+use super::codon::{CodonTable, CodonUsage, PartialCodon};
+use super::alphabet::{Alphabet, DNAAlphabet, RNAAlphabet, ProteinAlphabet, GAP_CHARS};
+
+/// Bytes copied at a time when streaming storage without a contiguous slice
+const STREAM_CHUNK_SIZE: usize = 1 << 20;
+
+/// Error type for sequence operations
+#[derive(Error, Debug)]
+pub enum SequenceError {
+    #[error("Invalid sequence: {0}")]
+    InvalidSequence(String),
+    
+    #[error("Invalid alphabet: {0}")]
+    InvalidAlphabet(String),
+    
+    #[error("Index out of bounds: {0}")]
+    IndexOutOfBounds(String),
+    
+    #[error("Operation not supported: {0}")]
+    UnsupportedOperation(String),
+    
+    #[error("Engine error: {0}")]
+    EngineError(#[from] crate::engines::EngineError),
+    
+    #[error("Compute error: {0}")]
+    ComputeError(#[from] crate::engines::compute::ComputeError),
+}
+
+/// Result type for sequence operations
+pub type SequenceResult<T> = Result<T, SequenceError>;
+
+/// Strand of a feature relative to the sequence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strand {
+    /// Forward (plus) strand
+    Forward,
+    /// Reverse (minus) strand
+    Reverse,
+}
+
+/// An open reading frame found by [`Sequence::find_orfs`]
+#[derive(Clone)]
+pub struct Orf {
+    /// Reading frame (0, 1 or 2) on its strand
+    pub frame: usize,
+    /// Strand the ORF was found on
+    pub strand: Strand,
+    /// Start position in the forward sequence (0-indexed, inclusive)
+    pub start: usize,
+    /// End position in the forward sequence (0-indexed, exclusive, includes the stop codon)
+    pub end: usize,
+    /// Translated protein, without the stop codon
+    pub protein: Sequence,
+}
+
+/// Common sequence type for all biological sequences
+#[derive(Clone)]
+pub struct Sequence {
+    /// The sequence data
+    data: Box<dyn StorableSequence>,
+    /// The alphabet used for this sequence
+    alphabet: Box<dyn Alphabet>,
+    /// Identifier for the sequence (optional)
+    id: Option<String>,
+    /// Description of the sequence (optional)
+    description: Option<String>,
+}
+
+impl Sequence {
+    /// Create a new sequence from raw bytes
+    pub fn new(data: &[u8]) -> SequenceResult<Self> {
+        // Detect alphabet
+        let alphabet = super::alphabet::detect_alphabet(data)
+            .ok_or_else(|| SequenceError::InvalidSequence(
+                "Could not detect alphabet for sequence".to_string()
+            ))?;
+        
+        Ok(Self {
+            data: Box::new(InMemoryStorage::new(data.to_vec())),
+            alphabet,
+            id: None,
+            description: None,
+        })
+    }
+    
+    /// Create a new sequence with a specific alphabet
+    pub fn with_alphabet<A: Alphabet + 'static>(data: &[u8], alphabet: A) -> SequenceResult<Self> {
+        // Validate sequence against alphabet
+        if !alphabet.is_valid_sequence(data) {
+            return Err(SequenceError::InvalidSequence(
+                format!("Sequence contains invalid characters for {} alphabet", alphabet.name())
+            ));
+        }
+        
+        Ok(Self {
+            data: Box::new(InMemoryStorage::new(data.to_vec())),
+            alphabet: Box::new(alphabet),
+            id: None,
+            description: None,
+        })
+    }
+    
+    /// Create a sequence from existing storage without validating it
+    ///
+    /// Unlike [`with_alphabet`](Self::with_alphabet) this never reads the
+    /// data, so wrapping a memory-mapped chromosome doesn't fault in the whole
+    /// file. Use it for data that was validated upstream, or call
+    /// [`validate`](Self::validate) later. This is not `unsafe`: characters
+    /// outside the alphabet can't cause undefined behaviour, but
+    /// alphabet-dependent operations (complement, translation, composition)
+    /// may return errors or meaningless results for them.
+    pub fn with_alphabet_unchecked<S, A>(storage: S, alphabet: A) -> Self
+    where
+        S: StorableSequence + 'static,
+        A: Alphabet + 'static,
+    {
+        Self {
+            data: Box::new(storage),
+            alphabet: Box::new(alphabet),
+            id: None,
+            description: None,
+        }
+    }
+    
+    /// Create an in-memory sequence with this sequence's alphabet
+    ///
+    /// The data is not validated and the new sequence has no id or
+    /// description.
+    pub(crate) fn with_same_alphabet(&self, data: Vec<u8>) -> Self {
+        Self {
+            data: Box::new(InMemoryStorage::new(data)),
+            alphabet: self.alphabet.clone(),
+            id: None,
+            description: None,
+        }
+    }
+    
+    /// Check that every character is valid in the sequence's alphabet
+    ///
+    /// Streams over the storage in chunks and reports the first invalid
+    /// character and its position.
+    pub fn validate(&self) -> SequenceResult<()> {
+        let mut offset = 0;
+        let mut invalid = None;
+        self.for_each_chunk(|chunk| {
+            if invalid.is_none() {
+                invalid = chunk.iter()
+                    .position(|&c| !self.alphabet.is_valid_char(c))
+                    .map(|i| (offset + i, chunk[i]));
+                offset += chunk.len();
+            }
+        });
+        
+        match invalid {
+            Some((position, c)) => Err(SequenceError::InvalidSequence(format!(
+                "Invalid character '{}' for {} alphabet at position {}",
+                c as char, self.alphabet.name(), position
+            ))),
+            None => Ok(()),
+        }
+    }
+    
+    /// Create a new DNA sequence
+    pub fn new_dna(data: &[u8]) -> SequenceResult<Self> {
+        Self::with_alphabet(data, DNAAlphabet::default())
+    }
+    
+    /// Create a new RNA sequence
+    pub fn new_rna(data: &[u8]) -> SequenceResult<Self> {
+        Self::with_alphabet(data, RNAAlphabet::default())
+    }
+    
+    /// Create a new protein sequence
+    pub fn new_protein(data: &[u8]) -> SequenceResult<Self> {
+        Self::with_alphabet(data, ProteinAlphabet::default())
+    }
+    
+    /// Set the sequence identifier
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.id = Some(id.to_string());
+        self
+    }
+    
+    /// Set the sequence description
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_string());
+        self
+    }
+    
+    /// Get the sequence length
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+    
+    /// Check if the sequence is empty
+    pub fn is_empty(&self) -> bool {
+        self.data.len() == 0
+    }
+    
+    /// Get the sequence as a string
+    pub fn as_string(&self) -> String {
+        String::from_utf8_lossy(self.as_bytes()).to_string()
+    }
+    
+    /// Get the sequence as bytes
+    pub fn as_bytes(&self) -> Cow<[u8]> {
+        if let Some(slice) = self.data.as_slice() {
+            Cow::Borrowed(slice)
+        } else {
+            Cow::Owned(self.data.subsequence(0, self.data.len()))
+        }
+    }
+    
+    /// Get the residue at `index`, or `None` if it is out of bounds
+    ///
+    /// Works for every storage backend, copying the byte out of storage that
+    /// has no contiguous slice.
+    pub fn get(&self, index: usize) -> Option<u8> {
+        if index >= self.len() {
+            return None;
+        }
+        
+        match self.data.as_slice() {
+            Some(slice) => slice.get(index).copied(),
+            None => self.data.subsequence(index, index + 1).first().copied(),
+        }
+    }
+    
+    /// Get the residues in `range`, or `None` if it is out of bounds
+    ///
+    /// Borrows from storage with a contiguous slice and copies otherwise.
+    pub fn get_range(&self, range: Range<usize>) -> Option<Cow<'_, [u8]>> {
+        if range.start > range.end || range.end > self.len() {
+            return None;
+        }
+        
+        match self.data.as_slice() {
+            Some(slice) => Some(Cow::Borrowed(&slice[range])),
+            None => Some(Cow::Owned(self.data.subsequence(range.start, range.end))),
+        }
+    }
+    
+    /// Get a subsequence
+    ///
+    /// Errors reading lazily loaded storage are returned rather than
+    /// replaced with placeholder bases.
+    pub fn subsequence(&self, start: usize, end: usize) -> SequenceResult<Self> {
+        if start > end || end > self.len() {
+            return Err(SequenceError::IndexOutOfBounds(
+                format!("Invalid range {}..{} for sequence of length {}", start, end, self.len())
+            ));
+        }
+        
+        let subseq = self.data.try_subsequence(start, end)?;
+        
+        Ok(Self {
+            data: Box::new(InMemoryStorage::new(subseq)),
+            alphabet: self.alphabet.clone(),
+            id: self.id.clone(),
+            description: self.description.clone().map(|desc| format!("{} (subsequence {}..{})", desc, start, end)),
+        })
+    }
+    
+    /// Slice the sequence with Python semantics
+    ///
+    /// Works like `seq[start:end:step]` in Python: negative indices count
+    /// from the end, `None` takes the default for the direction of `step`,
+    /// and out-of-range indices are clamped rather than rejected. A negative
+    /// step walks backwards, so `slice(None, None, Some(-1))` reverses the
+    /// sequence (without complementing it). A step of zero is an error.
+    pub fn slice(&self, start: Option<isize>, end: Option<isize>, step: Option<isize>) -> SequenceResult<Self> {
+        let step = step.unwrap_or(1);
+        if step == 0 {
+            return Err(SequenceError::IndexOutOfBounds("Slice step cannot be zero".to_string()));
+        }
+        
+        // Resolve indices as Python's `slice.indices` does
+        let len = self.len() as isize;
+        let resolve = |index: Option<isize>, default: isize, low: isize, high: isize| match index {
+            None => default,
+            Some(i) if i < 0 => (i + len).clamp(low, high),
+            Some(i) => i.clamp(low, high),
+        };
+        let (start, end) = if step > 0 {
+            (resolve(start, 0, 0, len), resolve(end, len, 0, len))
+        } else {
+            (resolve(start, len - 1, -1, len - 1), resolve(end, -1, -1, len - 1))
+        };
+        
+        if step == 1 {
+            return self.subsequence(start as usize, start.max(end) as usize);
+        }
+        
+        let count = match step > 0 {
+            true if start < end => (end - start - 1) / step + 1,
+            false if end < start => (end - start + 1) / step + 1,
+            _ => 0,
+        };
+        
+        // Read the covered range once, then pick every step-th residue
+        let last = start + (count - 1) * step;
+        let (low, high) = if count == 0 { (0, 0) } else { (start.min(last), start.max(last) + 1) };
+        let covered = self.data.try_subsequence(low as usize, high as usize)?;
+        let residues = (0..count).map(|k| covered[(start + k * step - low) as usize]).collect();
+        
+        Ok(Self {
+            data: Box::new(InMemoryStorage::new(residues)),
+            alphabet: self.alphabet.clone(),
+            id: self.id.clone(),
+            description: self.description.clone().map(|desc| format!("{} (slice {}..{} step {})", desc, low, high, step)),
+        })
+    }
+    
+    /// Get a view of the sequence
+    pub fn view(&self) -> SequenceView {
+        SequenceView {
+            sequence: self,
+            start: 0,
+            end: self.len(),
+        }
+    }
+    
+    /// Iterate over overlapping windows of the sequence, stepping by one
+    ///
+    /// Yields nothing if `size` is zero or longer than the sequence.
+    pub fn windows(&self, size: usize) -> impl Iterator<Item = SequenceView<'_>> {
+        let count = if size == 0 || size > self.len() { 0 } else { self.len() - size + 1 };
+        
+        (0..count).map(move |start| SequenceView {
+            sequence: self,
+            start,
+            end: start + size,
+        })
+    }
+    
+    /// Get the alphabet name
+    pub fn alphabet_name(&self) -> &str {
+        self.alphabet.name()
+    }
+    
+    /// Get the identifier (if any)
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+    
+    /// Get the description (if any)
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+    
+    /// Get the base composition
+    pub fn base_composition(&self) -> std::collections::HashMap<u8, usize> {
         // Count occurrences of each base
-        for &base in self.as_bytes().iter() {
-            *counts.entry(base).or_insert(0) += 1;
+        let counts = self.byte_counts();
+        
+        (0..=u8::MAX)
+            .filter(|&base| counts[base as usize] > 0)
+            .map(|base| (base, counts[base as usize]))
+            .collect()
+    }
+    
+    /// Call `f` on the sequence bytes one chunk at a time
+    ///
+    /// Storage with a contiguous slice is passed whole; other backends are
+    /// copied out in chunks so the full sequence is never materialized.
+    fn for_each_chunk<F: FnMut(&[u8])>(&self, mut f: F) {
+        if let Some(slice) = self.data.as_slice() {
+            f(slice);
+            return;
+        }
+        
+        let len = self.data.len();
+        let mut start = 0;
+        while start < len {
+            let end = (start + STREAM_CHUNK_SIZE).min(len);
+            f(&self.data.subsequence(start, end));
+            start = end;
+        }
+    }
+    
+    /// Compute the MD5 checksum used for the SAM `M5` tag
+    ///
+    /// Letters are uppercased and whitespace is ignored, so the result
+    /// matches `samtools dict` for the same sequence. Storage is read in
+    /// chunks rather than copied whole.
+    pub fn md5(&self) -> String {
+        self.checksum(ChecksumAlgorithm::Md5)
+    }
+    
+    /// Compute a checksum of the normalized sequence with any algorithm
+    ///
+    /// See [`SequenceHasher`](crate::engines::compute::checksum::SequenceHasher)
+    /// for the normalization.
+    pub fn checksum(&self, algorithm: ChecksumAlgorithm) -> String {
+        checksum::checksum_storage(self.data.as_ref(), algorithm)
+    }
+    
+    /// Count every byte value in the sequence
+    fn byte_counts(&self) -> [usize; 256] {
+        let mut counts = [0usize; 256];
+        self.for_each_chunk(|chunk| {
+            for &base in chunk {
+                counts[base as usize] += 1;
+            }
+        });
+        counts
+    }
+    
+    /// Get the count of every symbol in the alphabet, including zeros
+    ///
+    /// Symbols are listed in the order of the alphabet's `valid_chars`, so
+    /// the result has a fixed length for a given alphabet. Upper and lower
+    /// case are counted separately.
+    pub fn base_composition_full(&self) -> Vec<(u8, usize)> {
+        let counts = self.byte_counts();
+        
+        self.alphabet
+            .valid_chars()
+            .iter()
+            .map(|&symbol| (symbol, counts[symbol as usize]))
+            .collect()
+    }
+    
+    /// Get the GC content (for DNA/RNA sequences)
+    pub fn gc_content(&self) -> SequenceResult<f64> {
+        if self.alphabet_name() != "DNA" && self.alphabet_name() != "RNA" {
+            return Err(SequenceError::UnsupportedOperation(
+                format!("GC content calculation not supported for {} alphabet", self.alphabet_name())
+            ));
+        }
+        
+        // Count G and C bases (both upper and lowercase) and gaps chunk by chunk
+        let mut gc_count = 0;
+        let mut gaps = 0;
+        self.for_each_chunk(|chunk| {
+            gc_count += b"GgCc".iter().map(|&base| simd::count_byte(chunk, base)).sum::<usize>();
+            gaps += GAP_CHARS.iter().map(|&gap| simd::count_byte(chunk, gap)).sum::<usize>();
+        });
+        
+        // Alignment gaps are not bases, so they don't count towards the total
+        let total = (self.len() - gaps) as f64;
+        
+        if total == 0.0 {
+            return Ok(0.0);
+        }
+        
+        Ok((gc_count as f64) / total * 100.0)
+    }
+    
+    /// Get the reverse of the sequence
+    pub fn reverse(&self) -> Self {
+        let mut reversed = self.as_bytes().to_vec();
+        string_ops::reverse_in_place(&mut reversed);
+        
+        Self {
+            data: Box::new(InMemoryStorage::new(reversed)),
+            alphabet: self.alphabet.clone(),
+            id: self.id.clone(),
+            description: self.description.clone().map(|desc| format!("{} (reversed)", desc)),
+        }
+    }
+    
+    /// Get the complement of the sequence
+    ///
+    /// Supported for DNA, RNA and any alphabet that defines a complement for
+    /// every character, such as a [`CustomAlphabet`](super::alphabet::CustomAlphabet)
+    /// with complement pairs.
+    pub fn complement(&self) -> SequenceResult<Self> {
+        if !self.alphabet.supports_complement() {
+            return Err(SequenceError::UnsupportedOperation(
+                format!("Complement operation not supported for {} alphabet", self.alphabet_name())
+            ));
+        }
+        
+        let seq_bytes = self.as_bytes();
+        let complemented = self.alphabet.complement_sequence(&seq_bytes)
+            .ok_or_else(|| SequenceError::UnsupportedOperation(
+                "Failed to compute complement".to_string()
+            ))?;
+        
+        Ok(Self {
+            data: Box::new(InMemoryStorage::new(complemented)),
+            alphabet: self.alphabet.clone(),
+            id: self.id.clone(),
+            description: self.description.clone().map(|desc| format!("{} (complement)", desc)),
+        })
+    }
+    
+    /// Get the reverse complement of the sequence (for DNA/RNA)
+    pub fn reverse_complement(&self) -> SequenceResult<Self> {
+        let mut rev_comp = self.as_bytes().to_vec();
+        
+        // First complement
+        let complemented = self.alphabet.complement_sequence(&rev_comp)
+            .ok_or_else(|| SequenceError::UnsupportedOperation(
+                "Failed to compute complement".to_string()
+            ))?;
+        
+        // Then reverse
+        let mut reversed = complemented;
+        string_ops::reverse_in_place(&mut reversed);
+        
+        Ok(Self {
+            data: Box::new(InMemoryStorage::new(reversed)),
+            alphabet: self.alphabet.clone(),
+            id: self.id.clone(),
+            description: self.description.clone().map(|desc| format!("{} (reverse complement)", desc)),
+        })
+    }
+    
+    /// Get a lazy view of the reverse complement
+    ///
+    /// Checks once that every residue has a complement, failing where
+    /// [`reverse_complement`](Self::reverse_complement) would, but allocates
+    /// nothing; residues are complemented as the view is read.
+    pub fn reverse_complement_view(&self) -> SequenceResult<ReverseComplementView<'_>> {
+        let mut complementable = true;
+        self.for_each_chunk(|chunk| {
+            complementable = complementable && chunk.iter().all(|&base| self.alphabet.complement(base).is_some());
+        });
+        
+        if !complementable {
+            return Err(SequenceError::UnsupportedOperation(
+                "Failed to compute complement".to_string()
+            ));
+        }
+        
+        Ok(ReverseComplementView { sequence: self })
+    }
+    
+    /// Simulate bisulfite conversion of one strand of a DNA sequence
+    ///
+    /// Every `C` on the chosen strand becomes `T`, as unmethylated cytosines
+    /// do after bisulfite treatment and PCR; case is preserved. With
+    /// `methylated_cpg`, cytosines followed by `G` are treated as methylated
+    /// and left unchanged. For [`Strand::Reverse`] the reverse complement is
+    /// converted, so the result is the converted reverse strand read 5' to 3'.
+    pub fn bisulfite_convert(&self, strand: Strand, methylated_cpg: bool) -> SequenceResult<Self> {
+        if self.alphabet_name() != "DNA" {
+            return Err(SequenceError::UnsupportedOperation(
+                "Bisulfite conversion only supported for DNA alphabet".to_string()
+            ));
+        }
+        
+        let (mut bases, label) = match strand {
+            Strand::Forward => (self.as_bytes().into_owned(), "bisulfite converted"),
+            Strand::Reverse => (
+                self.reverse_complement()?.as_bytes().into_owned(),
+                "reverse strand bisulfite converted",
+            ),
+        };
+        
+        for i in 0..bases.len() {
+            let protected = methylated_cpg && matches!(bases.get(i + 1), Some(b'G' | b'g'));
+            match bases[i] {
+                b'C' if !protected => bases[i] = b'T',
+                b'c' if !protected => bases[i] = b't',
+                _ => {}
+            }
+        }
+        
+        Ok(Self {
+            data: Box::new(InMemoryStorage::new(bases)),
+            alphabet: self.alphabet.clone(),
+            id: self.id.clone(),
+            description: self.description.clone().map(|desc| format!("{} ({})", desc, label)),
+        })
+    }
+    
+    /// Transcribe a DNA sequence to RNA
+    pub fn transcribe(&self) -> SequenceResult<Self> {
+        if self.alphabet_name() != "DNA" {
+            return Err(SequenceError::UnsupportedOperation(
+                "Transcription operation only supported for DNA alphabet".to_string()
+            ));
+        }
+        
+        let dna = self.as_bytes();
+        let rna = string_ops::transcribe(&dna);
+        
+        Ok(Self {
+            data: Box::new(InMemoryStorage::new(rna)),
+            alphabet: Box::new(RNAAlphabet::default()),
+            id: self.id.clone(),
+            description: self.description.clone().map(|desc| format!("{} (transcribed)", desc)),
+        })
+    }
+    
+    /// Find all occurrences of a subsequence
+    pub fn find_all(&self, pattern: &[u8]) -> Vec<usize> {
+        if pattern.is_empty() || pattern.len() > self.len() {
+            return Vec::new();
+        }
+        
+        // Use the KMP algorithm for searching
+        match string_ops::kmp_search(self.as_bytes().as_ref(), pattern) {
+            Ok(matches) => matches,
+            Err(_) => Vec::new(),
+        }
+    }
+    
+    /// Find all occurrences of a subsequence with up to `max_mismatches` mismatches
+    ///
+    /// Returns each match start and its Hamming distance to the pattern,
+    /// including overlapping matches.
+    pub fn find_approximate(&self, pattern: &[u8], max_mismatches: usize) -> Vec<(usize, usize)> {
+        if pattern.is_empty() || pattern.len() > self.len() {
+            return Vec::new();
+        }
+        
+        string_ops::approximate_search(self.as_bytes().as_ref(), pattern, max_mismatches)
+            .unwrap_or_default()
+    }
+    
+    /// Find all occurrences of an IUPAC degenerate pattern
+    ///
+    /// Pattern codes match every base they stand for, so `GGWGG` finds both
+    /// `GGAGG` and `GGTGG`. Only supported for DNA and RNA sequences.
+    pub fn find_iupac(&self, pattern: &[u8]) -> SequenceResult<Vec<usize>> {
+        if !matches!(self.alphabet_name(), "DNA" | "RNA") {
+            return Err(SequenceError::UnsupportedOperation(
+                "IUPAC pattern search only supported for DNA and RNA alphabets".to_string()
+            ));
+        }
+        
+        Ok(string_ops::iupac_search(self.as_bytes().as_ref(), pattern)?)
+    }
+    
+    /// Count the occurrences of a subsequence
+    pub fn count(&self, pattern: &[u8]) -> usize {
+        self.count_pattern(pattern)
+    }
+    
+    /// Count the occurrences of a subsequence without collecting positions
+    ///
+    /// Counts overlapping matches, like `find_all`. Single-byte patterns use
+    /// a SIMD byte count; longer ones a KMP scan.
+    pub fn count_pattern(&self, pattern: &[u8]) -> usize {
+        if pattern.is_empty() || pattern.len() > self.len() {
+            return 0;
+        }
+        
+        let data = self.as_bytes();
+        match pattern {
+            [byte] => simd::count_byte(&data, *byte),
+            _ => string_ops::kmp_count(&data, pattern).unwrap_or(0),
+        }
+    }
+    
+    /// Convert to packed 2-bit storage
+    ///
+    /// Uses about a quarter of the memory of the unpacked sequence. Bases
+    /// other than uppercase ACGT are stored separately, so `as_bytes` still
+    /// returns the original sequence.
+    pub fn to_packed_storage(&self) -> SequenceResult<Self> {
+        if self.alphabet_name() != "DNA" {
+            return Err(SequenceError::UnsupportedOperation(
+                "Packed storage is only supported for DNA sequences".to_string()
+            ));
+        }
+        
+        let seq_data = self.as_bytes();
+        let mut packed = PackedDnaStorage::with_capacity(seq_data.len());
+        packed.pack(&seq_data);
+        
+        // Create a sequence with the packed storage
+        Ok(Self {
+            data: Box::new(packed),
+            alphabet: self.alphabet.clone(),
+            id: self.id.clone(),
+            description: self.description.clone(),
+        })
+    }
+    
+    /// Create a mask from the given positions
+    pub fn mask(&self, positions: &[usize], mask_char: u8) -> SequenceResult<Self> {
+        let mut masked = self.as_bytes().to_vec();
+        
+        for &pos in positions {
+            if pos >= self.len() {
+                return Err(SequenceError::IndexOutOfBounds(
+                    format!("Position {} is out of bounds for sequence of length {}", pos, self.len())
+                ));
+            }
+            
+            masked[pos] = mask_char;
+        }
+        
+        Ok(Self {
+            data: Box::new(InMemoryStorage::new(masked)),
+            alphabet: self.alphabet.clone(),
+            id: self.id.clone(),
+            description: self.description.clone().map(|desc| format!("{} (masked)", desc)),
+        })
+    }
+    
+    /// Soft-mask regions by lowercasing them
+    ///
+    /// Intervals are half-open `(start, end)` ranges and may overlap. Bases
+    /// outside the intervals keep their case.
+    pub fn soft_mask(&self, intervals: &[(usize, usize)]) -> SequenceResult<Self> {
+        let mut masked = self.as_bytes().to_vec();
+        
+        for &(start, end) in intervals {
+            if start > end || end > masked.len() {
+                return Err(SequenceError::IndexOutOfBounds(
+                    format!("Interval {}..{} is out of bounds for sequence of length {}", start, end, masked.len())
+                ));
+            }
+            
+            simd::to_lowercase_ascii(&mut masked[start..end]);
+        }
+        
+        Ok(Self {
+            data: Box::new(InMemoryStorage::new(masked)),
+            alphabet: self.alphabet.clone(),
+            id: self.id.clone(),
+            description: self.description.clone(),
+        })
+    }
+    
+    /// Concatenate with another sequence
+    pub fn concatenate(&self, other: &Self) -> SequenceResult<Self> {
+        if self.alphabet_name() != other.alphabet_name() {
+            return Err(SequenceError::InvalidAlphabet(
+                format!("Cannot concatenate sequences with different alphabets: {} and {}", 
+                        self.alphabet_name(), other.alphabet_name())
+            ));
+        }
+        
+        // Combine the sequences
+        let mut combined = self.as_bytes().to_vec();
+        combined.extend_from_slice(&other.as_bytes());
+        
+        Ok(Self {
+            data: Box::new(InMemoryStorage::new(combined)),
+            alphabet: self.alphabet.clone(),
+            id: self.id.clone().or_else(|| other.id.clone()),
+            description: match (self.description.clone(), other.description.clone()) {
+                (Some(desc1), Some(desc2)) => Some(format!("{} + {}", desc1, desc2)),
+                (Some(desc1), None) => Some(format!("{} + [unnamed]", desc1)),
+                (None, Some(desc2)) => Some(format!("[unnamed] + {}", desc2)),
+                (None, None) => None,
+            },
+        })
+    }
+    
+    /// Concatenate many sequences with a single allocation
+    ///
+    /// All sequences must share an alphabet. The result takes the first id
+    /// present; a single sequence keeps its description, while several are
+    /// described as "concatenation of N sequences". An empty slice is an
+    /// error, since there is no alphabet to give the result.
+    pub fn concat_all(seqs: &[Sequence]) -> SequenceResult<Self> {
+        let first = seqs.first().ok_or_else(|| SequenceError::InvalidSequence(
+            "Cannot concatenate an empty list of sequences".to_string()
+        ))?;
+        
+        if let Some(other) = seqs.iter().find(|seq| seq.alphabet_name() != first.alphabet_name()) {
+            return Err(SequenceError::InvalidAlphabet(
+                format!("Cannot concatenate sequences with different alphabets: {} and {}",
+                        first.alphabet_name(), other.alphabet_name())
+            ));
+        }
+        
+        // Allocate the full length up front, then copy each sequence in
+        let total_len = seqs.iter().map(|seq| seq.len()).sum();
+        let mut combined = Vec::with_capacity(total_len);
+        for seq in seqs {
+            combined.extend_from_slice(&seq.as_bytes());
+        }
+        
+        Ok(Self {
+            data: Box::new(InMemoryStorage::new(combined)),
+            alphabet: first.alphabet.clone(),
+            id: seqs.iter().find_map(|seq| seq.id.clone()),
+            description: match seqs.len() {
+                1 => first.description.clone(),
+                n => Some(format!("concatenation of {} sequences", n)),
+            },
+        })
+    }
+    
+    /// Estimate the melting temperature in °C
+    ///
+    /// Uses the Wallace rule for oligos shorter than 14 nt and the
+    /// nearest-neighbor method (50 mM Na+, 25 nM strands) otherwise.
+    pub fn melting_temperature(&self) -> SequenceResult<f64> {
+        self.melting_temperature_with(TmMethod::Auto)
+    }
+    
+    /// Estimate the melting temperature in °C with a specific method
+    ///
+    /// Only supported for DNA; ambiguous bases are an error.
+    pub fn melting_temperature_with(&self, method: TmMethod) -> SequenceResult<f64> {
+        if self.alphabet_name() != "DNA" {
+            return Err(SequenceError::UnsupportedOperation(
+                format!("Melting temperature not supported for {} alphabet", self.alphabet_name())
+            ));
+        }
+        
+        melting::melting_temperature(self.as_bytes().as_ref(), method)
+    }
+    
+    /// Calculate the average molecular weight of a protein in Da
+    ///
+    /// Matches ExPASy ProtParam. Ambiguous residues are an error; see
+    /// [`molecular_weight_with`](Self::molecular_weight_with) to average them.
+    pub fn molecular_weight(&self) -> SequenceResult<f64> {
+        self.molecular_weight_with(MassType::Average, AmbiguousResidues::Error)
+    }
+    
+    /// Calculate the molecular weight of a protein with the given masses
+    ///
+    /// Only supported for proteins.
+    pub fn molecular_weight_with(&self, mass_type: MassType, ambiguous: AmbiguousResidues) -> SequenceResult<f64> {
+        self.check_protein("Molecular weight")?;
+        protein::molecular_weight(self.as_bytes().as_ref(), mass_type, ambiguous)
+    }
+    
+    /// Calculate the isoelectric point of a protein
+    ///
+    /// Matches ExPASy ProtParam. Ambiguous residues are an error; see
+    /// [`isoelectric_point_with`](Self::isoelectric_point_with) to average them.
+    pub fn isoelectric_point(&self) -> SequenceResult<f64> {
+        self.isoelectric_point_with(AmbiguousResidues::Error)
+    }
+    
+    /// Calculate the isoelectric point of a protein, choosing how to treat ambiguous residues
+    ///
+    /// Only supported for proteins.
+    pub fn isoelectric_point_with(&self, ambiguous: AmbiguousResidues) -> SequenceResult<f64> {
+        self.check_protein("Isoelectric point")?;
+        protein::isoelectric_point(self.as_bytes().as_ref(), ambiguous)
+    }
+    
+    /// Reject protein-only operations on other alphabets
+    fn check_protein(&self, operation: &str) -> SequenceResult<()> {
+        if self.alphabet_name() != "Protein" {
+            return Err(SequenceError::UnsupportedOperation(
+                format!("{} not supported for {} alphabet", operation, self.alphabet_name())
+            ));
+        }
+        Ok(())
+    }
+    
+    /// Calculate the edit (Levenshtein) distance to another sequence
+    pub fn edit_distance(&self, other: &Self) -> usize {
+        alignment::edit_distance(self.as_bytes().as_ref(), other.as_bytes().as_ref())
+    }
+    
+    /// Calculate the Levenshtein distance to another sequence
+    ///
+    /// Same as [`edit_distance`](Self::edit_distance).
+    pub fn levenshtein(&self, other: &Self) -> usize {
+        self.edit_distance(other)
+    }
+    
+    /// Count the positions at which two equal-length sequences differ
+    ///
+    /// Only substitutions are counted, so this is much faster than
+    /// [`levenshtein`](Self::levenshtein) for reads of the same length.
+    /// Comparison is case-sensitive.
+    pub fn hamming_distance(&self, other: &Self) -> SequenceResult<usize> {
+        if self.len() != other.len() {
+            return Err(SequenceError::InvalidSequence(format!(
+                "Hamming distance needs sequences of equal length, got {} and {}",
+                self.len(), other.len()
+            )));
+        }
+        
+        Ok(simd::count_mismatches(&self.as_bytes(), &other.as_bytes()))
+    }
+    
+    /// Align this sequence against another
+    ///
+    /// Both sequences must use the same alphabet. Accepts a
+    /// [`ScoringScheme`](alignment::ScoringScheme) or a
+    /// [`SubstitutionMatrix`](alignment::SubstitutionMatrix).
+    pub fn align<'a>(
+        &self,
+        other: &Self,
+        alignment_type: AlignmentType,
+        scoring: impl Into<Scoring<'a>>,
+    ) -> SequenceResult<Alignment> {
+        if self.alphabet_name() != other.alphabet_name() {
+            return Err(SequenceError::InvalidAlphabet(
+                format!("Cannot align sequences with different alphabets: {} and {}",
+                        self.alphabet_name(), other.alphabet_name())
+            ));
+        }
+        
+        Ok(alignment::align(
+            self.as_bytes().as_ref(),
+            other.as_bytes().as_ref(),
+            alignment_type,
+            scoring,
+        )?)
+    }
+    
+    /// Get the k-mer containment of this sequence in another sequence
+    ///
+    /// Returns the fraction of this sequence's distinct k-mers that also occur
+    /// in `other`. Unlike Jaccard similarity this is not symmetric, which makes
+    /// it suitable for comparing a short sequence against a long reference.
+    pub fn kmer_containment(&self, other: &Sequence, k: usize) -> SequenceResult<f64> {
+        if k == 0 {
+            return Err(SequenceError::InvalidSequence(
+                "k-mer size must be greater than zero".to_string()
+            ));
+        }
+        
+        let self_bytes = self.as_bytes();
+        let other_bytes = other.as_bytes();
+        
+        let self_kmers: HashSet<&[u8]> = self_bytes.windows(k).collect();
+        if self_kmers.is_empty() {
+            return Ok(0.0);
+        }
+        
+        let other_kmers: HashSet<&[u8]> = other_bytes.windows(k).collect();
+        let shared = self_kmers.iter().filter(|kmer| other_kmers.contains(*kmer)).count();
+        
+        Ok(shared as f64 / self_kmers.len() as f64)
+    }
+    
+    /// Convert the sequence to uppercase
+    pub fn to_upper(&self) -> Self {
+        let mut upper = self.as_bytes().to_vec();
+        simd::to_uppercase_ascii(&mut upper);
+        
+        Self {
+            data: Box::new(InMemoryStorage::new(upper)),
+            alphabet: self.alphabet.clone(),
+            id: self.id.clone(),
+            description: self.description.clone(),
+        }
+    }
+    
+    /// Convert the sequence to lowercase (e.g. to soft-mask it)
+    pub fn to_lower(&self) -> Self {
+        let mut lower = self.as_bytes().to_vec();
+        simd::to_lowercase_ascii(&mut lower);
+        
+        Self {
+            data: Box::new(InMemoryStorage::new(lower)),
+            alphabet: self.alphabet.clone(),
+            id: self.id.clone(),
+            description: self.description.clone(),
+        }
+    }
+    
+    /// Get the fraction of the sequence that is lowercase (soft-masked)
+    ///
+    /// Returns 0.0 for an empty sequence.
+    pub fn masked_fraction(&self) -> f64 {
+        if self.is_empty() {
+            return 0.0;
+        }
+        
+        let masked = self.as_bytes().iter().filter(|b| b.is_ascii_lowercase()).count();
+        masked as f64 / self.len() as f64
+    }
+    
+    /// Find matches of a motif written in a restricted regular-expression syntax
+    ///
+    /// Supports literal residues, IUPAC codes, character classes such as
+    /// `[AG]` and bounded repeats such as `N{3,5}`. Returns non-overlapping
+    /// half-open `(start, end)` spans.
+    pub fn find_regex_motif(&self, pattern: &str) -> SequenceResult<Vec<(usize, usize)>> {
+        let nucleotide = matches!(self.alphabet_name(), "DNA" | "RNA");
+        let motif = Motif::compile(pattern, nucleotide)?;
+        
+        Ok(motif.find_all(self.as_bytes().as_ref()))
+    }
+    
+    /// Translate a nucleotide sequence into protein
+    ///
+    /// Accepts DNA and RNA. Codons are read three at a time from the start of
+    /// the sequence; stop codons translate to `*`. A trailing partial codon is
+    /// either rejected or translated to `X`, depending on the table's
+    /// [`PartialCodon`] setting.
+    ///
+    /// With `treat_alt_start_as_met`, a first codon that is a start codon of
+    /// the table (e.g. `TTG` in table 11) translates to `M`; the same codon
+    /// later in the sequence translates normally.
+    pub fn translate(&self, table: CodonTable, treat_alt_start_as_met: bool) -> SequenceResult<Self> {
+        let protein = self.translate_frame(&table, treat_alt_start_as_met, false)?;
+        Ok(self.translated(protein))
+    }
+    
+    /// Translate a nucleotide sequence up to the first stop codon
+    ///
+    /// Like [`translate`](Self::translate), but translation halts at the
+    /// first stop codon, which is not included in the protein. Anything after
+    /// the stop, including a trailing partial codon, is ignored.
+    pub fn translate_to_stop(&self, table: CodonTable, treat_alt_start_as_met: bool) -> SequenceResult<Self> {
+        let protein = self.translate_frame(&table, treat_alt_start_as_met, true)?;
+        Ok(self.translated(protein))
+    }
+    
+    /// Translate a complete coding sequence
+    ///
+    /// Follows Biopython's `translate(cds=True)`: the sequence must be a
+    /// multiple of three, begin with a start codon of the table and end with
+    /// its only in-frame stop codon. The start codon translates to `M` and
+    /// the final stop is left out of the protein.
+    pub fn translate_cds(&self, table: CodonTable) -> SequenceResult<Self> {
+        self.check_translatable()?;
+        
+        let nucleotides = self.as_bytes();
+        if !nucleotides.len().is_multiple_of(3) {
+            return Err(SequenceError::InvalidSequence(format!(
+                "CDS length {} is not a multiple of three",
+                nucleotides.len()
+            )));
         }
         
-        counts
+        let codons: Vec<&[u8]> = nucleotides.chunks_exact(3).collect();
+        let (first, last) = match (codons.first(), codons.last()) {
+            (Some(first), Some(last)) if codons.len() >= 2 => (*first, *last),
+            _ => return Err(SequenceError::InvalidSequence(
+                "CDS must contain at least a start and a stop codon".to_string()
+            )),
+        };
+        
+        if !table.is_start_codon(first) {
+            return Err(SequenceError::InvalidSequence(format!(
+                "First codon '{}' is not a start codon",
+                String::from_utf8_lossy(first)
+            )));
+        }
+        if !table.is_stop_codon(last) {
+            return Err(SequenceError::InvalidSequence(format!(
+                "Final codon '{}' is not a stop codon",
+                String::from_utf8_lossy(last)
+            )));
+        }
+        
+        let mut protein = Vec::with_capacity(codons.len() - 1);
+        protein.push(b'M');
+        for (i, codon) in codons[1..codons.len() - 1].iter().enumerate() {
+            let amino_acid = table.translate_codon(codon);
+            if amino_acid == b'*' {
+                return Err(SequenceError::InvalidSequence(format!(
+                    "Extra in-frame stop codon '{}' at position {}",
+                    String::from_utf8_lossy(codon),
+                    (i + 1) * 3
+                )));
+            }
+            protein.push(amino_acid);
+        }
+        
+        Ok(self.translated(protein))
     }
     
-    /// Get the GC content (for DNA/RNA sequences)
-    pub fn gc_content(&self) -> SequenceResult<f64> {
-        if self.alphabet_name() != "DNA" && self.alphabet_name() != "RNA" {
+    /// Reject translation of anything other than DNA or RNA
+    fn check_translatable(&self) -> SequenceResult<()> {
+        if !matches!(self.alphabet_name(), "DNA" | "RNA") {
             return Err(SequenceError::UnsupportedOperation(
-                format!("GC content calculation not supported for {} alphabet", self.alphabet_name())
+                "Translation operation only supported for DNA and RNA alphabets".to_string()
             ));
         }
+        Ok(())
+    }
+    
+    /// Translate the first reading frame, optionally halting at a stop codon
+    fn translate_frame(&self, table: &CodonTable, treat_alt_start_as_met: bool, to_stop: bool) -> SequenceResult<Vec<u8>> {
+        self.check_translatable()?;
         
-        let composition = self.base_composition();
-        let total = self.len() as f64;
+        let nucleotides = self.as_bytes();
+        let mut protein = Vec::with_capacity(nucleotides.len() / 3 + 1);
         
-        if total == 0.0 {
-            return Ok(0.0);
+        for (i, codon) in nucleotides.chunks_exact(3).enumerate() {
+            let amino_acid = match i {
+                0 => table.translate_start_codon(codon, treat_alt_start_as_met),
+                _ => table.translate_codon(codon),
+            };
+            if to_stop && amino_acid == b'*' {
+                return Ok(protein);
+            }
+            protein.push(amino_acid);
         }
         
-        // Count G and C bases (both upper and lowercase)
-        let gc_count = 
-            composition.get(&b'G').unwrap_or(&0) +
-            composition.get(&b'g').unwrap_or(&0) +
-            composition.get(&b'C').unwrap_or(&0) +
-            composition.get(&b'c').unwrap_or(&0);
+        if !nucleotides.len().is_multiple_of(3) {
+            if table.partial_codon() == PartialCodon::Error {
+                return Err(SequenceError::InvalidSequence(format!(
+                    "Sequence length {} is not a multiple of three",
+                    nucleotides.len()
+                )));
+            }
+            protein.push(b'X');
+        }
         
-        Ok((gc_count as f64) / total * 100.0)
+        Ok(protein)
     }
     
-    /// Get the reverse of the sequence
-    pub fn reverse(&self) -> Self {
-        let mut reversed = self.as_bytes().to_vec();
-        string_ops::reverse_in_place(&mut reversed);
-        
+    /// Wrap translated residues as a protein carrying this sequence's metadata
+    fn translated(&self, protein: Vec<u8>) -> Self {
         Self {
-            data: Box::new(InMemoryStorage::new(reversed)),
-            alphabet: self.alphabet.clone(),
+            data: Box::new(InMemoryStorage::new(protein)),
+            alphabet: Box::new(ProteinAlphabet::default()),
             id: self.id.clone(),
-            description: self.description.clone().map(|desc| format!("{} (reversed)", desc)),
+            description: self.description.clone().map(|desc| format!("{} (translated)", desc)),
         }
     }
     
-    /// Get the complement of the sequence (for DNA/RNA)
-    pub fn complement(&self) -> SequenceResult<Self> {
-        if self.alphabet_name() != "DNA" && self.alphabet_name() != "RNA" {
+    /// Count codon usage in the first reading frame
+    ///
+    /// Accepts DNA and RNA. See [`CodonTable::codon_usage`] for how codons
+    /// are counted and grouped.
+    pub fn codon_usage(&self, table: CodonTable) -> SequenceResult<Vec<CodonUsage>> {
+        if !matches!(self.alphabet_name(), "DNA" | "RNA") {
             return Err(SequenceError::UnsupportedOperation(
-                format!("Complement operation not supported for {} alphabet", self.alphabet_name())
+                "Codon usage only supported for DNA and RNA alphabets".to_string()
             ));
         }
         
-        let seq_bytes = self.as_bytes();
-        let complemented = self.alphabet.complement_sequence(&seq_bytes)
-            .ok_or_else(|| SequenceError::UnsupportedOperation(
-                "Failed to compute complement".to_string()
-            ))?;
-        
-        Ok(Self {
-            data: Box::new(InMemoryStorage::new(complemented)),
-            alphabet: self.alphabet.clone(),
-            id: self.id.clone(),
-            description: self.description.clone().map(|desc| format!("{} (complement)", desc)),
-        })
+        Ok(table.codon_usage(&self.as_bytes()))
     }
     
-    /// Get the reverse complement of the sequence (for DNA/RNA)
-    pub fn reverse_complement(&self) -> SequenceResult<Self> {
-        let mut rev_comp = self.as_bytes().to_vec();
-        
-        // First complement
-        let complemented = self.alphabet.complement_sequence(&rev_comp)
-            .ok_or_else(|| SequenceError::UnsupportedOperation(
-                "Failed to compute complement".to_string()
-            ))?;
+    /// Iterate over the codons of a reading frame
+    ///
+    /// `frame` is the offset of the first codon (0, 1 or 2). Codons are
+    /// successive non-overlapping triplets as stored, so DNA and RNA behave
+    /// alike; a trailing partial codon is dropped. Only supported for DNA
+    /// and RNA, and frames of 3 or more are an error.
+    pub fn codons(&self, frame: usize) -> SequenceResult<impl Iterator<Item = [u8; 3]> + '_> {
+        if !matches!(self.alphabet_name(), "DNA" | "RNA") {
+            return Err(SequenceError::UnsupportedOperation(
+                "Codons only supported for DNA and RNA alphabets".to_string()
+            ));
+        }
+        if frame > 2 {
+            return Err(SequenceError::IndexOutOfBounds(format!(
+                "Reading frame {} is not 0, 1 or 2",
+                frame
+            )));
+        }
         
-        // Then reverse
-        let mut reversed = complemented;
-        string_ops::reverse_in_place(&mut reversed);
+        let count = self.len().saturating_sub(frame) / 3;
+        let slice = self.data.as_slice();
         
-        Ok(Self {
-            data: Box::new(InMemoryStorage::new(reversed)),
-            alphabet: self.alphabet.clone(),
-            id: self.id.clone(),
-            description: self.description.clone().map(|desc| format!("{} (reverse complement)", desc)),
-        })
+        Ok((0..count).map(move |i| {
+            let start = frame + 3 * i;
+            match slice {
+                Some(slice) => [slice[start], slice[start + 1], slice[start + 2]],
+                None => {
+                    let codon = self.data.subsequence(start, start + 3);
+                    [codon[0], codon[1], codon[2]]
+                },
+            }
+        }))
     }
     
-    /// Transcribe a DNA sequence to RNA
-    pub fn transcribe(&self) -> SequenceResult<Self> {
-        if self.alphabet_name() != "DNA" {
+    /// Find open reading frames on both strands
+    ///
+    /// An ORF runs from a start codon to the first in-frame stop codon. Start
+    /// codons nested inside an ORF on the same frame share its stop, so only
+    /// the longest ORF is reported for each stop. ORFs without a stop codon
+    /// are ignored. Coordinates of reverse-strand ORFs refer to the forward
+    /// sequence.
+    ///
+    /// With `treat_alt_start_as_met`, each protein begins with `M` even when
+    /// its ORF starts at an alternative start codon such as `GTG`.
+    pub fn find_orfs(&self, min_len: usize, table: CodonTable, treat_alt_start_as_met: bool) -> SequenceResult<Vec<Orf>> {
+        if !matches!(self.alphabet_name(), "DNA" | "RNA") {
             return Err(SequenceError::UnsupportedOperation(
-                "Transcription operation only supported for DNA alphabet".to_string()
+                "ORF finding only supported for DNA and RNA alphabets".to_string()
             ));
         }
         
-        let dna = self.as_bytes();
-        let rna = string_ops::transcribe(&dna);
+        let forward = self.as_bytes();
+        let reverse = self.reverse_complement()?;
+        let total_len = forward.len();
         
-        Ok(Self {
-            data: Box::new(InMemoryStorage::new(rna)),
-            alphabet: Box::new(RNAAlphabet::default()),
-            id: self.id.clone(),
-            description: self.description.clone().map(|desc| format!("{} (transcribed)", desc)),
+        let mut orfs = Vec::new();
+        
+        for (strand, nucleotides) in [(Strand::Forward, forward.as_ref()), (Strand::Reverse, reverse.as_bytes().as_ref())] {
+            for frame in 0..3 {
+                let mut orf_start = None;
+                let mut pos = frame;
+                
+                while pos + 3 <= nucleotides.len() {
+                    let codon = &nucleotides[pos..pos + 3];
+                    
+                    match orf_start {
+                        Some(start) if table.translate_codon(codon) == b'*' => {
+                            // Close the ORF at the first in-frame stop
+                            let protein: Vec<u8> = nucleotides[start..pos]
+                                .chunks_exact(3)
+                                .enumerate()
+                                .map(|(i, c)| match i {
+                                    0 => table.translate_start_codon(c, treat_alt_start_as_met),
+                                    _ => table.translate_codon(c),
+                                })
+                                .collect();
+                            
+                            if protein.len() >= min_len {
+                                // Map reverse-strand coordinates back to the forward sequence
+                                let (orf_begin, orf_end) = match strand {
+                                    Strand::Forward => (start, pos + 3),
+                                    Strand::Reverse => (total_len - (pos + 3), total_len - start),
+                                };
+                                
+                                orfs.push(Orf {
+                                    frame,
+                                    strand,
+                                    start: orf_begin,
+                                    end: orf_end,
+                                    protein: Self {
+                                        data: Box::new(InMemoryStorage::new(protein)),
+                                        alphabet: Box::new(ProteinAlphabet::default()),
+                                        id: self.id.clone(),
+                                        description: None,
+                                    },
+                                });
+                            }
+                            
+                            orf_start = None;
+                        },
+                        None if table.is_start_codon(codon) => orf_start = Some(pos),
+                        _ => {},
+                    }
+                    
+                    pos += 3;
+                }
+            }
+        }
+        
+        orfs.sort_by_key(|orf| (orf.start, orf.end));
+        Ok(orfs)
+    }
+    
+    /// Count the occurrences of every k-mer
+    ///
+    /// Returns an empty map if `k` is larger than the sequence.
+    pub fn kmer_counts(&self, k: usize) -> SequenceResult<HashMap<Vec<u8>, usize>> {
+        Self::check_kmer_size(k)?;
+        Ok(count_kmers(&self.as_bytes(), k, None))
+    }
+    
+    /// Iterate over every k-mer without building a map
+    ///
+    /// For in-memory storage each k-mer borrows from the sequence buffer and
+    /// nothing is allocated. Storage that can't be sliced (packed or
+    /// on-demand) yields owned copies extracted one k-mer at a time. Yields
+    /// nothing if `k` is zero or larger than the sequence.
+    pub fn kmers_iter(&self, k: usize) -> impl Iterator<Item = Cow<'_, [u8]>> + '_ {
+        let count = if k == 0 || k > self.len() { 0 } else { self.len() - k + 1 };
+        let slice = self.data.as_slice();
+        
+        (0..count).map(move |start| match slice {
+            Some(slice) => Cow::Borrowed(&slice[start..start + k]),
+            None => Cow::Owned(self.data.subsequence(start, start + k)),
         })
     }
     
-    /// Find all occurrences of a subsequence
-    pub fn find_all(&self, pattern: &[u8]) -> Vec<usize> {
-        if pattern.is_empty() || pattern.len() > self.len() {
-            return Vec::new();
+    /// Count canonical k-mers (the smaller of a k-mer and its reverse complement)
+    ///
+    /// Only supported for DNA and RNA, so that both strands of a read
+    /// contribute to the same counts.
+    pub fn kmer_counts_canonical(&self, k: usize) -> SequenceResult<HashMap<Vec<u8>, usize>> {
+        Self::check_kmer_size(k)?;
+        let complement = self.complement_table()?;
+        Ok(count_kmers(&self.as_bytes(), k, Some(&complement)))
+    }
+    
+    /// Count k-mers in parallel, optionally canonicalized
+    ///
+    /// The sequence is split into chunks that overlap by `k - 1` bases so that
+    /// no k-mer is lost or counted twice; the result is identical to the
+    /// serial [`kmer_counts`](Self::kmer_counts) and
+    /// [`kmer_counts_canonical`](Self::kmer_counts_canonical).
+    pub fn par_kmer_counts(&self, k: usize, canonical: bool) -> SequenceResult<HashMap<Vec<u8>, usize>> {
+        Self::check_kmer_size(k)?;
+        let complement = if canonical { Some(self.complement_table()?) } else { None };
+        
+        let data = self.as_bytes();
+        if k > data.len() {
+            return Ok(HashMap::new());
         }
         
-        // Use the KMP algorithm for searching
-        match string_ops::kmp_search(self.as_bytes().as_ref(), pattern) {
-            Ok(matches) => matches,
-            Err(_) => Vec::new(),
+        // Each chunk owns the k-mers starting in its range plus k - 1 bases of overlap
+        let num_kmers = data.len() - k + 1;
+        let chunk_size = parallel::calculate_chunk_size(num_kmers, None);
+        let chunks: Vec<&[u8]> = (0..num_kmers)
+            .step_by(chunk_size)
+            .map(|start| &data[start..(start + chunk_size + k - 1).min(data.len())])
+            .collect();
+        
+        let processor = ParallelChunkProcessor::new(chunks);
+        let partial_counts = processor.process(move |chunk: &[u8]| {
+            count_kmers(chunk, k, complement.as_ref())
+        });
+        
+        // Merge the per-chunk counts
+        let mut counts = HashMap::new();
+        for partial in partial_counts {
+            for (kmer, count) in partial {
+                *counts.entry(kmer).or_insert(0) += count;
+            }
         }
+        
+        Ok(counts)
     }
     
-    /// Count the occurrences of a subsequence
-    pub fn count(&self, pattern: &[u8]) -> usize {
-        self.find_all(pattern).len()
+    /// Validate a k-mer size
+    fn check_kmer_size(k: usize) -> SequenceResult<()> {
+        if k == 0 {
+            return Err(SequenceError::InvalidSequence(
+                "k-mer size must be greater than zero".to_string()
+            ));
+        }
+        Ok(())
     }
     
-    /// Convert to a specific storage format
-    pub fn to_packed_storage(&self) -> SequenceResult<Self> {
-        if self.alphabet_name() != "DNA" {
+    /// Build a byte-wise complement lookup table for canonical k-mers
+    fn complement_table(&self) -> SequenceResult<[u8; 256]> {
+        if self.alphabet_name() != "DNA" && self.alphabet_name() != "RNA" {
             return Err(SequenceError::UnsupportedOperation(
-                "Packed storage is only supported for DNA sequences".to_string()
+                format!("Canonical k-mers not supported for {} alphabet", self.alphabet_name())
             ));
         }
         
-        let seq_data = self.as_bytes();
-        let mut packed = PackedDnaStorage::with_capacity(seq_data.len());
-        packed.pack(&seq_data);
+        let mut table = [0u8; 256];
+        for (b, slot) in table.iter_mut().enumerate() {
+            *slot = self.alphabet.complement(b as u8).unwrap_or(b as u8);
+        }
+        Ok(table)
+    }
+    
+    /// Compare residues with another sequence, ignoring case
+    ///
+    /// Like `==`, the alphabets must match and the id and description are ignored.
+    pub fn eq_ignore_case(&self, other: &Sequence) -> bool {
+        self.alphabet_name() == other.alphabet_name()
+            && self.as_bytes().eq_ignore_ascii_case(&other.as_bytes())
+    }
+    
+    /// Get the GC content (as a percentage) of sliding windows
+    ///
+    /// Windows start every `step` bases; a step larger than the window samples
+    /// non-overlapping windows. The final window may be shorter than `window`
+    /// and uses its actual length. Like `string_ops::gc_content`, N and other
+    /// non-ACGT(U) bases are left out of each window's denominator.
+    pub fn gc_content_windows(&self, window: usize, step: usize) -> SequenceResult<Vec<f64>> {
+        if self.alphabet_name() != "DNA" && self.alphabet_name() != "RNA" {
+            return Err(SequenceError::UnsupportedOperation(
+                format!("GC content calculation not supported for {} alphabet", self.alphabet_name())
+            ));
+        }
         
-        // Create a sequence with the packed storage
-        Ok(Self {
-            data: Box::new(InMemoryStorage::new(seq_data.to_vec())), // We'd use packed storage here in a real implementation
-            alphabet: self.alphabet.clone(),
-            id: self.id.clone(),
-            description: self.description.clone(),
-        })
+        if window == 0 || step == 0 {
+            return Err(SequenceError::InvalidSequence(
+                "Window size and step must be greater than zero".to_string()
+            ));
+        }
+        
+        let data = self.as_bytes();
+        let mut values = Vec::with_capacity(data.len() / step + 1);
+        
+        let mut start = 0;
+        while start < data.len() {
+            let end = (start + window).min(data.len());
+            values.push(string_ops::gc_content(&data[start..end]));
+            
+            // Stop once a window reaches the end of the sequence
+            if end == data.len() {
+                break;
+            }
+            start += step;
+        }
+        
+        Ok(values)
     }
     
-    /// Create a mask from the given positions
-    pub fn mask(&self, positions: &[usize], mask_char: u8) -> SequenceResult<Self> {
-        let mut masked = self.as_bytes().to_vec();
+    /// Get the Shannon entropy of the sequence in bits per base
+    ///
+    /// Computed from `base_composition`, so upper and lower case are
+    /// distinct symbols. An empty sequence has entropy 0.0.
+    pub fn shannon_entropy(&self) -> f64 {
+        let composition = self.base_composition();
+        shannon_entropy(composition.values().copied(), self.len())
+    }
+    
+    /// Get the Shannon entropy of sliding windows in bits per base
+    ///
+    /// Windows follow the same layout as `gc_content_windows`. Symbol counts
+    /// are updated incrementally as the window slides.
+    pub fn entropy_windows(&self, window: usize, step: usize) -> SequenceResult<Vec<f64>> {
+        if window == 0 || step == 0 {
+            return Err(SequenceError::InvalidSequence(
+                "Window size and step must be greater than zero".to_string()
+            ));
+        }
         
-        for &pos in positions {
-            if pos >= self.len() {
-                return Err(SequenceError::IndexOutOfBounds(
-                    format!("Position {} is out of bounds for sequence of length {}", pos, self.len())
-                ));
+        let data = self.as_bytes();
+        let mut values = Vec::with_capacity(data.len() / step + 1);
+        let mut counts = [0usize; 256];
+        let (mut counted_start, mut counted_end) = (0, 0);
+        
+        let mut start = 0;
+        while start < data.len() {
+            let end = (start + window).min(data.len());
+            
+            // Reuse the previous window's counts when the windows overlap
+            if start >= counted_end {
+                counts = [0; 256];
+                counted_end = start;
+            } else {
+                data[counted_start..start].iter().for_each(|&b| counts[b as usize] -= 1);
             }
+            data[counted_end..end].iter().for_each(|&b| counts[b as usize] += 1);
+            counted_start = start;
+            counted_end = end;
             
-            masked[pos] = mask_char;
+            values.push(shannon_entropy(counts.iter().copied(), end - start));
+            
+            // Stop once a window reaches the end of the sequence
+            if end == data.len() {
+                break;
+            }
+            start += step;
         }
         
-        Ok(Self {
-            data: Box::new(InMemoryStorage::new(masked)),
-            alphabet: self.alphabet.clone(),
-            id: self.id.clone(),
-            description: self.description.clone().map(|desc| format!("{} (masked)", desc)),
-        })
+        Ok(values)
     }
     
-    /// Concatenate with another sequence
-    pub fn concatenate(&self, other: &Self) -> SequenceResult<Self> {
-        if self.alphabet_name() != other.alphabet_name() {
-            return Err(SequenceError::InvalidAlphabet(
-                format!("Cannot concatenate sequences with different alphabets: {} and {}", 
-                        self.alphabet_name(), other.alphabet_name())
+    /// Get the DUST low-complexity score of the sequence
+    ///
+    /// Counts case-insensitive triplets and returns `sum(c * (c - 1) / 2) /
+    /// (l - 1)` over the `l` triplets. Random sequence scores near 0, while
+    /// short tandem repeats score highly (a homopolymer of length `n` scores
+    /// `(n - 2) / 2`). Sequences with fewer than two triplets score 0.0.
+    pub fn dust_score(&self) -> f64 {
+        let data = self.as_bytes();
+        if data.len() < 4 {
+            return 0.0;
+        }
+        
+        let mut counts: HashMap<[u8; 3], usize> = HashMap::new();
+        for triplet in data.windows(3) {
+            let key = [
+                triplet[0].to_ascii_uppercase(),
+                triplet[1].to_ascii_uppercase(),
+                triplet[2].to_ascii_uppercase(),
+            ];
+            *counts.entry(key).or_insert(0) += 1;
+        }
+        
+        let num_triplets = data.len() - 2;
+        let score: usize = counts.values().map(|&c| c * (c - 1) / 2).sum();
+        score as f64 / (num_triplets - 1) as f64
+    }
+    
+    /// Split the sequence at runs of `ch` at least `min_run` long
+    ///
+    /// Useful for breaking scaffolds into contigs at `N` gaps. Shorter runs
+    /// are kept inside the pieces, and runs of any length at either end are
+    /// dropped. The match on `ch` is exact, so `N` and `n` are different
+    /// characters. Each piece's description records its original offset.
+    pub fn split_on_runs(&self, ch: u8, min_run: usize) -> Vec<Self> {
+        let data = self.as_bytes();
+        let min_run = min_run.max(1);
+        
+        let mut pieces = Vec::new();
+        let mut piece_start = 0;
+        let mut pos = 0;
+        
+        while let Some(offset) = simd::find_byte(&data[pos..], ch) {
+            let run_start = pos + offset;
+            let run_end = data[run_start..]
+                .iter()
+                .position(|&b| b != ch)
+                .map_or(data.len(), |len| run_start + len);
+            
+            // Runs at either end are dropped whatever their length
+            if run_end - run_start >= min_run || run_start == 0 || run_end == data.len() {
+                if run_start > piece_start {
+                    pieces.push((piece_start, run_start));
+                }
+                piece_start = run_end;
+            }
+            pos = run_end;
+        }
+        
+        if piece_start < data.len() {
+            pieces.push((piece_start, data.len()));
+        }
+        
+        pieces
+            .into_iter()
+            .map(|(start, end)| Self {
+                data: Box::new(InMemoryStorage::new(data[start..end].to_vec())),
+                alphabet: self.alphabet.clone(),
+                id: self.id.clone(),
+                description: Some(match &self.description {
+                    Some(desc) => format!("{} (offset {})", desc, start),
+                    None => format!("offset {}", start),
+                }),
+            })
+            .collect()
+    }
+    
+    /// Count every pair of adjacent residues, ignoring case
+    pub fn dinucleotide_counts(&self) -> HashMap<[u8; 2], usize> {
+        let mut counts = HashMap::new();
+        for pair in self.as_bytes().windows(2) {
+            *counts.entry([pair[0].to_ascii_uppercase(), pair[1].to_ascii_uppercase()]).or_insert(0) += 1;
+        }
+        counts
+    }
+    
+    /// Get the observed/expected CpG ratio
+    ///
+    /// Computed as `CG count / (C count * G count / length)`. Returns 0.0
+    /// when the sequence has no C or no G.
+    pub fn cpg_ratio(&self) -> SequenceResult<f64> {
+        if self.alphabet_name() != "DNA" && self.alphabet_name() != "RNA" {
+            return Err(SequenceError::UnsupportedOperation(
+                format!("CpG ratio not supported for {} alphabet", self.alphabet_name())
             ));
         }
         
-        // Combine the sequences
-        let mut combined = self.as_bytes().to_vec();
-        combined.extend_from_slice(&other.as_bytes());
+        let data = self.as_bytes();
+        let (c, g) = data.iter().fold((0, 0), |(c, g), &b| match b.to_ascii_uppercase() {
+            b'C' => (c + 1, g),
+            b'G' => (c, g + 1),
+            _ => (c, g),
+        });
+        let cg = data.windows(2).filter(|pair| is_cpg(pair[0], pair[1])).count();
         
-        Ok(Self {
-            data: Box::new(InMemoryStorage::new(combined)),
-            alphabet: self.alphabet.clone(),
-            id: self.id.clone().or_else(|| other.id.clone()),
-            description: match (self.description.clone(), other.description.clone()) {
-                (Some(desc1), Some(desc2)) => Some(format!("{} + {}", desc1, desc2)),
-                (Some(desc1), None) => Some(format!("{} + [unnamed]", desc1)),
-                (None, Some(desc2)) => Some(format!("[unnamed] + {}", desc2)),
-                (None, None) => None,
-            },
+        Ok(cpg_observed_expected(cg, c, g, data.len()))
+    }
+    
+    /// Find candidate CpG islands with a sliding window
+    ///
+    /// Every window of `window` bases with a GC content of at least `min_gc`
+    /// percent and a CpG observed/expected ratio of at least `min_ratio` is
+    /// a candidate (Gardiner-Garden and Frommer use 200, 50.0 and 0.6).
+    /// Overlapping or adjacent candidate windows are merged into half-open
+    /// `(start, end)` intervals. Sequences shorter than the window have none.
+    pub fn find_cpg_islands(&self, window: usize, min_gc: f64, min_ratio: f64) -> SequenceResult<Vec<(usize, usize)>> {
+        if self.alphabet_name() != "DNA" && self.alphabet_name() != "RNA" {
+            return Err(SequenceError::UnsupportedOperation(
+                format!("CpG island detection not supported for {} alphabet", self.alphabet_name())
+            ));
+        }
+        
+        if window < 2 {
+            return Err(SequenceError::InvalidSequence(
+                "CpG island window must be at least two bases".to_string()
+            ));
+        }
+        
+        let data = self.as_bytes();
+        let mut islands: Vec<(usize, usize)> = Vec::new();
+        if data.len() < window {
+            return Ok(islands);
+        }
+        
+        let base_delta = |b: u8| match b.to_ascii_uppercase() {
+            b'C' => (1, 0),
+            b'G' => (0, 1),
+            _ => (0, 0),
+        };
+        
+        // Counts for the first window
+        let (mut c, mut g) = data[..window].iter().fold((0, 0), |(c, g), &b| {
+            let (dc, dg) = base_delta(b);
+            (c + dc, g + dg)
+        });
+        let mut cg = data[..window].windows(2).filter(|pair| is_cpg(pair[0], pair[1])).count();
+        
+        for start in 0..=data.len() - window {
+            let end = start + window;
+            
+            // Slide the counts by one base
+            if start > 0 {
+                let (dc, dg) = base_delta(data[start - 1]);
+                c -= dc;
+                g -= dg;
+                let (dc, dg) = base_delta(data[end - 1]);
+                c += dc;
+                g += dg;
+                cg -= usize::from(is_cpg(data[start - 1], data[start]));
+                cg += usize::from(is_cpg(data[end - 2], data[end - 1]));
+            }
+            
+            let gc_percent = (c + g) as f64 * 100.0 / window as f64;
+            if gc_percent >= min_gc && cpg_observed_expected(cg, c, g, window) >= min_ratio {
+                match islands.last_mut() {
+                    Some(last) if last.1 >= start => last.1 = end,
+                    _ => islands.push((start, end)),
+                }
+            }
+        }
+        
+        Ok(islands)
+    }
+    
+    /// Get the approximate memory used by this sequence in bytes
+    ///
+    /// Includes the storage backend's own estimate (which excludes mapped
+    /// files) plus the id, description and alphabet.
+    pub fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.data.memory_usage()
+            + std::mem::size_of_val(&*self.alphabet)
+            + self.id.as_ref().map_or(0, |id| id.capacity())
+            + self.description.as_ref().map_or(0, |desc| desc.capacity())
+    }
+}
+
+/// Shannon entropy in bits of symbol counts summing to `total`
+fn shannon_entropy(counts: impl Iterator<Item = usize>, total: usize) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    
+    let total = total as f64;
+    counts
+        .filter(|&count| count > 0)
+        .map(|count| {
+            let p = count as f64 / total;
+            -p * p.log2()
         })
+        .sum()
+}
+
+/// Check whether two adjacent bases form a CpG dinucleotide
+fn is_cpg(first: u8, second: u8) -> bool {
+    first.eq_ignore_ascii_case(&b'C') && second.eq_ignore_ascii_case(&b'G')
+}
+
+/// CpG observed/expected ratio, or 0.0 when no CpG is expected
+fn cpg_observed_expected(cg: usize, c: usize, g: usize, length: usize) -> f64 {
+    if c == 0 || g == 0 {
+        return 0.0;
     }
-}
\ No newline at end of file
+    cg as f64 * length as f64 / (c as f64 * g as f64)
+}
+
+/// Count k-mers in `data`, canonicalizing them when a complement table is given
+fn count_kmers(data: &[u8], k: usize, complement: Option<&[u8; 256]>) -> HashMap<Vec<u8>, usize> {
+    let mut counts = HashMap::new();
+    
+    for kmer in data.windows(k) {
+        let key = match complement {
+            Some(table) => {
+                let rev_comp: Vec<u8> = kmer.iter().rev().map(|&b| table[b as usize]).collect();
+                if rev_comp.as_slice() < kmer { rev_comp } else { kmer.to_vec() }
+            },
+            None => kmer.to_vec(),
+        };
+        *counts.entry(key).or_insert(0) += 1;
+    }
+    
+    counts
+}
@@ -0,0 +1,147 @@
+//! Assembly statistics
+//!
+//! This module summarizes sets of contigs or scaffolds with the length
+//! statistics commonly used to compare assemblies (N50, L50 and friends).
+
+use crate::engines::compute::string_ops;
+use crate::engines::storage::formats::SequenceRecord;
+
+/// Bases counted at a time for storage without a contiguous slice
+const GC_CHUNK_SIZE: usize = 1 << 20;
+
+/// Length and composition summary of an assembly
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct AssemblyStats {
+    /// Number of sequences
+    pub count: usize,
+    /// Sum of all sequence lengths
+    pub total_length: usize,
+    /// Length of the shortest sequence
+    pub min_length: usize,
+    /// Length of the longest sequence
+    pub max_length: usize,
+    /// Mean sequence length
+    pub mean_length: f64,
+    /// Length of the sequence at which the longest sequences cover half the total
+    pub n50: usize,
+    /// Number of sequences needed to reach N50
+    pub l50: usize,
+    /// Length of the sequence at which the longest sequences cover 90% of the total
+    pub n90: usize,
+    /// Number of sequences needed to reach N90
+    pub l90: usize,
+    /// G+C as a percentage of unambiguous bases (A, C, G, T/U)
+    pub gc_percent: f64,
+}
+
+/// Compute assembly statistics for a set of records
+///
+/// Lengths are sorted in descending order and N50 is the length of the
+/// sequence at which the cumulative length first reaches half the total.
+/// Empty input gives all-zero statistics, as does GC% when there are no
+/// unambiguous bases.
+pub fn assembly_stats(records: &[SequenceRecord]) -> AssemblyStats {
+    if records.is_empty() {
+        return AssemblyStats::default();
+    }
+    
+    let mut lengths: Vec<usize> = records.iter().map(|record| record.len()).collect();
+    lengths.sort_unstable_by(|a, b| b.cmp(a));
+    let total_length: usize = lengths.iter().sum();
+    
+    let (n50, l50) = nx(&lengths, total_length, 50);
+    let (n90, l90) = nx(&lengths, total_length, 90);
+    
+    // Count bases across all records
+    let mut counts = [0usize; 5];
+    for record in records {
+        for (total, count) in counts.iter_mut().zip(record_base_counts(record)) {
+            *total += count;
+        }
+    }
+    let called = counts[0] + counts[1] + counts[2] + counts[3];
+    let gc_percent = if called == 0 {
+        0.0
+    } else {
+        (counts[1] + counts[2]) as f64 * 100.0 / called as f64
+    };
+    
+    AssemblyStats {
+        count: lengths.len(),
+        total_length,
+        min_length: lengths[lengths.len() - 1],
+        max_length: lengths[0],
+        mean_length: total_length as f64 / lengths.len() as f64,
+        n50,
+        l50,
+        n90,
+        l90,
+        gc_percent,
+    }
+}
+
+/// Find the Nx length and Lx count for lengths sorted in descending order
+fn nx(lengths: &[usize], total_length: usize, percent: usize) -> (usize, usize) {
+    let mut cumulative = 0;
+    for (i, &length) in lengths.iter().enumerate() {
+        cumulative += length;
+        if cumulative * 100 >= total_length * percent {
+            return (length, i + 1);
+        }
+    }
+    (0, 0)
+}
+
+/// Count bases in a record without copying storage that exposes a slice
+fn record_base_counts(record: &SequenceRecord) -> [usize; 5] {
+    if let Some(slice) = record.sequence.as_slice() {
+        return string_ops::count_bases(slice);
+    }
+    
+    let mut counts = [0usize; 5];
+    for start in (0..record.len()).step_by(GC_CHUNK_SIZE) {
+        let end = (start + GC_CHUNK_SIZE).min(record.len());
+        let chunk = record.sequence.subsequence(start, end);
+        for (total, count) in counts.iter_mut().zip(string_ops::count_bases(&chunk)) {
+            *total += count;
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn record(id: &str, sequence: &[u8]) -> SequenceRecord {
+        SequenceRecord::new(id.to_string(), None, sequence.to_vec())
+    }
+    
+    #[test]
+    fn test_assembly_stats() {
+        // Lengths 80, 70, 50, 40, 30, 20, 10: total 300
+        let records: Vec<SequenceRecord> = [40, 80, 10, 70, 30, 50, 20].iter()
+            .enumerate()
+            .map(|(i, &len)| record(&format!("contig{}", i), &b"GC".repeat(len / 2)))
+            .collect();
+        
+        let stats = assembly_stats(&records);
+        assert_eq!(stats.count, 7);
+        assert_eq!(stats.total_length, 300);
+        assert_eq!((stats.min_length, stats.max_length), (10, 80));
+        assert!((stats.mean_length - 300.0 / 7.0).abs() < 1e-12);
+        assert_eq!((stats.n50, stats.l50), (70, 2));
+        assert_eq!((stats.n90, stats.l90), (30, 5));
+        assert_eq!(stats.gc_percent, 100.0);
+        
+        // A single sequence is its own N50; ambiguous bases don't count towards GC%
+        let single = assembly_stats(&[record("chr1", b"AACGNN")]);
+        assert_eq!((single.n50, single.l50, single.n90), (6, 1, 6));
+        assert_eq!(single.mean_length, 6.0);
+        assert_eq!(single.gc_percent, 50.0);
+        
+        // Empty input and all-N input don't divide by zero
+        assert_eq!(assembly_stats(&[]), AssemblyStats::default());
+        assert_eq!(assembly_stats(&[record("gap", b"NNNN")]).gc_percent, 0.0);
+    }
+}
@@ -0,0 +1,674 @@
+//! Python bindings
+//!
+//! Built with the `python` feature into the `biopython_rust._rust_bindings`
+//! extension module that backs the `biopython_rust` Python package. The
+//! module exposes a Biopython-style `Seq` class along with the functional
+//! API used by the pure-Python wrappers.
+//!
+//! Rust errors become Python exceptions: invalid sequences raise
+//! `SequenceError` (a `ValueError`), out-of-range indices `IndexError`,
+//! I/O failures `IOError` and engine failures `RuntimeError`.
+
+use std::collections::HashMap;
+
+use pyo3::basic::CompareOp;
+use pyo3::exceptions::{PyIOError, PyIndexError, PyMemoryError, PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyBytes, PyDict, PyList, PySlice};
+
+use crate::engines::EngineError;
+use crate::engines::compute::{string_ops, ComputeError};
+use crate::engines::storage::formats::{
+    self, FastaParser, FastaWriter, FastqParser, FastqWriter, SequenceParser, SequenceRecord, SequenceWriter,
+};
+use crate::modules::seq::alphabet::{detect_alphabet, rna_to_dna};
+use crate::modules::seq::{AmbiguousResidues, CodonTable, MassType, Sequence, SequenceError};
+
+/// Python exception types defined by the extension module
+mod exceptions {
+    pyo3::create_exception!(
+        _rust_bindings,
+        SequenceError,
+        pyo3::exceptions::PyValueError,
+        "Raised for invalid sequences and unsupported sequence operations."
+    );
+}
+
+impl From<SequenceError> for PyErr {
+    fn from(err: SequenceError) -> PyErr {
+        match err {
+            SequenceError::IndexOutOfBounds(_) => PyIndexError::new_err(err.to_string()),
+            SequenceError::EngineError(inner) => inner.into(),
+            SequenceError::ComputeError(inner) => inner.into(),
+            _ => exceptions::SequenceError::new_err(err.to_string()),
+        }
+    }
+}
+
+impl From<EngineError> for PyErr {
+    fn from(err: EngineError) -> PyErr {
+        match err {
+            EngineError::IoError(_) => PyIOError::new_err(err.to_string()),
+            EngineError::MemoryError(_) => PyMemoryError::new_err(err.to_string()),
+            EngineError::InvalidSequenceData(_) => exceptions::SequenceError::new_err(err.to_string()),
+            _ => PyRuntimeError::new_err(err.to_string()),
+        }
+    }
+}
+
+impl From<ComputeError> for PyErr {
+    fn from(err: ComputeError) -> PyErr {
+        match err {
+            ComputeError::InvalidInput(_) => PyValueError::new_err(err.to_string()),
+            _ => PyRuntimeError::new_err(err.to_string()),
+        }
+    }
+}
+
+/// Sequence data accepted from Python: a `Seq`, `bytes` or `str`
+#[derive(FromPyObject)]
+enum SeqInput<'py> {
+    Seq(PyRef<'py, PySeq>),
+    Bytes(Bound<'py, PyBytes>),
+    Text(String),
+}
+
+impl SeqInput<'_> {
+    /// Copy the sequence bytes out of the Python object
+    fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            SeqInput::Seq(seq) => seq.inner.as_bytes().into_owned(),
+            SeqInput::Bytes(bytes) => bytes.as_bytes().to_vec(),
+            SeqInput::Text(text) => text.as_bytes().to_vec(),
+        }
+    }
+}
+
+/// Integer index or slice accepted by `Seq.__getitem__`
+#[derive(FromPyObject)]
+enum SeqIndex<'py> {
+    Index(isize),
+    Slice(Bound<'py, PySlice>),
+}
+
+/// Build a sequence with an alphabet named by Python code
+///
+/// `None` or `"auto"` detects the alphabet; names are case-insensitive.
+fn sequence_with_alphabet(data: &[u8], alphabet: Option<&str>) -> PyResult<Sequence> {
+    let sequence = match alphabet.map(str::to_ascii_lowercase).as_deref() {
+        None | Some("auto") => Sequence::new(data)?,
+        Some("dna") => Sequence::new_dna(data)?,
+        Some("rna") => Sequence::new_rna(data)?,
+        Some("protein") => Sequence::new_protein(data)?,
+        Some(other) => return Err(PyValueError::new_err(format!("Unknown alphabet '{}'", other))),
+    };
+    Ok(sequence)
+}
+
+/// Look up an NCBI translation table
+fn codon_table(table: u8) -> PyResult<CodonTable> {
+    CodonTable::by_id(table)
+        .ok_or_else(|| PyValueError::new_err(format!("Unknown translation table {}", table)))
+}
+
+/// Biological sequence, mirroring Biopython's `Seq`
+#[pyclass(name = "Seq", module = "biopython_rust._rust_bindings")]
+#[derive(Clone)]
+pub struct PySeq {
+    inner: Sequence,
+}
+
+impl From<Sequence> for PySeq {
+    fn from(inner: Sequence) -> Self {
+        Self { inner }
+    }
+}
+
+#[pymethods]
+impl PySeq {
+    /// Create a sequence, detecting the alphabet unless one is given
+    #[new]
+    #[pyo3(signature = (data, alphabet = None, id = None, description = None))]
+    fn py_new(data: SeqInput<'_>, alphabet: Option<&str>, id: Option<&str>, description: Option<&str>) -> PyResult<Self> {
+        let mut sequence = sequence_with_alphabet(&data.to_bytes(), alphabet)?;
+        if let Some(id) = id {
+            sequence = sequence.with_id(id);
+        }
+        if let Some(description) = description {
+            sequence = sequence.with_description(description);
+        }
+        Ok(sequence.into())
+    }
+    
+    #[getter]
+    fn id(&self) -> Option<&str> {
+        self.inner.id()
+    }
+    
+    #[setter]
+    fn set_id(&mut self, id: &str) {
+        self.inner = self.inner.clone().with_id(id);
+    }
+    
+    #[getter]
+    fn description(&self) -> Option<&str> {
+        self.inner.description()
+    }
+    
+    #[setter]
+    fn set_description(&mut self, description: &str) {
+        self.inner = self.inner.clone().with_description(description);
+    }
+    
+    /// Name of the alphabet ("DNA", "RNA" or "Protein")
+    #[getter]
+    fn alphabet(&self) -> &str {
+        self.inner.alphabet_name()
+    }
+    
+    fn __len__(&self) -> usize {
+        self.inner.len()
+    }
+    
+    fn __str__(&self) -> String {
+        self.inner.as_string()
+    }
+    
+    fn __repr__(&self) -> String {
+        format!("Seq('{}')", self.inner.as_string())
+    }
+    
+    fn __bytes__<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.inner.as_bytes())
+    }
+    
+    fn __getitem__(&self, py: Python<'_>, index: SeqIndex<'_>) -> PyResult<PyObject> {
+        let len = self.inner.len();
+        match index {
+            SeqIndex::Index(i) => {
+                let position = if i < 0 { i + len as isize } else { i };
+                let base = usize::try_from(position).ok().and_then(|p| self.inner.get(p)).ok_or_else(|| {
+                    PyIndexError::new_err(format!("Index {} out of range for sequence of length {}", i, len))
+                })?;
+                Ok((base as char).to_string().into_pyobject(py)?.into_any().unbind())
+            },
+            SeqIndex::Slice(slice) => {
+                // `indices` clamps huge bounds and raises ValueError for a zero step;
+                // a resolved stop of -1 means "before the first base" when stepping back
+                let indices = slice.indices(len as isize)?;
+                let stop = (indices.stop >= 0).then_some(indices.stop);
+                let sequence = self.inner.slice(Some(indices.start), stop, Some(indices.step))?;
+                Ok(Py::new(py, PySeq::from(sequence))?.into_any())
+            },
+        }
+    }
+    
+    /// Compare with another `Seq`, `str` or `bytes` by content
+    fn __richcmp__(&self, other: &Bound<'_, PyAny>, op: CompareOp) -> PyObject {
+        let py = other.py();
+        let Ok(other) = other.extract::<SeqInput<'_>>() else {
+            return py.NotImplemented();
+        };
+        let equal = self.inner.as_bytes().as_ref() == other.to_bytes().as_slice();
+        match op {
+            CompareOp::Eq => PyBool::new(py, equal).to_owned().into_any().unbind(),
+            CompareOp::Ne => PyBool::new(py, !equal).to_owned().into_any().unbind(),
+            _ => py.NotImplemented(),
+        }
+    }
+    
+    fn complement(&self) -> PyResult<Self> {
+        Ok(self.inner.complement()?.into())
+    }
+    
+    fn reverse_complement(&self) -> PyResult<Self> {
+        Ok(self.inner.reverse_complement()?.into())
+    }
+    
+    fn transcribe(&self) -> PyResult<Self> {
+        Ok(self.inner.transcribe()?.into())
+    }
+    
+    /// Convert RNA back to DNA
+    fn back_transcribe(&self) -> PyResult<Self> {
+        if self.inner.alphabet_name() != "RNA" {
+            return Err(exceptions::SequenceError::new_err("Only RNA can be back-transcribed"));
+        }
+        Ok(Sequence::new_dna(&rna_to_dna(&self.inner.as_bytes()))?.into())
+    }
+    
+    /// Translate with an NCBI translation table
+    ///
+    /// `to_stop` halts before the first stop codon; `cds` validates a
+    /// complete coding sequence as Biopython's `translate(cds=True)` does.
+    #[pyo3(signature = (table = 1, alt_start_as_met = false, to_stop = false, cds = false))]
+    fn translate(&self, table: u8, alt_start_as_met: bool, to_stop: bool, cds: bool) -> PyResult<Self> {
+        let table = codon_table(table)?;
+        let protein = if cds {
+            self.inner.translate_cds(table)?
+        } else if to_stop {
+            self.inner.translate_to_stop(table, alt_start_as_met)?
+        } else {
+            self.inner.translate(table, alt_start_as_met)?
+        };
+        Ok(protein.into())
+    }
+    
+    fn gc_content(&self) -> PyResult<f64> {
+        Ok(self.inner.gc_content()?)
+    }
+    
+    /// Protein molecular weight in Da, as ExPASy ProtParam computes it
+    #[pyo3(signature = (monoisotopic = false))]
+    fn molecular_weight(&self, monoisotopic: bool) -> PyResult<f64> {
+        let mass_type = if monoisotopic { MassType::Monoisotopic } else { MassType::Average };
+        Ok(self.inner.molecular_weight_with(mass_type, AmbiguousResidues::Error)?)
+    }
+    
+    /// Protein isoelectric point, as ExPASy ProtParam computes it
+    fn isoelectric_point(&self) -> PyResult<f64> {
+        Ok(self.inner.isoelectric_point()?)
+    }
+    
+    /// Start positions of every (overlapping) occurrence of a pattern
+    fn find_all(&self, pattern: SeqInput<'_>) -> Vec<usize> {
+        self.inner.find_all(&pattern.to_bytes())
+    }
+    
+    fn count(&self, pattern: SeqInput<'_>) -> usize {
+        self.inner.count(&pattern.to_bytes())
+    }
+    
+    fn upper(&self) -> Self {
+        self.inner.to_upper().into()
+    }
+    
+    fn lower(&self) -> Self {
+        self.inner.to_lower().into()
+    }
+}
+
+/// Initialize the library
+#[pyfunction]
+fn init() {
+    crate::init();
+}
+
+#[pyfunction]
+fn create_sequence(data: SeqInput<'_>) -> PyResult<PySeq> {
+    Ok(sequence_with_alphabet(&data.to_bytes(), None)?.into())
+}
+
+#[pyfunction]
+fn create_dna_sequence(data: SeqInput<'_>) -> PyResult<PySeq> {
+    Ok(Sequence::new_dna(&data.to_bytes())?.into())
+}
+
+#[pyfunction]
+fn create_rna_sequence(data: SeqInput<'_>) -> PyResult<PySeq> {
+    Ok(Sequence::new_rna(&data.to_bytes())?.into())
+}
+
+#[pyfunction]
+fn create_protein_sequence(data: SeqInput<'_>) -> PyResult<PySeq> {
+    Ok(Sequence::new_protein(&data.to_bytes())?.into())
+}
+
+#[pyfunction]
+fn set_sequence_id(seq: &PySeq, id: &str) -> PySeq {
+    seq.inner.clone().with_id(id).into()
+}
+
+#[pyfunction]
+fn set_sequence_description(seq: &PySeq, description: &str) -> PySeq {
+    seq.inner.clone().with_description(description).into()
+}
+
+#[pyfunction]
+fn get_sequence_id(seq: &PySeq) -> Option<String> {
+    seq.inner.id().map(str::to_string)
+}
+
+#[pyfunction]
+fn get_sequence_description(seq: &PySeq) -> Option<String> {
+    seq.inner.description().map(str::to_string)
+}
+
+#[pyfunction]
+fn get_sequence_length(seq: &PySeq) -> usize {
+    seq.inner.len()
+}
+
+/// Alphabet name of a `Seq`, or the detected alphabet of raw data
+#[pyfunction]
+fn get_sequence_alphabet(data: SeqInput<'_>) -> String {
+    match data {
+        SeqInput::Seq(seq) => seq.inner.alphabet_name().to_string(),
+        other => detect_alphabet(&other.to_bytes())
+            .map_or_else(|| "Unknown".to_string(), |alphabet| alphabet.name().to_string()),
+    }
+}
+
+#[pyfunction]
+fn sequence_to_string(seq: &PySeq) -> String {
+    seq.inner.as_string()
+}
+
+#[pyfunction]
+fn sequence_to_bytes<'py>(py: Python<'py>, seq: &PySeq) -> Bound<'py, PyBytes> {
+    PyBytes::new(py, &seq.inner.as_bytes())
+}
+
+#[pyfunction]
+fn get_sequence_base<'py>(py: Python<'py>, seq: &PySeq, index: usize) -> PyResult<Bound<'py, PyBytes>> {
+    let base = seq.inner.get(index).ok_or_else(|| {
+        PyIndexError::new_err(format!("Index {} out of range for sequence of length {}", index, seq.inner.len()))
+    })?;
+    Ok(PyBytes::new(py, &[base]))
+}
+
+#[pyfunction]
+fn get_subsequence<'py>(py: Python<'py>, seq: &PySeq, start: usize, end: usize) -> PyResult<Bound<'py, PyBytes>> {
+    let subsequence = seq.inner.subsequence(start, end)?;
+    Ok(PyBytes::new(py, &subsequence.as_bytes()))
+}
+
+#[pyfunction]
+fn count_pattern(seq: &PySeq, pattern: SeqInput<'_>) -> usize {
+    seq.inner.count(&pattern.to_bytes())
+}
+
+#[pyfunction]
+fn find_all_patterns(seq: &PySeq, pattern: SeqInput<'_>) -> Vec<usize> {
+    seq.inner.find_all(&pattern.to_bytes())
+}
+
+#[pyfunction]
+fn complement_sequence<'py>(py: Python<'py>, seq: &PySeq) -> PyResult<Bound<'py, PyBytes>> {
+    Ok(PyBytes::new(py, &seq.inner.complement()?.as_bytes()))
+}
+
+#[pyfunction]
+fn reverse_complement_sequence<'py>(py: Python<'py>, seq: &PySeq) -> PyResult<Bound<'py, PyBytes>> {
+    Ok(PyBytes::new(py, &seq.inner.reverse_complement()?.as_bytes()))
+}
+
+#[pyfunction]
+fn transcribe_sequence<'py>(py: Python<'py>, seq: &PySeq) -> PyResult<Bound<'py, PyBytes>> {
+    Ok(PyBytes::new(py, &seq.inner.transcribe()?.as_bytes()))
+}
+
+#[pyfunction]
+fn reverse_transcribe_sequence<'py>(py: Python<'py>, seq: &PySeq) -> PyResult<Bound<'py, PyBytes>> {
+    let dna = seq.back_transcribe()?;
+    Ok(PyBytes::new(py, &dna.inner.as_bytes()))
+}
+
+#[pyfunction]
+#[pyo3(signature = (seq, table = 1, alt_start_as_met = false, to_stop = false, cds = false))]
+fn translate_sequence<'py>(py: Python<'py>, seq: &PySeq, table: u8, alt_start_as_met: bool, to_stop: bool, cds: bool) -> PyResult<Bound<'py, PyBytes>> {
+    let protein = seq.translate(table, alt_start_as_met, to_stop, cds)?;
+    Ok(PyBytes::new(py, &protein.inner.as_bytes()))
+}
+
+#[pyfunction]
+fn gc_content(seq: &PySeq) -> PyResult<f64> {
+    seq.gc_content()
+}
+
+#[pyfunction]
+#[pyo3(signature = (seq, monoisotopic = false))]
+fn protein_molecular_weight(seq: &PySeq, monoisotopic: bool) -> PyResult<f64> {
+    seq.molecular_weight(monoisotopic)
+}
+
+#[pyfunction]
+fn protein_isoelectric_point(seq: &PySeq) -> PyResult<f64> {
+    seq.isoelectric_point()
+}
+
+#[pyfunction]
+fn random_dna_sequence(py: Python<'_>, length: usize) -> Bound<'_, PyBytes> {
+    PyBytes::new(py, &string_ops::random_dna(length))
+}
+
+#[pyfunction]
+fn detect_file_format(path: &str) -> PyResult<&'static str> {
+    Ok(formats::detect_format(path)?)
+}
+
+/// Convert a parsed record into a dict of `id`, `description`, `sequence`
+/// and, for FASTQ, `quality`
+fn record_to_dict<'py>(py: Python<'py>, record: &SequenceRecord) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("id", &record.id)?;
+    dict.set_item("description", record.description.as_deref())?;
+    dict.set_item("sequence", PyBytes::new(py, &record.sequence_as_vec()))?;
+    if let Some(quality) = record.quality_as_vec() {
+        dict.set_item("quality", PyBytes::new(py, &quality))?;
+    }
+    if !record.metadata.is_empty() {
+        dict.set_item("metadata", &record.metadata)?;
+    }
+    Ok(dict)
+}
+
+/// Build a record from a dict with the keys produced by [`record_to_dict`]
+fn record_from_dict(dict: &Bound<'_, PyDict>) -> PyResult<SequenceRecord> {
+    let required = |key: &str| {
+        dict.get_item(key)?
+            .ok_or_else(|| PyValueError::new_err(format!("Record is missing '{}'", key)))
+    };
+    let optional = |key: &str| -> PyResult<Option<Bound<'_, PyAny>>> {
+        Ok(dict.get_item(key)?.filter(|value| !value.is_none()))
+    };
+    
+    let id: String = required("id")?.extract()?;
+    let description: Option<String> = optional("description")?.map(|value| value.extract()).transpose()?;
+    let sequence = required("sequence")?.extract::<SeqInput<'_>>()?.to_bytes();
+    
+    let mut record = match optional("quality")? {
+        Some(quality) => {
+            let quality = quality.extract::<SeqInput<'_>>()?.to_bytes();
+            SequenceRecord::with_quality(id, description, sequence, quality)
+        },
+        None => SequenceRecord::new(id, description, sequence),
+    };
+    if let Some(metadata) = optional("metadata")? {
+        record.metadata = metadata.extract::<HashMap<String, String>>()?;
+    }
+    Ok(record)
+}
+
+/// Extract records from a list of dicts
+fn records_from_list(records: &Bound<'_, PyList>) -> PyResult<Vec<SequenceRecord>> {
+    records.iter()
+        .map(|item| record_from_dict(item.downcast::<PyDict>()?))
+        .collect()
+}
+
+#[pyfunction]
+fn read_fasta<'py>(py: Python<'py>, path: &str) -> PyResult<Bound<'py, PyList>> {
+    let records = FastaParser::new().parse_file(path)?;
+    let dicts = records.iter().map(|record| record_to_dict(py, record)).collect::<PyResult<Vec<_>>>()?;
+    PyList::new(py, dicts)
+}
+
+#[pyfunction]
+fn write_fasta(records: &Bound<'_, PyList>, path: &str) -> PyResult<()> {
+    Ok(FastaWriter::new().write_file(&records_from_list(records)?, path)?)
+}
+
+#[pyfunction]
+fn read_fastq<'py>(py: Python<'py>, path: &str) -> PyResult<Bound<'py, PyList>> {
+    let records = FastqParser::new().parse_file(path)?;
+    let dicts = records.iter().map(|record| record_to_dict(py, record)).collect::<PyResult<Vec<_>>>()?;
+    PyList::new(py, dicts)
+}
+
+#[pyfunction]
+fn write_fastq(records: &Bound<'_, PyList>, path: &str) -> PyResult<()> {
+    Ok(FastqWriter::new().write_file(&records_from_list(records)?, path)?)
+}
+
+/// The `biopython_rust._rust_bindings` extension module
+#[pymodule]
+fn _rust_bindings(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PySeq>()?;
+    m.add("SequenceError", m.py().get_type::<exceptions::SequenceError>())?;
+    
+    m.add_function(wrap_pyfunction!(init, m)?)?;
+    m.add_function(wrap_pyfunction!(create_sequence, m)?)?;
+    m.add_function(wrap_pyfunction!(create_dna_sequence, m)?)?;
+    m.add_function(wrap_pyfunction!(create_rna_sequence, m)?)?;
+    m.add_function(wrap_pyfunction!(create_protein_sequence, m)?)?;
+    m.add_function(wrap_pyfunction!(set_sequence_id, m)?)?;
+    m.add_function(wrap_pyfunction!(set_sequence_description, m)?)?;
+    m.add_function(wrap_pyfunction!(get_sequence_id, m)?)?;
+    m.add_function(wrap_pyfunction!(get_sequence_description, m)?)?;
+    m.add_function(wrap_pyfunction!(get_sequence_length, m)?)?;
+    m.add_function(wrap_pyfunction!(get_sequence_alphabet, m)?)?;
+    m.add_function(wrap_pyfunction!(sequence_to_string, m)?)?;
+    m.add_function(wrap_pyfunction!(sequence_to_bytes, m)?)?;
+    m.add_function(wrap_pyfunction!(get_sequence_base, m)?)?;
+    m.add_function(wrap_pyfunction!(get_subsequence, m)?)?;
+    m.add_function(wrap_pyfunction!(count_pattern, m)?)?;
+    m.add_function(wrap_pyfunction!(find_all_patterns, m)?)?;
+    m.add_function(wrap_pyfunction!(complement_sequence, m)?)?;
+    m.add_function(wrap_pyfunction!(reverse_complement_sequence, m)?)?;
+    m.add_function(wrap_pyfunction!(transcribe_sequence, m)?)?;
+    m.add_function(wrap_pyfunction!(reverse_transcribe_sequence, m)?)?;
+    m.add_function(wrap_pyfunction!(translate_sequence, m)?)?;
+    m.add_function(wrap_pyfunction!(gc_content, m)?)?;
+    m.add_function(wrap_pyfunction!(protein_molecular_weight, m)?)?;
+    m.add_function(wrap_pyfunction!(protein_isoelectric_point, m)?)?;
+    m.add_function(wrap_pyfunction!(random_dna_sequence, m)?)?;
+    m.add_function(wrap_pyfunction!(detect_file_format, m)?)?;
+    m.add_function(wrap_pyfunction!(read_fasta, m)?)?;
+    m.add_function(wrap_pyfunction!(write_fasta, m)?)?;
+    m.add_function(wrap_pyfunction!(read_fastq, m)?)?;
+    m.add_function(wrap_pyfunction!(write_fastq, m)?)?;
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pyo3::ffi::c_str;
+    
+    /// Run a test with the module imported as `m`
+    fn with_module<F: FnOnce(Python<'_>, &Bound<'_, PyModule>)>(f: F) {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let module = PyModule::new(py, "_rust_bindings").unwrap();
+            _rust_bindings(&module).unwrap();
+            f(py, &module);
+        });
+    }
+    
+    #[test]
+    fn test_seq_class() {
+        with_module(|py, m| {
+            let locals = PyDict::new(py);
+            locals.set_item("m", m).unwrap();
+            py.run(
+                c_str!(r#"
+s = m.Seq("ATGGCCTAA", id="orf1")
+assert len(s) == 9 and str(s) == "ATGGCCTAA" and s.id == "orf1"
+assert s.alphabet == "DNA"
+assert s.reverse_complement() == "TTAGGCCAT"
+assert str(s.translate()) == "MA*"
+assert str(s.translate(to_stop=True)) == "MA" and str(s.translate(cds=True)) == "MA"
+assert s.transcribe() == b"AUGGCCUAA"
+assert s.transcribe().back_transcribe() == s
+assert s.find_all("GC") == [3]
+assert abs(s.gc_content() - 400 / 9) < 1e-9
+assert s[0] == "A" and s[-1] == "A" and s[3:6] == "GCC" and s[::-1] == "AATCCGGTA"
+assert m.Seq("MKV").alphabet == "Protein"
+assert m.Seq("ACGTR")[0:5] == "ACGTR" and m.Seq("ACGTR")[::2] == "AGR"
+assert s[5::-1] == "CCGGTA" and s[:10**30] == s and s[-10**30:2] == "AT" and s[10**30::-1] == s[::-1]
+try:
+    s[::0]
+    raise AssertionError("zero step accepted")
+except ValueError:
+    pass
+assert round(m.protein_molecular_weight(m.Seq("GG", alphabet="protein")), 4) == 132.1190
+assert 8 < m.Seq("MKV").isoelectric_point() < 9
+"#),
+                None,
+                Some(&locals),
+            ).unwrap();
+        });
+    }
+    
+    #[test]
+    fn test_error_mapping() {
+        with_module(|py, m| {
+            let locals = PyDict::new(py);
+            locals.set_item("m", m).unwrap();
+            py.run(
+                c_str!(r#"
+try:
+    m.Seq("ACGTJ", alphabet="dna")
+    raise AssertionError("expected SequenceError")
+except m.SequenceError as e:
+    assert isinstance(e, ValueError)
+
+try:
+    m.Seq("MKV", alphabet="protein").reverse_complement()
+    raise AssertionError("expected SequenceError")
+except m.SequenceError:
+    pass
+
+try:
+    m.Seq("ACGT")[4]
+    raise AssertionError("expected IndexError")
+except IndexError:
+    pass
+
+try:
+    m.read_fasta("/nonexistent/file.fasta")
+    raise AssertionError("expected IOError")
+except IOError:
+    pass
+"#),
+                None,
+                Some(&locals),
+            ).unwrap();
+        });
+    }
+    
+    #[test]
+    fn test_fasta_fastq_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let fasta = dir.path().join("out.fasta");
+        let fastq = dir.path().join("out.fastq");
+        
+        with_module(|py, m| {
+            let locals = PyDict::new(py);
+            locals.set_item("m", m).unwrap();
+            locals.set_item("fasta", fasta.to_str().unwrap()).unwrap();
+            locals.set_item("fastq", fastq.to_str().unwrap()).unwrap();
+            py.run(
+                c_str!(r#"
+seq = m.create_dna_sequence(b"ACGTACGT")
+m.write_fasta([{"id": "seq1", "description": "first", "sequence": seq},
+               {"id": "seq2", "description": None, "sequence": "GGCC"}], fasta)
+records = m.read_fasta(fasta)
+assert [r["id"] for r in records] == ["seq1", "seq2"]
+assert records[0]["description"] == "first" and records[1]["description"] is None
+assert records[0]["sequence"] == b"ACGTACGT"
+assert m.detect_file_format(fasta) == "FASTA"
+
+m.write_fastq([{"id": "read1", "sequence": b"ACGT", "quality": b"IIII"}], fastq)
+records = m.read_fastq(fastq)
+assert records[0]["sequence"] == b"ACGT" and records[0]["quality"] == b"IIII"
+assert m.detect_file_format(fastq) == "FASTQ"
+"#),
+                None,
+                Some(&locals),
+            ).unwrap();
+        });
+    }
+}